@@ -1,14 +1,21 @@
 use candid::{encode_args, Principal};
 use ic_cdk::api::time;
+use std::collections::HashMap;
 
 use crate::activity_log::log_activity;
 use crate::auth;
 use crate::blockchain_proof::{generate_hash, generate_signature};
 use crate::storage::{next_aje_id, next_aje_line_item_id, STORAGE};
 use crate::types::{
-    AdjustingJournalEntry, AjeLineItem, AjeStatus, CreateAjeRequest,
-    Result,
+    AdjustingJournalEntry, AjeEntryType, AjeLineItem, AjeStatus, BookAdjustment,
+    CreateAjeLineItemRequest, CreateAjeRequest, PassAjeRequest, Result, TrialBalance,
+    UpdateAjeRequest,
 };
+use crate::trial_balance;
+use crate::working_papers;
+
+// Proof format of newly signed entries; see proof_data
+const PROOF_VERSION: u32 = 2;
 
 // Create a new adjusting journal entry with blockchain proof
 pub fn create_aje(caller: Principal, req: CreateAjeRequest) -> Result<AdjustingJournalEntry> {
@@ -23,102 +30,229 @@ pub fn create_aje(caller: Principal, req: CreateAjeRequest) -> Result<AdjustingJ
         .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
         .ok_or("Engagement not found")?;
 
-    let _trial_balance = STORAGE
+    let trial_balance = STORAGE
         .with(|storage| storage.borrow().trial_balances.get(&req.trial_balance_id))
         .ok_or("Trial balance not found")?;
-
-    // Validate line items balance
-    let total_debits: i64 = req.line_items.iter().map(|li| li.debit_amount).sum();
-    let total_credits: i64 = req.line_items.iter().map(|li| li.credit_amount).sum();
-
-    if total_debits != total_credits {
-        return Err(format!(
-            "AJE does not balance: debits {} != credits {}",
-            total_debits, total_credits
-        ));
+    if trial_balance.engagement_id != req.engagement_id {
+        return Err("Trial balance belongs to another engagement".to_string());
     }
 
-    let aje_id = next_aje_id();
+    let total = validate_line_items(req.trial_balance_id, &req.line_items)?;
+    let entry_type = req.entry_type.unwrap_or(AjeEntryType::Adjusting);
     let timestamp = time();
 
-    // Generate blockchain proof
-    let data = format!(
-        "{}:{}:{}:{}:{}:{}",
-        aje_id,
-        req.engagement_id,
-        req.trial_balance_id,
-        req.aje_number,
-        req.description,
-        total_debits
-    );
-    let blockchain_hash = generate_hash(&data);
-    
-    let blockchain_signature = generate_signature(
-        aje_id,
-        caller,
-        "CREATE_AJE",
-        "AdjustingJournalEntry",
-        &aje_id.to_string(),
-        timestamp,
-        &blockchain_hash,
-    );
-
-    let aje = AdjustingJournalEntry {
-        id: aje_id,
+    let mut aje = AdjustingJournalEntry {
+        id: next_aje_id(),
         engagement_id: req.engagement_id,
         trial_balance_id: req.trial_balance_id,
-        aje_number: req.aje_number.clone(),
-        description: req.description.clone(),
+        aje_number: next_aje_number(req.engagement_id, &entry_type),
+        description: req.description,
+        entry_type: Some(entry_type),
         status: AjeStatus::Draft,
-        amount: total_debits,
+        amount: total,
         created_at: timestamp,
         created_by: caller,
         reviewed_at: None,
         reviewed_by: None,
         approved_at: None,
         approved_by: None,
-        blockchain_hash: blockchain_hash.clone(),
-        blockchain_signature: blockchain_signature.clone(),
+        classification: None,
+        passed_reason: None,
+        passed_at: None,
+        passed_by: None,
+        reverses_aje_id: None,
+        reversal_aje_id: None,
+        reversal_pending: Some(false),
+        blockchain_hash: String::new(),
+        blockchain_signature: String::new(),
+        proof_version: None,
     };
 
+    // Generate blockchain proof once the lines it covers are stored
+    replace_line_items(aje.id, req.line_items);
+    sign_aje(&mut aje, caller, "CREATE_AJE", timestamp);
+
     STORAGE.with(|storage| {
         storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
     });
 
-    // Create line items
-    for line_req in req.line_items {
-        let account = STORAGE
-            .with(|storage| storage.borrow().trial_balance_accounts.get(&line_req.account_id))
-            .ok_or("Account not found")?;
-
-        let line_item = AjeLineItem {
-            id: next_aje_line_item_id(),
-            aje_id: aje.id,
-            account_id: line_req.account_id,
-            account_name: account.account_name.clone(),
-            account_number: account.account_number.clone(),
-            debit_amount: line_req.debit_amount,
-            credit_amount: line_req.credit_amount,
-            description: line_req.description,
-        };
+    log_activity(
+        caller,
+        "create_aje".to_string(),
+        "aje".to_string(),
+        aje.id.to_string(),
+        format!("AJE {} created", aje.aje_number),
+        encode_args((aje.clone(),)).ok(),
+    );
 
-        STORAGE.with(|storage| {
-            storage.borrow_mut().aje_line_items.insert(line_item.id, line_item);
-        });
+    Ok(aje)
+}
+
+// Edit a draft AJE; the line items given replace the existing ones
+pub fn update_aje(caller: Principal, req: UpdateAjeRequest) -> Result<AdjustingJournalEntry> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut aje = STORAGE
+        .with(|storage| storage.borrow().adjusting_entries.get(&req.aje_id))
+        .ok_or("AJE not found")?;
+
+    if aje.created_by != caller && !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to edit AJE".to_string());
+    }
+
+    if !matches!(aje.status, AjeStatus::Draft) {
+        return Err(format!("AJE cannot be edited in {:?} status", aje.status));
+    }
+
+    let total = validate_line_items(aje.trial_balance_id, &req.line_items)?;
+    if let Some(entry_type) = req.entry_type {
+        // Moving between AJE and RJE sequences takes the next number in the new one
+        if number_prefix(&entry_type) != number_prefix(&entry_type_of(&aje)) {
+            aje.aje_number = next_aje_number(aje.engagement_id, &entry_type);
+        }
+        aje.entry_type = Some(entry_type);
     }
+    aje.description = req.description;
+    aje.amount = total;
+    replace_line_items(aje.id, req.line_items);
+    sign_aje(&mut aje, caller, "UPDATE_AJE", time());
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
+    });
 
     log_activity(
         caller,
-        "create_aje".to_string(),
+        "update_aje".to_string(),
         "aje".to_string(),
         aje.id.to_string(),
-        format!("AJE {} created", aje.id),
+        format!("AJE {} updated", aje.aje_number),
         encode_args((aje.clone(),)).ok(),
     );
 
     Ok(aje)
 }
 
+// Each line is a debit or a credit on an account of the trial balance, and
+// the entry balances. Returns the total debits.
+fn validate_line_items(trial_balance_id: u64, line_items: &[CreateAjeLineItemRequest]) -> Result<i64> {
+    if line_items.len() < 2 {
+        return Err("An AJE needs at least two line items".to_string());
+    }
+
+    for (index, line) in line_items.iter().enumerate() {
+        let line_number = index + 1;
+        if line.debit_amount < 0 || line.credit_amount < 0 {
+            return Err(format!("Line {}: amounts cannot be negative", line_number));
+        }
+        if line.debit_amount > 0 && line.credit_amount > 0 {
+            return Err(format!("Line {}: a line cannot be both a debit and a credit", line_number));
+        }
+        if line.debit_amount == 0 && line.credit_amount == 0 {
+            return Err(format!("Line {}: a debit or credit amount is required", line_number));
+        }
+
+        let account = STORAGE
+            .with(|storage| storage.borrow().trial_balance_accounts.get(&line.account_id))
+            .ok_or_else(|| format!("Line {}: account {} not found", line_number, line.account_id))?;
+        if account.trial_balance_id != trial_balance_id {
+            return Err(format!(
+                "Line {}: account {} is not in the AJE's trial balance",
+                line_number, account.account_number
+            ));
+        }
+    }
+
+    let total_debits: i64 = line_items.iter().map(|li| li.debit_amount).sum();
+    let total_credits: i64 = line_items.iter().map(|li| li.credit_amount).sum();
+
+    if total_debits != total_credits {
+        return Err(format!(
+            "AJE does not balance: debits {} != credits {}",
+            total_debits, total_credits
+        ));
+    }
+
+    Ok(total_debits)
+}
+
+fn replace_line_items(aje_id: u64, line_items: Vec<CreateAjeLineItemRequest>) {
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let existing: Vec<u64> = storage
+            .aje_line_items
+            .iter()
+            .filter(|(_, item)| item.aje_id == aje_id)
+            .map(|(id, _)| id)
+            .collect();
+        for id in existing {
+            storage.aje_line_items.remove(&id);
+        }
+
+        for line_req in line_items {
+            // Accounts were checked by validate_line_items
+            let Some(account) = storage.trial_balance_accounts.get(&line_req.account_id) else {
+                continue;
+            };
+            let line_item = AjeLineItem {
+                id: next_aje_line_item_id(),
+                aje_id,
+                account_id: line_req.account_id,
+                account_name: account.account_name.clone(),
+                account_number: account.account_number.clone(),
+                debit_amount: line_req.debit_amount,
+                credit_amount: line_req.credit_amount,
+                description: line_req.description,
+            };
+            storage.aje_line_items.insert(line_item.id, line_item);
+        }
+    });
+}
+
+fn number_prefix(entry_type: &AjeEntryType) -> &'static str {
+    match entry_type {
+        AjeEntryType::Reclassification => "RJE",
+        AjeEntryType::Adjusting | AjeEntryType::AutoReversing => "AJE",
+    }
+}
+
+// Next number in the engagement's sequence for the entry type, e.g. AJE-004
+fn next_aje_number(engagement_id: u64, entry_type: &AjeEntryType) -> String {
+    let prefix = number_prefix(entry_type);
+    let last = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .adjusting_entries
+            .iter()
+            .filter(|(_, aje)| aje.engagement_id == engagement_id)
+            .filter_map(|(_, aje)| {
+                aje.aje_number
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_prefix('-'))
+                    .and_then(|n| n.parse::<u32>().ok())
+            })
+            .max()
+            .unwrap_or(0)
+    });
+
+    format!("{}-{:03}", prefix, last + 1)
+}
+
+// Hash the entry's content and sign the action
+fn sign_aje(aje: &mut AdjustingJournalEntry, caller: Principal, action: &str, timestamp: u64) {
+    aje.proof_version = Some(PROOF_VERSION);
+    aje.blockchain_hash = generate_hash(&proof_data(aje));
+    aje.blockchain_signature = generate_signature(
+        aje.id,
+        caller,
+        action,
+        "AdjustingJournalEntry",
+        &aje.id.to_string(),
+        timestamp,
+        &aje.blockchain_hash,
+    );
+}
+
 // Submit AJE for review (change status from Draft to Proposed)
 pub fn submit_aje(caller: Principal, aje_id: u64) -> Result<AdjustingJournalEntry> {
     let user = auth::get_user(caller).ok_or("User not found")?;
@@ -255,18 +389,57 @@ pub fn post_aje(caller: Principal, aje_id: u64) -> Result<AdjustingJournalEntry>
         return Err(format!("AJE cannot be posted from {:?} status", aje.status));
     }
 
-    // Get line items
-    let line_items: Vec<AjeLineItem> = STORAGE.with(|storage| {
-        storage
-            .borrow()
-            .aje_line_items
-            .iter()
-            .filter(|(_, item)| item.aje_id == aje_id)
-            .map(|(_, item)| item)
-            .collect()
+    // An auto-reversing entry is mirrored into the next period right away,
+    // or held until that period's trial balance exists
+    let reverses =
+        entry_type_of(&aje) == AjeEntryType::AutoReversing && aje.reverses_aje_id.is_none();
+    let next = if reverses {
+        STORAGE
+            .with(|storage| storage.borrow().trial_balances.get(&aje.trial_balance_id))
+            .and_then(|tb| trial_balance::next_period_trial_balance(&tb))
+    } else {
+        None
+    };
+    if let Some(next_tb) = &next {
+        check_reversal_accounts(&aje, next_tb)?;
+    }
+
+    apply_to_trial_balance(&aje)?;
+    aje.status = AjeStatus::Posted;
+
+    if reverses {
+        match &next {
+            Some(next_tb) => aje.reversal_aje_id = Some(create_reversal(caller, &aje, next_tb)?.id),
+            None => aje.reversal_pending = Some(true),
+        }
+    }
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
     });
 
-    // Apply adjustments to trial balance accounts
+    working_papers::mark_trial_balance_papers_stale(
+        caller,
+        aje.trial_balance_id,
+        &format!("AJE {} posted to the trial balance", aje.aje_number),
+    );
+
+    log_activity(
+        caller,
+        "post_aje".to_string(),
+        "aje".to_string(),
+        aje.id.to_string(),
+        format!("AJE {} posted", aje.id),
+        encode_args((aje.clone(),)).ok(),
+    );
+
+    Ok(aje)
+}
+
+// Apply an AJE's lines to the balances of its trial balance
+fn apply_to_trial_balance(aje: &AdjustingJournalEntry) -> Result<()> {
+    let line_items = line_items_for(aje.id);
+
     for item in line_items {
         let mut account = STORAGE
             .with(|storage| storage.borrow().trial_balance_accounts.get(&item.account_id))
@@ -280,29 +453,276 @@ pub fn post_aje(caller: Principal, aje_id: u64) -> Result<AdjustingJournalEntry>
         });
     }
 
-    aje.status = AjeStatus::Posted;
-
-    STORAGE.with(|storage| {
-        storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
-    });
-
     // Mark trial balance as adjusted
     let mut tb = STORAGE
         .with(|storage| storage.borrow().trial_balances.get(&aje.trial_balance_id))
         .ok_or("Trial balance not found")?;
-    
+
     tb.is_adjusted = true;
-    
+
     STORAGE.with(|storage| {
         storage.borrow_mut().trial_balances.insert(tb.id, tb);
     });
 
+    Ok(())
+}
+
+// Post the mirror of an auto-reversing entry to the next period's trial
+// balance, matching accounts by number
+fn create_reversal(
+    caller: Principal,
+    original: &AdjustingJournalEntry,
+    next_tb: &TrialBalance,
+) -> Result<AdjustingJournalEntry> {
+    check_reversal_accounts(original, next_tb)?;
+    let next_accounts = trial_balance::accounts_by_number(next_tb.id);
+    let original_items = line_items_for(original.id);
+
+    let timestamp = time();
+    let mut reversal = AdjustingJournalEntry {
+        id: next_aje_id(),
+        engagement_id: next_tb.engagement_id,
+        trial_balance_id: next_tb.id,
+        aje_number: format!("{}-R", original.aje_number),
+        description: format!("Reversal of {}: {}", original.aje_number, original.description),
+        entry_type: Some(AjeEntryType::AutoReversing),
+        status: AjeStatus::Posted,
+        amount: original.amount,
+        created_at: timestamp,
+        created_by: caller,
+        reviewed_at: original.reviewed_at,
+        reviewed_by: original.reviewed_by,
+        approved_at: original.approved_at,
+        approved_by: original.approved_by,
+        classification: None,
+        passed_reason: None,
+        passed_at: None,
+        passed_by: None,
+        reverses_aje_id: Some(original.id),
+        reversal_aje_id: None,
+        reversal_pending: Some(false),
+        blockchain_hash: String::new(),
+        blockchain_signature: String::new(),
+        proof_version: None,
+    };
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for item in &original_items {
+            let account = &next_accounts[&item.account_number];
+            let line_item = AjeLineItem {
+                id: next_aje_line_item_id(),
+                aje_id: reversal.id,
+                account_id: account.id,
+                account_name: account.account_name.clone(),
+                account_number: account.account_number.clone(),
+                debit_amount: item.credit_amount,
+                credit_amount: item.debit_amount,
+                description: item.description.clone(),
+            };
+            storage.aje_line_items.insert(line_item.id, line_item);
+        }
+    });
+    sign_aje(&mut reversal, caller, "REVERSE_AJE", timestamp);
+    STORAGE.with(|storage| {
+        storage.borrow_mut().adjusting_entries.insert(reversal.id, reversal.clone());
+    });
+    apply_to_trial_balance(&reversal)?;
+
+    working_papers::mark_trial_balance_papers_stale(
+        caller,
+        next_tb.id,
+        &format!("Reversal of AJE {} posted to the trial balance", original.aje_number),
+    );
+
     log_activity(
         caller,
-        "post_aje".to_string(),
+        "reverse_aje".to_string(),
+        "aje".to_string(),
+        reversal.id.to_string(),
+        format!(
+            "AJE {} reversed into trial balance {}",
+            original.aje_number, next_tb.id
+        ),
+        encode_args((reversal.clone(),)).ok(),
+    );
+
+    Ok(reversal)
+}
+
+// Every account of the entry must exist in the next period's trial balance
+fn check_reversal_accounts(original: &AdjustingJournalEntry, next_tb: &TrialBalance) -> Result<()> {
+    let next_accounts = trial_balance::accounts_by_number(next_tb.id);
+    let missing: Vec<String> = line_items_for(original.id)
+        .into_iter()
+        .filter(|item| !next_accounts.contains_key(&item.account_number))
+        .map(|item| item.account_number)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Accounts {} are not in the next period's trial balance",
+            missing.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+// Post the held reversals whose next period is this trial balance
+pub fn release_pending_reversals(caller: Principal, trial_balance: &TrialBalance) -> Vec<u64> {
+    let pending: Vec<AdjustingJournalEntry> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .adjusting_entries
+            .iter()
+            .filter(|(_, aje)| aje.reversal_pending == Some(true))
+            .map(|(_, aje)| aje)
+            .collect()
+    });
+
+    let mut released = Vec::new();
+    for mut aje in pending {
+        let is_next = STORAGE
+            .with(|storage| storage.borrow().trial_balances.get(&aje.trial_balance_id))
+            .and_then(|tb| trial_balance::next_period_trial_balance(&tb))
+            .is_some_and(|next| next.id == trial_balance.id);
+        if !is_next {
+            continue;
+        }
+
+        if let Ok(reversal) = create_reversal(caller, &aje, trial_balance) {
+            aje.reversal_aje_id = Some(reversal.id);
+            aje.reversal_pending = Some(false);
+            STORAGE.with(|storage| {
+                storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
+            });
+            released.push(reversal.id);
+        }
+    }
+
+    released
+}
+
+// Posted entries for the client to record, leaving out reclassifications
+pub fn list_book_adjustments(caller: Principal, trial_balance_id: u64) -> Result<Vec<BookAdjustment>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut ajes: Vec<AdjustingJournalEntry> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .adjusting_entries
+            .iter()
+            .filter(|(_, aje)| {
+                aje.trial_balance_id == trial_balance_id
+                    && matches!(aje.status, AjeStatus::Posted)
+                    && entry_type_of(aje) != AjeEntryType::Reclassification
+            })
+            .map(|(_, aje)| aje)
+            .collect()
+    });
+    ajes.sort_by_key(|aje| aje.id);
+
+    Ok(ajes
+        .into_iter()
+        .map(|aje| BookAdjustment {
+            line_items: line_items_for(aje.id),
+            aje,
+        })
+        .collect())
+}
+
+// AJEs stored before entry types were plain adjustments
+pub fn entry_type_of(aje: &AdjustingJournalEntry) -> AjeEntryType {
+    aje.entry_type.clone().unwrap_or(AjeEntryType::Adjusting)
+}
+
+fn line_items_for(aje_id: u64) -> Vec<AjeLineItem> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .aje_line_items
+            .iter()
+            .filter(|(_, item)| item.aje_id == aje_id)
+            .map(|(_, item)| item)
+            .collect()
+    })
+}
+
+// Data covered by an AJE's blockchain hash, in the format it was signed
+// under. Version 2 adds the stored line items, so callers sign only after
+// replacing the lines; entries signed before then cover the header only.
+fn proof_data(aje: &AdjustingJournalEntry) -> String {
+    if aje.proof_version.is_none() {
+        return format!(
+            "{}:{}:{}:{}:{}:{}",
+            aje.id,
+            aje.engagement_id,
+            aje.trial_balance_id,
+            aje.aje_number,
+            aje.description,
+            aje.amount
+        );
+    }
+
+    let mut lines: Vec<(u64, i64, i64)> = line_items_for(aje.id)
+        .iter()
+        .map(|item| (item.account_id, item.debit_amount, item.credit_amount))
+        .collect();
+    lines.sort();
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|(account_id, debit, credit)| format!("{}/{}/{}", account_id, debit, credit))
+        .collect();
+
+    format!(
+        "{}:{}:{}:{}:{}:{}:{}",
+        aje.id,
+        aje.engagement_id,
+        aje.trial_balance_id,
+        aje.aje_number,
+        aje.description,
+        aje.amount,
+        lines.join(",")
+    )
+}
+
+// Record an AJE the client declined to book as an uncorrected misstatement
+pub fn pass_aje(caller: Principal, req: PassAjeRequest) -> Result<AdjustingJournalEntry> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_review_work(&user) {
+        return Err("Insufficient permissions to pass AJE".to_string());
+    }
+
+    let mut aje = STORAGE
+        .with(|storage| storage.borrow().adjusting_entries.get(&req.aje_id))
+        .ok_or("AJE not found")?;
+
+    if !matches!(
+        aje.status,
+        AjeStatus::Proposed | AjeStatus::Reviewed | AjeStatus::Approved
+    ) {
+        return Err(format!("AJE cannot be passed from {:?} status", aje.status));
+    }
+    if req.reason.trim().is_empty() {
+        return Err("A reason is required to pass an AJE".to_string());
+    }
+
+    aje.status = AjeStatus::Passed;
+    aje.classification = Some(req.classification);
+    aje.passed_reason = Some(req.reason);
+    aje.passed_at = Some(time());
+    aje.passed_by = Some(caller);
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().adjusting_entries.insert(aje.id, aje.clone());
+    });
+
+    log_activity(
+        caller,
+        "pass_aje".to_string(),
         "aje".to_string(),
         aje.id.to_string(),
-        format!("AJE {} posted", aje.id),
+        format!("AJE {} passed as {:?}", aje.id, aje.classification),
         encode_args((aje.clone(),)).ok(),
     );
 
@@ -352,6 +772,32 @@ pub fn list_ajes_by_engagement(caller: Principal, engagement_id: u64) -> Result<
     Ok(ajes)
 }
 
+// Net effect (debit positive) of posted AJEs on each trial balance account,
+// keyed by account ID, with the AJEs that make it up
+pub fn posted_effects_by_account(trial_balance_id: u64) -> HashMap<u64, (i64, Vec<u64>)> {
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let mut effects: HashMap<u64, (i64, Vec<u64>)> = HashMap::new();
+
+        for (_, item) in storage.aje_line_items.iter() {
+            let posted = storage.adjusting_entries.get(&item.aje_id).is_some_and(|aje| {
+                aje.trial_balance_id == trial_balance_id && matches!(aje.status, AjeStatus::Posted)
+            });
+            if !posted {
+                continue;
+            }
+
+            let (net, aje_ids) = effects.entry(item.account_id).or_default();
+            *net += item.debit_amount - item.credit_amount;
+            if !aje_ids.contains(&item.aje_id) {
+                aje_ids.push(item.aje_id);
+            }
+        }
+
+        effects
+    })
+}
+
 // Verify blockchain proof for an AJE
 pub fn verify_aje_blockchain(caller: Principal, aje_id: u64) -> Result<AjeBlockchainVerification> {
     let _user = auth::get_user(caller).ok_or("User not found")?;
@@ -361,16 +807,7 @@ pub fn verify_aje_blockchain(caller: Principal, aje_id: u64) -> Result<AjeBlockc
         .ok_or("AJE not found")?;
 
     // Regenerate hash and verify
-    let data = format!(
-        "{}:{}:{}:{}:{}:{}",
-        aje.id,
-        aje.engagement_id,
-        aje.trial_balance_id,
-        aje.aje_number,
-        aje.description,
-        aje.amount
-    );
-    let computed_hash = generate_hash(&data);
+    let computed_hash = generate_hash(&proof_data(&aje));
 
     let hash_matches = computed_hash == aje.blockchain_hash;
 
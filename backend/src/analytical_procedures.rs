@@ -0,0 +1,383 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::data_import;
+use crate::data_quality;
+use crate::financial_statements;
+use crate::general_ledger::parse_amount_cents;
+use crate::storage::{next_expectation_id, STORAGE};
+use crate::trend_analysis;
+use crate::types::{
+    AnalyticalExpectation, CreateExpectationRequest, ExpectationMethod, ExpectationTarget,
+    ExplainVarianceRequest, RegressionPoint, Result, TrialBalanceAccount, VarianceAnalysis,
+};
+
+const MIN_REGRESSION_POINTS: usize = 3;
+
+// Set an expectation for an account or FS line and compare it with the trial balance
+pub fn create_analytical_expectation(
+    caller: Principal,
+    req: CreateExpectationRequest,
+) -> Result<AnalyticalExpectation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to set analytical expectations".to_string());
+    }
+
+    if req.threshold_amount.is_none() && req.threshold_percent.is_none() {
+        return Err("Set a threshold amount, a threshold percentage or both".to_string());
+    }
+    if req.threshold_amount.is_some_and(|t| t < 0) || req.threshold_percent.is_some_and(|t| t < 0.0) {
+        return Err("Thresholds cannot be negative".to_string());
+    }
+
+    let trial_balance = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&req.trial_balance_id))
+        .ok_or_else(|| "Trial balance not found".to_string())?;
+
+    let accounts = trend_analysis::accounts_for(trial_balance.id);
+    let actual = target_amount(&accounts, &req.target).ok_or_else(|| match &req.target {
+        ExpectationTarget::Account(number) => format!("Account {} is not in the trial balance", number),
+        ExpectationTarget::FsLine(code) => format!("No accounts are mapped to {}", code),
+    })?;
+
+    let (expected, basis, r_squared) = match &req.method {
+        ExpectationMethod::PriorYearGrowth { growth_percent } => {
+            let (prior, _) = trend_analysis::prior_periods(&trial_balance, 1);
+            let (period, prior_accounts) = prior
+                .first()
+                .ok_or_else(|| "No prior-year trial balance in the engagement chain".to_string())?;
+            let prior_amount = target_amount(prior_accounts, &req.target).unwrap_or(0);
+            let expected = (prior_amount as f64 * (1.0 + growth_percent / 100.0)).round() as i64;
+            let basis = format!(
+                "Prior year {} ({}) of {:.2} grown by {}%",
+                period.engagement_name,
+                period.period_end_date,
+                prior_amount as f64 / 100.0,
+                growth_percent
+            );
+            (expected, basis, None)
+        }
+        ExpectationMethod::Budget {
+            dataset_id,
+            sheet_name,
+            key_column,
+            amount_column,
+        } => {
+            let expected = budget_amount(
+                caller,
+                *dataset_id,
+                sheet_name.as_deref(),
+                key_column,
+                amount_column,
+                &req.target,
+                req.file_data.as_deref(),
+            )?;
+            let basis = format!("Budget from dataset {} ({})", dataset_id, amount_column);
+            (expected, basis, None)
+        }
+        ExpectationMethod::Regression {
+            driver_name,
+            observations,
+            current_driver,
+        } => {
+            let (intercept, slope, r_squared) = fit_regression(observations)?;
+            let expected = (intercept + slope * current_driver).round() as i64;
+            let basis = format!(
+                "Regression on {} over {} periods: {:.2} + {:.4} x {}",
+                driver_name,
+                observations.len(),
+                intercept / 100.0,
+                slope / 100.0,
+                current_driver
+            );
+            (expected, basis, Some(r_squared))
+        }
+    };
+
+    let variance = actual - expected;
+    let outside_threshold =
+        is_outside_threshold(variance, expected, req.threshold_amount, req.threshold_percent);
+
+    let expectation = AnalyticalExpectation {
+        id: next_expectation_id(),
+        engagement_id: trial_balance.engagement_id,
+        trial_balance_id: trial_balance.id,
+        target_name: target_name(&accounts, &req.target),
+        target: req.target.clone(),
+        method: req.method.clone(),
+        basis,
+        r_squared,
+        expected,
+        actual,
+        variance,
+        variance_percent: (expected != 0).then(|| variance as f64 / expected.abs() as f64 * 100.0),
+        threshold_amount: req.threshold_amount,
+        threshold_percent: req.threshold_percent,
+        outside_threshold,
+        explanation: None,
+        explained_by: None,
+        explained_at: None,
+        created_at: time(),
+        created_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .analytical_expectations
+            .insert(expectation.id, expectation.clone());
+    });
+
+    log_activity(
+        caller,
+        "create_analytical_expectation".to_string(),
+        "analytical_expectation".to_string(),
+        expectation.id.to_string(),
+        format!(
+            "Expectation for {}: expected {:.2}, actual {:.2}{}",
+            expectation.target_name,
+            expected as f64 / 100.0,
+            actual as f64 / 100.0,
+            if outside_threshold { ", outside threshold" } else { "" }
+        ),
+        encode_args((expectation.clone(),)).ok(),
+    );
+
+    Ok(expectation)
+}
+
+// Document why a variance arose
+pub fn explain_variance(caller: Principal, req: ExplainVarianceRequest) -> Result<AnalyticalExpectation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to explain variances".to_string());
+    }
+
+    if req.explanation.trim().is_empty() {
+        return Err("Explanation cannot be empty".to_string());
+    }
+
+    let mut expectation = STORAGE
+        .with(|storage| storage.borrow().analytical_expectations.get(&req.expectation_id))
+        .ok_or_else(|| "Analytical expectation not found".to_string())?;
+
+    expectation.explanation = Some(req.explanation.trim().to_string());
+    expectation.explained_by = Some(caller);
+    expectation.explained_at = Some(time());
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .analytical_expectations
+            .insert(expectation.id, expectation.clone());
+    });
+
+    log_activity(
+        caller,
+        "explain_variance".to_string(),
+        "analytical_expectation".to_string(),
+        expectation.id.to_string(),
+        format!("Variance on {} explained", expectation.target_name),
+        encode_args((expectation.clone(),)).ok(),
+    );
+
+    Ok(expectation)
+}
+
+// Get analytical expectation by ID
+pub fn get_analytical_expectation(caller: Principal, id: u64) -> Result<AnalyticalExpectation> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().analytical_expectations.get(&id))
+        .ok_or_else(|| "Analytical expectation not found".to_string())
+}
+
+// List analytical expectations by engagement
+pub fn list_expectations_by_engagement(
+    caller: Principal,
+    engagement_id: u64,
+) -> Result<Vec<AnalyticalExpectation>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(expectations_where(|e| e.engagement_id == engagement_id))
+}
+
+// Variances outside threshold that still need an explanation
+pub fn unexplained_variances(engagement_id: u64) -> Vec<AnalyticalExpectation> {
+    expectations_where(|e| {
+        e.engagement_id == engagement_id && e.outside_threshold && e.explanation.is_none()
+    })
+}
+
+// Working paper variance rows from the expectations set on a trial balance
+pub fn variance_rows_for_trial_balance(trial_balance_id: u64) -> Vec<VarianceAnalysis> {
+    expectations_where(|e| e.trial_balance_id == trial_balance_id)
+        .into_iter()
+        .map(|e| VarianceAnalysis {
+            item_name: e.target_name,
+            actual: e.actual as f64 / 100.0,
+            expected: e.expected as f64 / 100.0,
+            variance: e.variance as f64 / 100.0,
+            variance_percent: e.variance_percent.unwrap_or(0.0),
+        })
+        .collect()
+}
+
+fn expectations_where(filter: impl Fn(&AnalyticalExpectation) -> bool) -> Vec<AnalyticalExpectation> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .analytical_expectations
+            .iter()
+            .map(|(_, e)| e)
+            .filter(|e| filter(e))
+            .collect()
+    })
+}
+
+// Target amount in its natural sign; None when nothing in the trial balance matches
+fn target_amount(accounts: &[TrialBalanceAccount], target: &ExpectationTarget) -> Option<i64> {
+    match target {
+        ExpectationTarget::Account(number) => {
+            let matching: Vec<&TrialBalanceAccount> = accounts
+                .iter()
+                .filter(|a| a.account_number == *number)
+                .collect();
+            (!matching.is_empty()).then(|| {
+                matching
+                    .iter()
+                    .map(|a| financial_statements::net_balance(a))
+                    .sum()
+            })
+        }
+        ExpectationTarget::FsLine(code) => {
+            let mapped = accounts
+                .iter()
+                .any(|a| a.fs_line_item.as_deref() == Some(code.as_str()));
+            let totals = financial_statements::fs_line_totals(accounts);
+            // Subtotals exist whether or not anything is mapped under them
+            totals
+                .get(code)
+                .copied()
+                .filter(|total| mapped || *total != 0)
+        }
+    }
+}
+
+fn target_name(accounts: &[TrialBalanceAccount], target: &ExpectationTarget) -> String {
+    match target {
+        ExpectationTarget::Account(number) => accounts
+            .iter()
+            .find(|a| a.account_number == *number)
+            .map(|a| format!("{} {}", a.account_number, a.account_name))
+            .unwrap_or_else(|| number.clone()),
+        ExpectationTarget::FsLine(code) => trend_analysis::fs_line_order()
+            .into_iter()
+            .find(|(line_code, _)| line_code == code)
+            .map(|(_, name)| name)
+            .unwrap_or_else(|| code.clone()),
+    }
+}
+
+// Sum of the budget rows whose key column holds the target's account number or code
+fn budget_amount(
+    caller: Principal,
+    dataset_id: u64,
+    sheet_name: Option<&str>,
+    key_column: &str,
+    amount_column: &str,
+    target: &ExpectationTarget,
+    file_data: Option<&[u8]>,
+) -> Result<i64> {
+    let dataset = data_import::get_dataset(caller, dataset_id)?;
+    data_quality::ensure_not_blocked(&dataset)?;
+
+    let sheet_name = match sheet_name {
+        Some(name) => name.to_string(),
+        None => dataset
+            .sheets
+            .first()
+            .map(|s| s.name.clone())
+            .ok_or_else(|| "Dataset has no sheets".to_string())?,
+    };
+    let population = data_import::load_population(caller, &dataset, &sheet_name, file_data)?;
+    let column = |name: &str| {
+        population
+            .headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| format!("Column '{}' not found", name))
+    };
+    let key_idx = column(key_column)?;
+    let amount_idx = column(amount_column)?;
+
+    let key = match target {
+        ExpectationTarget::Account(number) => number,
+        ExpectationTarget::FsLine(code) => code,
+    };
+    let amounts: Vec<i64> = population
+        .rows
+        .iter()
+        .filter(|row| row.get(key_idx).map(|v| v.trim()) == Some(key.as_str()))
+        .filter_map(|row| row.get(amount_idx).and_then(|v| parse_amount_cents(v)))
+        .collect();
+
+    if amounts.is_empty() {
+        return Err(format!("No budget amount for {} in sheet '{}'", key, sheet_name));
+    }
+
+    Ok(amounts.iter().sum())
+}
+
+// Ordinary least squares of amount on driver: (intercept, slope, r squared)
+fn fit_regression(points: &[RegressionPoint]) -> Result<(f64, f64, f64)> {
+    if points.len() < MIN_REGRESSION_POINTS {
+        return Err(format!(
+            "Regression needs at least {} observations",
+            MIN_REGRESSION_POINTS
+        ));
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.driver).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.amount as f64).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.driver - mean_x).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|p| (p.driver - mean_x) * (p.amount as f64 - mean_y))
+        .sum();
+    let syy: f64 = points.iter().map(|p| (p.amount as f64 - mean_y).powi(2)).sum();
+
+    if sxx == 0.0 {
+        return Err("Driver values must not all be the same".to_string());
+    }
+
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let r_squared = if syy == 0.0 { 1.0 } else { sxy * sxy / (sxx * syy) };
+
+    Ok((intercept, slope, r_squared))
+}
+
+// The tighter of the amount and percentage thresholds applies
+fn is_outside_threshold(
+    variance: i64,
+    expected: i64,
+    threshold_amount: Option<i64>,
+    threshold_percent: Option<f64>,
+) -> bool {
+    let by_percent = threshold_percent.map(|p| (expected.abs() as f64 * p / 100.0).round() as i64);
+    let allowed = match (threshold_amount, by_percent) {
+        (Some(amount), Some(percent)) => amount.min(percent),
+        (Some(amount), None) => amount,
+        (None, Some(percent)) => percent,
+        (None, None) => return false,
+    };
+    variance.abs() > allowed
+}
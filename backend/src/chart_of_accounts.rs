@@ -0,0 +1,387 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::{HashMap, HashSet};
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::storage::{next_coa_account_id, STORAGE};
+use crate::types::{
+    AccountType, CoaAccount, CoaAccountInput, CoaMappingReport, CoaOwner, EngagementLink, NormalBalance,
+    Result, TrialBalanceAccount,
+};
+
+// Add or update chart of accounts entries for a client or entity, keyed by
+// account number. Updating an entry reactivates it.
+pub fn set_coa_accounts(caller: Principal, owner: CoaOwner, accounts: Vec<CoaAccountInput>) -> Result<Vec<CoaAccount>> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to maintain the chart of accounts".to_string());
+    }
+
+    validate_owner(&owner)?;
+    if accounts.is_empty() {
+        return Err("No accounts given".to_string());
+    }
+    let mut numbers = HashSet::new();
+    for input in &accounts {
+        let number = input.account_number.trim();
+        if number.is_empty() {
+            return Err("Account number is required".to_string());
+        }
+        if !numbers.insert(number.to_string()) {
+            return Err(format!("Account {} is listed more than once", number));
+        }
+    }
+
+    let existing = by_number(&owner, false);
+    let now = time();
+    let saved: Vec<CoaAccount> = accounts
+        .into_iter()
+        .map(|input| {
+            let account_number = input.account_number.trim().to_string();
+            let current = existing.get(&account_number);
+            CoaAccount {
+                id: current.map(|account| account.id).unwrap_or_else(next_coa_account_id),
+                owner: owner.clone(),
+                normal_balance: input
+                    .normal_balance
+                    .unwrap_or_else(|| default_normal_balance(&input.account_type)),
+                account_number,
+                account_name: input.account_name.trim().to_string(),
+                account_type: input.account_type,
+                fs_line_item: input.fs_line_item.filter(|line| !line.is_empty()),
+                is_active: true,
+                created_at: current.map(|account| account.created_at).unwrap_or(now),
+                created_by: current.map(|account| account.created_by).unwrap_or(caller),
+                updated_at: now,
+            }
+        })
+        .collect();
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for account in &saved {
+            storage.coa_accounts.insert(account.id, account.clone());
+        }
+    });
+
+    log_activity(
+        caller,
+        "set_coa_accounts".to_string(),
+        "chart_of_accounts".to_string(),
+        owner_label(&owner),
+        format!("Saved {} chart of accounts entries for {}", saved.len(), owner_label(&owner)),
+        encode_args((saved.clone(),)).ok(),
+    );
+
+    Ok(saved)
+}
+
+pub fn list_coa_accounts(caller: Principal, owner: CoaOwner) -> Result<Vec<CoaAccount>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut accounts: Vec<CoaAccount> = by_number(&owner, false).into_values().collect();
+    accounts.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+    Ok(accounts)
+}
+
+// Inactive entries stay for history but are no longer applied at import
+pub fn deactivate_coa_account(caller: Principal, coa_account_id: u64) -> Result<CoaAccount> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to maintain the chart of accounts".to_string());
+    }
+
+    let mut account = STORAGE
+        .with(|storage| storage.borrow().coa_accounts.get(&coa_account_id))
+        .ok_or("Chart of accounts entry not found")?;
+    account.is_active = false;
+    account.updated_at = time();
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().coa_accounts.insert(account.id, account.clone());
+    });
+
+    log_activity(
+        caller,
+        "deactivate_coa_account".to_string(),
+        "chart_of_accounts".to_string(),
+        account.id.to_string(),
+        format!("Deactivated account {} in the chart of accounts", account.account_number),
+        encode_args((account.clone(),)).ok(),
+    );
+
+    Ok(account)
+}
+
+// Add a trial balance's accounts that are not yet in the chart, with their
+// current type and mapping, and link them to the new entries
+pub fn adopt_trial_balance_accounts(caller: Principal, trial_balance_id: u64) -> Result<Vec<CoaAccount>> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to maintain the chart of accounts".to_string());
+    }
+
+    let trial_balance = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&trial_balance_id))
+        .ok_or("Trial balance not found")?;
+    let owner = owner_for_engagement(trial_balance.engagement_id)
+        .ok_or("The engagement is not linked to a client or entity")?;
+    let existing = by_number(&owner, false);
+
+    let now = time();
+    let mut adopted: Vec<CoaAccount> = Vec::new();
+    let mut linked: Vec<TrialBalanceAccount> = Vec::new();
+    for mut account in trial_balance_accounts(trial_balance_id) {
+        if account.coa_account_id.is_some() || existing.contains_key(account.account_number.trim()) {
+            continue;
+        }
+        if adopted.iter().any(|coa| coa.account_number == account.account_number.trim()) {
+            continue;
+        }
+        let coa = CoaAccount {
+            id: next_coa_account_id(),
+            owner: owner.clone(),
+            account_number: account.account_number.trim().to_string(),
+            account_name: account.account_name.clone(),
+            account_type: account.account_type.clone(),
+            normal_balance: default_normal_balance(&account.account_type),
+            fs_line_item: account.fs_line_item.clone().filter(|line| !line.is_empty()),
+            is_active: true,
+            created_at: now,
+            created_by: caller,
+            updated_at: now,
+        };
+        account.coa_account_id = Some(coa.id);
+        linked.push(account);
+        adopted.push(coa);
+    }
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for coa in &adopted {
+            storage.coa_accounts.insert(coa.id, coa.clone());
+        }
+        for account in &linked {
+            storage.trial_balance_accounts.insert(account.id, account.clone());
+        }
+    });
+
+    log_activity(
+        caller,
+        "adopt_trial_balance_accounts".to_string(),
+        "chart_of_accounts".to_string(),
+        owner_label(&owner),
+        format!(
+            "Added {} accounts from trial balance {} to the chart of accounts",
+            adopted.len(),
+            trial_balance_id
+        ),
+        encode_args((adopted.clone(),)).ok(),
+    );
+
+    Ok(adopted)
+}
+
+// Link accounts to the engagement's chart of accounts and take their type and
+// FS line from it. Returns how many accounts matched.
+pub fn apply_chart_of_accounts(engagement_id: u64, accounts: &mut [TrialBalanceAccount]) -> usize {
+    let Some(owner) = owner_for_engagement(engagement_id) else {
+        return 0;
+    };
+    let chart = by_number(&owner, true);
+
+    let mut matched = 0;
+    for account in accounts.iter_mut() {
+        if let Some(coa) = chart.get(account.account_number.trim()) {
+            account.coa_account_id = Some(coa.id);
+            account.account_type = coa.account_type.clone();
+            if coa.fs_line_item.is_some() {
+                account.fs_line_item = coa.fs_line_item.clone();
+            }
+            matched += 1;
+        }
+    }
+    matched
+}
+
+// Record an account's FS line in the chart of accounts so later imports pick
+// it up, adding the account to the chart if it is not there yet
+pub fn carry_mapping(caller: Principal, engagement_id: u64, account: &mut TrialBalanceAccount) {
+    let Some(owner) = owner_for_engagement(engagement_id) else {
+        return;
+    };
+    let now = time();
+
+    let coa = match account
+        .coa_account_id
+        .and_then(|id| STORAGE.with(|storage| storage.borrow().coa_accounts.get(&id)))
+        .or_else(|| by_number(&owner, false).remove(account.account_number.trim()))
+    {
+        Some(mut coa) => {
+            coa.fs_line_item = account.fs_line_item.clone();
+            coa.updated_at = now;
+            coa
+        }
+        None => CoaAccount {
+            id: next_coa_account_id(),
+            owner,
+            account_number: account.account_number.trim().to_string(),
+            account_name: account.account_name.clone(),
+            account_type: account.account_type.clone(),
+            normal_balance: default_normal_balance(&account.account_type),
+            fs_line_item: account.fs_line_item.clone(),
+            is_active: true,
+            created_at: now,
+            created_by: caller,
+            updated_at: now,
+        },
+    };
+    account.coa_account_id = Some(coa.id);
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().coa_accounts.insert(coa.id, coa);
+    });
+}
+
+// Accounts of a trial balance that need attention against the chart of accounts
+pub fn get_coa_mapping_report(caller: Principal, trial_balance_id: u64) -> Result<CoaMappingReport> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let trial_balance = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&trial_balance_id))
+        .ok_or("Trial balance not found")?;
+    let owner = owner_for_engagement(trial_balance.engagement_id);
+    let chart = owner.as_ref().map(|owner| by_number(owner, true)).unwrap_or_default();
+    let accounts = trial_balance_accounts(trial_balance_id);
+
+    let mut report = CoaMappingReport {
+        trial_balance_id,
+        owner,
+        matched_accounts: 0,
+        new_accounts: Vec::new(),
+        unmapped_accounts: Vec::new(),
+        type_mismatches: Vec::new(),
+        abnormal_balances: Vec::new(),
+        missing_accounts: Vec::new(),
+    };
+
+    for account in &accounts {
+        let coa = chart.get(account.account_number.trim());
+        match coa {
+            Some(coa) => {
+                report.matched_accounts += 1;
+                if coa.account_type != account.account_type {
+                    report.type_mismatches.push(account.clone());
+                }
+            }
+            None => report.new_accounts.push(account.clone()),
+        }
+        if account.fs_line_item.as_deref().is_none_or(str::is_empty) {
+            report.unmapped_accounts.push(account.clone());
+        }
+
+        let normal = coa
+            .map(|coa| coa.normal_balance.clone())
+            .unwrap_or_else(|| default_normal_balance(&account.account_type));
+        let net = account.debit_balance - account.credit_balance;
+        let abnormal = match normal {
+            NormalBalance::Debit => net < 0,
+            NormalBalance::Credit => net > 0,
+        };
+        if abnormal {
+            report.abnormal_balances.push(account.clone());
+        }
+    }
+
+    let present: HashSet<&str> = accounts.iter().map(|account| account.account_number.trim()).collect();
+    report.missing_accounts = chart
+        .into_values()
+        .filter(|coa| !present.contains(coa.account_number.as_str()))
+        .collect();
+    report.missing_accounts.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+
+    Ok(report)
+}
+
+// Entity engagements use the entity's chart. A client engagement uses the
+// client's own chart, falling back to its entity's when it has none.
+pub fn owner_for_engagement(engagement_id: u64) -> Option<CoaOwner> {
+    let engagement = STORAGE.with(|storage| storage.borrow().engagements.get(&engagement_id))?;
+    match engagement.link {
+        EngagementLink::Entity(entity_id) => Some(CoaOwner::Entity(entity_id)),
+        EngagementLink::Client(client_id) => {
+            let own = CoaOwner::Client(client_id);
+            if !by_number(&own, false).is_empty() {
+                return Some(own);
+            }
+            let entity_id = STORAGE
+                .with(|storage| storage.borrow().clients.get(&client_id))
+                .and_then(|client| client.entity_id);
+            match entity_id.map(CoaOwner::Entity) {
+                Some(entity) if !by_number(&entity, false).is_empty() => Some(entity),
+                _ => Some(own),
+            }
+        }
+        EngagementLink::Organization(_) => None,
+    }
+}
+
+pub fn default_normal_balance(account_type: &AccountType) -> NormalBalance {
+    match account_type {
+        AccountType::Asset | AccountType::Expense => NormalBalance::Debit,
+        AccountType::Liability | AccountType::Equity | AccountType::Revenue => NormalBalance::Credit,
+    }
+}
+
+fn by_number(owner: &CoaOwner, active_only: bool) -> HashMap<String, CoaAccount> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .coa_accounts
+            .iter()
+            .filter(|(_, account)| &account.owner == owner && (account.is_active || !active_only))
+            .map(|(_, account)| (account.account_number.clone(), account))
+            .collect()
+    })
+}
+
+fn trial_balance_accounts(trial_balance_id: u64) -> Vec<TrialBalanceAccount> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .trial_balance_accounts
+            .iter()
+            .filter(|(_, account)| account.trial_balance_id == trial_balance_id)
+            .map(|(_, account)| account)
+            .collect()
+    })
+}
+
+fn validate_owner(owner: &CoaOwner) -> Result<()> {
+    let exists = STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        match owner {
+            CoaOwner::Client(id) => storage.clients.contains_key(id),
+            CoaOwner::Entity(id) => storage.entities.contains_key(id),
+        }
+    });
+    if exists {
+        Ok(())
+    } else {
+        Err(match owner {
+            CoaOwner::Client(_) => "Client not found".to_string(),
+            CoaOwner::Entity(_) => "Entity not found".to_string(),
+        })
+    }
+}
+
+fn owner_label(owner: &CoaOwner) -> String {
+    match owner {
+        CoaOwner::Client(id) => format!("client {}", id),
+        CoaOwner::Entity(id) => format!("entity {}", id),
+    }
+}
@@ -0,0 +1,405 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::dates;
+use crate::entities;
+use crate::fx;
+use crate::organizations;
+use crate::storage::{next_account_id, next_consolidation_id, STORAGE};
+use crate::trial_balance;
+use crate::types::{
+    AccountType, Consolidation, ConsolidateRequest, ConsolidationColumn, ConsolidationRow,
+    CreateTrialBalanceRequest, EliminationEntry, EliminationLine, EngagementLink, IntercompanyMatch,
+    Result, TrialBalanceAccount,
+};
+
+const NCI_LINE: &str = "BS_NCI";
+const NCI_NUMBER: &str = "3997";
+const NCI_PROFIT_LINE: &str = "IS_NCI_PROFIT";
+const NCI_PROFIT_NUMBER: &str = "6997";
+
+// Name, type and FS line of an account number, from the first entity that has it
+struct AccountInfo {
+    account_name: String,
+    account_type: AccountType,
+    fs_line_item: Option<String>,
+}
+
+// Consolidate the organization's entity trial balances into a new trial
+// balance on the group engagement. Each entity is translated into the
+// presentation currency, intercompany balances are eliminated to the extent
+// they agree, manual eliminations are posted and the non-controlling share
+// of each partly owned subsidiary's equity is reclassified to NCI, with its
+// share of profit attributed to NCI as well.
+pub fn consolidate_trial_balances(caller: Principal, req: ConsolidateRequest) -> Result<Consolidation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_engagement(&user) {
+        return Err("Insufficient permissions to consolidate trial balances".to_string());
+    }
+
+    let organization = organizations::get_organization(caller, req.organization_id)?;
+    let engagement = STORAGE
+        .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
+        .ok_or("Engagement not found")?;
+    if !matches!(engagement.link, EngagementLink::Organization(id) if id == organization.id) {
+        return Err("The group engagement must be linked to the organization".to_string());
+    }
+
+    let presentation_currency = fx::normalise_currency(&req.presentation_currency);
+    let period_end = dates::parse_date(&req.period_end_date)
+        .ok_or_else(|| format!("Invalid period end date '{}'", req.period_end_date))?;
+    if req.entities.is_empty() {
+        return Err("At least one entity trial balance is required".to_string());
+    }
+
+    // Translated balances per entity, by account number
+    let mut columns: Vec<ConsolidationColumn> = Vec::with_capacity(req.entities.len());
+    let mut balances: Vec<HashMap<String, i64>> = Vec::with_capacity(req.entities.len());
+    let mut info: BTreeMap<String, AccountInfo> = BTreeMap::new();
+    let mut period_type = None;
+    let mut seen: HashSet<u64> = HashSet::new();
+
+    for input in &req.entities {
+        if !seen.insert(input.entity_id) {
+            return Err(format!("Entity {} is listed more than once", input.entity_id));
+        }
+        if !organization.entity_ids.contains(&input.entity_id) {
+            return Err(format!("Entity {} is not part of organization {}", input.entity_id, organization.id));
+        }
+        if !(input.ownership_percentage > 0.0 && input.ownership_percentage <= 100.0) {
+            return Err(format!("Ownership of entity {} must be above 0% and at most 100%", input.entity_id));
+        }
+
+        let entity = entities::get_entity(caller, input.entity_id)?;
+        let tb = trial_balance::get_trial_balance(caller, input.trial_balance_id)?;
+        if dates::parse_date(&tb.period_end_date) != Some(period_end) {
+            return Err(format!(
+                "Trial balance {} of {} ends on {}, not {}",
+                tb.id, entity.name, tb.period_end_date, req.period_end_date
+            ));
+        }
+        let accounts = trial_balance::get_accounts(caller, tb.id)?;
+        let (lines, translation_difference) =
+            fx::translate_accounts(&tb, &accounts, &presentation_currency, &input.historical_rates)
+                .map_err(|e| format!("{}: {}", entity.name, e))?;
+
+        let mut entity_balances: HashMap<String, i64> = HashMap::new();
+        for (account, line) in accounts.iter().filter(|account| !fx::is_cta(account)).zip(&lines) {
+            *entity_balances.entry(account.account_number.clone()).or_insert(0) += line.translated_balance;
+            record_info(&mut info, account);
+        }
+        if translation_difference != 0 {
+            let source_cta = accounts.iter().find(|account| fx::is_cta(account));
+            if let Some(account) = source_cta {
+                record_info(&mut info, account);
+            }
+            let cta = source_cta
+                .map(|account| account.account_number.clone())
+                .unwrap_or_else(|| fx::CTA_NUMBER.to_string());
+            *entity_balances.entry(cta.clone()).or_insert(0) += translation_difference;
+            info.entry(cta).or_insert_with(|| AccountInfo {
+                account_name: "Foreign currency translation reserve".to_string(),
+                account_type: AccountType::Equity,
+                fs_line_item: Some(fx::CTA_LINE.to_string()),
+            });
+        }
+
+        // The non-controlling share of equity, rounded per account as it is
+        // reclassified, and of profit for attribution
+        let share = (100.0 - input.ownership_percentage) / 100.0;
+        let nci_share_of_equity: i64 = entity_balances
+            .iter()
+            .filter(|(number, _)| info.get(*number).is_some_and(|i| matches!(i.account_type, AccountType::Equity)))
+            .map(|(_, balance)| (*balance as f64 * share).round() as i64)
+            .sum();
+        let profit: i64 = entity_balances
+            .iter()
+            .filter(|(number, _)| {
+                info.get(*number)
+                    .is_some_and(|i| matches!(i.account_type, AccountType::Revenue | AccountType::Expense))
+            })
+            .map(|(_, balance)| balance)
+            .sum();
+
+        period_type = period_type.or(tb.period_type.clone());
+        columns.push(ConsolidationColumn {
+            entity_id: entity.id,
+            entity_name: entity.name,
+            trial_balance_id: tb.id,
+            currency: tb.currency,
+            ownership_percentage: input.ownership_percentage,
+            translation_difference,
+            nci_share_of_equity,
+            nci_share_of_profit: (profit as f64 * share).round() as i64,
+        });
+        balances.push(entity_balances);
+    }
+
+    let column_of = |entity_id: u64| columns.iter().position(|column| column.entity_id == entity_id);
+
+    // Intercompany balances eliminate to the extent they offset; any
+    // difference stays in the consolidated balances
+    let mut eliminations: Vec<EliminationEntry> = Vec::new();
+    let mut intercompany: Vec<IntercompanyMatch> = Vec::with_capacity(req.intercompany_pairs.len());
+    for pair in &req.intercompany_pairs {
+        let balance_of = |entity_id: u64, account_number: &str| -> Result<(usize, i64)> {
+            let idx = column_of(entity_id)
+                .ok_or_else(|| format!("Entity {} is not part of this consolidation", entity_id))?;
+            let balance = balances[idx]
+                .get(account_number)
+                .copied()
+                .ok_or_else(|| format!("Account {} not found for entity {}", account_number, entity_id))?;
+            Ok((idx, balance))
+        };
+        let (idx, balance) = balance_of(pair.entity_id, &pair.account_number)?;
+        let (counterparty_idx, counterparty_balance) =
+            balance_of(pair.counterparty_entity_id, &pair.counterparty_account_number)?;
+
+        let eliminated = if balance.signum() * counterparty_balance.signum() < 0 {
+            balance.abs().min(counterparty_balance.abs())
+        } else {
+            0
+        };
+        if eliminated != 0 {
+            eliminations.push(EliminationEntry {
+                description: format!(
+                    "Intercompany {} ({}) against {} ({})",
+                    pair.account_number,
+                    columns[idx].entity_name,
+                    pair.counterparty_account_number,
+                    columns[counterparty_idx].entity_name
+                ),
+                lines: vec![
+                    reversing_line(&pair.account_number, balance, eliminated),
+                    reversing_line(&pair.counterparty_account_number, counterparty_balance, eliminated),
+                ],
+            });
+        }
+        intercompany.push(IntercompanyMatch {
+            entity_id: pair.entity_id,
+            account_number: pair.account_number.clone(),
+            balance,
+            counterparty_entity_id: pair.counterparty_entity_id,
+            counterparty_account_number: pair.counterparty_account_number.clone(),
+            counterparty_balance,
+            eliminated,
+            difference: balance + counterparty_balance,
+        });
+    }
+
+    for (index, entry) in req.eliminations.iter().enumerate() {
+        validate_elimination(entry, &info).map_err(|e| format!("Elimination {}: {}", index + 1, e))?;
+    }
+    eliminations.extend(req.eliminations.iter().cloned());
+
+    let mut elimination_totals: HashMap<String, i64> = HashMap::new();
+    for line in eliminations.iter().flat_map(|entry| &entry.lines) {
+        *elimination_totals.entry(line.account_number.clone()).or_insert(0) += line.debit_amount - line.credit_amount;
+    }
+
+    // NCI takes its share of each subsidiary equity account, and its share of
+    // profit is attributed (Dr profit attributable to NCI, Cr NCI), so the NCI
+    // account ends at the reported non-controlling interest
+    let mut nci_totals: HashMap<String, i64> = HashMap::new();
+    for (column, entity_balances) in columns.iter().zip(&balances) {
+        if column.ownership_percentage >= 100.0 {
+            continue;
+        }
+        let share = (100.0 - column.ownership_percentage) / 100.0;
+        for (number, balance) in entity_balances {
+            if info.get(number).is_some_and(|i| matches!(i.account_type, AccountType::Equity)) {
+                *nci_totals.entry(number.clone()).or_insert(0) -= (*balance as f64 * share).round() as i64;
+            }
+        }
+        *nci_totals.entry(NCI_NUMBER.to_string()).or_insert(0) +=
+            column.nci_share_of_equity + column.nci_share_of_profit;
+        *nci_totals.entry(NCI_PROFIT_NUMBER.to_string()).or_insert(0) -= column.nci_share_of_profit;
+    }
+    if nci_totals.contains_key(NCI_NUMBER) {
+        info.entry(NCI_NUMBER.to_string()).or_insert_with(|| AccountInfo {
+            account_name: "Non-controlling interests".to_string(),
+            account_type: AccountType::Equity,
+            fs_line_item: Some(NCI_LINE.to_string()),
+        });
+        info.entry(NCI_PROFIT_NUMBER.to_string()).or_insert_with(|| AccountInfo {
+            account_name: "Profit attributable to non-controlling interests".to_string(),
+            account_type: AccountType::Expense,
+            fs_line_item: Some(NCI_PROFIT_LINE.to_string()),
+        });
+    }
+
+    let rows: Vec<ConsolidationRow> = info
+        .iter()
+        .map(|(number, account)| {
+            let entity_balances: Vec<i64> = balances
+                .iter()
+                .map(|entity_balances| entity_balances.get(number).copied().unwrap_or(0))
+                .collect();
+            let aggregated_balance: i64 = entity_balances.iter().sum();
+            let eliminations = elimination_totals.get(number).copied().unwrap_or(0);
+            let nci_adjustment = nci_totals.get(number).copied().unwrap_or(0);
+            ConsolidationRow {
+                account_number: number.clone(),
+                account_name: account.account_name.clone(),
+                account_type: account.account_type.clone(),
+                fs_line_item: account.fs_line_item.clone(),
+                entity_balances,
+                aggregated_balance,
+                eliminations,
+                nci_adjustment,
+                consolidated_balance: aggregated_balance + eliminations + nci_adjustment,
+            }
+        })
+        .collect();
+
+    let consolidated_tb = trial_balance::create_trial_balance(
+        caller,
+        CreateTrialBalanceRequest {
+            engagement_id: engagement.id,
+            period_end_date: req.period_end_date.clone(),
+            description: req.description.unwrap_or_else(|| {
+                format!("Consolidated trial balance of {} at {}", organization.name, req.period_end_date)
+            }),
+            currency: Some(presentation_currency.clone()),
+            period_type,
+            prior_trial_balance_id: None,
+        },
+    )?;
+
+    let timestamp = time();
+    let accounts: Vec<TrialBalanceAccount> = rows
+        .iter()
+        .map(|row| TrialBalanceAccount {
+            id: next_account_id(),
+            trial_balance_id: consolidated_tb.id,
+            account_number: row.account_number.clone(),
+            account_name: row.account_name.clone(),
+            account_type: row.account_type.clone(),
+            debit_balance: row.consolidated_balance.max(0),
+            credit_balance: (-row.consolidated_balance).max(0),
+            fs_line_item: row.fs_line_item.clone(),
+            notes: String::new(),
+            opening_balance: None,
+            currency: None,
+            coa_account_id: None,
+            is_reconciled: false,
+            created_at: timestamp,
+            created_by: caller,
+        })
+        .collect();
+
+    let nci_share_of_profit: i64 = columns.iter().map(|column| column.nci_share_of_profit).sum();
+    let consolidation = Consolidation {
+        id: next_consolidation_id(),
+        organization_id: organization.id,
+        engagement_id: engagement.id,
+        trial_balance_id: consolidated_tb.id,
+        period_end_date: consolidated_tb.period_end_date.clone(),
+        presentation_currency,
+        non_controlling_interest: columns.iter().map(|column| column.nci_share_of_equity).sum::<i64>()
+            + nci_share_of_profit,
+        nci_share_of_profit,
+        columns,
+        rows,
+        intercompany,
+        eliminations,
+        created_at: timestamp,
+        created_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for account in &accounts {
+            storage.trial_balance_accounts.insert(account.id, account.clone());
+        }
+        storage.consolidations.insert(consolidation.id, consolidation.clone());
+    });
+
+    let snapshot = encode_args((consolidation.clone(),)).ok();
+    log_activity(
+        caller,
+        "consolidate_trial_balances".to_string(),
+        "consolidation".to_string(),
+        consolidation.id.to_string(),
+        format!(
+            "Consolidated {} entities of {} into trial balance {} with {} eliminations",
+            consolidation.columns.len(),
+            organization.name,
+            consolidated_tb.id,
+            consolidation.eliminations.len()
+        ),
+        snapshot,
+    );
+
+    Ok(consolidation)
+}
+
+pub fn get_consolidation(caller: Principal, id: u64) -> Result<Consolidation> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().consolidations.get(&id))
+        .ok_or_else(|| "Consolidation not found".to_string())
+}
+
+pub fn list_consolidations(caller: Principal, organization_id: u64) -> Result<Vec<Consolidation>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .consolidations
+            .iter()
+            .filter(|(_, consolidation)| consolidation.organization_id == organization_id)
+            .map(|(_, consolidation)| consolidation)
+            .collect()
+    }))
+}
+
+fn record_info(info: &mut BTreeMap<String, AccountInfo>, account: &TrialBalanceAccount) {
+    let entry = info.entry(account.account_number.clone()).or_insert_with(|| AccountInfo {
+        account_name: account.account_name.clone(),
+        account_type: account.account_type.clone(),
+        fs_line_item: None,
+    });
+    if entry.fs_line_item.is_none() {
+        entry.fs_line_item = account.fs_line_item.clone();
+    }
+}
+
+// The line taking an amount off a debit-positive balance
+fn reversing_line(account_number: &str, balance: i64, amount: i64) -> EliminationLine {
+    EliminationLine {
+        account_number: account_number.to_string(),
+        debit_amount: if balance < 0 { amount } else { 0 },
+        credit_amount: if balance > 0 { amount } else { 0 },
+    }
+}
+
+fn validate_elimination(entry: &EliminationEntry, info: &BTreeMap<String, AccountInfo>) -> Result<()> {
+    if entry.lines.len() < 2 {
+        return Err("An elimination needs at least two lines".to_string());
+    }
+    for line in &entry.lines {
+        if line.debit_amount < 0 || line.credit_amount < 0 {
+            return Err(format!("Negative amount on account {}", line.account_number));
+        }
+        if !info.contains_key(&line.account_number) {
+            return Err(format!("Account {} is not in any entity trial balance", line.account_number));
+        }
+    }
+    let debits: i64 = entry.lines.iter().map(|line| line.debit_amount).sum();
+    let credits: i64 = entry.lines.iter().map(|line| line.credit_amount).sum();
+    if debits != credits {
+        return Err(format!(
+            "Debits {:.2} do not equal credits {:.2}",
+            debits as f64 / 100.0,
+            credits as f64 / 100.0
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,454 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::HashSet;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::storage::{next_annotation_id, next_cross_reference_id, next_tick_mark_id, STORAGE};
+use crate::trend_analysis;
+use crate::types::{
+    AddAnnotationRequest, AddCrossReferenceRequest, Annotation, BrokenReference, CrossReference,
+    DefineTickMarkRequest, ReferenceIntegrityReport, ReferenceTarget, Result, TickMark,
+};
+use crate::working_papers;
+
+// Normalise a working paper reference code ("b-3.2" -> "B-3.2") and make sure
+// no other paper in the engagement uses it
+pub fn validate_reference_code(
+    engagement_id: u64,
+    code: Option<String>,
+    working_paper_id: Option<u64>,
+) -> Result<Option<String>> {
+    let code = match code.map(|c| c.trim().to_uppercase()) {
+        Some(c) if !c.is_empty() => c,
+        _ => return Ok(None),
+    };
+
+    if !is_valid_reference_code(&code) {
+        return Err(format!(
+            "Invalid reference code {}; expected a form like A-1 or B-3.2",
+            code
+        ));
+    }
+
+    let taken = STORAGE.with(|storage| {
+        storage.borrow().working_papers.iter().any(|(id, wp)| {
+            wp.engagement_id == engagement_id
+                && Some(id) != working_paper_id
+                && wp.reference_code.as_deref() == Some(code.as_str())
+        })
+    });
+    if taken {
+        return Err(format!("Reference code {} is already used in this engagement", code));
+    }
+
+    Ok(Some(code))
+}
+
+// One to three letters, a dash, then dot-separated numbers
+fn is_valid_reference_code(code: &str) -> bool {
+    let Some((prefix, number)) = code.split_once('-') else {
+        return false;
+    };
+
+    (1..=3).contains(&prefix.len())
+        && prefix.chars().all(|c| c.is_ascii_uppercase())
+        && number
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Add a cross-reference from a working paper to another paper, an AJE, a
+// document or an FS line
+pub fn add_cross_reference(caller: Principal, req: AddCrossReferenceRequest) -> Result<CrossReference> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to add cross-references".to_string());
+    }
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&req.working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    working_papers::ensure_editable(&wp)?;
+
+    if req.description.trim().is_empty() {
+        return Err("Cross-reference description is required".to_string());
+    }
+    if let Some(account_number) = &req.account_number {
+        if !working_papers::leadsheet_account_numbers(&wp).contains(account_number) {
+            return Err(format!("Account {} is not on the leadsheet", account_number));
+        }
+    }
+    if req.target == ReferenceTarget::WorkingPaper(wp.id) {
+        return Err("A working paper cannot reference itself".to_string());
+    }
+    if let Some(reason) = broken_target_reason(wp.engagement_id, &req.target) {
+        return Err(reason);
+    }
+
+    let reference = CrossReference {
+        id: next_cross_reference_id(),
+        engagement_id: wp.engagement_id,
+        working_paper_id: wp.id,
+        account_number: req.account_number,
+        target: req.target,
+        description: req.description.trim().to_string(),
+        created_at: time(),
+        created_by: caller,
+    };
+    working_papers::record_edit(&mut wp, caller);
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage.cross_references.insert(reference.id, reference.clone());
+        storage.working_papers.insert(wp.id, wp.clone());
+    });
+
+    log_activity(
+        caller,
+        "add_cross_reference".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        format!(
+            "Cross-reference to {} added to working paper {}",
+            describe_target(&reference.target),
+            wp.name
+        ),
+        encode_args((reference.clone(),)).ok(),
+    );
+
+    Ok(reference)
+}
+
+// List cross-references made from a working paper
+pub fn list_cross_references(caller: Principal, working_paper_id: u64) -> Result<Vec<CrossReference>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .cross_references
+            .iter()
+            .filter(|(_, reference)| reference.working_paper_id == working_paper_id)
+            .map(|(_, reference)| reference)
+            .collect()
+    }))
+}
+
+// Add a symbol to the engagement's tick-mark legend
+pub fn define_tick_mark(caller: Principal, req: DefineTickMarkRequest) -> Result<TickMark> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to define tick marks".to_string());
+    }
+
+    STORAGE
+        .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let symbol = req.symbol.trim().to_string();
+    if symbol.is_empty() || req.meaning.trim().is_empty() {
+        return Err("Tick mark symbol and meaning are required".to_string());
+    }
+    if tick_marks_for(req.engagement_id).iter().any(|mark| mark.symbol == symbol) {
+        return Err(format!("Tick mark {} is already in the legend", symbol));
+    }
+
+    let tick_mark = TickMark {
+        id: next_tick_mark_id(),
+        engagement_id: req.engagement_id,
+        symbol,
+        meaning: req.meaning.trim().to_string(),
+        created_at: time(),
+        created_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .tick_marks
+            .insert(tick_mark.id, tick_mark.clone());
+    });
+
+    log_activity(
+        caller,
+        "define_tick_mark".to_string(),
+        "engagement".to_string(),
+        req.engagement_id.to_string(),
+        format!("Tick mark {} defined: {}", tick_mark.symbol, tick_mark.meaning),
+        encode_args((tick_mark.clone(),)).ok(),
+    );
+
+    Ok(tick_mark)
+}
+
+// Get the tick-mark legend for an engagement
+pub fn list_tick_marks(caller: Principal, engagement_id: u64) -> Result<Vec<TickMark>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(tick_marks_for(engagement_id))
+}
+
+fn tick_marks_for(engagement_id: u64) -> Vec<TickMark> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .tick_marks
+            .iter()
+            .filter(|(_, mark)| mark.engagement_id == engagement_id)
+            .map(|(_, mark)| mark)
+            .collect()
+    })
+}
+
+// Attach a tick mark to a leadsheet account
+pub fn add_annotation(caller: Principal, req: AddAnnotationRequest) -> Result<Annotation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to annotate working papers".to_string());
+    }
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&req.working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    working_papers::ensure_editable(&wp)?;
+
+    if !working_papers::leadsheet_account_numbers(&wp).contains(&req.account_number) {
+        return Err(format!("Account {} is not on the leadsheet", req.account_number));
+    }
+    let tick_mark = STORAGE
+        .with(|storage| storage.borrow().tick_marks.get(&req.tick_mark_id))
+        .filter(|mark| mark.engagement_id == wp.engagement_id)
+        .ok_or_else(|| "Tick mark not found in this engagement's legend".to_string())?;
+
+    let annotation = Annotation {
+        id: next_annotation_id(),
+        engagement_id: wp.engagement_id,
+        working_paper_id: wp.id,
+        account_number: req.account_number,
+        tick_mark_id: tick_mark.id,
+        comment: req.comment.filter(|c| !c.trim().is_empty()),
+        created_at: time(),
+        created_by: caller,
+    };
+    working_papers::record_edit(&mut wp, caller);
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage.annotations.insert(annotation.id, annotation.clone());
+        storage.working_papers.insert(wp.id, wp.clone());
+    });
+
+    log_activity(
+        caller,
+        "add_annotation".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        format!(
+            "Tick mark {} added to account {} on working paper {}",
+            tick_mark.symbol, annotation.account_number, wp.name
+        ),
+        encode_args((annotation.clone(),)).ok(),
+    );
+
+    Ok(annotation)
+}
+
+// List the annotations on a working paper
+pub fn list_annotations(caller: Principal, working_paper_id: u64) -> Result<Vec<Annotation>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .annotations
+            .iter()
+            .filter(|(_, annotation)| annotation.working_paper_id == working_paper_id)
+            .map(|(_, annotation)| annotation)
+            .collect()
+    }))
+}
+
+// Report every reference in the engagement that no longer resolves
+pub fn check_reference_integrity(caller: Principal, engagement_id: u64) -> Result<ReferenceIntegrityReport> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().engagements.get(&engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let (papers, references, annotations) = STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        (
+            storage
+                .working_papers
+                .iter()
+                .filter(|(_, wp)| wp.engagement_id == engagement_id)
+                .map(|(_, wp)| wp)
+                .collect::<Vec<_>>(),
+            storage
+                .cross_references
+                .iter()
+                .filter(|(_, reference)| reference.engagement_id == engagement_id)
+                .map(|(_, reference)| reference)
+                .collect::<Vec<_>>(),
+            storage
+                .annotations
+                .iter()
+                .filter(|(_, annotation)| annotation.engagement_id == engagement_id)
+                .map(|(_, annotation)| annotation)
+                .collect::<Vec<_>>(),
+        )
+    });
+    let paper_by_id = |id: u64| papers.iter().find(|wp| wp.id == id);
+
+    let mut checked = 0u64;
+    let mut broken = Vec::new();
+
+    for reference in &references {
+        checked += 1;
+        let source = format!("Cross-reference {}", reference.id);
+        let Some(wp) = paper_by_id(reference.working_paper_id) else {
+            broken.push(BrokenReference {
+                working_paper_id: reference.working_paper_id,
+                source,
+                target: describe_target(&reference.target),
+                reason: "Referencing working paper no longer exists".to_string(),
+            });
+            continue;
+        };
+        if let Some(reason) = broken_target_reason(engagement_id, &reference.target) {
+            broken.push(BrokenReference {
+                working_paper_id: wp.id,
+                source: source.clone(),
+                target: describe_target(&reference.target),
+                reason,
+            });
+        }
+        if let Some(account_number) = &reference.account_number {
+            if !working_papers::leadsheet_account_numbers(wp).contains(account_number) {
+                broken.push(BrokenReference {
+                    working_paper_id: wp.id,
+                    source,
+                    target: format!("Account {}", account_number),
+                    reason: "Account is no longer on the leadsheet".to_string(),
+                });
+            }
+        }
+    }
+
+    for wp in &papers {
+        for document_id in &wp.linked_document_ids {
+            checked += 1;
+            let target = ReferenceTarget::Document(*document_id);
+            if let Some(reason) = broken_target_reason(engagement_id, &target) {
+                broken.push(BrokenReference {
+                    working_paper_id: wp.id,
+                    source: "Linked document".to_string(),
+                    target: describe_target(&target),
+                    reason,
+                });
+            }
+        }
+    }
+
+    let tick_mark_ids: HashSet<u64> = tick_marks_for(engagement_id).iter().map(|mark| mark.id).collect();
+    for annotation in &annotations {
+        checked += 1;
+        let source = format!("Annotation {}", annotation.id);
+        if !tick_mark_ids.contains(&annotation.tick_mark_id) {
+            broken.push(BrokenReference {
+                working_paper_id: annotation.working_paper_id,
+                source: source.clone(),
+                target: format!("Tick mark {}", annotation.tick_mark_id),
+                reason: "Tick mark is not in the engagement's legend".to_string(),
+            });
+        }
+        match paper_by_id(annotation.working_paper_id) {
+            None => broken.push(BrokenReference {
+                working_paper_id: annotation.working_paper_id,
+                source,
+                target: format!("Working paper {}", annotation.working_paper_id),
+                reason: "Annotated working paper no longer exists".to_string(),
+            }),
+            Some(wp) if !working_papers::leadsheet_account_numbers(wp).contains(&annotation.account_number) => {
+                broken.push(BrokenReference {
+                    working_paper_id: wp.id,
+                    source,
+                    target: format!("Account {}", annotation.account_number),
+                    reason: "Account is no longer on the leadsheet".to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(ReferenceIntegrityReport {
+        engagement_id,
+        references_checked: checked,
+        broken,
+        checked_at: time(),
+    })
+}
+
+// Why a reference target does not resolve, if it doesn't
+fn broken_target_reason(engagement_id: u64, target: &ReferenceTarget) -> Option<String> {
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        match target {
+            ReferenceTarget::WorkingPaper(id) => match storage.working_papers.get(id) {
+                None => Some("Working paper not found".to_string()),
+                Some(wp) if wp.engagement_id != engagement_id => {
+                    Some("Working paper belongs to another engagement".to_string())
+                }
+                Some(_) => None,
+            },
+            ReferenceTarget::Aje(id) => match storage.adjusting_entries.get(id) {
+                None => Some("AJE not found".to_string()),
+                Some(aje) if aje.engagement_id != engagement_id => {
+                    Some("AJE belongs to another engagement".to_string())
+                }
+                Some(_) => None,
+            },
+            ReferenceTarget::Document(id) => {
+                (!storage.documents.contains_key(id)).then(|| "Document not found".to_string())
+            }
+            ReferenceTarget::FsLine(code) => {
+                let standard = trend_analysis::fs_line_order()
+                    .iter()
+                    .any(|(line, _)| line == code);
+                let engagement_tbs: HashSet<u64> = storage
+                    .trial_balances
+                    .iter()
+                    .filter(|(_, tb)| tb.engagement_id == engagement_id)
+                    .map(|(id, _)| id)
+                    .collect();
+                let mapped = storage.trial_balance_accounts.iter().any(|(_, account)| {
+                    engagement_tbs.contains(&account.trial_balance_id)
+                        && account.fs_line_item.as_deref() == Some(code.as_str())
+                });
+                (!standard && !mapped).then(|| format!("FS line {} is not defined", code))
+            }
+        }
+    })
+}
+
+fn describe_target(target: &ReferenceTarget) -> String {
+    match target {
+        ReferenceTarget::WorkingPaper(id) => {
+            let code = STORAGE
+                .with(|storage| storage.borrow().working_papers.get(id))
+                .and_then(|wp| wp.reference_code);
+            match code {
+                Some(code) => format!("WP {}", code),
+                None => format!("Working paper {}", id),
+            }
+        }
+        ReferenceTarget::Aje(id) => format!("AJE {}", id),
+        ReferenceTarget::Document(id) => format!("Document {}", id),
+        ReferenceTarget::FsLine(code) => format!("FS line {}", code),
+    }
+}
@@ -2,15 +2,21 @@ use candid::{Principal, encode_args};
 use ic_cdk::api::time;
 use calamine::{Reader, open_workbook_from_rs, Xlsx, Data};
 use std::io::Cursor;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::activity_log::log_activity;
 use crate::auth;
-use crate::storage::{next_dataset_id, STORAGE};
+use crate::mapping_profiles;
+use crate::pii_detection;
+use crate::pii_masking;
+use crate::storage::{next_dataset_id, next_dataset_version_id, STORAGE};
 use crate::types::{
-    ColumnMetadata, ColumnType, ImportExcelRequest, ImportedDataset, PIIDetection, Result,
-    SheetData,
+    CellChange, ColumnMaskingPolicy, ColumnMetadata, ColumnType, DatasetDiff, DatasetVersion,
+    ImportExcelRequest, ImportMappingProfile, ImportedDataset, MappingProfileMatch, MaskingMethod,
+    ReimportDatasetRequest, ReimportResult, Result, RowChange, RowChangeType, SheetData,
+    SheetDiff,
 };
+use crate::working_papers;
 
 // Import Excel file
 pub fn import_excel(caller: Principal, req: ImportExcelRequest) -> Result<ImportedDataset> {
@@ -20,39 +26,44 @@ pub fn import_excel(caller: Principal, req: ImportExcelRequest) -> Result<Import
         return Err("Insufficient permissions to import data".to_string());
     }
 
-    // Parse Excel file
-    let cursor = Cursor::new(&req.file_data);
-    let mut workbook: Xlsx<_> = open_workbook_from_rs(cursor)
-        .map_err(|e| format!("Failed to open Excel file: {}", e))?;
-
-    let sheet_names = workbook.sheet_names().to_vec();
-    let mut sheets = Vec::new();
-
-    for sheet_name in sheet_names {
-        if let Ok(range) = workbook.worksheet_range(&sheet_name) {
-            let sheet_data = process_sheet(&sheet_name, &range)?;
-            sheets.push(sheet_data);
-        }
-    }
+    let ranges = load_workbook(&req.file_data)?;
+    let profile_match = match_mapping_profile(&ranges, req.engagement_id, req.mapping_profile_id)?;
+    let sheets = build_sheets(&ranges, profile_match.as_ref().map(|(profile, _)| profile))?;
 
-    let dataset = ImportedDataset {
+    let mut dataset = ImportedDataset {
         id: next_dataset_id(),
         name: req.name.clone(),
         engagement_id: req.engagement_id,
         file_name: req.file_name.clone(),
         file_size: req.file_data.len() as u64,
         sheets,
+        masking_policies: Some(Vec::new()),
+        suggested_mapping: profile_match.map(|(_, m)| m),
+        quality_report: None,
         version: 1,
         created_at: time(),
         created_by: caller,
     };
 
+    // Mask PII before anything is stored or logged
+    let policies = collect_masking_policies(
+        &dataset,
+        req.masking_policies.clone().unwrap_or_default(),
+        req.auto_mask_pii.unwrap_or(false),
+    );
+    let vault_entries = if policies.is_empty() {
+        Vec::new()
+    } else {
+        pii_masking::apply_masking_policies(caller, &mut dataset, &policies)?.1
+    };
+
     STORAGE.with(|storage| {
         storage
             .borrow_mut()
             .datasets
             .insert(dataset.id, dataset.clone());
     });
+    pii_masking::store_vault_entries(vault_entries);
 
     log_activity(
         caller,
@@ -66,43 +77,588 @@ pub fn import_excel(caller: Principal, req: ImportExcelRequest) -> Result<Import
     Ok(dataset)
 }
 
-// Process a single sheet
+// Re-import a corrected file as the next version of an existing dataset
+pub fn reimport_dataset(caller: Principal, req: ReimportDatasetRequest) -> Result<ReimportResult> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to import data".to_string());
+    }
+
+    let previous = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&req.dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    // Re-use the profile the previous version was imported with
+    let ranges = load_workbook(&req.file_data)?;
+    let previous_profile_id = previous.suggested_mapping.as_ref().map(|m| m.profile_id);
+    let engagement_client = previous.engagement_id.and_then(mapping_profiles::client_for_engagement);
+    let still_applies = |id: u64| {
+        STORAGE
+            .with(|storage| storage.borrow().mapping_profiles.get(&id))
+            .is_some_and(|profile| Some(profile.client_id) == engagement_client)
+    };
+    let profile_match = match previous_profile_id {
+        Some(id) if still_applies(id) => {
+            match_mapping_profile(&ranges, previous.engagement_id, Some(id))?
+        }
+        _ => match_mapping_profile(&ranges, previous.engagement_id, None)?,
+    };
+    let sheets = build_sheets(&ranges, profile_match.as_ref().map(|(profile, _)| profile))?;
+
+    let mut dataset = ImportedDataset {
+        id: previous.id,
+        name: previous.name.clone(),
+        engagement_id: previous.engagement_id,
+        file_name: req.file_name.clone(),
+        file_size: req.file_data.len() as u64,
+        sheets,
+        masking_policies: Some(Vec::new()),
+        suggested_mapping: profile_match.map(|(_, m)| m),
+        quality_report: None,
+        version: previous.version + 1,
+        created_at: time(),
+        created_by: caller,
+    };
+
+    // Carry the existing policies over to columns that are still present
+    let carried: Vec<ColumnMaskingPolicy> = previous
+        .masking_policies
+        .iter()
+        .flatten()
+        .filter(|p| {
+            dataset.sheets.iter().any(|sheet| {
+                p.sheet_name.as_ref().is_none_or(|name| *name == sheet.name)
+                    && sheet.columns.iter().any(|c| c.name == p.column_name)
+            })
+        })
+        .cloned()
+        .collect();
+    let policies = collect_masking_policies(&dataset, carried, req.auto_mask_pii.unwrap_or(false));
+    let vault_entries = if policies.is_empty() {
+        Vec::new()
+    } else {
+        pii_masking::apply_masking_policies(caller, &mut dataset, &policies)?.1
+    };
+
+    // Mask the previous version with the new policies too, so both sides of the
+    // diff are masked the same way and it never exposes raw PII
+    let mut previous_sheets = previous.sheets.clone();
+    pii_masking::mask_version_sheets(caller, &dataset, previous.version, &mut previous_sheets);
+    let diff = diff_sheets(
+        dataset.id,
+        (previous.version, &previous_sheets),
+        (dataset.version, &dataset.sheets),
+        &req.key_column,
+        req.sheet_name.as_deref(),
+    )?;
+
+    let prior_version = DatasetVersion {
+        id: next_dataset_version_id(),
+        dataset_id: previous.id,
+        version: previous.version,
+        file_name: previous.file_name.clone(),
+        file_size: previous.file_size,
+        sheets: previous.sheets.clone(),
+        masking_policies: previous.masking_policies.clone().unwrap_or_default(),
+        created_at: previous.created_at,
+        created_by: previous.created_by,
+        superseded_at: time(),
+        superseded_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage.dataset_versions.insert(prior_version.id, prior_version);
+        storage.datasets.insert(dataset.id, dataset.clone());
+    });
+    pii_masking::store_vault_entries(vault_entries);
+
+    let (added, removed, changed) = diff.sheets.iter().fold((0, 0, 0), |acc, sheet| {
+        (
+            acc.0 + sheet.added_count,
+            acc.1 + sheet.removed_count,
+            acc.2 + sheet.changed_count,
+        )
+    });
+
+    let stale_reason = format!(
+        "Dataset {} updated to version {} ({} added, {} removed, {} changed rows)",
+        dataset.id, dataset.version, added, removed, changed
+    );
+    let stale_working_paper_ids =
+        working_papers::mark_working_papers_stale(caller, dataset.id, &stale_reason);
+
+    log_activity(
+        caller,
+        "reimport_dataset".to_string(),
+        "dataset".to_string(),
+        dataset.id.to_string(),
+        format!(
+            "Dataset {} re-imported from {} as version {}",
+            dataset.id, dataset.file_name, dataset.version
+        ),
+        encode_args((dataset.clone(),)).ok(),
+    );
+
+    Ok(ReimportResult {
+        dataset,
+        diff,
+        stale_working_paper_ids,
+    })
+}
+
+// Prior versions of a dataset, oldest first, masked like the current version
+pub fn list_dataset_versions(caller: Principal, dataset_id: u64) -> Result<Vec<DatasetVersion>> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to view dataset versions".to_string());
+    }
+
+    let current = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    let mut versions: Vec<DatasetVersion> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .dataset_versions
+            .iter()
+            .filter(|(_, v)| v.dataset_id == dataset_id)
+            .map(|(_, v)| v)
+            .collect()
+    });
+    versions.sort_by_key(|v| v.version);
+    for version in versions.iter_mut() {
+        pii_masking::mask_version_sheets(caller, &current, version.version, &mut version.sheets);
+    }
+
+    Ok(versions)
+}
+
+// Diff any two versions of a dataset, including the current one
+pub fn get_dataset_diff(
+    caller: Principal,
+    dataset_id: u64,
+    from_version: u32,
+    to_version: u32,
+    key_column: String,
+    sheet_name: Option<String>,
+) -> Result<DatasetDiff> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to view dataset versions".to_string());
+    }
+
+    let current = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+    let versions = list_dataset_versions(caller, dataset_id)?;
+
+    let sheets_for = |version: u32| -> Result<Vec<SheetData>> {
+        if version == current.version {
+            return Ok(current.sheets.clone());
+        }
+        versions
+            .iter()
+            .find(|v| v.version == version)
+            .map(|v| v.sheets.clone())
+            .ok_or_else(|| format!("Version {} not found", version))
+    };
+
+    let old_sheets = sheets_for(from_version)?;
+    let new_sheets = sheets_for(to_version)?;
+
+    diff_sheets(
+        dataset_id,
+        (from_version, &old_sheets),
+        (to_version, &new_sheets),
+        &key_column,
+        sheet_name.as_deref(),
+    )
+}
+
+// Full set of rows for analytics. Stored datasets keep only the first rows of
+// each sheet, so callers can pass the original file to read the whole sheet.
+pub struct Population {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub complete: bool, // False when only the stored rows were available
+}
+
+pub fn load_population(
+    caller: Principal,
+    dataset: &ImportedDataset,
+    sheet_name: &str,
+    file_data: Option<&[u8]>,
+) -> Result<Population> {
+    let sheet = dataset
+        .sheets
+        .iter()
+        .find(|s| s.name == sheet_name)
+        .ok_or_else(|| format!("Sheet '{}' not found", sheet_name))?;
+    let headers: Vec<String> = sheet.columns.iter().map(|c| c.name.clone()).collect();
+
+    let file_data = match file_data {
+        Some(data) => data,
+        None => {
+            return Ok(Population {
+                headers,
+                rows: sheet.data.clone(),
+                complete: sheet.data.len() as u64 >= sheet.row_count,
+            })
+        }
+    };
+
+    let ranges = load_workbook(file_data)?;
+    let (_, range) = ranges
+        .iter()
+        .find(|(name, _)| name == sheet_name)
+        .ok_or_else(|| format!("Sheet '{}' not found in file", sheet_name))?;
+
+    let header_row = sheet.header_row.unwrap_or(1).saturating_sub(1) as usize;
+    if read_headers(range, header_row) != headers {
+        return Err(format!(
+            "File headers do not match sheet '{}' of dataset {}",
+            sheet_name, dataset.id
+        ));
+    }
+
+    let (height, width) = range.get_size();
+    let mut rows: Vec<Vec<String>> = (header_row + 1..height)
+        .map(|row_idx| {
+            (0..width)
+                .map(|col_idx| cell_to_string(range.get((row_idx, col_idx))))
+                .collect()
+        })
+        .collect();
+
+    // Apply the dataset's masking so raw PII never leaves this function
+    let masked: Vec<(usize, &MaskingMethod)> = sheet
+        .columns
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, c)| c.masking.as_ref().map(|m| (idx, m)))
+        .collect();
+    if !masked.is_empty() {
+        let salt = pii_masking::salt_for_dataset(dataset, caller);
+        for row in rows.iter_mut() {
+            for (idx, method) in &masked {
+                if let Some(cell) = row.get_mut(*idx) {
+                    *cell = pii_masking::mask_value(cell, method, &salt);
+                }
+            }
+        }
+    }
+
+    Ok(Population {
+        headers,
+        rows,
+        complete: true,
+    })
+}
+
+fn load_workbook(file_data: &[u8]) -> Result<Vec<(String, calamine::Range<Data>)>> {
+    let cursor = Cursor::new(file_data);
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(cursor)
+        .map_err(|e| format!("Failed to open Excel file: {}", e))?;
+
+    let sheet_names = workbook.sheet_names().to_vec();
+    let mut ranges = Vec::new();
+
+    for sheet_name in sheet_names {
+        if let Ok(range) = workbook.worksheet_range(&sheet_name) {
+            ranges.push((sheet_name, range));
+        }
+    }
+
+    Ok(ranges)
+}
+
+// The profile's header row applies to its sheet (or every sheet if it names none)
+fn build_sheets(
+    ranges: &[(String, calamine::Range<Data>)],
+    profile: Option<&ImportMappingProfile>,
+) -> Result<Vec<SheetData>> {
+    let mut sheets = Vec::new();
+
+    for (sheet_name, range) in ranges {
+        let header_row = profile
+            .filter(|p| p.sheet_name.as_ref().is_none_or(|name| name == sheet_name))
+            .map(mapping_profiles::header_row_index)
+            .unwrap_or(0);
+        sheets.push(process_sheet(sheet_name, range, header_row)?);
+    }
+
+    Ok(sheets)
+}
+
+// Pick the profile to import with: the requested one, or the client's best match
+fn match_mapping_profile(
+    ranges: &[(String, calamine::Range<Data>)],
+    engagement_id: Option<u64>,
+    profile_id: Option<u64>,
+) -> Result<Option<(ImportMappingProfile, MappingProfileMatch)>> {
+    let (candidates, min_score) = match profile_id {
+        Some(id) => {
+            let profile = STORAGE
+                .with(|storage| storage.borrow().mapping_profiles.get(&id))
+                .ok_or_else(|| "Mapping profile not found".to_string())?;
+            // Another client's profile would leak its header layout into this engagement
+            if engagement_id.and_then(mapping_profiles::client_for_engagement) != Some(profile.client_id) {
+                return Err("Mapping profile belongs to a different client than the engagement".to_string());
+            }
+            (vec![profile], 0.0)
+        }
+        None => match engagement_id.and_then(mapping_profiles::client_for_engagement) {
+            Some(client_id) => (
+                mapping_profiles::profiles_for_client(client_id),
+                mapping_profiles::MIN_MATCH_SCORE,
+            ),
+            None => return Ok(None),
+        },
+    };
+
+    let mut best: Option<(ImportMappingProfile, MappingProfileMatch)> = None;
+    for profile in candidates {
+        for (sheet_name, range) in ranges {
+            if profile.sheet_name.as_ref().is_some_and(|name| name != sheet_name) {
+                continue;
+            }
+            let headers = read_headers(range, mapping_profiles::header_row_index(&profile));
+            let candidate = mapping_profiles::score_headers(&profile, sheet_name, &headers);
+            if candidate.score >= min_score
+                && best.as_ref().is_none_or(|(_, b)| candidate.score > b.score)
+            {
+                best = Some((profile.clone(), candidate));
+            }
+        }
+    }
+
+    if profile_id.is_some() && best.is_none() {
+        return Err("Mapping profile sheet not found in workbook".to_string());
+    }
+
+    Ok(best)
+}
+
+// Explicit policies win over the detector defaults for the same column
+fn collect_masking_policies(
+    dataset: &ImportedDataset,
+    mut policies: Vec<ColumnMaskingPolicy>,
+    auto_mask_pii: bool,
+) -> Vec<ColumnMaskingPolicy> {
+    if auto_mask_pii {
+        for detected in pii_masking::policies_for_detected_pii(dataset) {
+            let already_set = policies.iter().any(|p| {
+                p.column_name == detected.column_name
+                    && (p.sheet_name.is_none() || p.sheet_name == detected.sheet_name)
+            });
+            if !already_set {
+                policies.push(detected);
+            }
+        }
+    }
+    policies
+}
+
+fn diff_sheets(
+    dataset_id: u64,
+    (from_version, old_sheets): (u32, &[SheetData]),
+    (to_version, new_sheets): (u32, &[SheetData]),
+    key_column: &str,
+    sheet_name: Option<&str>,
+) -> Result<DatasetDiff> {
+    let mut sheets = Vec::new();
+
+    for new_sheet in new_sheets {
+        if sheet_name.is_some_and(|name| name != new_sheet.name) {
+            continue;
+        }
+        let old_sheet = match old_sheets.iter().find(|s| s.name == new_sheet.name) {
+            Some(sheet) => sheet,
+            None => continue,
+        };
+        if let Some(diff) = diff_sheet(old_sheet, new_sheet, key_column) {
+            sheets.push(diff);
+        }
+    }
+
+    if sheets.is_empty() {
+        return Err(match sheet_name {
+            Some(name) => format!("Key column '{}' not found in sheet '{}' of both versions", key_column, name),
+            None => format!("Key column '{}' not found in any sheet of both versions", key_column),
+        });
+    }
+
+    Ok(DatasetDiff {
+        dataset_id,
+        from_version,
+        to_version,
+        key_column: key_column.to_string(),
+        sheets,
+    })
+}
+
+// Row-level diff keyed on one column; None if either side lacks the key column
+fn diff_sheet(old: &SheetData, new: &SheetData, key_column: &str) -> Option<SheetDiff> {
+    let old_key_idx = old.columns.iter().position(|c| c.name == key_column)?;
+    let new_key_idx = new.columns.iter().position(|c| c.name == key_column)?;
+
+    let old_rows = keyed_rows(&old.data, old_key_idx);
+    let new_rows = keyed_rows(&new.data, new_key_idx);
+    let old_index: HashMap<&String, usize> = old_rows.iter().map(|(k, idx)| (k, *idx)).collect();
+    let new_index: HashMap<&String, usize> = new_rows.iter().map(|(k, idx)| (k, *idx)).collect();
+
+    // Cells are only compared for columns present in both versions
+    let shared_columns: Vec<(String, usize, usize)> = new
+        .columns
+        .iter()
+        .enumerate()
+        .filter_map(|(new_idx, col)| {
+            old.columns
+                .iter()
+                .position(|c| c.name == col.name)
+                .map(|old_idx| (col.name.clone(), old_idx, new_idx))
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut unchanged_count = 0;
+
+    for (key, new_idx) in &new_rows {
+        match old_index.get(key) {
+            Some(old_idx) => {
+                let old_row = &old.data[*old_idx];
+                let new_row = &new.data[*new_idx];
+                let cell_changes: Vec<CellChange> = shared_columns
+                    .iter()
+                    .filter_map(|(name, old_col, new_col)| {
+                        let old_value = old_row.get(*old_col).cloned().unwrap_or_default();
+                        let new_value = new_row.get(*new_col).cloned().unwrap_or_default();
+                        if old_value.trim() == new_value.trim() {
+                            None
+                        } else {
+                            Some(CellChange {
+                                column_name: name.clone(),
+                                old_value,
+                                new_value,
+                            })
+                        }
+                    })
+                    .collect();
+
+                if cell_changes.is_empty() {
+                    unchanged_count += 1;
+                } else {
+                    rows.push(RowChange {
+                        key: key.clone(),
+                        change_type: RowChangeType::Changed,
+                        old_row_index: Some(*old_idx as u64),
+                        new_row_index: Some(*new_idx as u64),
+                        cell_changes,
+                    });
+                }
+            }
+            None => rows.push(RowChange {
+                key: key.clone(),
+                change_type: RowChangeType::Added,
+                old_row_index: None,
+                new_row_index: Some(*new_idx as u64),
+                cell_changes: Vec::new(),
+            }),
+        }
+    }
+
+    for (key, old_idx) in &old_rows {
+        if !new_index.contains_key(key) {
+            rows.push(RowChange {
+                key: key.clone(),
+                change_type: RowChangeType::Removed,
+                old_row_index: Some(*old_idx as u64),
+                new_row_index: None,
+                cell_changes: Vec::new(),
+            });
+        }
+    }
+
+    let count = |change_type: RowChangeType| {
+        rows.iter().filter(|r| r.change_type == change_type).count() as u64
+    };
+
+    Some(SheetDiff {
+        sheet_name: new.name.clone(),
+        added_columns: new
+            .columns
+            .iter()
+            .filter(|c| !old.columns.iter().any(|o| o.name == c.name))
+            .map(|c| c.name.clone())
+            .collect(),
+        removed_columns: old
+            .columns
+            .iter()
+            .filter(|c| !new.columns.iter().any(|n| n.name == c.name))
+            .map(|c| c.name.clone())
+            .collect(),
+        added_count: count(RowChangeType::Added),
+        removed_count: count(RowChangeType::Removed),
+        changed_count: count(RowChangeType::Changed),
+        unchanged_count,
+        rows,
+    })
+}
+
+// Pair each row with its key; repeated keys get an occurrence suffix ("1000#2")
+fn keyed_rows(data: &[Vec<String>], key_idx: usize) -> Vec<(String, usize)> {
+    let mut seen: HashMap<String, u32> = HashMap::new();
+    let mut keyed = Vec::new();
+
+    for (idx, row) in data.iter().enumerate() {
+        let key = row.get(key_idx).map(|k| k.trim().to_string()).unwrap_or_default();
+        if key.is_empty() {
+            continue;
+        }
+        let occurrence = seen.entry(key.clone()).or_insert(0);
+        *occurrence += 1;
+        let key = if *occurrence > 1 {
+            format!("{}#{}", key, occurrence)
+        } else {
+            key
+        };
+        keyed.push((key, idx));
+    }
+
+    keyed
+}
+
+// Process a single sheet; rows above the header row are skipped
 fn process_sheet(
     sheet_name: &str,
     range: &calamine::Range<Data>,
+    header_row: usize,
 ) -> Result<SheetData> {
     let (height, width) = range.get_size();
     
-    if height == 0 || width == 0 {
+    if height <= header_row || width == 0 {
         return Ok(SheetData {
             name: sheet_name.to_string(),
             columns: Vec::new(),
             row_count: 0,
+            header_row: Some(header_row as u32 + 1),
             data: Vec::new(),
         });
     }
 
-    let max_rows_to_store = 100.min(height);
-    let max_rows_to_analyze = 50.min(height - 1);
+    let first_data_row = header_row + 1;
+    let max_rows_to_store = (header_row + 100).min(height);
+    let max_rows_to_analyze = (header_row + 50).min(height - 1);
 
-    let mut headers = Vec::new();
-    for col_idx in 0..width {
-        let cell = range.get((0, col_idx));
-        let header = match cell {
-            Some(Data::String(s)) => s.clone(),
-            Some(Data::Int(i)) => i.to_string(),
-            Some(Data::Float(f)) => f.to_string(),
-            Some(Data::Bool(b)) => b.to_string(),
-            Some(Data::DateTime(dt)) => format!("{}", dt),
-            Some(Data::DateTimeIso(dt)) => dt.clone(),
-            Some(Data::DurationIso(d)) => d.clone(),
-            _ => format!("Column_{}", col_idx + 1),
-        };
-        headers.push(header);
-    }
+    let headers = read_headers(range, header_row);
 
     let mut data_rows = Vec::new();
-    for row_idx in 1..max_rows_to_store {
+    for row_idx in first_data_row..max_rows_to_store {
         if row_idx >= height {
             break;
         }
@@ -118,7 +674,7 @@ fn process_sheet(
     let mut columns = Vec::new();
     for (col_idx, header) in headers.iter().enumerate() {
         let mut column_data: Vec<String> = Vec::new();
-        for row_idx in 1..=max_rows_to_analyze {
+        for row_idx in first_data_row..=max_rows_to_analyze {
             if row_idx >= height {
                 break;
             }
@@ -133,11 +689,32 @@ fn process_sheet(
     Ok(SheetData {
         name: sheet_name.to_string(),
         columns,
-        row_count: (height - 1) as u64,
+        row_count: (height - first_data_row) as u64,
+        header_row: Some(header_row as u32 + 1),
         data: data_rows,
     })
 }
 
+fn read_headers(range: &calamine::Range<Data>, header_row: usize) -> Vec<String> {
+    let (_, width) = range.get_size();
+    let mut headers = Vec::new();
+    for col_idx in 0..width {
+        let cell = range.get((header_row, col_idx));
+        let header = match cell {
+            Some(Data::String(s)) => s.clone(),
+            Some(Data::Int(i)) => i.to_string(),
+            Some(Data::Float(f)) => f.to_string(),
+            Some(Data::Bool(b)) => b.to_string(),
+            Some(Data::DateTime(dt)) => format!("{}", dt),
+            Some(Data::DateTimeIso(dt)) => dt.clone(),
+            Some(Data::DurationIso(d)) => d.clone(),
+            _ => format!("Column_{}", col_idx + 1),
+        };
+        headers.push(header);
+    }
+    headers
+}
+
 // Convert cell to string
 fn cell_to_string(cell: Option<&Data>) -> String {
     match cell {
@@ -179,7 +756,7 @@ fn analyze_column(name: String, data: Vec<String>) -> Result<ColumnMetadata> {
         .map(|s| (*s).clone())
         .collect();
 
-    let pii_detection = detect_pii_simple(&non_empty);
+    let pii_detection = pii_detection::detect_pii(&name, &non_empty);
 
     Ok(ColumnMetadata {
         name: name.clone(),
@@ -191,6 +768,7 @@ fn analyze_column(name: String, data: Vec<String>) -> Result<ColumnMetadata> {
         max_value,
         sample_values,
         pii_detection,
+        masking: None,
     })
 }
 
@@ -260,30 +838,6 @@ fn get_min_max_simple(values: &[&String], col_type: &ColumnType) -> (String, Str
     }
 }
 
-fn detect_pii_simple(values: &[&String]) -> PIIDetection {
-    let mut has_emails = false;
-    let mut has_phones = false;
-
-    for value in values.iter().take(10) {
-        if value.contains('@') && value.contains('.') {
-            has_emails = true;
-        }
-        if value.contains("(") && value.contains(")") && value.chars().filter(|c| c.is_numeric()).count() >= 7 {
-            has_phones = true;
-        }
-        if has_emails && has_phones {
-            break;
-        }
-    }
-
-    PIIDetection {
-        has_names: false,
-        has_emails,
-        has_phone_numbers: has_phones,
-        has_national_ids: false,
-    }
-}
-
 // Get dataset by ID
 pub fn get_dataset(caller: Principal, id: u64) -> Result<ImportedDataset> {
     let _user = auth::get_user(caller).ok_or("User not found")?;
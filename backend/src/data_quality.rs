@@ -0,0 +1,353 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::HashMap;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::data_import;
+use crate::dates;
+use crate::storage::STORAGE;
+use crate::types::{
+    DataQualityCheck, DataQualityReport, DataQualityRule, ImportedDataset, Result, RuleResult,
+    RuleSeverity, RuleViolation, RunDataQualityRequest, SheetData,
+};
+
+// Violations kept per rule; the count is always complete
+const MAX_VIOLATIONS_PER_RULE: usize = 100;
+
+// Labels that mark subtotal/total rows in English and Arabic exports
+const TOTAL_LABELS: &[&str] = &[
+    "total", "totals", "grand total", "subtotal", "sub total", "sub-total", "sum",
+    "إجمالي", "الإجمالي", "اجمالي", "الاجمالي", "المجموع", "مجموع",
+];
+
+// Evaluate a rule set against a dataset sheet and store the report on the dataset.
+// Without the original file only the stored rows are checked, and such a partial
+// run never clears a block (see ensure_not_blocked).
+pub fn run_data_quality_checks(
+    caller: Principal,
+    req: RunDataQualityRequest,
+) -> Result<DataQualityReport> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to run data quality checks".to_string());
+    }
+
+    if req.rules.is_empty() {
+        return Err("At least one rule is required".to_string());
+    }
+
+    let mut dataset = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&req.dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    let sheet = match &req.sheet_name {
+        Some(name) => dataset
+            .sheets
+            .iter()
+            .find(|s| s.name == *name)
+            .ok_or_else(|| format!("Sheet '{}' not found", name))?,
+        None => dataset
+            .sheets
+            .first()
+            .ok_or_else(|| "Dataset has no sheets".to_string())?,
+    };
+
+    let population = data_import::load_population(caller, &dataset, &sheet.name, req.file_data.as_deref())?;
+    let rows = Rows {
+        sheet,
+        data: &population.rows,
+        first_row_number: sheet.header_row.unwrap_or(1) as usize + 1,
+    };
+
+    let results = req
+        .rules
+        .iter()
+        .map(|rule| evaluate_rule(&rows, rule))
+        .collect::<Result<Vec<_>>>()?;
+
+    let failed_with = |severity: RuleSeverity| {
+        results
+            .iter()
+            .filter(|r| !r.passed && r.severity == severity)
+            .count() as u32
+    };
+
+    let report = DataQualityReport {
+        dataset_id: dataset.id,
+        dataset_version: dataset.version,
+        sheet_name: sheet.name.clone(),
+        rows_checked: population.rows.len() as u64,
+        total_rows: sheet.row_count,
+        critical_failures: failed_with(RuleSeverity::Critical),
+        warnings: failed_with(RuleSeverity::Warning),
+        results,
+        block_on_critical: req.block_on_critical,
+        evaluated_at: time(),
+        evaluated_by: caller,
+    };
+
+    dataset.quality_report = Some(report.clone());
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .datasets
+            .insert(dataset.id, dataset.clone());
+    });
+
+    log_activity(
+        caller,
+        "run_data_quality_checks".to_string(),
+        "dataset".to_string(),
+        dataset.id.to_string(),
+        format!(
+            "Data quality checks on sheet {}: {} rule(s), {} critical failure(s), {} warning(s)",
+            report.sheet_name,
+            req.rules.len(),
+            report.critical_failures,
+            report.warnings
+        ),
+        encode_args((report.clone(),)).ok(),
+    );
+
+    Ok(report)
+}
+
+// Get the latest data quality report for a dataset
+pub fn get_data_quality_report(caller: Principal, dataset_id: u64) -> Result<DataQualityReport> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let dataset = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    dataset
+        .quality_report
+        .ok_or_else(|| "No data quality report for this dataset".to_string())
+}
+
+// Reject downstream use of a dataset whose blocking report has critical failures,
+// or did not cover every row and so cannot show that there are none
+pub fn ensure_not_blocked(dataset: &ImportedDataset) -> Result<()> {
+    match &dataset.quality_report {
+        Some(report)
+            if report.block_on_critical
+                && report.rows_checked < report.total_rows
+                && report.dataset_version == dataset.version =>
+        {
+            Err(format!(
+                "Dataset {} data quality checks covered only {} of {} rows; run them with the original file",
+                dataset.id, report.rows_checked, report.total_rows
+            ))
+        }
+        Some(report)
+            if report.block_on_critical
+                && report.critical_failures > 0
+                && report.dataset_version == dataset.version =>
+        {
+            let failed: Vec<&str> = report
+                .results
+                .iter()
+                .filter(|r| !r.passed && r.severity == RuleSeverity::Critical)
+                .map(|r| r.rule_name.as_str())
+                .collect();
+            Err(format!(
+                "Dataset {} failed critical data quality rules: {}",
+                dataset.id,
+                failed.join(", ")
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+// The rows a run checks, with the sheet they are laid out like
+struct Rows<'a> {
+    sheet: &'a SheetData,
+    data: &'a [Vec<String>],
+    first_row_number: usize, // Spreadsheet row of data[0], just below the headers
+}
+
+impl Rows<'_> {
+    fn row_number(&self, idx: usize) -> usize {
+        self.first_row_number + idx
+    }
+}
+
+fn evaluate_rule(rows: &Rows, rule: &DataQualityRule) -> Result<RuleResult> {
+    let sheet = rows.sheet;
+    let violations = match &rule.check {
+        DataQualityCheck::Unique { column } => {
+            let idx = column_index(sheet, column)?;
+            let mut first_seen: HashMap<String, usize> = HashMap::new();
+            let mut violations = Vec::new();
+            for (row_idx, row) in rows.data.iter().enumerate() {
+                let value = cell(row, idx).trim().to_string();
+                if value.is_empty() {
+                    continue;
+                }
+                match first_seen.get(&value) {
+                    Some(first) => violations.push(violation(
+                        rows.row_number(row_idx),
+                        column,
+                        &value,
+                        format!("Duplicate of row {}", first),
+                    )),
+                    None => {
+                        first_seen.insert(value, rows.row_number(row_idx));
+                    }
+                }
+            }
+            violations
+        }
+        DataQualityCheck::NotBlank { column } => {
+            let idx = column_index(sheet, column)?;
+            data_rows(rows)
+                .filter(|(_, row)| cell(row, idx).trim().is_empty())
+                .map(|(row_number, _)| violation(row_number, column, "", "Value is blank".to_string()))
+                .collect()
+        }
+        DataQualityCheck::NotBothNonZero { first_column, second_column } => {
+            let first_idx = column_index(sheet, first_column)?;
+            let second_idx = column_index(sheet, second_column)?;
+            data_rows(rows)
+                .filter(|(_, row)| {
+                    is_non_zero(cell(row, first_idx)) && is_non_zero(cell(row, second_idx))
+                })
+                .map(|(row_number, row)| {
+                    violation(
+                        row_number,
+                        first_column,
+                        cell(row, first_idx),
+                        format!(
+                            "{} and {} are both non-zero ({})",
+                            first_column,
+                            second_column,
+                            cell(row, second_idx)
+                        ),
+                    )
+                })
+                .collect()
+        }
+        DataQualityCheck::ExcludeTotalsRow { label_columns } => {
+            let indexes = label_columns
+                .iter()
+                .map(|c| column_index(sheet, c).map(|idx| (c, idx)))
+                .collect::<Result<Vec<_>>>()?;
+            let mut violations = Vec::new();
+            for (row_idx, row) in rows.data.iter().enumerate() {
+                if let Some((column, idx)) = indexes.iter().find(|(_, idx)| is_total_label(cell(row, *idx))) {
+                    violations.push(violation(
+                        rows.row_number(row_idx),
+                        column,
+                        cell(row, *idx),
+                        "Looks like a totals row".to_string(),
+                    ));
+                }
+            }
+            violations
+        }
+        DataQualityCheck::DateWithinPeriod { column, period_start, period_end } => {
+            let idx = column_index(sheet, column)?;
+            let start = dates::parse_date(period_start)
+                .ok_or_else(|| format!("Invalid period start '{}'", period_start))?;
+            let end = dates::parse_date(period_end)
+                .ok_or_else(|| format!("Invalid period end '{}'", period_end))?;
+            if start > end {
+                return Err(format!("Period start is after period end in rule '{}'", rule.name));
+            }
+
+            let mut violations = Vec::new();
+            for (row_number, row) in data_rows(rows) {
+                let value = cell(row, idx).trim();
+                if value.is_empty() {
+                    continue;
+                }
+                match dates::parse_date(value) {
+                    Some(day) if day < start || day > end => violations.push(violation(
+                        row_number,
+                        column,
+                        value,
+                        format!(
+                            "{} is outside {} to {}",
+                            dates::format_date(day),
+                            dates::format_date(start),
+                            dates::format_date(end)
+                        ),
+                    )),
+                    Some(_) => {}
+                    None => violations.push(violation(
+                        row_number,
+                        column,
+                        value,
+                        "Not a recognisable date".to_string(),
+                    )),
+                }
+            }
+            violations
+        }
+    };
+
+    let violation_count = violations.len() as u64;
+
+    Ok(RuleResult {
+        rule_name: rule.name.clone(),
+        severity: rule.severity.clone(),
+        passed: violations.is_empty(),
+        violation_count,
+        violations: violations.into_iter().take(MAX_VIOLATIONS_PER_RULE).collect(),
+    })
+}
+
+// Rows to check with their spreadsheet row numbers, skipping fully blank rows
+fn data_rows<'a>(rows: &'a Rows) -> impl Iterator<Item = (usize, &'a Vec<String>)> {
+    rows.data
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.iter().any(|v| !v.trim().is_empty()))
+        .map(|(idx, row)| (rows.row_number(idx), row))
+}
+
+fn column_index(sheet: &SheetData, column: &str) -> Result<usize> {
+    sheet
+        .columns
+        .iter()
+        .position(|c| c.name == column)
+        .ok_or_else(|| format!("Column '{}' not found in sheet '{}'", column, sheet.name))
+}
+
+fn cell(row: &[String], idx: usize) -> &str {
+    row.get(idx).map(|v| v.as_str()).unwrap_or("")
+}
+
+fn is_non_zero(value: &str) -> bool {
+    let clean: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+    clean.parse::<f64>().map(|v| v != 0.0).unwrap_or(false)
+}
+
+fn is_total_label(value: &str) -> bool {
+    let lower = value.trim().to_lowercase();
+    if lower.is_empty() {
+        return false;
+    }
+    TOTAL_LABELS.iter().any(|label| {
+        lower == *label
+            || lower.starts_with(&format!("{} ", label))
+            || lower.ends_with(&format!(" {}", label))
+            || lower.starts_with(&format!("{}:", label))
+    })
+}
+
+fn violation(row_number: usize, column: &str, value: &str, message: String) -> RuleViolation {
+    RuleViolation {
+        row_number: row_number as u64,
+        column_name: column.to_string(),
+        value: value.to_string(),
+        message,
+    }
+}
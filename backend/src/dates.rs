@@ -0,0 +1,97 @@
+// Calendar helpers for dates found in imported spreadsheets. Dates are handled
+// as day numbers (days since 1970-01-01) so they compare and subtract cheaply.
+
+// Days between the Excel epoch (1899-12-30) and 1970-01-01
+const EXCEL_EPOCH_OFFSET: i64 = 25_569;
+
+// Parse ISO (2024-12-31, optionally with a time part), day-first
+// (31/12/2024, 31-12-2024, 31.12.2024) and Excel serial dates
+pub fn parse_date(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    // Excel serial, possibly with a fractional time of day
+    if let Ok(serial) = value.parse::<f64>() {
+        // 1900-01-01 .. 2199-12-31
+        if (1.0..=109_574.0).contains(&serial) {
+            return Some(serial.floor() as i64 - EXCEL_EPOCH_OFFSET);
+        }
+        return None;
+    }
+
+    let date_part = value
+        .split(['T', ' '])
+        .next()
+        .unwrap_or(value);
+    let parts: Vec<&str> = date_part.split(['-', '/', '.']).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let nums: Vec<i64> = parts
+        .iter()
+        .map(|p| p.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let (year, month, day) = if parts[0].len() == 4 {
+        (nums[0], nums[1], nums[2])
+    } else if parts[2].len() == 4 {
+        (nums[2], nums[1], nums[0])
+    } else {
+        return None;
+    };
+
+    days_from_civil(year, month, day)
+}
+
+// Day number for a calendar date, None if the date does not exist
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+// Calendar date (year, month, day) for a day number
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = if month <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 };
+    (year, month, day)
+}
+
+// ISO weekday: 1 = Monday .. 7 = Sunday
+pub fn weekday(days: i64) -> u32 {
+    // 1970-01-01 was a Thursday
+    ((days + 3).rem_euclid(7) + 1) as u32
+}
+
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ => {
+            let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+            if leap { 29 } else { 28 }
+        }
+    }
+}
@@ -2,6 +2,7 @@ use candid::{encode_args, Principal};
 use ic_cdk::api::time;
 
 use crate::activity_log::log_activity;
+use crate::analytical_procedures;
 use crate::auth;
 use crate::storage::STORAGE;
 use crate::types::{
@@ -115,6 +116,8 @@ pub fn create_engagement_from_template(
         start_date: req.start_date,
         end_date: req.end_date,
         status: "Planning".to_string(),
+        prior_year_engagement_id: req.prior_year_engagement_id,
+        materiality: None,
         created_at: time(),
         created_by: caller,
     };
@@ -470,6 +473,7 @@ pub fn get_engagement_dashboard(
         on_schedule,
         at_risk_milestones,
         recent_time_entries: time_entries.into_iter().take(10).collect(),
+        unexplained_variances: analytical_procedures::unexplained_variances(engagement_id),
     };
 
     Ok(dashboard)
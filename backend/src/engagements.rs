@@ -1,11 +1,16 @@
 use candid::Principal;
 use ic_cdk::api::time;
 use candid::encode_args;
+use std::collections::HashSet;
 
 use crate::activity_log::log_activity;
 use crate::auth;
 use crate::storage::{next_engagement_id, STORAGE};
-use crate::types::{CreateEngagementRequest, Engagement, EngagementLink, Result, UpdateEngagementRequest};
+use crate::types::{
+    CreateEngagementRequest, Engagement, EngagementLink, Materiality, Result, SetMaterialityRequest,
+    UpdateEngagementRequest,
+};
+use crate::working_papers;
 
 // Create engagement
 pub fn create_engagement(caller: Principal, req: CreateEngagementRequest) -> Result<Engagement> {
@@ -34,6 +39,10 @@ pub fn create_engagement(caller: Principal, req: CreateEngagementRequest) -> Res
         }
     }
 
+    if let Some(prior_year_id) = req.prior_year_engagement_id {
+        validate_prior_year(None, prior_year_id)?;
+    }
+
     let engagement = Engagement {
         id: next_engagement_id(),
         name: req.name,
@@ -42,6 +51,8 @@ pub fn create_engagement(caller: Principal, req: CreateEngagementRequest) -> Res
         start_date: req.start_date,
         end_date: req.end_date,
         status: req.status,
+        prior_year_engagement_id: req.prior_year_engagement_id,
+        materiality: None,
         created_at: time(),
         created_by: caller,
     };
@@ -160,6 +171,12 @@ pub fn update_engagement(caller: Principal, req: UpdateEngagementRequest) -> Res
     engagement.end_date = req.end_date;
     engagement.status = req.status;
 
+    if let Some(prior_year_id) = req.prior_year_engagement_id {
+        validate_prior_year(Some(engagement.id), prior_year_id)?;
+    }
+    engagement.prior_year_engagement_id = req.prior_year_engagement_id;
+    let archiving = is_archived(&engagement);
+
     STORAGE.with(|storage| {
         storage
             .borrow_mut()
@@ -177,6 +194,67 @@ pub fn update_engagement(caller: Principal, req: UpdateEngagementRequest) -> Res
         snapshot,
     );
 
+    if archiving {
+        working_papers::lock_working_papers_for_engagement(caller, engagement.id);
+    }
+
+    Ok(engagement)
+}
+
+// Set overall, performance and clearly trivial materiality
+pub fn set_engagement_materiality(caller: Principal, req: SetMaterialityRequest) -> Result<Engagement> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to set materiality".to_string());
+    }
+
+    let mut engagement = STORAGE
+        .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let clearly_trivial = req.clearly_trivial.unwrap_or(req.overall / 20);
+    if req.overall <= 0 || req.performance <= 0 || clearly_trivial < 0 {
+        return Err("Materiality amounts must be positive".to_string());
+    }
+    if req.performance > req.overall || clearly_trivial > req.performance {
+        return Err(
+            "Performance materiality cannot exceed overall, nor clearly trivial exceed performance"
+                .to_string(),
+        );
+    }
+
+    engagement.materiality = Some(Materiality {
+        overall: req.overall,
+        performance: req.performance,
+        clearly_trivial,
+        basis: req.basis,
+        set_at: time(),
+        set_by: caller,
+    });
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .engagements
+            .insert(engagement.id, engagement.clone());
+    });
+
+    let snapshot = encode_args((engagement.clone(),)).ok();
+    log_activity(
+        caller,
+        "set_engagement_materiality".to_string(),
+        "engagement".to_string(),
+        engagement.id.to_string(),
+        format!(
+            "Materiality for engagement {} set to {:.2} (performance {:.2})",
+            engagement.name,
+            req.overall as f64 / 100.0,
+            req.performance as f64 / 100.0
+        ),
+        snapshot,
+    );
+
     Ok(engagement)
 }
 
@@ -209,3 +287,62 @@ pub fn delete_engagement(caller: Principal, id: u64) -> Result<()> {
     Ok(())
 }
 
+// Prior-year engagements, most recent first, following the chain up to `limit` links
+pub fn prior_year_chain(engagement_id: u64, limit: usize) -> Vec<Engagement> {
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let mut chain = Vec::new();
+        let mut seen = HashSet::from([engagement_id]);
+        let mut next = storage
+            .engagements
+            .get(&engagement_id)
+            .and_then(|e| e.prior_year_engagement_id);
+
+        while let Some(id) = next {
+            if chain.len() >= limit || !seen.insert(id) {
+                break;
+            }
+            let Some(engagement) = storage.engagements.get(&id) else {
+                break;
+            };
+            next = engagement.prior_year_engagement_id;
+            chain.push(engagement);
+        }
+
+        chain
+    })
+}
+
+// A prior-year link must point at an existing engagement and must not loop back.
+// A new engagement has no ID yet, and nothing links to it.
+fn validate_prior_year(engagement_id: Option<u64>, prior_year_id: u64) -> Result<()> {
+    if Some(prior_year_id) == engagement_id {
+        return Err("An engagement cannot be its own prior year".to_string());
+    }
+
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let mut next = Some(prior_year_id);
+        let mut seen = HashSet::new();
+
+        while let Some(id) = next {
+            if Some(id) == engagement_id || !seen.insert(id) {
+                return Err("Prior-year link would create a cycle".to_string());
+            }
+            next = match storage.engagements.get(&id) {
+                Some(engagement) => engagement.prior_year_engagement_id,
+                None if id == prior_year_id => {
+                    return Err("Prior year engagement not found".to_string())
+                }
+                None => None,
+            };
+        }
+
+        Ok(())
+    })
+}
+
+// Archived engagements are read-only; their working papers are locked
+pub fn is_archived(engagement: &Engagement) -> bool {
+    engagement.status.trim().eq_ignore_ascii_case("archived")
+}
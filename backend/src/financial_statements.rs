@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use crate::activity_log::log_activity;
 use crate::auth;
 use crate::storage::{STORAGE, next_fs_id};
+use crate::trend_analysis;
 use crate::types::*;
 
 // Financial Statement Line Items by Taxonomy
@@ -159,12 +160,30 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             is_subtotal: false,
             parent: None,
         },
+        FSLineItem {
+            code: "BS_CTA".to_string(),
+            name: "احتياطي فروق ترجمة العملات / Foreign Currency Translation Reserve".to_string(),
+            category: FSCategory::Equity,
+            subcategory: "Equity".to_string(),
+            order: 16,
+            is_subtotal: false,
+            parent: None,
+        },
+        FSLineItem {
+            code: "BS_NCI".to_string(),
+            name: "الحقوق غير المسيطرة / Non-controlling Interests".to_string(),
+            category: FSCategory::Equity,
+            subcategory: "Equity".to_string(),
+            order: 17,
+            is_subtotal: false,
+            parent: None,
+        },
         FSLineItem {
             code: "BS_TOTAL_EQUITY".to_string(),
             name: "إجمالي حقوق الملكية / Total Equity".to_string(),
             category: FSCategory::Equity,
             subcategory: "Total".to_string(),
-            order: 16,
+            order: 18,
             is_subtotal: true,
             parent: None,
         },
@@ -174,7 +193,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "الإيرادات / Revenue".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Revenue".to_string(),
-            order: 17,
+            order: 19,
             is_subtotal: false,
             parent: None,
         },
@@ -183,7 +202,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "تكلفة المبيعات / Cost of Sales".to_string(),
             category: FSCategory::Expense,
             subcategory: "Cost of Sales".to_string(),
-            order: 18,
+            order: 20,
             is_subtotal: false,
             parent: None,
         },
@@ -192,7 +211,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "مجمل الربح / Gross Profit".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Subtotal".to_string(),
-            order: 19,
+            order: 21,
             is_subtotal: true,
             parent: None,
         },
@@ -201,7 +220,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "مصروفات تشغيلية / Operating Expenses".to_string(),
             category: FSCategory::Expense,
             subcategory: "Operating Expenses".to_string(),
-            order: 20,
+            order: 22,
             is_subtotal: false,
             parent: None,
         },
@@ -210,7 +229,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "إهلاك / Depreciation".to_string(),
             category: FSCategory::Expense,
             subcategory: "Operating Expenses".to_string(),
-            order: 21,
+            order: 23,
             is_subtotal: false,
             parent: None,
         },
@@ -219,7 +238,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "تكاليف التمويل / Finance Costs".to_string(),
             category: FSCategory::Expense,
             subcategory: "Finance Costs".to_string(),
-            order: 22,
+            order: 24,
             is_subtotal: false,
             parent: None,
         },
@@ -228,7 +247,7 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "الربح التشغيلي / Operating Profit".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Subtotal".to_string(),
-            order: 23,
+            order: 25,
             is_subtotal: true,
             parent: None,
         },
@@ -237,10 +256,19 @@ fn get_eas_line_items() -> Vec<FSLineItem> {
             name: "صافي الربح / Net Profit".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Bottom Line".to_string(),
-            order: 24,
+            order: 26,
             is_subtotal: true,
             parent: None,
         },
+        FSLineItem {
+            code: "IS_NCI_PROFIT".to_string(),
+            name: "الربح العائد إلى الحقوق غير المسيطرة / Profit Attributable to Non-controlling Interests".to_string(),
+            category: FSCategory::Expense,
+            subcategory: "Attribution".to_string(),
+            order: 27,
+            is_subtotal: false,
+            parent: None,
+        },
     ]
 }
 
@@ -389,12 +417,30 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             is_subtotal: false,
             parent: None,
         },
+        FSLineItem {
+            code: "BS_CTA".to_string(),
+            name: "Foreign Currency Translation Reserve".to_string(),
+            category: FSCategory::Equity,
+            subcategory: "Equity".to_string(),
+            order: 16,
+            is_subtotal: false,
+            parent: None,
+        },
+        FSLineItem {
+            code: "BS_NCI".to_string(),
+            name: "Non-controlling Interests".to_string(),
+            category: FSCategory::Equity,
+            subcategory: "Equity".to_string(),
+            order: 17,
+            is_subtotal: false,
+            parent: None,
+        },
         FSLineItem {
             code: "BS_TOTAL_EQUITY".to_string(),
             name: "Total Equity".to_string(),
             category: FSCategory::Equity,
             subcategory: "Total".to_string(),
-            order: 16,
+            order: 18,
             is_subtotal: true,
             parent: None,
         },
@@ -404,7 +450,7 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             name: "Revenue".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Revenue".to_string(),
-            order: 17,
+            order: 19,
             is_subtotal: false,
             parent: None,
         },
@@ -413,7 +459,7 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             name: "Cost of Goods Sold".to_string(),
             category: FSCategory::Expense,
             subcategory: "Cost of Sales".to_string(),
-            order: 18,
+            order: 20,
             is_subtotal: false,
             parent: None,
         },
@@ -422,7 +468,7 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             name: "Gross Profit".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Subtotal".to_string(),
-            order: 19,
+            order: 21,
             is_subtotal: true,
             parent: None,
         },
@@ -431,7 +477,7 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             name: "Operating Expenses".to_string(),
             category: FSCategory::Expense,
             subcategory: "Operating Expenses".to_string(),
-            order: 20,
+            order: 22,
             is_subtotal: false,
             parent: None,
         },
@@ -440,10 +486,19 @@ fn get_us_gaap_line_items() -> Vec<FSLineItem> {
             name: "Net Income".to_string(),
             category: FSCategory::Revenue,
             subcategory: "Bottom Line".to_string(),
-            order: 21,
+            order: 23,
             is_subtotal: true,
             parent: None,
         },
+        FSLineItem {
+            code: "IS_NCI_PROFIT".to_string(),
+            name: "Profit Attributable to Non-controlling Interests".to_string(),
+            category: FSCategory::Expense,
+            subcategory: "Attribution".to_string(),
+            order: 24,
+            is_subtotal: false,
+            parent: None,
+        },
     ]
 }
 
@@ -502,73 +557,16 @@ pub fn generate_financial_statements(
             .collect()
     });
 
-    // Map accounts to FS line items
-    let mut line_item_values: HashMap<String, i64> = HashMap::new();
-
-    for account in accounts.iter() {
-        if let Some(fs_line) = &account.fs_line_item {
-            if !fs_line.is_empty() {
-                // Calculate net balance based on account type
-                // Assets & Expenses: Debit positive (Debit - Credit)
-                // Liabilities, Equity, Revenue: Credit positive (Credit - Debit)
-                let net_balance = match account.account_type {
-                    AccountType::Asset | AccountType::Expense => {
-                        account.debit_balance - account.credit_balance
-                    },
-                    AccountType::Liability | AccountType::Equity | AccountType::Revenue => {
-                        account.credit_balance - account.debit_balance
-                    },
-                };
-
-                *line_item_values.entry(fs_line.clone()).or_insert(0) += net_balance;
-            }
-        }
-    }
-
-    // Calculate subtotals
-    line_item_values.insert("BS_CURRENT_ASSETS".to_string(), 
-        line_item_values.get("BS_CASH").unwrap_or(&0) +
-        line_item_values.get("BS_AR").unwrap_or(&0) +
-        line_item_values.get("BS_INVENTORY").unwrap_or(&0) +
-        line_item_values.get("BS_PREPAID").unwrap_or(&0)
-    );
-
-    line_item_values.insert("BS_TOTAL_ASSETS".to_string(),
-        line_item_values.get("BS_CURRENT_ASSETS").unwrap_or(&0) +
-        line_item_values.get("BS_PPE").unwrap_or(&0) +
-        line_item_values.get("BS_ACCUM_DEPR").unwrap_or(&0)
-    );
-
-    line_item_values.insert("BS_CURRENT_LIAB".to_string(),
-        line_item_values.get("BS_AP").unwrap_or(&0) +
-        line_item_values.get("BS_ACCRUED").unwrap_or(&0)
-    );
-
-    line_item_values.insert("BS_TOTAL_LIAB".to_string(),
-        line_item_values.get("BS_CURRENT_LIAB").unwrap_or(&0) +
-        line_item_values.get("BS_LONG_TERM_DEBT").unwrap_or(&0)
-    );
+    // Map accounts to FS line items, with subtotals
+    let line_item_values = fs_line_totals(&accounts);
 
-    line_item_values.insert("BS_TOTAL_EQUITY".to_string(),
-        line_item_values.get("BS_CAPITAL").unwrap_or(&0) +
-        line_item_values.get("BS_RETAINED").unwrap_or(&0)
-    );
-
-    line_item_values.insert("IS_GROSS_PROFIT".to_string(),
-        line_item_values.get("IS_REVENUE").unwrap_or(&0) -
-        line_item_values.get("IS_COGS").unwrap_or(&0)
-    );
-
-    line_item_values.insert("IS_OPERATING_PROFIT".to_string(),
-        line_item_values.get("IS_GROSS_PROFIT").unwrap_or(&0) -
-        line_item_values.get("IS_OPEX").unwrap_or(&0) -
-        line_item_values.get("IS_DEPRECIATION").unwrap_or(&0)
-    );
-
-    line_item_values.insert("IS_NET_INCOME".to_string(),
-        line_item_values.get("IS_OPERATING_PROFIT").unwrap_or(&0) -
-        line_item_values.get("IS_INTEREST").unwrap_or(&0)
-    );
+    // Comparatives from the prior-year engagement chain
+    let (prior_periods, _) =
+        trend_analysis::prior_periods(&trial_balance, trend_analysis::MAX_YEARS - 1);
+    let prior_values: Vec<HashMap<String, i64>> = prior_periods
+        .iter()
+        .map(|(_, prior_accounts)| fs_line_totals(prior_accounts))
+        .collect();
 
     // Create FS line items with values
     let fs_lines: Vec<FSLine> = line_items
@@ -583,6 +581,12 @@ pub fn generate_financial_statements(
                 })
                 .map(|acc| acc.id)
                 .collect(),
+            prior_amounts: Some(
+                prior_values
+                    .iter()
+                    .map(|values| values.get(&item.code).copied())
+                    .collect(),
+            ),
         })
         .collect();
 
@@ -594,6 +598,7 @@ pub fn generate_financial_statements(
         taxonomy: request.taxonomy.clone(),
         period_end_date: trial_balance.period_end_date.clone(),
         lines: fs_lines,
+        comparative_periods: Some(prior_periods.into_iter().map(|(period, _)| period).collect()),
         notes: vec![],
         created_at: time(),
         created_by: caller,
@@ -628,6 +633,82 @@ pub fn generate_financial_statements(
     Ok(financial_statement)
 }
 
+// Net balance in the account's natural sign
+// Assets & Expenses: Debit positive (Debit - Credit)
+// Liabilities, Equity, Revenue: Credit positive (Credit - Debit)
+pub fn net_balance(account: &TrialBalanceAccount) -> i64 {
+    match account.account_type {
+        AccountType::Asset | AccountType::Expense => {
+            account.debit_balance - account.credit_balance
+        },
+        AccountType::Liability | AccountType::Equity | AccountType::Revenue => {
+            account.credit_balance - account.debit_balance
+        },
+    }
+}
+
+// Totals per FS line code for mapped accounts, including the standard subtotals
+pub fn fs_line_totals(accounts: &[TrialBalanceAccount]) -> HashMap<String, i64> {
+    let mut line_item_values: HashMap<String, i64> = HashMap::new();
+
+    for account in accounts.iter() {
+        if let Some(fs_line) = &account.fs_line_item {
+            if !fs_line.is_empty() {
+                *line_item_values.entry(fs_line.clone()).or_insert(0) += net_balance(account);
+            }
+        }
+    }
+
+    // Calculate subtotals
+    line_item_values.insert("BS_CURRENT_ASSETS".to_string(), 
+        line_item_values.get("BS_CASH").unwrap_or(&0) +
+        line_item_values.get("BS_AR").unwrap_or(&0) +
+        line_item_values.get("BS_INVENTORY").unwrap_or(&0) +
+        line_item_values.get("BS_PREPAID").unwrap_or(&0)
+    );
+
+    line_item_values.insert("BS_TOTAL_ASSETS".to_string(),
+        line_item_values.get("BS_CURRENT_ASSETS").unwrap_or(&0) +
+        line_item_values.get("BS_PPE").unwrap_or(&0) +
+        line_item_values.get("BS_ACCUM_DEPR").unwrap_or(&0)
+    );
+
+    line_item_values.insert("BS_CURRENT_LIAB".to_string(),
+        line_item_values.get("BS_AP").unwrap_or(&0) +
+        line_item_values.get("BS_ACCRUED").unwrap_or(&0)
+    );
+
+    line_item_values.insert("BS_TOTAL_LIAB".to_string(),
+        line_item_values.get("BS_CURRENT_LIAB").unwrap_or(&0) +
+        line_item_values.get("BS_LONG_TERM_DEBT").unwrap_or(&0)
+    );
+
+    line_item_values.insert("BS_TOTAL_EQUITY".to_string(),
+        line_item_values.get("BS_CAPITAL").unwrap_or(&0) +
+        line_item_values.get("BS_RETAINED").unwrap_or(&0) +
+        line_item_values.get("BS_CTA").unwrap_or(&0) +
+        line_item_values.get("BS_NCI").unwrap_or(&0)
+    );
+
+    line_item_values.insert("IS_GROSS_PROFIT".to_string(),
+        line_item_values.get("IS_REVENUE").unwrap_or(&0) -
+        line_item_values.get("IS_COGS").unwrap_or(&0)
+    );
+
+    line_item_values.insert("IS_OPERATING_PROFIT".to_string(),
+        line_item_values.get("IS_GROSS_PROFIT").unwrap_or(&0) -
+        line_item_values.get("IS_OPEX").unwrap_or(&0) -
+        line_item_values.get("IS_DEPRECIATION").unwrap_or(&0)
+    );
+
+    line_item_values.insert("IS_NET_INCOME".to_string(),
+        line_item_values.get("IS_OPERATING_PROFIT").unwrap_or(&0) -
+        line_item_values.get("IS_INTEREST").unwrap_or(&0)
+    );
+
+    line_item_values
+}
+
 // Get Financial Statement
 pub fn get_financial_statement(caller: Principal, fs_id: u64) -> Result<FinancialStatement> {
     let _user = auth::get_user(caller).ok_or("User not found")?;
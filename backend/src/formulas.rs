@@ -0,0 +1,596 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{CellValue, WorkingPaperCell};
+
+pub const MAX_CELLS: usize = 500;
+const MAX_INPUT_LENGTH: usize = 1000;
+const MAX_RANGE_CELLS: usize = 10_000;
+
+// Balances a formula can look up, in currency units by account number. Both
+// LEADSHEET() and TB() are debit positive (credits negative), matching the
+// leadsheet and AJE columns.
+pub struct FormulaContext {
+    pub leadsheet: HashMap<String, f64>,
+    pub trial_balance: Option<HashMap<String, f64>>, // None without a linked trial balance
+}
+
+// Evaluate a grid of cell inputs. Inputs starting with "=" are formulas;
+// anything else is a number, TRUE/FALSE or text.
+pub fn evaluate_cells(inputs: &[(String, String)], context: &FormulaContext) -> Vec<WorkingPaperCell> {
+    let mut evaluator = Evaluator {
+        inputs: inputs.iter().cloned().collect(),
+        results: HashMap::new(),
+        visiting: HashSet::new(),
+        context,
+    };
+
+    let mut cells: Vec<WorkingPaperCell> = inputs
+        .iter()
+        .map(|(address, input)| {
+            let (value, error) = match evaluator.cell(address) {
+                Ok(value) => (value, None),
+                Err(error) => (CellValue::Empty, Some(error)),
+            };
+            WorkingPaperCell {
+                address: address.clone(),
+                input: input.clone(),
+                value,
+                error,
+            }
+        })
+        .collect();
+    cells.sort_by_key(|cell| parse_address(&cell.address));
+    cells
+}
+
+// Upper-case a cell address and check it is letters followed by a row number
+pub fn normalise_address(address: &str) -> Result<String, String> {
+    let address = address.trim().to_uppercase();
+    parse_address(&address)
+        .map(|_| address.clone())
+        .ok_or_else(|| format!("Invalid cell address {}", address))
+}
+
+// (column, row), both from 1
+fn parse_address(address: &str) -> Option<(usize, usize)> {
+    let split = address.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = address.split_at(split);
+    if letters.is_empty() || letters.len() > 3 || !letters.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    if digits.starts_with('0') || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let column = letters
+        .chars()
+        .fold(0, |acc, c| acc * 26 + (c as usize - 'A' as usize + 1));
+    Some((column, digits.parse().ok()?))
+}
+
+fn column_letters(mut column: usize) -> String {
+    let mut letters = Vec::new();
+    while column > 0 {
+        let rem = (column - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        column = (column - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
+struct Evaluator<'a> {
+    inputs: HashMap<String, String>,
+    results: HashMap<String, Result<CellValue, String>>,
+    visiting: HashSet<String>,
+    context: &'a FormulaContext,
+}
+
+impl Evaluator<'_> {
+    fn cell(&mut self, address: &str) -> Result<CellValue, String> {
+        if let Some(result) = self.results.get(address) {
+            return result.clone();
+        }
+        if !self.visiting.insert(address.to_string()) {
+            return Err(format!("Circular reference at {}", address));
+        }
+
+        let result = match self.inputs.get(address).cloned() {
+            None => Ok(CellValue::Empty),
+            Some(input) => match input.trim().strip_prefix('=') {
+                Some(formula) => parse(formula).and_then(|expr| self.eval(&expr)),
+                None => Ok(literal(&input)),
+            },
+        };
+
+        self.visiting.remove(address);
+        self.results.insert(address.to_string(), result.clone());
+        result
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Result<CellValue, String> {
+        match expr {
+            Expr::Number(n) => Ok(CellValue::Number(*n)),
+            Expr::Text(t) => Ok(CellValue::Text(t.clone())),
+            Expr::Boolean(b) => Ok(CellValue::Boolean(*b)),
+            Expr::Ref(address) => self.cell(address),
+            Expr::Range(_, _) => Err("A range can only be used inside SUM, MIN or MAX".to_string()),
+            Expr::Negate(inner) => Ok(CellValue::Number(-number(&self.eval(inner)?)?)),
+            Expr::Binary(op, left, right) => {
+                let left = self.eval(left)?;
+                let right = self.eval(right)?;
+                binary(*op, &left, &right)
+            }
+            Expr::Call(name, args) => self.call(name, args),
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[Expr]) -> Result<CellValue, String> {
+        match name {
+            "SUM" | "MIN" | "MAX" => {
+                let values = self.numbers(args)?;
+                let result = match name {
+                    "SUM" => values.iter().sum(),
+                    "MIN" => values.iter().cloned().fold(f64::INFINITY, f64::min),
+                    _ => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                };
+                // MIN and MAX of nothing are zero, as in Excel
+                Ok(CellValue::Number(if result.is_finite() { result } else { 0.0 }))
+            }
+            "IF" => {
+                if !(2..=3).contains(&args.len()) {
+                    return Err("IF takes a condition and one or two values".to_string());
+                }
+                if truthy(&self.eval(&args[0])?)? {
+                    self.eval(&args[1])
+                } else {
+                    args.get(2)
+                        .map_or(Ok(CellValue::Boolean(false)), |arg| self.eval(arg))
+                }
+            }
+            "ROUND" => {
+                if !(1..=2).contains(&args.len()) {
+                    return Err("ROUND takes a value and an optional number of digits".to_string());
+                }
+                let value = number(&self.eval(&args[0])?)?;
+                let digits = match args.get(1) {
+                    Some(arg) => number(&self.eval(arg)?)?.trunc() as i32,
+                    None => 0,
+                };
+                let factor = 10f64.powi(digits);
+                Ok(CellValue::Number((value * factor).round() / factor))
+            }
+            "ABS" => {
+                let [arg] = args else {
+                    return Err("ABS takes one value".to_string());
+                };
+                Ok(CellValue::Number(number(&self.eval(arg)?)?.abs()))
+            }
+            "LS" | "TB" => {
+                let [arg] = args else {
+                    return Err(format!("{} takes one account number", name));
+                };
+                let account = account_key(&self.eval(arg)?)?;
+                let (balances, label) = if name == "LS" {
+                    (Some(&self.context.leadsheet), "leadsheet")
+                } else {
+                    (self.context.trial_balance.as_ref(), "trial balance")
+                };
+                let balances = balances.ok_or("No trial balance linked to this working paper")?;
+                balances
+                    .get(&account)
+                    .map(|balance| CellValue::Number(*balance))
+                    .ok_or_else(|| format!("Account {} is not on the {}", account, label))
+            }
+            _ => Err(format!("Unknown function {}", name)),
+        }
+    }
+
+    // Numbers from arguments and ranges; text and blanks in a range are skipped
+    fn numbers(&mut self, args: &[Expr]) -> Result<Vec<f64>, String> {
+        let mut values = Vec::new();
+        for arg in args {
+            match arg {
+                Expr::Range(from, to) => {
+                    for address in expand_range(from, to)? {
+                        match self.cell(&address)? {
+                            CellValue::Number(n) => values.push(n),
+                            CellValue::Boolean(_) | CellValue::Text(_) | CellValue::Empty => {}
+                        }
+                    }
+                }
+                _ => values.push(number(&self.eval(arg)?)?),
+            }
+        }
+        Ok(values)
+    }
+}
+
+fn expand_range(from: &str, to: &str) -> Result<Vec<String>, String> {
+    let invalid = || format!("Invalid range {}:{}", from, to);
+    let (c1, r1) = parse_address(from).ok_or_else(invalid)?;
+    let (c2, r2) = parse_address(to).ok_or_else(invalid)?;
+    let (columns, rows) = (c1.min(c2)..=c1.max(c2), r1.min(r2)..=r1.max(r2));
+
+    if columns.clone().count() * rows.clone().count() > MAX_RANGE_CELLS {
+        return Err(format!("Range {}:{} is too large", from, to));
+    }
+
+    Ok(columns
+        .flat_map(|column| {
+            rows.clone()
+                .map(move |row| format!("{}{}", column_letters(column), row))
+        })
+        .collect())
+}
+
+fn literal(input: &str) -> CellValue {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return CellValue::Empty;
+    }
+    if let Ok(n) = trimmed.replace(',', "").parse::<f64>() {
+        return CellValue::Number(n);
+    }
+    match trimmed.to_uppercase().as_str() {
+        "TRUE" => CellValue::Boolean(true),
+        "FALSE" => CellValue::Boolean(false),
+        _ => CellValue::Text(input.to_string()),
+    }
+}
+
+fn number(value: &CellValue) -> Result<f64, String> {
+    match value {
+        CellValue::Number(n) => Ok(*n),
+        CellValue::Boolean(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        CellValue::Empty => Ok(0.0),
+        CellValue::Text(t) => t
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("\"{}\" is not a number", t)),
+    }
+}
+
+fn truthy(value: &CellValue) -> Result<bool, String> {
+    match value {
+        CellValue::Boolean(b) => Ok(*b),
+        other => Ok(number(other)? != 0.0),
+    }
+}
+
+// Account numbers may be typed as text or as a number
+fn account_key(value: &CellValue) -> Result<String, String> {
+    match value {
+        CellValue::Text(t) => Ok(t.trim().to_string()),
+        CellValue::Number(n) if n.fract() == 0.0 => Ok(format!("{}", *n as i64)),
+        _ => Err("Account number must be text or a whole number".to_string()),
+    }
+}
+
+fn binary(op: Op, left: &CellValue, right: &CellValue) -> Result<CellValue, String> {
+    if let Op::Concat = op {
+        return Ok(CellValue::Text(format!("{}{}", display(left), display(right))));
+    }
+    if let Some(ordering) = comparison(op) {
+        let result = match (left, right) {
+            (CellValue::Text(a), CellValue::Text(b)) => {
+                ordering(a.to_lowercase().cmp(&b.to_lowercase()))
+            }
+            (CellValue::Text(_), _) | (_, CellValue::Text(_)) => matches!(op, Op::NotEqual),
+            _ => {
+                let (a, b) = (number(left)?, number(right)?);
+                match a.partial_cmp(&b) {
+                    Some(o) => ordering(o),
+                    None => false,
+                }
+            }
+        };
+        return Ok(CellValue::Boolean(result));
+    }
+
+    let (a, b) = (number(left)?, number(right)?);
+    let result = match op {
+        Op::Add => a + b,
+        Op::Subtract => a - b,
+        Op::Multiply => a * b,
+        Op::Divide if b == 0.0 => return Err("Division by zero".to_string()),
+        Op::Divide => a / b,
+        Op::Power => a.powf(b),
+        _ => unreachable!(),
+    };
+    if !result.is_finite() {
+        return Err("Result is not a finite number".to_string());
+    }
+    Ok(CellValue::Number(result))
+}
+
+fn comparison(op: Op) -> Option<fn(std::cmp::Ordering) -> bool> {
+    use std::cmp::Ordering;
+    match op {
+        Op::Equal => Some(|o| o == Ordering::Equal),
+        Op::NotEqual => Some(|o| o != Ordering::Equal),
+        Op::Less => Some(|o| o == Ordering::Less),
+        Op::LessEqual => Some(|o| o != Ordering::Greater),
+        Op::Greater => Some(|o| o == Ordering::Greater),
+        Op::GreaterEqual => Some(|o| o != Ordering::Less),
+        _ => None,
+    }
+}
+
+fn display(value: &CellValue) -> String {
+    match value {
+        CellValue::Empty => String::new(),
+        CellValue::Number(n) => n.to_string(),
+        CellValue::Text(t) => t.clone(),
+        CellValue::Boolean(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+    }
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Concat,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    Ref(String),
+    Range(String, String),
+    Negate(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    Op(Op),
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+}
+
+fn parse(formula: &str) -> Result<Expr, String> {
+    if formula.len() > MAX_INPUT_LENGTH {
+        return Err("Formula is too long".to_string());
+    }
+
+    let tokens = tokenize(formula)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.comparison()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(format!("Unexpected {:?} in formula", token)),
+    }
+}
+
+fn tokenize(formula: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' => i += 1,
+            '0'..='9' | '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let n = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number {}", text))?;
+                tokens.push(Token::Number(n));
+            }
+            '"' => {
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|c| *c == '"')
+                    .ok_or("Unterminated text in formula")?;
+                tokens.push(Token::Text(chars[start..start + end].iter().collect()));
+                i = start + end + 1;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+                tokens.push(Token::Ident(ident.to_uppercase()));
+            }
+            _ => {
+                let next = chars.get(i + 1).copied();
+                let (token, width) = match (c, next) {
+                    ('<', Some('=')) => (Token::Op(Op::LessEqual), 2),
+                    ('>', Some('=')) => (Token::Op(Op::GreaterEqual), 2),
+                    ('<', Some('>')) => (Token::Op(Op::NotEqual), 2),
+                    ('<', _) => (Token::Op(Op::Less), 1),
+                    ('>', _) => (Token::Op(Op::Greater), 1),
+                    ('=', _) => (Token::Op(Op::Equal), 1),
+                    ('+', _) => (Token::Op(Op::Add), 1),
+                    ('-', _) => (Token::Op(Op::Subtract), 1),
+                    ('*', _) => (Token::Op(Op::Multiply), 1),
+                    ('/', _) => (Token::Op(Op::Divide), 1),
+                    ('^', _) => (Token::Op(Op::Power), 1),
+                    ('&', _) => (Token::Op(Op::Concat), 1),
+                    ('(', _) => (Token::LParen, 1),
+                    (')', _) => (Token::RParen, 1),
+                    (',', _) => (Token::Comma, 1),
+                    (':', _) => (Token::Colon, 1),
+                    _ => return Err(format!("Unexpected character '{}' in formula", c)),
+                };
+                tokens.push(token);
+                i += width;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {:?} but found {:?}", expected, token)),
+            None => Err(format!("Expected {:?} at end of formula", expected)),
+        }
+    }
+
+    // Lowest precedence: comparisons, then &, + and -, * and /, ^
+    fn comparison(&mut self) -> Result<Expr, String> {
+        let left = self.concat()?;
+        match self.peek() {
+            Some(Token::Op(op)) if comparison(*op).is_some() => {
+                let op = *op;
+                self.pos += 1;
+                let right = self.concat()?;
+                Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+            }
+            _ => Ok(left),
+        }
+    }
+
+    fn concat(&mut self) -> Result<Expr, String> {
+        let mut left = self.additive()?;
+        while let Some(Token::Op(Op::Concat)) = self.peek() {
+            self.pos += 1;
+            let right = self.additive()?;
+            left = Expr::Binary(Op::Concat, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ (Op::Add | Op::Subtract))) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            let right = self.term()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op @ (Op::Multiply | Op::Divide))) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some(Token::Op(Op::Subtract)) => {
+                self.pos += 1;
+                Ok(Expr::Negate(Box::new(self.unary()?)))
+            }
+            Some(Token::Op(Op::Add)) => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Expr, String> {
+        let base = self.primary()?;
+        if let Some(Token::Op(Op::Power)) = self.peek() {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(Op::Power, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Text(t)) => Ok(Expr::Text(t)),
+            Some(Token::LParen) => {
+                let expr = self.comparison()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(Token::LParen) = self.peek() {
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if let Some(Token::RParen) = self.peek() {
+                        self.pos += 1;
+                        return Ok(Expr::Call(name, args));
+                    }
+                    loop {
+                        args.push(self.comparison()?);
+                        match self.next() {
+                            Some(Token::Comma) => continue,
+                            Some(Token::RParen) => return Ok(Expr::Call(name, args)),
+                            _ => return Err(format!("Expected , or ) in call to {}", name)),
+                        }
+                    }
+                }
+                match name.as_str() {
+                    "TRUE" => return Ok(Expr::Boolean(true)),
+                    "FALSE" => return Ok(Expr::Boolean(false)),
+                    _ => {}
+                }
+                if parse_address(&name).is_none() {
+                    return Err(format!("Unknown name {}", name));
+                }
+                if let Some(Token::Colon) = self.peek() {
+                    self.pos += 1;
+                    return match self.next() {
+                        Some(Token::Ident(end)) if parse_address(&end).is_some() => {
+                            Ok(Expr::Range(name, end))
+                        }
+                        _ => Err(format!("Invalid range starting at {}", name)),
+                    };
+                }
+                Ok(Expr::Ref(name))
+            }
+            Some(token) => Err(format!("Unexpected {:?} in formula", token)),
+            None => Err("Formula ended unexpectedly".to_string()),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::dates;
+use crate::storage::{next_account_id, next_fx_rate_id, STORAGE};
+use crate::trial_balance;
+use crate::types::{
+    AccountRate, AccountType, CreateTrialBalanceRequest, FxRate, FxRateSource, FxRateType, Result,
+    SetFxRateRequest, TranslateTrialBalanceRequest, TranslationLine, TranslationResult, TrialBalance,
+    TrialBalanceAccount,
+};
+
+pub const CTA_LINE: &str = "BS_CTA";
+pub const CTA_NUMBER: &str = "3998";
+
+// The cumulative translation adjustment account of a translated trial balance
+pub fn is_cta(account: &TrialBalanceAccount) -> bool {
+    account.fs_line_item.as_deref() == Some(CTA_LINE)
+}
+
+pub fn normalise_currency(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+pub fn set_fx_rate(caller: Principal, req: SetFxRateRequest) -> Result<FxRate> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to maintain FX rates".to_string());
+    }
+
+    let rate = store_rate(caller, req, FxRateSource::Manual)?;
+
+    let snapshot = encode_args((rate.clone(),)).ok();
+    log_activity(
+        caller,
+        "set_fx_rate".to_string(),
+        "fx_rate".to_string(),
+        rate.id.to_string(),
+        format!(
+            "Set {:?} rate {}/{} at {} to {}",
+            rate.rate_type, rate.from_currency, rate.to_currency, rate.effective_date, rate.rate
+        ),
+        snapshot,
+    );
+
+    Ok(rate)
+}
+
+// Rates from a bank or central bank feed. All rows are checked before any is
+// stored, and a row replaces an existing rate for the same pair, type and date.
+pub fn import_fx_rates(caller: Principal, rows: Vec<SetFxRateRequest>) -> Result<Vec<FxRate>> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to maintain FX rates".to_string());
+    }
+
+    if rows.is_empty() {
+        return Err("No FX rates to import".to_string());
+    }
+    for (index, row) in rows.iter().enumerate() {
+        validate_rate(row).map_err(|e| format!("Row {}: {}", index + 1, e))?;
+    }
+
+    let rates: Vec<FxRate> = rows
+        .into_iter()
+        .map(|row| store_rate(caller, row, FxRateSource::Imported))
+        .collect::<Result<_>>()?;
+
+    let snapshot = encode_args((rates.clone(),)).ok();
+    log_activity(
+        caller,
+        "import_fx_rates".to_string(),
+        "fx_rate".to_string(),
+        rates.iter().map(|rate| rate.id.to_string()).collect::<Vec<_>>().join(","),
+        format!("Imported {} FX rates", rates.len()),
+        snapshot,
+    );
+
+    Ok(rates)
+}
+
+// Stored rates, optionally only those quoted against a currency
+pub fn list_fx_rates(caller: Principal, currency: Option<String>) -> Result<Vec<FxRate>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let currency = currency.map(|code| normalise_currency(&code));
+    let mut rates: Vec<FxRate> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .fx_rates
+            .iter()
+            .map(|(_, rate)| rate)
+            .filter(|rate| {
+                currency
+                    .as_ref()
+                    .is_none_or(|code| &rate.from_currency == code || &rate.to_currency == code)
+            })
+            .collect()
+    });
+    rates.sort_by(|a, b| {
+        (&a.from_currency, &a.to_currency)
+            .cmp(&(&b.from_currency, &b.to_currency))
+            .then(dates::parse_date(&b.effective_date).cmp(&dates::parse_date(&a.effective_date)))
+    });
+
+    Ok(rates)
+}
+
+fn validate_rate(req: &SetFxRateRequest) -> Result<()> {
+    let from = normalise_currency(&req.from_currency);
+    let to = normalise_currency(&req.to_currency);
+    for code in [&from, &to] {
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("'{}' is not a three-letter currency code", code));
+        }
+    }
+    if from == to {
+        return Err("A rate needs two different currencies".to_string());
+    }
+    if !req.rate.is_finite() || req.rate <= 0.0 {
+        return Err("Rate must be a positive number".to_string());
+    }
+    if dates::parse_date(&req.effective_date).is_none() {
+        return Err(format!("Invalid effective date '{}'", req.effective_date));
+    }
+    Ok(())
+}
+
+fn store_rate(caller: Principal, req: SetFxRateRequest, source: FxRateSource) -> Result<FxRate> {
+    validate_rate(&req)?;
+
+    let from_currency = normalise_currency(&req.from_currency);
+    let to_currency = normalise_currency(&req.to_currency);
+    let day = dates::parse_date(&req.effective_date);
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        let existing = storage.fx_rates.iter().find(|(_, rate)| {
+            rate.from_currency == from_currency
+                && rate.to_currency == to_currency
+                && rate.rate_type == req.rate_type
+                && dates::parse_date(&rate.effective_date) == day
+        });
+
+        let rate = FxRate {
+            id: existing.map(|(id, _)| id).unwrap_or_else(next_fx_rate_id),
+            from_currency,
+            to_currency,
+            rate_type: req.rate_type,
+            rate: req.rate,
+            effective_date: day.map(dates::format_date).unwrap_or(req.effective_date),
+            source,
+            created_at: time(),
+            created_by: caller,
+        };
+        storage.fx_rates.insert(rate.id, rate.clone());
+        Ok(rate)
+    })
+}
+
+// The latest rate of a type dated on or before the given date, using the
+// inverse of the opposite quote when only that is stored
+pub fn find_rate(from: &str, to: &str, rate_type: &FxRateType, as_of: &str) -> Option<f64> {
+    let from = normalise_currency(from);
+    let to = normalise_currency(to);
+    if from == to {
+        return Some(1.0);
+    }
+    let as_of = dates::parse_date(as_of);
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .fx_rates
+            .iter()
+            .map(|(_, rate)| rate)
+            .filter(|rate| &rate.rate_type == rate_type)
+            .filter_map(|rate| {
+                let day = dates::parse_date(&rate.effective_date);
+                if as_of.is_some() && day > as_of {
+                    return None;
+                }
+                if rate.from_currency == from && rate.to_currency == to {
+                    Some((day, true, rate.rate))
+                } else if rate.from_currency == to && rate.to_currency == from {
+                    Some((day, false, 1.0 / rate.rate))
+                } else {
+                    None
+                }
+            })
+            .max_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)))
+            .map(|(_, _, rate)| rate)
+    })
+}
+
+// IAS 21 rate for an account: closing for the balance sheet, average for the
+// income statement and historical for equity
+pub fn rate_type_for(account_type: &AccountType) -> FxRateType {
+    match account_type {
+        AccountType::Asset | AccountType::Liability => FxRateType::Closing,
+        AccountType::Revenue | AccountType::Expense => FxRateType::Average,
+        AccountType::Equity => FxRateType::Historical,
+    }
+}
+
+// Translate a trial balance's accounts into the presentation currency. Returns
+// the lines and the translation difference that brings them back into balance.
+pub fn translate_accounts(
+    trial_balance: &TrialBalance,
+    accounts: &[TrialBalanceAccount],
+    presentation_currency: &str,
+    historical_rates: &[AccountRate],
+) -> Result<(Vec<TranslationLine>, i64)> {
+    let mut missing: Vec<String> = Vec::new();
+    let mut lines = Vec::with_capacity(accounts.len());
+
+    // An existing CTA is not translated; it is recomputed as the balancing figure
+    for account in accounts.iter().filter(|account| !is_cta(account)) {
+        let currency = account.currency.clone().unwrap_or_else(|| trial_balance.currency.clone());
+        let override_rate = historical_rates
+            .iter()
+            .find(|rate| rate.account_number == account.account_number)
+            .map(|rate| rate.rate);
+        let rate_type = if override_rate.is_some() {
+            FxRateType::Historical
+        } else {
+            rate_type_for(&account.account_type)
+        };
+        let rate = override_rate.or_else(|| {
+            find_rate(&currency, presentation_currency, &rate_type, &trial_balance.period_end_date)
+        });
+        let Some(rate) = rate else {
+            let pair = format!("{:?} {}/{}", rate_type, currency, presentation_currency);
+            if !missing.contains(&pair) {
+                missing.push(pair);
+            }
+            continue;
+        };
+
+        let balance = account.debit_balance - account.credit_balance;
+        lines.push(TranslationLine {
+            account_id: account.id,
+            account_number: account.account_number.clone(),
+            account_name: account.account_name.clone(),
+            account_type: account.account_type.clone(),
+            currency,
+            balance,
+            rate_type,
+            rate,
+            translated_balance: (balance as f64 * rate).round() as i64,
+        });
+    }
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "No rate on or before {} for: {}",
+            trial_balance.period_end_date,
+            missing.join(", ")
+        ));
+    }
+
+    let translation_difference = -lines.iter().map(|line| line.translated_balance).sum::<i64>();
+    Ok((lines, translation_difference))
+}
+
+// Create a new trial balance in the presentation currency, posting the
+// translation difference to the cumulative translation adjustment account
+pub fn translate_trial_balance(caller: Principal, req: TranslateTrialBalanceRequest) -> Result<TranslationResult> {
+    let source = trial_balance::get_trial_balance(caller, req.trial_balance_id)?;
+    let accounts = trial_balance::get_accounts(caller, source.id)?;
+
+    let presentation_currency = normalise_currency(&req.presentation_currency);
+    if presentation_currency.len() != 3 || !presentation_currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("'{}' is not a three-letter currency code", presentation_currency));
+    }
+
+    let (lines, translation_difference) =
+        translate_accounts(&source, &accounts, &presentation_currency, &req.historical_rates)?;
+
+    let new_tb = trial_balance::create_trial_balance(
+        caller,
+        CreateTrialBalanceRequest {
+            engagement_id: req.engagement_id.unwrap_or(source.engagement_id),
+            period_end_date: source.period_end_date.clone(),
+            description: req.description.unwrap_or_else(|| {
+                format!("{} translated from {} to {}", source.description, source.currency, presentation_currency)
+            }),
+            currency: Some(presentation_currency.clone()),
+            period_type: source.period_type.clone(),
+            prior_trial_balance_id: None,
+        },
+    )?;
+
+    let timestamp = time();
+    let mut new_accounts: Vec<TrialBalanceAccount> = accounts
+        .iter()
+        .filter(|account| !is_cta(account))
+        .zip(&lines)
+        .map(|(account, line)| TrialBalanceAccount {
+            id: next_account_id(),
+            trial_balance_id: new_tb.id,
+            account_number: account.account_number.clone(),
+            account_name: account.account_name.clone(),
+            account_type: account.account_type.clone(),
+            debit_balance: line.translated_balance.max(0),
+            credit_balance: (-line.translated_balance).max(0),
+            fs_line_item: account.fs_line_item.clone(),
+            notes: account.notes.clone(),
+            opening_balance: None,
+            currency: None,
+            coa_account_id: account.coa_account_id,
+            is_reconciled: false,
+            created_at: timestamp,
+            created_by: caller,
+        })
+        .collect();
+
+    // A source that was itself translated already carries a CTA account; it
+    // keeps its number and name and takes the new balancing figure
+    let source_cta = accounts.iter().find(|account| is_cta(account));
+    if translation_difference != 0 || source_cta.is_some() {
+        let cta = TrialBalanceAccount {
+            id: next_account_id(),
+            trial_balance_id: new_tb.id,
+            account_number: CTA_NUMBER.to_string(),
+            account_name: "Foreign currency translation reserve".to_string(),
+            account_type: AccountType::Equity,
+            debit_balance: translation_difference.max(0),
+            credit_balance: (-translation_difference).max(0),
+            fs_line_item: Some(CTA_LINE.to_string()),
+            notes: format!("Translation difference on translating trial balance {}", source.id),
+            opening_balance: None,
+            currency: None,
+            coa_account_id: None,
+            is_reconciled: false,
+            created_at: timestamp,
+            created_by: caller,
+        };
+        new_accounts.push(match source_cta {
+            Some(account) => TrialBalanceAccount {
+                account_number: account.account_number.clone(),
+                account_name: account.account_name.clone(),
+                coa_account_id: account.coa_account_id,
+                ..cta
+            },
+            None => cta,
+        });
+    }
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for account in &new_accounts {
+            storage.trial_balance_accounts.insert(account.id, account.clone());
+        }
+    });
+
+    let snapshot = encode_args((new_tb.clone(), lines.clone())).ok();
+    log_activity(
+        caller,
+        "translate_trial_balance".to_string(),
+        "trial_balance".to_string(),
+        new_tb.id.to_string(),
+        format!(
+            "Translated trial balance {} into {} with a translation difference of {:.2}",
+            source.id,
+            presentation_currency,
+            translation_difference as f64 / 100.0
+        ),
+        snapshot,
+    );
+
+    Ok(TranslationResult {
+        source_trial_balance_id: source.id,
+        trial_balance: new_tb,
+        lines,
+        translation_difference,
+    })
+}
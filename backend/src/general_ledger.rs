@@ -0,0 +1,294 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::HashMap;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::data_import;
+use crate::dates;
+use crate::storage::{next_gl_import_id, next_journal_entry_id, next_journal_line_id, STORAGE};
+use crate::types::{GlImport, ImportGlJournalRequest, JournalEntry, JournalLine, Result};
+
+// Map a dataset sheet into journal entries and lines. Rows are grouped by
+// entry number; header fields come from the first row of each entry.
+pub fn import_gl_journal(caller: Principal, req: ImportGlJournalRequest) -> Result<GlImport> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to import journals".to_string());
+    }
+
+    STORAGE
+        .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let dataset = data_import::get_dataset(caller, req.dataset_id)?;
+    let sheet_name = match &req.sheet_name {
+        Some(name) => name.clone(),
+        None => dataset
+            .sheets
+            .first()
+            .map(|s| s.name.clone())
+            .ok_or_else(|| "Dataset has no sheets".to_string())?,
+    };
+
+    let population =
+        data_import::load_population(caller, &dataset, &sheet_name, req.file_data.as_deref())?;
+
+    let mapping = &req.mapping;
+    if mapping.amount.is_none() && mapping.debit.is_none() && mapping.credit.is_none() {
+        return Err("Map either an amount column or debit/credit columns".to_string());
+    }
+
+    let column = |name: &String| -> Result<usize> {
+        population
+            .headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| format!("Column '{}' not found", name))
+    };
+    let optional = |name: &Option<String>| -> Result<Option<usize>> {
+        name.as_ref().map(column).transpose()
+    };
+
+    let entry_idx = column(&mapping.entry_number)?;
+    let posting_date_idx = column(&mapping.posting_date)?;
+    let account_idx = column(&mapping.account_number)?;
+    let account_name_idx = optional(&mapping.account_name)?;
+    let amount_idx = optional(&mapping.amount)?;
+    let debit_idx = optional(&mapping.debit)?;
+    let credit_idx = optional(&mapping.credit)?;
+    let posted_by_idx = optional(&mapping.posted_by)?;
+    let entry_date_idx = optional(&mapping.entry_date)?;
+    let description_idx = optional(&mapping.description)?;
+
+    let gl_import_id = next_gl_import_id();
+    let mut entries: Vec<JournalEntry> = Vec::new();
+    let mut entry_positions: HashMap<String, usize> = HashMap::new();
+    let mut lines: Vec<JournalLine> = Vec::new();
+    let mut skipped_rows = Vec::new();
+
+    for (row_idx, row) in population.rows.iter().enumerate() {
+        let value = |idx: Option<usize>| -> String {
+            idx.and_then(|i| row.get(i))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+
+        let entry_number = value(Some(entry_idx));
+        let account_number = value(Some(account_idx));
+        if entry_number.is_empty() || account_number.is_empty() {
+            if row.iter().any(|v| !v.trim().is_empty()) {
+                skipped_rows.push(row_idx as u64);
+            }
+            continue;
+        }
+
+        let posting_day = match dates::parse_date(&value(Some(posting_date_idx))) {
+            Some(day) => day,
+            None => {
+                skipped_rows.push(row_idx as u64);
+                continue;
+            }
+        };
+
+        let (debit, credit) = match amount_idx {
+            Some(idx) => {
+                let amount = parse_amount_cents(&value(Some(idx))).unwrap_or(0);
+                (amount.max(0), (-amount).max(0))
+            }
+            None => {
+                let debit = parse_amount_cents(&value(debit_idx)).unwrap_or(0);
+                let credit = parse_amount_cents(&value(credit_idx)).unwrap_or(0);
+                // Negative debits/credits are reversals of the other side
+                let net = debit - credit;
+                (net.max(0), (-net).max(0))
+            }
+        };
+
+        let position = match entry_positions.get(&entry_number) {
+            Some(position) => *position,
+            None => {
+                entry_positions.insert(entry_number.clone(), entries.len());
+                entries.push(JournalEntry {
+                    id: next_journal_entry_id(),
+                    gl_import_id,
+                    engagement_id: req.engagement_id,
+                    entry_number: entry_number.clone(),
+                    posting_date: dates::format_date(posting_day),
+                    entry_date: dates::parse_date(&value(entry_date_idx)).map(dates::format_date),
+                    posted_by: value(posted_by_idx),
+                    description: value(description_idx),
+                    total_debit: 0,
+                    total_credit: 0,
+                    line_count: 0,
+                });
+                entries.len() - 1
+            }
+        };
+        let entry = &mut entries[position];
+
+        entry.total_debit += debit;
+        entry.total_credit += credit;
+        entry.line_count += 1;
+
+        lines.push(JournalLine {
+            id: next_journal_line_id(),
+            journal_entry_id: entry.id,
+            gl_import_id,
+            line_number: entry.line_count,
+            account_number,
+            account_name: value(account_name_idx),
+            debit,
+            credit,
+            description: value(description_idx),
+            row_index: row_idx as u64,
+        });
+    }
+
+    if entries.is_empty() {
+        return Err("No journal lines found with the given mapping".to_string());
+    }
+
+    let gl_import = GlImport {
+        id: gl_import_id,
+        engagement_id: req.engagement_id,
+        dataset_id: dataset.id,
+        dataset_version: dataset.version,
+        sheet_name,
+        mapping: req.mapping.clone(),
+        entry_count: entries.len() as u64,
+        line_count: lines.len() as u64,
+        total_debits: entries.iter().map(|e| e.total_debit).sum(),
+        total_credits: entries.iter().map(|e| e.total_credit).sum(),
+        unbalanced_entries: entries
+            .iter()
+            .filter(|e| e.total_debit != e.total_credit)
+            .map(|e| e.entry_number.clone())
+            .collect(),
+        skipped_rows,
+        complete_population: population.complete,
+        imported_at: time(),
+        imported_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage.gl_imports.insert(gl_import.id, gl_import.clone());
+        for entry in &entries {
+            storage.journal_entries.insert(entry.id, entry.clone());
+        }
+        for line in &lines {
+            storage.journal_lines.insert(line.id, line.clone());
+        }
+    });
+
+    log_activity(
+        caller,
+        "import_gl_journal".to_string(),
+        "gl_import".to_string(),
+        gl_import.id.to_string(),
+        format!(
+            "Imported {} journal entries ({} lines) from dataset {}",
+            gl_import.entry_count, gl_import.line_count, dataset.id
+        ),
+        encode_args((gl_import.clone(),)).ok(),
+    );
+
+    Ok(gl_import)
+}
+
+// Get GL import by ID
+pub fn get_gl_import(caller: Principal, id: u64) -> Result<GlImport> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().gl_imports.get(&id))
+        .ok_or_else(|| "GL import not found".to_string())
+}
+
+// List GL imports by engagement
+pub fn list_gl_imports_by_engagement(caller: Principal, engagement_id: u64) -> Result<Vec<GlImport>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let imports = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .gl_imports
+            .iter()
+            .filter(|(_, gl)| gl.engagement_id == engagement_id)
+            .map(|(_, gl)| gl)
+            .collect()
+    });
+
+    Ok(imports)
+}
+
+// Get journal entries of a GL import
+pub fn get_journal_entries(caller: Principal, gl_import_id: u64) -> Result<Vec<JournalEntry>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(entries_for_import(gl_import_id))
+}
+
+// Get the lines of one journal entry
+pub fn get_journal_lines(caller: Principal, journal_entry_id: u64) -> Result<Vec<JournalLine>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let lines = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .journal_lines
+            .iter()
+            .filter(|(_, line)| line.journal_entry_id == journal_entry_id)
+            .map(|(_, line)| line)
+            .collect()
+    });
+
+    Ok(lines)
+}
+
+pub fn entries_for_import(gl_import_id: u64) -> Vec<JournalEntry> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .journal_entries
+            .iter()
+            .filter(|(_, entry)| entry.gl_import_id == gl_import_id)
+            .map(|(_, entry)| entry)
+            .collect()
+    })
+}
+
+pub fn lines_for_import(gl_import_id: u64) -> Vec<JournalLine> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .journal_lines
+            .iter()
+            .filter(|(_, line)| line.gl_import_id == gl_import_id)
+            .map(|(_, line)| line)
+            .collect()
+    })
+}
+
+// Parse an exported amount into cents. Handles thousands separators, currency
+// symbols and accounting negatives such as "(1,250.00)" or "1,250.00-".
+pub fn parse_amount_cents(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let negative = (value.starts_with('(') && value.ends_with(')'))
+        || value.ends_with('-')
+        || value.starts_with('-');
+    let clean: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let amount = clean.parse::<f64>().ok()?;
+    let cents = (amount * 100.0).round() as i64;
+
+    Some(if negative { -cents } else { cents })
+}
@@ -0,0 +1,294 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::{HashMap, HashSet};
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::dates;
+use crate::general_ledger;
+use crate::storage::{next_jet_run_id, STORAGE};
+use crate::types::{JetConfig, JetFinding, JetFlag, JetFlagCount, JetRun, JournalLine, Result};
+
+const DEFAULT_WEEKEND_DAYS: &[u32] = &[5, 6]; // Friday and Saturday
+const DEFAULT_ROUND_AMOUNT_UNIT: i64 = 100_000; // 1,000.00
+const DEFAULT_JUST_BELOW_PERCENT: f64 = 5.0;
+const DEFAULT_RARE_PAIR_MAX_COUNT: u32 = 1;
+// Users with fewer lines than this have no meaningful "usual" accounts
+const MIN_USER_LINES_FOR_PAIRS: usize = 20;
+
+// Run the journal entry tests over a GL import and save the results to the engagement
+pub fn run_journal_entry_tests(
+    caller: Principal,
+    gl_import_id: u64,
+    config: JetConfig,
+) -> Result<JetRun> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to run journal entry tests".to_string());
+    }
+
+    let gl_import = STORAGE
+        .with(|storage| storage.borrow().gl_imports.get(&gl_import_id))
+        .ok_or_else(|| "GL import not found".to_string())?;
+
+    let period_end = dates::parse_date(&config.period_end_date)
+        .ok_or_else(|| format!("Invalid period end date '{}'", config.period_end_date))?;
+    let close_day = match &config.close_date {
+        Some(date) => dates::parse_date(date).ok_or_else(|| format!("Invalid close date '{}'", date))?,
+        None => period_end,
+    };
+    let holidays = config
+        .holidays
+        .iter()
+        .map(|h| dates::parse_date(h).ok_or_else(|| format!("Invalid holiday date '{}'", h)))
+        .collect::<Result<HashSet<i64>>>()?;
+    let weekend_days = config
+        .weekend_days
+        .clone()
+        .unwrap_or_else(|| DEFAULT_WEEKEND_DAYS.to_vec());
+    let round_unit = config.round_amount_unit.unwrap_or(DEFAULT_ROUND_AMOUNT_UNIT);
+    if round_unit <= 0 {
+        return Err("Round amount unit must be positive".to_string());
+    }
+    let below_percent = config.just_below_percent.unwrap_or(DEFAULT_JUST_BELOW_PERCENT);
+    let rare_pair_max = config.rare_pair_max_count.unwrap_or(DEFAULT_RARE_PAIR_MAX_COUNT) as usize;
+
+    let entries = general_ledger::entries_for_import(gl_import_id);
+    let lines = general_ledger::lines_for_import(gl_import_id);
+
+    let mut lines_by_entry: HashMap<u64, Vec<&JournalLine>> = HashMap::new();
+    for line in &lines {
+        lines_by_entry.entry(line.journal_entry_id).or_default().push(line);
+    }
+
+    // Usage counts for user/account pairs across the whole population
+    let mut pair_counts: HashMap<(String, String), usize> = HashMap::new();
+    let mut user_line_counts: HashMap<String, usize> = HashMap::new();
+    for entry in &entries {
+        if entry.posted_by.is_empty() {
+            continue;
+        }
+        for line in lines_by_entry.get(&entry.id).into_iter().flatten() {
+            *pair_counts
+                .entry((entry.posted_by.clone(), line.account_number.clone()))
+                .or_insert(0) += 1;
+            *user_line_counts.entry(entry.posted_by.clone()).or_insert(0) += 1;
+        }
+    }
+
+    // Entries with the same date, amount and account lines
+    let mut fingerprints: HashMap<String, Vec<String>> = HashMap::new();
+    let mut entry_fingerprints: HashMap<u64, String> = HashMap::new();
+    for entry in &entries {
+        let mut parts: Vec<String> = lines_by_entry
+            .get(&entry.id)
+            .into_iter()
+            .flatten()
+            .map(|l| format!("{}:{}:{}", l.account_number, l.debit, l.credit))
+            .collect();
+        parts.sort();
+        let fingerprint = format!("{}|{}|{}", entry.posting_date, entry.total_debit, parts.join(","));
+        fingerprints
+            .entry(fingerprint.clone())
+            .or_default()
+            .push(entry.entry_number.clone());
+        entry_fingerprints.insert(entry.id, fingerprint);
+    }
+
+    let mut findings = Vec::new();
+
+    for entry in &entries {
+        let mut flags = Vec::new();
+        let mut details = Vec::new();
+        let posting_day = dates::parse_date(&entry.posting_date).unwrap_or(period_end);
+        let amount = entry.total_debit.max(entry.total_credit);
+
+        if weekend_days.contains(&dates::weekday(posting_day)) {
+            flags.push(JetFlag::WeekendPosting);
+            details.push(format!("Posted on a weekend ({})", entry.posting_date));
+        }
+
+        if holidays.contains(&posting_day) {
+            flags.push(JetFlag::HolidayPosting);
+            details.push(format!("Posted on a holiday ({})", entry.posting_date));
+        }
+
+        if amount >= round_unit && amount % round_unit == 0 {
+            flags.push(JetFlag::RoundAmount);
+            details.push(format!("Round amount {:.2}", amount as f64 / 100.0));
+        }
+
+        if let Some(threshold) = config.approval_thresholds.iter().find(|t| {
+            let floor = **t as f64 * (1.0 - below_percent / 100.0);
+            (amount as f64) >= floor && amount < **t
+        }) {
+            flags.push(JetFlag::JustBelowThreshold);
+            details.push(format!(
+                "{:.2} is just below the {:.2} approval threshold",
+                amount as f64 / 100.0,
+                *threshold as f64 / 100.0
+            ));
+        }
+
+        if !entry.posted_by.is_empty()
+            && user_line_counts.get(&entry.posted_by).copied().unwrap_or(0) >= MIN_USER_LINES_FOR_PAIRS
+        {
+            let rare_accounts: Vec<&str> = lines_by_entry
+                .get(&entry.id)
+                .into_iter()
+                .flatten()
+                .filter(|l| {
+                    pair_counts
+                        .get(&(entry.posted_by.clone(), l.account_number.clone()))
+                        .copied()
+                        .unwrap_or(0)
+                        <= rare_pair_max
+                })
+                .map(|l| l.account_number.as_str())
+                .collect();
+            if !rare_accounts.is_empty() {
+                flags.push(JetFlag::UnusualUserAccount);
+                details.push(format!(
+                    "{} rarely posts to account(s) {}",
+                    entry.posted_by,
+                    rare_accounts.join(", ")
+                ));
+            }
+        }
+
+        let entered_day = entry.entry_date.as_deref().and_then(dates::parse_date);
+        if posting_day > period_end {
+            flags.push(JetFlag::PostClose);
+            details.push(format!("Posted after period end ({})", entry.posting_date));
+        } else if let Some(day) = entered_day.filter(|day| *day > close_day) {
+            flags.push(JetFlag::PostClose);
+            details.push(format!(
+                "Entered {} after close, dated into the period",
+                dates::format_date(day)
+            ));
+        }
+
+        if let Some(duplicates) = entry_fingerprints
+            .get(&entry.id)
+            .and_then(|fp| fingerprints.get(fp))
+            .filter(|numbers| numbers.len() > 1)
+        {
+            flags.push(JetFlag::Duplicate);
+            let others: Vec<&str> = duplicates
+                .iter()
+                .filter(|n| **n != entry.entry_number)
+                .map(|n| n.as_str())
+                .collect();
+            details.push(format!("Same date, amount and accounts as {}", others.join(", ")));
+        }
+
+        if entry.total_debit != entry.total_credit {
+            flags.push(JetFlag::Unbalanced);
+            details.push(format!(
+                "Debits {:.2} and credits {:.2} do not balance",
+                entry.total_debit as f64 / 100.0,
+                entry.total_credit as f64 / 100.0
+            ));
+        }
+
+        if !flags.is_empty() {
+            findings.push(JetFinding {
+                journal_entry_id: entry.id,
+                entry_number: entry.entry_number.clone(),
+                posting_date: entry.posting_date.clone(),
+                posted_by: entry.posted_by.clone(),
+                amount,
+                risk_score: flags.iter().map(flag_weight).sum(),
+                flags,
+                details,
+            });
+        }
+    }
+
+    findings.sort_by(|a, b| b.risk_score.cmp(&a.risk_score).then(b.amount.cmp(&a.amount)));
+
+    let all_flags = [
+        JetFlag::WeekendPosting,
+        JetFlag::HolidayPosting,
+        JetFlag::RoundAmount,
+        JetFlag::JustBelowThreshold,
+        JetFlag::UnusualUserAccount,
+        JetFlag::PostClose,
+        JetFlag::Duplicate,
+        JetFlag::Unbalanced,
+    ];
+    let flag_counts = all_flags
+        .into_iter()
+        .map(|flag| JetFlagCount {
+            count: findings.iter().filter(|f| f.flags.contains(&flag)).count() as u64,
+            flag,
+        })
+        .collect();
+
+    let run = JetRun {
+        id: next_jet_run_id(),
+        engagement_id: gl_import.engagement_id,
+        gl_import_id,
+        config,
+        entries_tested: entries.len() as u64,
+        flagged_entries: findings.len() as u64,
+        flag_counts,
+        findings,
+        run_at: time(),
+        run_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().jet_runs.insert(run.id, run.clone());
+    });
+
+    log_activity(
+        caller,
+        "run_journal_entry_tests".to_string(),
+        "jet_run".to_string(),
+        run.id.to_string(),
+        format!(
+            "Journal entry tests on GL import {}: {} of {} entries flagged",
+            gl_import_id, run.flagged_entries, run.entries_tested
+        ),
+        encode_args((run.clone(),)).ok(),
+    );
+
+    Ok(run)
+}
+
+// Get JET run by ID
+pub fn get_jet_run(caller: Principal, id: u64) -> Result<JetRun> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().jet_runs.get(&id))
+        .ok_or_else(|| "JET run not found".to_string())
+}
+
+// List JET runs by engagement
+pub fn list_jet_runs_by_engagement(caller: Principal, engagement_id: u64) -> Result<Vec<JetRun>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let runs = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .jet_runs
+            .iter()
+            .filter(|(_, run)| run.engagement_id == engagement_id)
+            .map(|(_, run)| run)
+            .collect()
+    });
+
+    Ok(runs)
+}
+
+// Management override indicators weigh more than calendar effects
+fn flag_weight(flag: &JetFlag) -> u32 {
+    match flag {
+        JetFlag::PostClose | JetFlag::Unbalanced => 3,
+        JetFlag::JustBelowThreshold | JetFlag::UnusualUserAccount | JetFlag::Duplicate => 2,
+        JetFlag::WeekendPosting | JetFlag::HolidayPosting | JetFlag::RoundAmount => 1,
+    }
+}
@@ -2,21 +2,40 @@ use candid::Principal;
 use ic_cdk_macros::{init, query, update};
 
 mod activity_log;
+mod analytical_procedures;
 mod adjustments;
 mod auth;
 mod blockchain_proof;
+mod chart_of_accounts;
 mod client_portal;
 mod clients;
+mod consolidation;
+mod cross_references;
 mod data_import;
+mod data_quality;
+mod dates;
+mod digit_analysis;
 mod documents;
 mod engagement_planning;
 mod engagements;
 mod entities;
 mod financial_statements;
+mod formulas;
+mod fx;
+mod general_ledger;
+mod journal_testing;
+mod mapping_profiles;
+mod misstatements;
 mod organizations;
+mod pii_detection;
+mod pii_masking;
 mod pre_engagement;
+mod reconciliations;
+mod sampling;
 mod storage;
+mod tb_comparison;
 mod templates;
+mod trend_analysis;
 mod trial_balance;
 mod types;
 mod working_papers;
@@ -244,6 +263,12 @@ fn update_engagement(req: UpdateEngagementRequest) -> Result<Engagement> {
     engagements::update_engagement(caller, req)
 }
 
+#[update]
+fn set_engagement_materiality(req: SetMaterialityRequest) -> Result<Engagement> {
+    let caller = ic_cdk::caller();
+    engagements::set_engagement_materiality(caller, req)
+}
+
 #[update]
 fn delete_engagement(id: u64) -> Result<()> {
     let caller = ic_cdk::caller();
@@ -278,6 +303,203 @@ fn list_datasets_by_engagement(engagement_id: u64) -> Result<Vec<ImportedDataset
     data_import::list_datasets_by_engagement(caller, engagement_id)
 }
 
+#[update]
+fn reimport_dataset(req: ReimportDatasetRequest) -> Result<ReimportResult> {
+    let caller = ic_cdk::caller();
+    data_import::reimport_dataset(caller, req)
+}
+
+#[query]
+fn list_dataset_versions(dataset_id: u64) -> Result<Vec<DatasetVersion>> {
+    let caller = ic_cdk::caller();
+    data_import::list_dataset_versions(caller, dataset_id)
+}
+
+#[query]
+fn get_dataset_diff(
+    dataset_id: u64,
+    from_version: u32,
+    to_version: u32,
+    key_column: String,
+    sheet_name: Option<String>,
+) -> Result<DatasetDiff> {
+    let caller = ic_cdk::caller();
+    data_import::get_dataset_diff(caller, dataset_id, from_version, to_version, key_column, sheet_name)
+}
+
+#[update]
+fn run_data_quality_checks(req: RunDataQualityRequest) -> Result<DataQualityReport> {
+    let caller = ic_cdk::caller();
+    data_quality::run_data_quality_checks(caller, req)
+}
+
+#[query]
+fn get_data_quality_report(dataset_id: u64) -> Result<DataQualityReport> {
+    let caller = ic_cdk::caller();
+    data_quality::get_data_quality_report(caller, dataset_id)
+}
+
+#[update]
+fn mask_dataset_columns(req: MaskDatasetColumnsRequest) -> Result<ImportedDataset> {
+    let caller = ic_cdk::caller();
+    pii_masking::mask_dataset_columns(caller, req)
+}
+
+#[update]
+fn unmask_dataset_column(
+    dataset_id: u64,
+    sheet_name: String,
+    column_name: String,
+) -> Result<PIIVaultEntry> {
+    let caller = ic_cdk::caller();
+    pii_masking::unmask_dataset_column(caller, dataset_id, sheet_name, column_name)
+}
+
+// ============================================================================
+// General Ledger & Journal Entry Testing
+// ============================================================================
+
+#[update]
+fn import_gl_journal(req: ImportGlJournalRequest) -> Result<GlImport> {
+    let caller = ic_cdk::caller();
+    general_ledger::import_gl_journal(caller, req)
+}
+
+#[query]
+fn get_gl_import(id: u64) -> Result<GlImport> {
+    let caller = ic_cdk::caller();
+    general_ledger::get_gl_import(caller, id)
+}
+
+#[query]
+fn list_gl_imports_by_engagement(engagement_id: u64) -> Result<Vec<GlImport>> {
+    let caller = ic_cdk::caller();
+    general_ledger::list_gl_imports_by_engagement(caller, engagement_id)
+}
+
+#[query]
+fn get_journal_entries(gl_import_id: u64) -> Result<Vec<JournalEntry>> {
+    let caller = ic_cdk::caller();
+    general_ledger::get_journal_entries(caller, gl_import_id)
+}
+
+#[query]
+fn get_journal_lines(journal_entry_id: u64) -> Result<Vec<JournalLine>> {
+    let caller = ic_cdk::caller();
+    general_ledger::get_journal_lines(caller, journal_entry_id)
+}
+
+#[update]
+fn run_journal_entry_tests(gl_import_id: u64, config: JetConfig) -> Result<JetRun> {
+    let caller = ic_cdk::caller();
+    journal_testing::run_journal_entry_tests(caller, gl_import_id, config)
+}
+
+#[query]
+fn get_jet_run(id: u64) -> Result<JetRun> {
+    let caller = ic_cdk::caller();
+    journal_testing::get_jet_run(caller, id)
+}
+
+#[query]
+fn list_jet_runs_by_engagement(engagement_id: u64) -> Result<Vec<JetRun>> {
+    let caller = ic_cdk::caller();
+    journal_testing::list_jet_runs_by_engagement(caller, engagement_id)
+}
+
+// ============================================================================
+// Digit Analysis
+// ============================================================================
+
+#[update]
+fn run_digit_analysis(req: RunDigitAnalysisRequest) -> Result<DigitAnalysis> {
+    let caller = ic_cdk::caller();
+    digit_analysis::run_digit_analysis(caller, req)
+}
+
+#[query]
+fn get_digit_analysis(id: u64) -> Result<DigitAnalysis> {
+    let caller = ic_cdk::caller();
+    digit_analysis::get_digit_analysis(caller, id)
+}
+
+#[query]
+fn list_digit_analyses_by_working_paper(working_paper_id: u64) -> Result<Vec<DigitAnalysis>> {
+    let caller = ic_cdk::caller();
+    digit_analysis::list_digit_analyses_by_working_paper(caller, working_paper_id)
+}
+
+// ============================================================================
+// Audit Sampling
+// ============================================================================
+
+#[update]
+fn create_audit_sample(req: CreateAuditSampleRequest) -> Result<AuditSample> {
+    let caller = ic_cdk::caller();
+    sampling::create_audit_sample(caller, req)
+}
+
+#[update]
+fn evaluate_audit_sample(req: EvaluateAuditSampleRequest) -> Result<AuditSample> {
+    let caller = ic_cdk::caller();
+    sampling::evaluate_audit_sample(caller, req)
+}
+
+#[query]
+fn get_audit_sample(id: u64) -> Result<AuditSample> {
+    let caller = ic_cdk::caller();
+    sampling::get_audit_sample(caller, id)
+}
+
+#[query]
+fn list_audit_samples_by_engagement(engagement_id: u64) -> Result<Vec<AuditSample>> {
+    let caller = ic_cdk::caller();
+    sampling::list_audit_samples_by_engagement(caller, engagement_id)
+}
+
+// ============================================================================
+// Import Mapping Profiles
+// ============================================================================
+
+#[update]
+fn create_mapping_profile(req: CreateMappingProfileRequest) -> Result<ImportMappingProfile> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::create_mapping_profile(caller, req)
+}
+
+#[query]
+fn get_mapping_profile(id: u64) -> Result<ImportMappingProfile> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::get_mapping_profile(caller, id)
+}
+
+#[query]
+fn list_mapping_profiles_by_client(client_id: u64) -> Result<Vec<ImportMappingProfile>> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::list_mapping_profiles_by_client(caller, client_id)
+}
+
+#[update]
+fn update_mapping_profile(req: UpdateMappingProfileRequest) -> Result<ImportMappingProfile> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::update_mapping_profile(caller, req)
+}
+
+#[update]
+fn delete_mapping_profile(id: u64) -> Result<()> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::delete_mapping_profile(caller, id)
+}
+
+#[query]
+fn suggest_mapping_profiles(
+    dataset_id: u64,
+    client_id: Option<u64>,
+) -> Result<Vec<MappingProfileMatch>> {
+    let caller = ic_cdk::caller();
+    mapping_profiles::suggest_mapping_profiles(caller, dataset_id, client_id)
+}
+
 // ============================================================================
 // Working Papers
 // ============================================================================
@@ -306,6 +528,94 @@ fn link_document_to_working_paper(working_paper_id: u64, document_id: u64) -> Re
     working_papers::link_document_to_working_paper(caller, working_paper_id, document_id)
 }
 
+#[update]
+fn update_working_paper(req: UpdateWorkingPaperRequest) -> Result<WorkingPaper> {
+    let caller = ic_cdk::caller();
+    working_papers::update_working_paper(caller, req)
+}
+
+#[update]
+fn refresh_working_paper(working_paper_id: u64) -> Result<WorkingPaper> {
+    let caller = ic_cdk::caller();
+    working_papers::refresh_working_paper(caller, working_paper_id)
+}
+
+#[update]
+fn set_working_paper_cells(req: SetCellsRequest) -> Result<WorkingPaper> {
+    let caller = ic_cdk::caller();
+    working_papers::set_working_paper_cells(caller, req)
+}
+
+#[update]
+fn sign_off_working_paper(working_paper_id: u64, role: SignOffRole) -> Result<WorkingPaper> {
+    let caller = ic_cdk::caller();
+    working_papers::sign_off_working_paper(caller, working_paper_id, role)
+}
+
+#[update]
+fn add_review_note(req: AddReviewNoteRequest) -> Result<ReviewNote> {
+    let caller = ic_cdk::caller();
+    working_papers::add_review_note(caller, req)
+}
+
+#[update]
+fn resolve_review_note(req: ResolveReviewNoteRequest) -> Result<ReviewNote> {
+    let caller = ic_cdk::caller();
+    working_papers::resolve_review_note(caller, req)
+}
+
+#[query]
+fn list_review_notes(working_paper_id: u64) -> Result<Vec<ReviewNote>> {
+    let caller = ic_cdk::caller();
+    working_papers::list_review_notes(caller, working_paper_id)
+}
+
+// ============================================================================
+// Cross-References & Tick Marks
+// ============================================================================
+
+#[update]
+fn add_cross_reference(req: AddCrossReferenceRequest) -> Result<CrossReference> {
+    let caller = ic_cdk::caller();
+    cross_references::add_cross_reference(caller, req)
+}
+
+#[query]
+fn list_cross_references(working_paper_id: u64) -> Result<Vec<CrossReference>> {
+    let caller = ic_cdk::caller();
+    cross_references::list_cross_references(caller, working_paper_id)
+}
+
+#[update]
+fn define_tick_mark(req: DefineTickMarkRequest) -> Result<TickMark> {
+    let caller = ic_cdk::caller();
+    cross_references::define_tick_mark(caller, req)
+}
+
+#[query]
+fn list_tick_marks(engagement_id: u64) -> Result<Vec<TickMark>> {
+    let caller = ic_cdk::caller();
+    cross_references::list_tick_marks(caller, engagement_id)
+}
+
+#[update]
+fn add_annotation(req: AddAnnotationRequest) -> Result<Annotation> {
+    let caller = ic_cdk::caller();
+    cross_references::add_annotation(caller, req)
+}
+
+#[query]
+fn list_annotations(working_paper_id: u64) -> Result<Vec<Annotation>> {
+    let caller = ic_cdk::caller();
+    cross_references::list_annotations(caller, working_paper_id)
+}
+
+#[query]
+fn check_reference_integrity(engagement_id: u64) -> Result<ReferenceIntegrityReport> {
+    let caller = ic_cdk::caller();
+    cross_references::check_reference_integrity(caller, engagement_id)
+}
+
 // ============================================================================
 // Document Management
 // ============================================================================
@@ -577,6 +887,18 @@ fn create_trial_balance(req: CreateTrialBalanceRequest) -> Result<TrialBalance>
     trial_balance::create_trial_balance(caller, req)
 }
 
+#[update]
+fn rollforward_trial_balance(req: RollforwardRequest) -> Result<TrialBalance> {
+    let caller = ic_cdk::caller();
+    trial_balance::rollforward_trial_balance(caller, req)
+}
+
+#[query]
+fn get_trial_balance_period_chain(trial_balance_id: u64) -> Result<Vec<TrialBalance>> {
+    let caller = ic_cdk::caller();
+    trial_balance::get_period_chain(caller, trial_balance_id)
+}
+
 #[update]
 fn add_trial_balance_account(
     trial_balance_id: u64,
@@ -610,20 +932,200 @@ fn validate_trial_balance(trial_balance_id: u64) -> Result<trial_balance::TrialB
     trial_balance::validate_trial_balance(caller, trial_balance_id)
 }
 
+#[query]
+fn get_adjusted_trial_balance(trial_balance_id: u64, include_proposed: bool) -> Result<AdjustedTrialBalance> {
+    let caller = ic_cdk::caller();
+    trial_balance::get_adjusted_trial_balance(caller, trial_balance_id, include_proposed)
+}
+
 #[update]
 fn map_account_to_fs_line(account_id: u64, fs_line_item: String) -> Result<TrialBalanceAccount> {
     let caller = ic_cdk::caller();
     trial_balance::map_to_fs_line(caller, account_id, fs_line_item)
 }
 
+#[update]
+fn import_trial_balance_from_dataset(
+    engagement_id: u64,
+    dataset_id: u64,
+    period_end_date: String,
+    column_mapping: Option<ColumnMapping>,
+    currency: Option<String>,
+    file_data: Option<Vec<u8>>,
+) -> Result<TrialBalance> {
+    let caller = ic_cdk::caller();
+    trial_balance::import_trial_balance_from_dataset(
+        caller,
+        engagement_id,
+        dataset_id,
+        period_end_date,
+        column_mapping,
+        currency,
+        file_data,
+    )
+}
+
 #[update]
 fn import_trial_balance_csv(
     engagement_id: u64,
     period_end_date: String,
     csv_data: Vec<trial_balance::CsvAccountRow>,
+    currency: Option<String>,
 ) -> Result<TrialBalance> {
     let caller = ic_cdk::caller();
-    trial_balance::import_trial_balance_csv(caller, engagement_id, period_end_date, csv_data)
+    trial_balance::import_trial_balance_csv(caller, engagement_id, period_end_date, csv_data, currency)
+}
+
+// ============================================================================
+// Chart of Accounts
+// ============================================================================
+
+#[update]
+fn set_coa_accounts(owner: CoaOwner, accounts: Vec<CoaAccountInput>) -> Result<Vec<CoaAccount>> {
+    let caller = ic_cdk::caller();
+    chart_of_accounts::set_coa_accounts(caller, owner, accounts)
+}
+
+#[query]
+fn list_coa_accounts(owner: CoaOwner) -> Result<Vec<CoaAccount>> {
+    let caller = ic_cdk::caller();
+    chart_of_accounts::list_coa_accounts(caller, owner)
+}
+
+#[update]
+fn deactivate_coa_account(coa_account_id: u64) -> Result<CoaAccount> {
+    let caller = ic_cdk::caller();
+    chart_of_accounts::deactivate_coa_account(caller, coa_account_id)
+}
+
+#[update]
+fn adopt_trial_balance_accounts(trial_balance_id: u64) -> Result<Vec<CoaAccount>> {
+    let caller = ic_cdk::caller();
+    chart_of_accounts::adopt_trial_balance_accounts(caller, trial_balance_id)
+}
+
+#[query]
+fn get_coa_mapping_report(trial_balance_id: u64) -> Result<CoaMappingReport> {
+    let caller = ic_cdk::caller();
+    chart_of_accounts::get_coa_mapping_report(caller, trial_balance_id)
+}
+
+// ============================================================================
+// Account Reconciliations
+// ============================================================================
+
+#[update]
+fn create_reconciliation(req: CreateReconciliationRequest) -> Result<AccountReconciliation> {
+    let caller = ic_cdk::caller();
+    reconciliations::create_reconciliation(caller, req)
+}
+
+#[update]
+fn update_reconciliation(req: UpdateReconciliationRequest) -> Result<AccountReconciliation> {
+    let caller = ic_cdk::caller();
+    reconciliations::update_reconciliation(caller, req)
+}
+
+#[update]
+fn sign_off_reconciliation(reconciliation_id: u64, role: SignOffRole) -> Result<AccountReconciliation> {
+    let caller = ic_cdk::caller();
+    reconciliations::sign_off_reconciliation(caller, reconciliation_id, role)
+}
+
+#[query]
+fn get_reconciliation(reconciliation_id: u64) -> Result<AccountReconciliation> {
+    let caller = ic_cdk::caller();
+    reconciliations::get_reconciliation(caller, reconciliation_id)
+}
+
+#[query]
+fn list_reconciliations(trial_balance_id: u64) -> Result<Vec<AccountReconciliation>> {
+    let caller = ic_cdk::caller();
+    reconciliations::list_reconciliations(caller, trial_balance_id)
+}
+
+#[query]
+fn get_unreconciled_accounts(trial_balance_id: u64, threshold: i64) -> Result<Vec<UnreconciledAccount>> {
+    let caller = ic_cdk::caller();
+    reconciliations::get_unreconciled_accounts(caller, trial_balance_id, threshold)
+}
+
+// ============================================================================
+// Trial Balance Comparison
+// ============================================================================
+
+#[query]
+fn compare_trial_balances(base_trial_balance_id: u64, compare_trial_balance_id: u64) -> Result<TrialBalanceComparison> {
+    let caller = ic_cdk::caller();
+    tb_comparison::compare_trial_balances(caller, base_trial_balance_id, compare_trial_balance_id)
+}
+
+#[update]
+fn confirm_account_mappings(trial_balance_id: u64, mappings: Vec<AccountMappingInput>) -> Result<Vec<AccountMapping>> {
+    let caller = ic_cdk::caller();
+    tb_comparison::confirm_account_mappings(caller, trial_balance_id, mappings)
+}
+
+#[query]
+fn list_account_mappings(owner: CoaOwner) -> Result<Vec<AccountMapping>> {
+    let caller = ic_cdk::caller();
+    tb_comparison::list_account_mappings(caller, owner)
+}
+
+#[update]
+fn delete_account_mapping(mapping_id: u64) -> Result<()> {
+    let caller = ic_cdk::caller();
+    tb_comparison::delete_account_mapping(caller, mapping_id)
+}
+
+// ============================================================================
+// Foreign Exchange
+// ============================================================================
+
+#[update]
+fn set_fx_rate(req: SetFxRateRequest) -> Result<FxRate> {
+    let caller = ic_cdk::caller();
+    fx::set_fx_rate(caller, req)
+}
+
+#[update]
+fn import_fx_rates(rows: Vec<SetFxRateRequest>) -> Result<Vec<FxRate>> {
+    let caller = ic_cdk::caller();
+    fx::import_fx_rates(caller, rows)
+}
+
+#[query]
+fn list_fx_rates(currency: Option<String>) -> Result<Vec<FxRate>> {
+    let caller = ic_cdk::caller();
+    fx::list_fx_rates(caller, currency)
+}
+
+#[update]
+fn translate_trial_balance(req: TranslateTrialBalanceRequest) -> Result<TranslationResult> {
+    let caller = ic_cdk::caller();
+    fx::translate_trial_balance(caller, req)
+}
+
+// ============================================================================
+// Group Consolidation
+// ============================================================================
+
+#[update]
+fn consolidate_trial_balances(req: ConsolidateRequest) -> Result<Consolidation> {
+    let caller = ic_cdk::caller();
+    consolidation::consolidate_trial_balances(caller, req)
+}
+
+#[query]
+fn get_consolidation(id: u64) -> Result<Consolidation> {
+    let caller = ic_cdk::caller();
+    consolidation::get_consolidation(caller, id)
+}
+
+#[query]
+fn list_consolidations(organization_id: u64) -> Result<Vec<Consolidation>> {
+    let caller = ic_cdk::caller();
+    consolidation::list_consolidations(caller, organization_id)
 }
 
 // ============================================================================
@@ -636,6 +1138,12 @@ fn create_aje(req: CreateAjeRequest) -> Result<AdjustingJournalEntry> {
     adjustments::create_aje(caller, req)
 }
 
+#[update]
+fn update_aje(req: UpdateAjeRequest) -> Result<AdjustingJournalEntry> {
+    let caller = ic_cdk::caller();
+    adjustments::update_aje(caller, req)
+}
+
 #[update]
 fn submit_aje(aje_id: u64) -> Result<AdjustingJournalEntry> {
     let caller = ic_cdk::caller();
@@ -660,6 +1168,12 @@ fn post_aje(aje_id: u64) -> Result<AdjustingJournalEntry> {
     adjustments::post_aje(caller, aje_id)
 }
 
+#[update]
+fn pass_aje(req: PassAjeRequest) -> Result<AdjustingJournalEntry> {
+    let caller = ic_cdk::caller();
+    adjustments::pass_aje(caller, req)
+}
+
 #[query]
 fn get_aje(aje_id: u64) -> Result<AdjustingJournalEntry> {
     let caller = ic_cdk::caller();
@@ -678,6 +1192,18 @@ fn list_ajes_by_engagement(engagement_id: u64) -> Result<Vec<AdjustingJournalEnt
     adjustments::list_ajes_by_engagement(caller, engagement_id)
 }
 
+#[query]
+fn list_book_adjustments(trial_balance_id: u64) -> Result<Vec<BookAdjustment>> {
+    let caller = ic_cdk::caller();
+    adjustments::list_book_adjustments(caller, trial_balance_id)
+}
+
+#[query]
+fn get_uncorrected_misstatements(engagement_id: u64) -> Result<MisstatementSummary> {
+    let caller = ic_cdk::caller();
+    misstatements::get_uncorrected_misstatements(caller, engagement_id)
+}
+
 #[query]
 fn verify_aje_blockchain(aje_id: u64) -> Result<adjustments::AjeBlockchainVerification> {
     let caller = ic_cdk::caller();
@@ -723,6 +1249,44 @@ fn get_line_items_for_taxonomy(taxonomy: XBRLTaxonomy) -> Vec<FSLineItem> {
     financial_statements::get_line_items_for_taxonomy(&taxonomy)
 }
 
+// ============================================================================
+// Trend Analysis
+// ============================================================================
+
+#[query]
+fn get_multi_period_trend(request: MultiPeriodTrendRequest) -> Result<MultiPeriodTrend> {
+    let caller = ic_cdk::caller();
+    trend_analysis::get_multi_period_trend(caller, request)
+}
+
+// ============================================================================
+// Analytical Procedures
+// ============================================================================
+
+#[update]
+fn create_analytical_expectation(request: CreateExpectationRequest) -> Result<AnalyticalExpectation> {
+    let caller = ic_cdk::caller();
+    analytical_procedures::create_analytical_expectation(caller, request)
+}
+
+#[update]
+fn explain_variance(request: ExplainVarianceRequest) -> Result<AnalyticalExpectation> {
+    let caller = ic_cdk::caller();
+    analytical_procedures::explain_variance(caller, request)
+}
+
+#[query]
+fn get_analytical_expectation(id: u64) -> Result<AnalyticalExpectation> {
+    let caller = ic_cdk::caller();
+    analytical_procedures::get_analytical_expectation(caller, id)
+}
+
+#[query]
+fn list_expectations_by_engagement(engagement_id: u64) -> Result<Vec<AnalyticalExpectation>> {
+    let caller = ic_cdk::caller();
+    analytical_procedures::list_expectations_by_engagement(caller, engagement_id)
+}
+
 // ============================================================================
 // PHASE 1: PRE-ENGAGEMENT & SETUP
 // ============================================================================
@@ -0,0 +1,364 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::storage::{next_mapping_profile_id, STORAGE};
+use crate::types::{
+    ColumnMapping, CreateMappingProfileRequest, EngagementLink, HeaderAlias, ImportMappingProfile,
+    MappingField, MappingProfileMatch, Result, UpdateMappingProfileRequest,
+};
+
+// Minimum score for a profile to be applied automatically at import
+pub const MIN_MATCH_SCORE: f64 = 0.6;
+
+// Create mapping profile for a client
+pub fn create_mapping_profile(
+    caller: Principal,
+    req: CreateMappingProfileRequest,
+) -> Result<ImportMappingProfile> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to create mapping profile".to_string());
+    }
+
+    STORAGE
+        .with(|storage| storage.borrow().clients.get(&req.client_id))
+        .ok_or_else(|| "Client not found".to_string())?;
+
+    let header_row = req.header_row.unwrap_or(1);
+    validate_profile(&req.name, header_row, &req.header_aliases)?;
+
+    let now = time();
+    let profile = ImportMappingProfile {
+        id: next_mapping_profile_id(),
+        client_id: req.client_id,
+        name: req.name,
+        erp_system: req.erp_system,
+        sheet_name: req.sheet_name,
+        header_row,
+        sign_convention: req.sign_convention,
+        header_aliases: req.header_aliases,
+        created_at: now,
+        created_by: caller,
+        updated_at: now,
+    };
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .mapping_profiles
+            .insert(profile.id, profile.clone());
+    });
+
+    log_activity(
+        caller,
+        "create_mapping_profile".to_string(),
+        "mapping_profile".to_string(),
+        profile.id.to_string(),
+        format!(
+            "Mapping profile {} created for client {}",
+            profile.name, profile.client_id
+        ),
+        encode_args((profile.clone(),)).ok(),
+    );
+
+    Ok(profile)
+}
+
+// Get mapping profile by ID
+pub fn get_mapping_profile(caller: Principal, id: u64) -> Result<ImportMappingProfile> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().mapping_profiles.get(&id))
+        .ok_or_else(|| "Mapping profile not found".to_string())
+}
+
+// List mapping profiles by client
+pub fn list_mapping_profiles_by_client(
+    caller: Principal,
+    client_id: u64,
+) -> Result<Vec<ImportMappingProfile>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(profiles_for_client(client_id))
+}
+
+// Update mapping profile
+pub fn update_mapping_profile(
+    caller: Principal,
+    req: UpdateMappingProfileRequest,
+) -> Result<ImportMappingProfile> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to update mapping profile".to_string());
+    }
+
+    let mut profile = STORAGE
+        .with(|storage| storage.borrow().mapping_profiles.get(&req.id))
+        .ok_or_else(|| "Mapping profile not found".to_string())?;
+
+    let header_row = req.header_row.unwrap_or(profile.header_row);
+    validate_profile(&req.name, header_row, &req.header_aliases)?;
+
+    profile.name = req.name;
+    profile.erp_system = req.erp_system;
+    profile.sheet_name = req.sheet_name;
+    profile.header_row = header_row;
+    profile.sign_convention = req.sign_convention;
+    profile.header_aliases = req.header_aliases;
+    profile.updated_at = time();
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .mapping_profiles
+            .insert(profile.id, profile.clone());
+    });
+
+    log_activity(
+        caller,
+        "update_mapping_profile".to_string(),
+        "mapping_profile".to_string(),
+        profile.id.to_string(),
+        format!("Mapping profile {} updated", profile.name),
+        encode_args((profile.clone(),)).ok(),
+    );
+
+    Ok(profile)
+}
+
+// Delete mapping profile
+pub fn delete_mapping_profile(caller: Principal, id: u64) -> Result<()> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to delete mapping profile".to_string());
+    }
+
+    let profile = STORAGE
+        .with(|storage| storage.borrow().mapping_profiles.get(&id))
+        .ok_or_else(|| "Mapping profile not found".to_string())?;
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().mapping_profiles.remove(&id);
+    });
+
+    log_activity(
+        caller,
+        "delete_mapping_profile".to_string(),
+        "mapping_profile".to_string(),
+        id.to_string(),
+        format!("Mapping profile {} deleted", profile.name),
+        encode_args((profile,)).ok(),
+    );
+
+    Ok(())
+}
+
+// Rank a client's profiles against the headers of a stored dataset
+pub fn suggest_mapping_profiles(
+    caller: Principal,
+    dataset_id: u64,
+    client_id: Option<u64>,
+) -> Result<Vec<MappingProfileMatch>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let dataset = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    let client_id = client_id
+        .or_else(|| dataset.engagement_id.and_then(client_for_engagement))
+        .ok_or_else(|| "Dataset is not linked to a client engagement".to_string())?;
+
+    let mut matches: Vec<MappingProfileMatch> = profiles_for_client(client_id)
+        .iter()
+        .flat_map(|profile| {
+            dataset
+                .sheets
+                .iter()
+                .filter(|sheet| profile.sheet_name.as_ref().is_none_or(|name| *name == sheet.name))
+                .map(|sheet| {
+                    let headers: Vec<String> = sheet.columns.iter().map(|c| c.name.clone()).collect();
+                    score_headers(profile, &sheet.name, &headers)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(matches)
+}
+
+pub fn profiles_for_client(client_id: u64) -> Vec<ImportMappingProfile> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .mapping_profiles
+            .iter()
+            .filter(|(_, profile)| profile.client_id == client_id)
+            .map(|(_, profile)| profile)
+            .collect()
+    })
+}
+
+// Client behind an engagement, if it is linked to one
+pub fn client_for_engagement(engagement_id: u64) -> Option<u64> {
+    let engagement = STORAGE.with(|storage| storage.borrow().engagements.get(&engagement_id))?;
+    match engagement.link {
+        EngagementLink::Client(client_id) => Some(client_id),
+        _ => None,
+    }
+}
+
+// Zero-based index of the profile's header row
+pub fn header_row_index(profile: &ImportMappingProfile) -> usize {
+    profile.header_row.saturating_sub(1) as usize
+}
+
+// Match each profile field to one header. Exact alias matches count fully,
+// partial matches (alias contained in the header or vice versa) count half.
+pub fn score_headers(
+    profile: &ImportMappingProfile,
+    sheet_name: &str,
+    headers: &[String],
+) -> MappingProfileMatch {
+    let normalized: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+    let mut used = vec![false; headers.len()];
+    let mut column_mapping = empty_mapping(profile);
+    let mut total = 0.0;
+    let mut matched_fields = 0;
+    let mut unmatched_fields = Vec::new();
+
+    for alias in &profile.header_aliases {
+        let mut best: Option<(usize, f64)> = None;
+
+        for (idx, header) in normalized.iter().enumerate() {
+            if used[idx] || header.is_empty() {
+                continue;
+            }
+            let weight = alias
+                .aliases
+                .iter()
+                .map(|a| alias_weight(&normalize_header(a), header))
+                .fold(0.0, f64::max);
+            if weight > best.map(|(_, w)| w).unwrap_or(0.0) {
+                best = Some((idx, weight));
+            }
+        }
+
+        match best {
+            Some((idx, weight)) => {
+                used[idx] = true;
+                total += weight;
+                matched_fields += 1;
+                set_mapping_field(&mut column_mapping, &alias.field, headers[idx].clone());
+            }
+            None => unmatched_fields.push(alias.field.clone()),
+        }
+    }
+
+    let score = if profile.header_aliases.is_empty() {
+        0.0
+    } else {
+        total / profile.header_aliases.len() as f64
+    };
+
+    MappingProfileMatch {
+        profile_id: profile.id,
+        profile_name: profile.name.clone(),
+        sheet_name: sheet_name.to_string(),
+        score,
+        matched_fields,
+        unmatched_fields,
+        column_mapping,
+    }
+}
+
+fn alias_weight(alias: &str, header: &str) -> f64 {
+    if alias.is_empty() {
+        0.0
+    } else if alias == header {
+        1.0
+    } else if alias.chars().count() >= 3 && (header.contains(alias) || alias.contains(header)) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+// Lowercase and drop punctuation and spacing, so "Acct. No" matches "acct_no"
+fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn empty_mapping(profile: &ImportMappingProfile) -> ColumnMapping {
+    ColumnMapping {
+        account_number: None,
+        account_name: None,
+        currency: None,
+        opening_debit: None,
+        opening_credit: None,
+        period_debit: None,
+        period_credit: None,
+        ytd_debit: None,
+        ytd_credit: None,
+        entity: None,
+        department: None,
+        project: None,
+        notes: None,
+        sign_convention: Some(profile.sign_convention.clone()),
+    }
+}
+
+fn set_mapping_field(mapping: &mut ColumnMapping, field: &MappingField, header: String) {
+    let slot = match field {
+        MappingField::AccountNumber => &mut mapping.account_number,
+        MappingField::AccountName => &mut mapping.account_name,
+        MappingField::Currency => &mut mapping.currency,
+        MappingField::OpeningDebit => &mut mapping.opening_debit,
+        MappingField::OpeningCredit => &mut mapping.opening_credit,
+        MappingField::PeriodDebit => &mut mapping.period_debit,
+        MappingField::PeriodCredit => &mut mapping.period_credit,
+        MappingField::YtdDebit => &mut mapping.ytd_debit,
+        MappingField::YtdCredit => &mut mapping.ytd_credit,
+        MappingField::Entity => &mut mapping.entity,
+        MappingField::Department => &mut mapping.department,
+        MappingField::Project => &mut mapping.project,
+        MappingField::Notes => &mut mapping.notes,
+    };
+    *slot = Some(header);
+}
+
+fn validate_profile(name: &str, header_row: u32, header_aliases: &[HeaderAlias]) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if header_row == 0 {
+        return Err("Header row is 1-based and must be at least 1".to_string());
+    }
+    if !header_aliases
+        .iter()
+        .any(|a| a.field == MappingField::AccountNumber)
+    {
+        return Err("Profile must map the account number column".to_string());
+    }
+    for (idx, alias) in header_aliases.iter().enumerate() {
+        if alias.aliases.iter().all(|a| normalize_header(a).is_empty()) {
+            return Err(format!("No usable aliases for {:?}", alias.field));
+        }
+        if header_aliases[..idx].iter().any(|a| a.field == alias.field) {
+            return Err(format!("{:?} is mapped more than once", alias.field));
+        }
+    }
+    Ok(())
+}
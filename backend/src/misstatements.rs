@@ -0,0 +1,213 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeMap;
+
+use crate::auth;
+use crate::engagements;
+use crate::storage::STORAGE;
+use crate::types::{
+    AccountType, AdjustingJournalEntry, AjeStatus, FsLineEffect, Materiality,
+    MisstatementClassification, MisstatementSummary, Result, UncorrectedMisstatement,
+};
+
+const UNMAPPED: &str = "UNMAPPED";
+const RETAINED_EARNINGS: &str = "BS_RETAINED";
+
+// ISA 450 summary of uncorrected misstatements: passed AJEs of this engagement
+// plus the turnaround of the prior year's, evaluated against materiality
+pub fn get_uncorrected_misstatements(caller: Principal, engagement_id: u64) -> Result<MisstatementSummary> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let engagement = STORAGE
+        .with(|storage| storage.borrow().engagements.get(&engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let mut items: Vec<UncorrectedMisstatement> = passed_ajes(engagement_id)
+        .iter()
+        .map(|aje| misstatement(aje, false))
+        .collect();
+    if let Some(prior) = engagements::prior_year_chain(engagement_id, 1).first() {
+        items.extend(
+            passed_ajes(prior.id)
+                .iter()
+                .map(|aje| misstatement(aje, true))
+                .filter(|item| item.profit_before_tax_effect != 0),
+        );
+    }
+
+    let mut fs_lines: BTreeMap<String, i64> = BTreeMap::new();
+    for effect in items.iter().flat_map(|item| &item.fs_line_effects) {
+        *fs_lines.entry(effect.fs_line_item.clone()).or_insert(0) += effect.amount;
+    }
+    let fs_lines: Vec<FsLineEffect> = fs_lines
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0)
+        .map(|(fs_line_item, amount)| FsLineEffect { fs_line_item, amount })
+        .collect();
+
+    let profit_where = |turnaround: bool| -> i64 {
+        items
+            .iter()
+            .filter(|item| item.is_prior_year_turnaround == turnaround)
+            .map(|item| item.profit_before_tax_effect)
+            .sum()
+    };
+    let classified = |classification: MisstatementClassification| -> i64 {
+        items
+            .iter()
+            .filter(|item| item.classification == classification)
+            .map(|item| item.profit_before_tax_effect)
+            .sum()
+    };
+    let current_year_profit_effect = profit_where(false);
+    let turnaround_profit_effect = profit_where(true);
+    let profit_before_tax_effect = current_year_profit_effect + turnaround_profit_effect;
+    let equity_effect: i64 = items.iter().map(|item| item.equity_effect).sum();
+    let factual_total = classified(MisstatementClassification::Factual);
+    let judgmental_total = classified(MisstatementClassification::Judgmental);
+    let projected_total = classified(MisstatementClassification::Projected);
+
+    // A reclassification can be material to a line without touching profit,
+    // so the largest line misstatement counts as well
+    let aggregate = fs_lines
+        .iter()
+        .map(|line| line.amount.abs())
+        .chain([profit_before_tax_effect.abs(), equity_effect.abs()])
+        .max()
+        .unwrap_or(0);
+    let materiality = engagement.materiality.clone();
+
+    Ok(MisstatementSummary {
+        engagement_id,
+        exceeds_performance_materiality: materiality.as_ref().map(|m| aggregate > m.performance),
+        exceeds_overall_materiality: materiality.as_ref().map(|m| aggregate > m.overall),
+        conclusion: conclusion(aggregate, materiality.as_ref()),
+        materiality,
+        items,
+        fs_lines,
+        current_year_profit_effect,
+        turnaround_profit_effect,
+        profit_before_tax_effect,
+        equity_effect,
+        factual_total,
+        judgmental_total,
+        projected_total,
+        generated_at: time(),
+    })
+}
+
+fn passed_ajes(engagement_id: u64) -> Vec<AdjustingJournalEntry> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .adjusting_entries
+            .iter()
+            .filter(|(_, aje)| aje.engagement_id == engagement_id && matches!(aje.status, AjeStatus::Passed))
+            .map(|(_, aje)| aje)
+            .collect()
+    })
+}
+
+// The passed AJE is the correction that was not booked, so its debits to
+// income statement and equity accounts are the amounts by which profit and
+// equity are overstated. A prior-year misstatement turns around through this
+// year's profit: the income statement lines reverse against opening retained
+// earnings and closing equity is unaffected.
+fn misstatement(aje: &AdjustingJournalEntry, is_prior_year_turnaround: bool) -> UncorrectedMisstatement {
+    let lines: Vec<(String, AccountType, i64)> = STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        storage
+            .aje_line_items
+            .iter()
+            .filter(|(_, item)| item.aje_id == aje.id)
+            .map(|(_, item)| {
+                let account = storage.trial_balance_accounts.get(&item.account_id);
+                (
+                    account
+                        .as_ref()
+                        .and_then(|a| a.fs_line_item.clone())
+                        .unwrap_or_else(|| UNMAPPED.to_string()),
+                    account.map(|a| a.account_type).unwrap_or(AccountType::Asset),
+                    item.debit_amount - item.credit_amount,
+                )
+            })
+            .collect()
+    });
+
+    let is_income_statement =
+        |account_type: &AccountType| matches!(account_type, AccountType::Revenue | AccountType::Expense);
+    let profit_effect: i64 = lines
+        .iter()
+        .filter(|(_, account_type, _)| is_income_statement(account_type))
+        .map(|(_, _, amount)| amount)
+        .sum();
+    let equity_effect: i64 = lines
+        .iter()
+        .filter(|(_, account_type, _)| {
+            is_income_statement(account_type) || matches!(account_type, AccountType::Equity)
+        })
+        .map(|(_, _, amount)| amount)
+        .sum();
+
+    let mut effects: BTreeMap<String, i64> = BTreeMap::new();
+    let (profit_before_tax_effect, equity_effect) = if is_prior_year_turnaround {
+        for (fs_line, account_type, amount) in &lines {
+            if is_income_statement(account_type) {
+                *effects.entry(fs_line.clone()).or_insert(0) -= amount;
+            }
+        }
+        *effects.entry(RETAINED_EARNINGS.to_string()).or_insert(0) += profit_effect;
+        (-profit_effect, 0)
+    } else {
+        for (fs_line, _, amount) in &lines {
+            *effects.entry(fs_line.clone()).or_insert(0) += amount;
+        }
+        (profit_effect, equity_effect)
+    };
+
+    UncorrectedMisstatement {
+        aje_id: aje.id,
+        engagement_id: aje.engagement_id,
+        aje_number: aje.aje_number.clone(),
+        description: aje.description.clone(),
+        classification: aje
+            .classification
+            .clone()
+            .unwrap_or(MisstatementClassification::Factual),
+        is_prior_year_turnaround,
+        fs_line_effects: effects
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(|(fs_line_item, amount)| FsLineEffect { fs_line_item, amount })
+            .collect(),
+        profit_before_tax_effect,
+        equity_effect,
+    }
+}
+
+fn conclusion(aggregate: i64, materiality: Option<&Materiality>) -> String {
+    let Some(materiality) = materiality else {
+        return "Materiality has not been set for this engagement".to_string();
+    };
+    let amount = |cents: i64| format!("{:.2}", cents as f64 / 100.0);
+
+    if aggregate > materiality.overall {
+        format!(
+            "Uncorrected misstatements of {} exceed overall materiality of {}; the financial statements may be materially misstated",
+            amount(aggregate),
+            amount(materiality.overall)
+        )
+    } else if aggregate > materiality.performance {
+        format!(
+            "Uncorrected misstatements of {} exceed performance materiality of {}; consider requesting corrections or further procedures",
+            amount(aggregate),
+            amount(materiality.performance)
+        )
+    } else {
+        format!(
+            "Uncorrected misstatements of {} are below performance materiality of {}",
+            amount(aggregate),
+            amount(materiality.performance)
+        )
+    }
+}
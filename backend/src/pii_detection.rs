@@ -0,0 +1,341 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::dates;
+use crate::types::{PIIDetection, PIIFinding, PIIKind};
+
+// A detector must reach this confidence before the column is flagged
+const PII_CONFIDENCE_THRESHOLD: f64 = 0.5;
+
+// Confidence bonus when the column header itself suggests the PII kind
+const HEADER_HINT_BONUS: f64 = 0.15;
+
+// Egyptian governorate codes (digits 8-9 of the national ID); 88 = born abroad
+const EGYPT_GOVERNORATE_CODES: &[u32] = &[
+    1, 2, 3, 4, 11, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 23, 24, 25, 26, 27, 28, 29, 31, 32,
+    33, 34, 35, 88,
+];
+
+// IBAN lengths for the countries we see most often; others fall back to 15-34
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("EG", 29),
+    ("SA", 24),
+    ("AE", 23),
+    ("KW", 30),
+    ("QA", 29),
+    ("BH", 22),
+    ("OM", 23),
+    ("JO", 30),
+    ("LB", 28),
+    ("GB", 22),
+    ("DE", 22),
+    ("FR", 27),
+    ("IT", 27),
+    ("NL", 18),
+    ("CH", 21),
+    ("TR", 26),
+];
+
+// Dialling codes and subscriber lengths accepted after a "00" prefix. Zero-padded
+// account and reference numbers also start with "00", so only numbers that fit
+// one of these plans count.
+const DIALLING_PLANS: &[(&str, usize)] = &[
+    ("20", 10), // Egypt
+    ("966", 9), // Saudi Arabia
+    ("971", 9), // UAE
+    ("965", 8), // Kuwait
+    ("974", 8), // Qatar
+    ("973", 8), // Bahrain
+    ("968", 8), // Oman
+    ("962", 9), // Jordan
+];
+
+// Common given names and family-name tokens, Latin transliterations
+const LATIN_NAME_TOKENS: &[&str] = &[
+    "mohamed", "mohammed", "muhammad", "mohammad", "ahmed", "ahmad", "mahmoud", "mahmud",
+    "mostafa", "mustafa", "ali", "hassan", "hasan", "hussein", "hussain", "omar", "umar",
+    "khaled", "khalid", "youssef", "yousef", "yusuf", "ibrahim", "abdullah", "abdallah",
+    "karim", "kareem", "tarek", "tariq", "amr", "hany", "hani", "sherif", "sharif", "walid",
+    "waleed", "osama", "usama", "hamza", "ayman", "essam", "emad", "adel", "magdy", "samir",
+    "sameh", "fatma", "fatima", "aisha", "aysha", "mariam", "maryam", "nour", "noura", "sara",
+    "sarah", "hana", "salma", "yasmin", "yasmine", "mona", "reem", "laila", "layla", "heba",
+    "dina", "rania", "nada", "khadija", "zainab", "zeinab", "saeed", "said", "saleh", "salih",
+    "faisal", "fahad", "fahd", "sultan", "nasser", "hamad", "rashid", "majed", "turki",
+    "bandar", "saud", "john", "michael", "david", "james", "robert", "mary", "peter", "george",
+];
+
+// Common given names and family-name tokens, Arabic script (normalised form)
+const ARABIC_NAME_TOKENS: &[&str] = &[
+    "محمد", "احمد", "محمود", "مصطفي", "علي", "حسن", "حسين", "عمر", "خالد", "يوسف", "ابراهيم",
+    "عبد", "كريم", "طارق", "عمرو", "هاني", "شريف", "وليد", "اسامه", "حمزه", "ايمن", "عصام",
+    "عماد", "عادل", "مجدي", "سمير", "فاطمه", "عايشه", "عائشه", "مريم", "نور", "نوره", "ساره",
+    "هناء", "سلمي", "ياسمين", "مني", "ريم", "ليلي", "هبه", "دينا", "رانيا", "ندي", "خديجه",
+    "زينب", "سعيد", "صالح", "فيصل", "فهد", "سلطان", "ناصر", "حمد", "راشد", "ماجد",
+];
+
+const NAME_HEADER_HINTS: &[&str] = &[
+    "name", "employee", "customer", "vendor", "supplier", "beneficiary", "اسم", "الموظف", "العميل",
+];
+const EMAIL_HEADER_HINTS: &[&str] = &["email", "e-mail", "mail", "بريد"];
+const PHONE_HEADER_HINTS: &[&str] = &["phone", "mobile", "tel", "gsm", "هاتف", "جوال", "موبايل"];
+const NATIONAL_ID_HEADER_HINTS: &[&str] = &[
+    "national", "nid", "id number", "iqama", "emirates", "هوية", "قومي", "اقامة",
+];
+const IBAN_HEADER_HINTS: &[&str] = &["iban", "account no", "حساب"];
+
+// (kind, weight, header hint, value matcher)
+type Detector = (PIIKind, f64, bool, Box<dyn Fn(&str) -> bool>);
+
+// Run every detector over a column's non-empty values
+pub fn detect_pii(header: &str, values: &[&String]) -> PIIDetection {
+    let header_lower = header.to_lowercase();
+    let has_hint = |hints: &[&str]| hints.iter().any(|h| header_lower.contains(h));
+
+    let name_hint = has_hint(NAME_HEADER_HINTS);
+    let national_id_hint = has_hint(NATIONAL_ID_HEADER_HINTS);
+
+    let detectors: Vec<Detector> = vec![
+        (PIIKind::PersonName, 0.85, name_hint, Box::new(move |v| is_person_name(v, name_hint))),
+        (PIIKind::Email, 1.0, has_hint(EMAIL_HEADER_HINTS), Box::new(is_email)),
+        (PIIKind::PhoneNumber, 0.9, has_hint(PHONE_HEADER_HINTS), Box::new(is_phone_number)),
+        (PIIKind::EgyptianNationalId, 1.0, national_id_hint, Box::new(is_egyptian_national_id)),
+        (PIIKind::SaudiNationalId, 0.9, national_id_hint, Box::new(is_saudi_national_id)),
+        (PIIKind::UaeEmiratesId, 1.0, national_id_hint, Box::new(is_uae_emirates_id)),
+        (PIIKind::Iban, 1.0, has_hint(IBAN_HEADER_HINTS), Box::new(is_iban)),
+    ];
+
+    let total = values.len();
+    let mut findings = Vec::new();
+
+    for (kind, weight, header_hint, detector) in detectors {
+        let matched_rows = values.iter().filter(|v| detector(v.trim())).count();
+        if matched_rows == 0 {
+            continue;
+        }
+
+        let mut confidence = (matched_rows as f64 / total as f64) * weight;
+        if header_hint {
+            confidence += HEADER_HINT_BONUS;
+        }
+
+        findings.push(PIIFinding {
+            kind,
+            confidence: confidence.min(1.0),
+            matched_rows: matched_rows as u64,
+        });
+    }
+
+    let flagged = |kinds: &[PIIKind]| {
+        findings
+            .iter()
+            .any(|f| kinds.contains(&f.kind) && f.confidence >= PII_CONFIDENCE_THRESHOLD)
+    };
+
+    PIIDetection {
+        has_names: flagged(&[PIIKind::PersonName]),
+        has_emails: flagged(&[PIIKind::Email]),
+        has_phone_numbers: flagged(&[PIIKind::PhoneNumber]),
+        has_national_ids: flagged(&[
+            PIIKind::EgyptianNationalId,
+            PIIKind::SaudiNationalId,
+            PIIKind::UaeEmiratesId,
+        ]),
+        has_ibans: Some(flagged(&[PIIKind::Iban])),
+        findings: Some(findings),
+    }
+}
+
+fn is_email(value: &str) -> bool {
+    static EMAIL_RE: OnceLock<Regex> = OnceLock::new();
+    EMAIL_RE
+        .get_or_init(|| Regex::new(r"^[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}$").unwrap())
+        .is_match(value)
+}
+
+// International (+ or 00 prefixed) numbers plus Egyptian and GCC local mobile formats
+fn is_phone_number(value: &str) -> bool {
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '(' | ')' | '.'))
+    {
+        return false;
+    }
+
+    let compact: String = value.chars().filter(|c| c.is_ascii_digit() || *c == '+').collect();
+
+    if let Some(rest) = compact.strip_prefix('+') {
+        return !rest.starts_with('0')
+            && (8..=15).contains(&rest.len())
+            && rest.chars().all(|c| c.is_ascii_digit());
+    }
+
+    if let Some(rest) = compact.strip_prefix("00") {
+        return DIALLING_PLANS.iter().any(|(code, subscriber_len)| {
+            rest.strip_prefix(code)
+                .is_some_and(|subscriber| subscriber.len() == *subscriber_len && !subscriber.starts_with('0'))
+        });
+    }
+
+    if compact.contains('+') {
+        return false;
+    }
+
+    // Egyptian mobile: 010/011/012/015 + 8 digits
+    let egyptian_mobile = compact.len() == 11
+        && compact.starts_with("01")
+        && matches!(compact.as_bytes()[2], b'0' | b'1' | b'2' | b'5');
+
+    // Saudi / UAE mobile: 05 + 8 digits
+    let gcc_mobile = compact.len() == 10 && compact.starts_with("05");
+
+    egyptian_mobile || gcc_mobile
+}
+
+// 14 digits: century, YYMMDD birth date, governorate, sequence, check digit
+fn is_egyptian_national_id(value: &str) -> bool {
+    let digits = strip_separators(value);
+    if digits.len() != 14 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let century = match &digits[0..1] {
+        "2" => 1900,
+        "3" => 2000,
+        _ => return false,
+    };
+
+    let year = century + digits[1..3].parse::<u32>().unwrap_or(0);
+    let month = digits[3..5].parse::<u32>().unwrap_or(0);
+    let day = digits[5..7].parse::<u32>().unwrap_or(0);
+    if dates::days_from_civil(year as i64, month as i64, day as i64).is_none() {
+        return false;
+    }
+
+    let governorate = digits[7..9].parse::<u32>().unwrap_or(0);
+    EGYPT_GOVERNORATE_CODES.contains(&governorate)
+}
+
+// 10 digits starting with 1 (citizen) or 2 (resident / iqama), Luhn checked
+fn is_saudi_national_id(value: &str) -> bool {
+    let digits = strip_separators(value);
+    digits.len() == 10
+        && (digits.starts_with('1') || digits.starts_with('2'))
+        && luhn_valid(&digits)
+}
+
+// 784-YYYY-NNNNNNN-C, Luhn checked
+fn is_uae_emirates_id(value: &str) -> bool {
+    let digits = strip_separators(value);
+    digits.len() == 15 && digits.starts_with("784") && luhn_valid(&digits)
+}
+
+// Country length check plus ISO 13616 mod-97 validation
+fn is_iban(value: &str) -> bool {
+    let iban: String = strip_separators(value).to_uppercase();
+    if iban.len() < 15 || iban.len() > 34 || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return false;
+    }
+
+    let bytes = iban.as_bytes();
+    if !bytes[0].is_ascii_alphabetic() || !bytes[1].is_ascii_alphabetic() {
+        return false;
+    }
+    if !bytes[2].is_ascii_digit() || !bytes[3].is_ascii_digit() {
+        return false;
+    }
+
+    let country = &iban[0..2];
+    if let Some((_, expected_len)) = IBAN_LENGTHS.iter().find(|(code, _)| *code == country) {
+        if iban.len() != *expected_len {
+            return false;
+        }
+    }
+
+    let rearranged = format!("{}{}", &iban[4..], &iban[0..4]);
+    let mut remainder: u32 = 0;
+    for c in rearranged.chars() {
+        let value = c.to_digit(36).unwrap_or(0);
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+
+    remainder == 1
+}
+
+// Two to five alphabetic tokens with at least one known name token.
+// A single known token only counts when the header already says "name".
+fn is_person_name(value: &str, header_hint: bool) -> bool {
+    let tokens: Vec<String> = value
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c: char| c == '.' || c == ','))
+        .filter(|t| !t.is_empty())
+        .map(normalize_name_token)
+        .collect();
+
+    if tokens.is_empty() || tokens.len() > 5 {
+        return false;
+    }
+
+    if !tokens.iter().all(|t| t.chars().all(|c| c.is_alphabetic() || c == '-' || c == '\'')) {
+        return false;
+    }
+
+    let known = tokens.iter().filter(|t| is_known_name_token(t)).count();
+
+    if tokens.len() == 1 {
+        known == 1 && header_hint
+    } else {
+        known >= 1
+    }
+}
+
+fn is_known_name_token(token: &str) -> bool {
+    // Compound names such as Abdelrahman / عبدالرحمن
+    if token.starts_with("abdel") || token.starts_with("abdul") || token.starts_with("عبد") {
+        return true;
+    }
+
+    let stripped = token.strip_prefix("el-").or_else(|| token.strip_prefix("al-")).unwrap_or(token);
+
+    LATIN_NAME_TOKENS.contains(&stripped) || ARABIC_NAME_TOKENS.contains(&stripped)
+}
+
+// Lowercase Latin and fold Arabic spelling variants (hamza forms, taa marbuta, alef maqsura)
+fn normalize_name_token(token: &str) -> String {
+    token
+        .chars()
+        .filter(|c| !('\u{064B}'..='\u{0652}').contains(c))
+        .map(|c| match c {
+            'أ' | 'إ' | 'آ' => 'ا',
+            'ة' => 'ه',
+            'ى' => 'ي',
+            _ => c,
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn strip_separators(value: &str) -> String {
+    value.chars().filter(|c| !matches!(c, ' ' | '-')).collect()
+}
+
+fn luhn_valid(digits: &str) -> bool {
+    let mut sum = 0;
+    for (idx, c) in digits.chars().rev().enumerate() {
+        let mut d = match c.to_digit(10) {
+            Some(d) => d,
+            None => return false,
+        };
+        if idx % 2 == 1 {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+    }
+    sum % 10 == 0
+}
@@ -0,0 +1,391 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::blockchain_proof::generate_hash;
+use crate::storage::{next_pii_vault_id, StorableString, STORAGE};
+use crate::types::{
+    ColumnMaskingPolicy, ImportedDataset, MaskDatasetColumnsRequest, MaskingMethod, PIIKind,
+    PIIVaultEntry, Result, SheetData,
+};
+
+const REDACTED_VALUE: &str = "[REDACTED]";
+
+// Mask columns of an existing dataset, moving raw values into the PII vault
+pub fn mask_dataset_columns(
+    caller: Principal,
+    req: MaskDatasetColumnsRequest,
+) -> Result<ImportedDataset> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_import_data(&user) {
+        return Err("Insufficient permissions to mask dataset columns".to_string());
+    }
+
+    let mut dataset = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&req.dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    let (masked_columns, vault_entries) = apply_masking_policies(caller, &mut dataset, &req.policies)?;
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .datasets
+            .insert(dataset.id, dataset.clone());
+    });
+    store_vault_entries(vault_entries);
+
+    // The activity log is append-only: earlier entries are left as they are,
+    // and from here on only the masked dataset is logged. Raw values live in the vault.
+    log_activity(
+        caller,
+        "mask_dataset_columns".to_string(),
+        "dataset".to_string(),
+        dataset.id.to_string(),
+        format!(
+            "Masked {} column(s) in dataset {}: {}",
+            masked_columns.len(),
+            dataset.id,
+            masked_columns.join(", ")
+        ),
+        encode_args((dataset.clone(),)).ok(),
+    );
+
+    Ok(dataset)
+}
+
+// Return the raw values of a masked column (managers and above, always logged)
+pub fn unmask_dataset_column(
+    caller: Principal,
+    dataset_id: u64,
+    sheet_name: String,
+    column_name: String,
+) -> Result<PIIVaultEntry> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::is_manager_or_above(&user) {
+        return Err("Only managers and above can unmask PII".to_string());
+    }
+
+    let dataset = STORAGE
+        .with(|storage| storage.borrow().datasets.get(&dataset_id))
+        .ok_or_else(|| "Dataset not found".to_string())?;
+
+    let entry = find_vault_entry(dataset_id, dataset.version, &sheet_name, &column_name)
+        .ok_or_else(|| format!("No masked column '{}' in sheet '{}'", column_name, sheet_name))?;
+
+    log_activity(
+        caller,
+        "unmask_dataset_column".to_string(),
+        "dataset".to_string(),
+        dataset_id.to_string(),
+        format!(
+            "Unmasked column '{}' in sheet '{}' of dataset {}",
+            column_name, sheet_name, dataset_id
+        ),
+        None,
+    );
+
+    Ok(entry)
+}
+
+// Mask every column the PII detectors flagged, using a sensible default per kind
+pub fn policies_for_detected_pii(dataset: &ImportedDataset) -> Vec<ColumnMaskingPolicy> {
+    let mut policies = Vec::new();
+
+    for sheet in &dataset.sheets {
+        for column in &sheet.columns {
+            let detection = &column.pii_detection;
+            let method = if detection.has_national_ids {
+                Some(MaskingMethod::Hash)
+            } else if detection.has_ibans.unwrap_or(false) {
+                Some(MaskingMethod::PartialMask { visible_prefix: 2, visible_suffix: 4 })
+            } else if detection.has_phone_numbers {
+                Some(MaskingMethod::PartialMask { visible_prefix: 0, visible_suffix: 3 })
+            } else if detection.has_emails {
+                Some(MaskingMethod::PartialMask { visible_prefix: 1, visible_suffix: 0 })
+            } else if detection.has_names {
+                let strongest = detection
+                    .findings
+                    .iter()
+                    .flatten()
+                    .find(|f| f.kind == PIIKind::PersonName)
+                    .map(|f| f.confidence)
+                    .unwrap_or(0.0);
+                // Likely-but-uncertain name columns keep a readable initial
+                if strongest >= 0.9 {
+                    Some(MaskingMethod::Hash)
+                } else {
+                    Some(MaskingMethod::PartialMask { visible_prefix: 1, visible_suffix: 0 })
+                }
+            } else {
+                None
+            };
+
+            if let Some(method) = method {
+                policies.push(ColumnMaskingPolicy {
+                    sheet_name: Some(sheet.name.clone()),
+                    column_name: column.name.clone(),
+                    method,
+                });
+            }
+        }
+    }
+
+    policies
+}
+
+// Apply policies in place. Raw values go to the vault the first time a column is
+// masked; re-masking with a different method starts again from the vault copy.
+// New vault entries are returned rather than stored, so the caller only persists
+// them (via store_vault_entries) once the dataset itself is committed.
+pub fn apply_masking_policies(
+    caller: Principal,
+    dataset: &mut ImportedDataset,
+    policies: &[ColumnMaskingPolicy],
+) -> Result<(Vec<String>, Vec<PIIVaultEntry>)> {
+    let salt = salt_for_dataset(dataset, caller);
+    let mut masked_columns = Vec::new();
+    let mut new_entries: Vec<PIIVaultEntry> = Vec::new();
+
+    // Vault entries for a version that was never stored are leftovers, not raw values
+    let committed = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .datasets
+            .get(&dataset.id)
+            .is_some_and(|stored| stored.version == dataset.version)
+    });
+
+    for policy in policies {
+        let mut matched = false;
+
+        for sheet in dataset.sheets.iter_mut() {
+            if policy.sheet_name.as_ref().is_some_and(|name| *name != sheet.name) {
+                continue;
+            }
+
+            let col_idx = match sheet.columns.iter().position(|c| c.name == policy.column_name) {
+                Some(idx) => idx,
+                None => continue,
+            };
+            matched = true;
+
+            let existing = new_entries
+                .iter()
+                .find(|e| e.sheet_name == sheet.name && e.column_name == policy.column_name)
+                .cloned()
+                .or_else(|| {
+                    committed
+                        .then(|| find_vault_entry(dataset.id, dataset.version, &sheet.name, &policy.column_name))
+                        .flatten()
+                });
+            let vault_entry = match existing {
+                Some(entry) => entry,
+                None => {
+                    let entry = PIIVaultEntry {
+                        id: next_pii_vault_id(),
+                        dataset_id: dataset.id,
+                        dataset_version: dataset.version,
+                        sheet_name: sheet.name.clone(),
+                        column_name: policy.column_name.clone(),
+                        raw_values: sheet
+                            .data
+                            .iter()
+                            .map(|row| row.get(col_idx).cloned().unwrap_or_default())
+                            .collect(),
+                        raw_sample_values: sheet.columns[col_idx].sample_values.clone(),
+                        created_at: time(),
+                        created_by: caller,
+                    };
+                    new_entries.push(entry.clone());
+                    entry
+                }
+            };
+
+            mask_column(
+                sheet,
+                col_idx,
+                &vault_entry.raw_values,
+                &vault_entry.raw_sample_values,
+                &policy.method,
+                &salt,
+            );
+            masked_columns.push(format!("{}/{}", sheet.name, policy.column_name));
+        }
+
+        if !matched {
+            return Err(format!("Column '{}' not found in dataset", policy.column_name));
+        }
+
+        let stored = dataset.masking_policies.get_or_insert_with(Vec::new);
+        stored.retain(|p| !(p.column_name == policy.column_name && p.sheet_name == policy.sheet_name));
+        stored.push(policy.clone());
+    }
+
+    Ok((masked_columns, new_entries))
+}
+
+// Mask an earlier version's sheets with the dataset's current policies, so version
+// history and diffs never show values the current dataset hides. A column that
+// version masked another way is re-masked from that version's vault copy.
+pub fn mask_version_sheets(caller: Principal, dataset: &ImportedDataset, version: u32, sheets: &mut [SheetData]) {
+    let policies = dataset.masking_policies.clone().unwrap_or_default();
+    if policies.is_empty() {
+        return;
+    }
+    let salt = salt_for_dataset(dataset, caller);
+
+    for policy in &policies {
+        for sheet in sheets.iter_mut() {
+            if policy.sheet_name.as_ref().is_some_and(|name| *name != sheet.name) {
+                continue;
+            }
+            let Some(col_idx) = sheet.columns.iter().position(|c| c.name == policy.column_name) else {
+                continue;
+            };
+
+            let raw = match &sheet.columns[col_idx].masking {
+                None => Some((
+                    sheet
+                        .data
+                        .iter()
+                        .map(|row| row.get(col_idx).cloned().unwrap_or_default())
+                        .collect(),
+                    sheet.columns[col_idx].sample_values.clone(),
+                )),
+                Some(method) if *method == policy.method => None,
+                Some(_) => find_vault_entry(dataset.id, version, &sheet.name, &policy.column_name)
+                    .map(|entry| (entry.raw_values, entry.raw_sample_values)),
+            };
+            if let Some((raw_values, raw_sample_values)) = raw {
+                mask_column(sheet, col_idx, &raw_values, &raw_sample_values, &policy.method, &salt);
+            }
+        }
+    }
+}
+
+fn mask_column(
+    sheet: &mut SheetData,
+    col_idx: usize,
+    raw_values: &[String],
+    raw_sample_values: &[String],
+    method: &MaskingMethod,
+    salt: &str,
+) {
+    for (row, raw) in sheet.data.iter_mut().zip(raw_values.iter()) {
+        if let Some(cell) = row.get_mut(col_idx) {
+            *cell = mask_value(raw, method, salt);
+        }
+    }
+
+    let column = &mut sheet.columns[col_idx];
+    column.sample_values = raw_sample_values.iter().map(|v| mask_value(v, method, salt)).collect();
+    column.min_value = String::new();
+    column.max_value = String::new();
+    column.masking = Some(method.clone());
+}
+
+// Persist vault entries for a committed dataset version, replacing any left behind
+// for the same column by an earlier attempt that failed before committing
+pub fn store_vault_entries(entries: Vec<PIIVaultEntry>) {
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for entry in entries {
+            let stale: Vec<u64> = storage
+                .pii_vault
+                .iter()
+                .filter(|(_, e)| {
+                    e.dataset_id == entry.dataset_id
+                        && e.dataset_version == entry.dataset_version
+                        && e.sheet_name == entry.sheet_name
+                        && e.column_name == entry.column_name
+                })
+                .map(|(id, _)| id)
+                .collect();
+            for id in stale {
+                storage.pii_vault.remove(&id);
+            }
+            storage.pii_vault.insert(entry.id, entry);
+        }
+    });
+}
+
+pub fn mask_value(value: &str, method: &MaskingMethod, salt: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+
+    match method {
+        MaskingMethod::Redact => REDACTED_VALUE.to_string(),
+        MaskingMethod::Hash => format!("#{}", generate_hash(&format!("{}:{}", salt, value))),
+        MaskingMethod::PartialMask { visible_prefix, visible_suffix } => {
+            let chars: Vec<char> = value.chars().collect();
+            let prefix = (*visible_prefix as usize).min(chars.len());
+            let suffix = (*visible_suffix as usize).min(chars.len() - prefix);
+            chars
+                .iter()
+                .enumerate()
+                .map(|(idx, c)| {
+                    if idx < prefix || idx >= chars.len() - suffix || c.is_whitespace() {
+                        *c
+                    } else {
+                        '*'
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+fn find_vault_entry(
+    dataset_id: u64,
+    dataset_version: u32,
+    sheet_name: &str,
+    column_name: &str,
+) -> Option<PIIVaultEntry> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .pii_vault
+            .iter()
+            .find(|(_, e)| {
+                e.dataset_id == dataset_id
+                    && e.dataset_version == dataset_version
+                    && e.sheet_name == sheet_name
+                    && e.column_name == column_name
+            })
+            .map(|(_, e)| e)
+    })
+}
+
+// Salt for hash masking, created on first use. Datasets share their
+// engagement's salt so equal values still join; a dataset without an
+// engagement gets its own, so unrelated clients' hashes cannot be correlated.
+pub fn salt_for_dataset(dataset: &ImportedDataset, caller: Principal) -> String {
+    let (key, existing) = match dataset.engagement_id {
+        Some(engagement_id) => (
+            format!("engagement:{}", engagement_id),
+            STORAGE.with(|storage| storage.borrow().pii_salts.get(&engagement_id)),
+        ),
+        None => (
+            format!("dataset:{}", dataset.id),
+            STORAGE.with(|storage| storage.borrow().dataset_pii_salts.get(&dataset.id)),
+        ),
+    };
+    if let Some(salt) = existing {
+        return salt.0;
+    }
+
+    let salt = generate_hash(&format!("pii-salt:{}:{}:{}", key, caller.to_text(), time()));
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        match dataset.engagement_id {
+            Some(engagement_id) => storage.pii_salts.insert(engagement_id, StorableString(salt.clone())),
+            None => storage.dataset_pii_salts.insert(dataset.id, StorableString(salt.clone())),
+        };
+    });
+    salt
+}
@@ -0,0 +1,351 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::storage::{next_reconciliation_id, STORAGE};
+use crate::types::{
+    AccountReconciliation, CreateReconciliationRequest, ReconcilingItem, ReconcilingItemInput,
+    ReconciliationStatus, Result, SignOff, SignOffRole, TrialBalanceAccount, UnreconciledAccount,
+    UpdateReconciliationRequest,
+};
+
+// Attach a reconciliation to a trial balance account. Each account has at
+// most one; later changes go through update_reconciliation.
+pub fn create_reconciliation(caller: Principal, req: CreateReconciliationRequest) -> Result<AccountReconciliation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to prepare reconciliations".to_string());
+    }
+
+    let account = STORAGE
+        .with(|storage| storage.borrow().trial_balance_accounts.get(&req.account_id))
+        .ok_or("Account not found")?;
+    if let Some(existing) = reconciliation_for_account(account.id) {
+        return Err(format!("Account {} already has reconciliation {}", account.account_number, existing.id));
+    }
+    validate_documents(&req.document_ids, &req.reconciling_items)?;
+
+    let now = time();
+    let mut reconciliation = AccountReconciliation {
+        id: next_reconciliation_id(),
+        trial_balance_id: account.trial_balance_id,
+        account_id: account.id,
+        account_number: account.account_number.clone(),
+        account_name: account.account_name.clone(),
+        source: req.source,
+        source_reference: req.source_reference,
+        supporting_balance: req.supporting_balance,
+        account_balance: 0,
+        reconciling_items: reconciling_items(req.reconciling_items)?,
+        unreconciled_difference: 0,
+        document_ids: req.document_ids,
+        status: ReconciliationStatus::Open,
+        prepared_by: None,
+        reviewed_by: None,
+        created_at: now,
+        created_by: caller,
+        updated_at: now,
+    };
+    recalculate(&mut reconciliation, &account);
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .account_reconciliations
+            .insert(reconciliation.id, reconciliation.clone());
+    });
+
+    log_activity(
+        caller,
+        "create_reconciliation".to_string(),
+        "account_reconciliation".to_string(),
+        reconciliation.id.to_string(),
+        format!(
+            "Reconciliation created for account {} with a difference of {:.2}",
+            reconciliation.account_number,
+            reconciliation.unreconciled_difference as f64 / 100.0
+        ),
+        encode_args((reconciliation.clone(),)).ok(),
+    );
+
+    Ok(reconciliation)
+}
+
+// Changing a signed-off reconciliation reopens it for preparation
+pub fn update_reconciliation(caller: Principal, req: UpdateReconciliationRequest) -> Result<AccountReconciliation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to prepare reconciliations".to_string());
+    }
+
+    let mut reconciliation = STORAGE
+        .with(|storage| storage.borrow().account_reconciliations.get(&req.reconciliation_id))
+        .ok_or("Reconciliation not found")?;
+    let mut account = STORAGE
+        .with(|storage| storage.borrow().trial_balance_accounts.get(&reconciliation.account_id))
+        .ok_or("Account not found")?;
+    validate_documents(
+        req.document_ids.as_deref().unwrap_or_default(),
+        req.reconciling_items.as_deref().unwrap_or_default(),
+    )?;
+
+    if let Some(source) = req.source {
+        reconciliation.source = source;
+    }
+    if let Some(source_reference) = req.source_reference {
+        reconciliation.source_reference = source_reference;
+    }
+    if let Some(supporting_balance) = req.supporting_balance {
+        reconciliation.supporting_balance = supporting_balance;
+    }
+    if let Some(items) = req.reconciling_items {
+        reconciliation.reconciling_items = reconciling_items(items)?;
+    }
+    if let Some(document_ids) = req.document_ids {
+        reconciliation.document_ids = document_ids;
+    }
+    recalculate(&mut reconciliation, &account);
+    reconciliation.status = ReconciliationStatus::Open;
+    reconciliation.prepared_by = None;
+    reconciliation.reviewed_by = None;
+    reconciliation.updated_at = time();
+    account.is_reconciled = false;
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage
+            .account_reconciliations
+            .insert(reconciliation.id, reconciliation.clone());
+        storage.trial_balance_accounts.insert(account.id, account);
+    });
+
+    log_activity(
+        caller,
+        "update_reconciliation".to_string(),
+        "account_reconciliation".to_string(),
+        reconciliation.id.to_string(),
+        format!(
+            "Reconciliation of account {} updated; difference {:.2}",
+            reconciliation.account_number,
+            reconciliation.unreconciled_difference as f64 / 100.0
+        ),
+        encode_args((reconciliation.clone(),)).ok(),
+    );
+
+    Ok(reconciliation)
+}
+
+// Preparer sign-off needs the account fully reconciled at its current
+// balance; reviewer sign-off marks the trial balance account reconciled
+pub fn sign_off_reconciliation(
+    caller: Principal,
+    reconciliation_id: u64,
+    role: SignOffRole,
+) -> Result<AccountReconciliation> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut reconciliation = STORAGE
+        .with(|storage| storage.borrow().account_reconciliations.get(&reconciliation_id))
+        .ok_or("Reconciliation not found")?;
+    let mut account = STORAGE
+        .with(|storage| storage.borrow().trial_balance_accounts.get(&reconciliation.account_id))
+        .ok_or("Account not found")?;
+    recalculate(&mut reconciliation, &account);
+    if reconciliation.unreconciled_difference != 0 {
+        return Err(format!(
+            "Account {} has an unreconciled difference of {:.2}",
+            reconciliation.account_number,
+            reconciliation.unreconciled_difference as f64 / 100.0
+        ));
+    }
+
+    let sign_off = Some(SignOff {
+        signed_by: caller,
+        signed_at: time(),
+    });
+    match role {
+        SignOffRole::Preparer => {
+            if !auth::can_create_working_paper(&user) {
+                return Err("Insufficient permissions to sign off as preparer".to_string());
+            }
+            if reconciliation.status != ReconciliationStatus::Open {
+                return Err("Reconciliation is already signed off as prepared".to_string());
+            }
+            reconciliation.prepared_by = sign_off;
+            reconciliation.status = ReconciliationStatus::Prepared;
+        }
+        SignOffRole::Reviewer => {
+            if !auth::can_review_work(&user) {
+                return Err("Insufficient permissions to sign off as reviewer".to_string());
+            }
+            if reconciliation.status != ReconciliationStatus::Prepared {
+                return Err("Reconciliation must be prepared before review".to_string());
+            }
+            if reconciliation.prepared_by.as_ref().is_some_and(|s| s.signed_by == caller) {
+                return Err("The preparer cannot also review the reconciliation".to_string());
+            }
+            reconciliation.reviewed_by = sign_off;
+            reconciliation.status = ReconciliationStatus::Reviewed;
+            account.is_reconciled = true;
+        }
+        SignOffRole::Approver => {
+            return Err("Reconciliations are signed off by a preparer and a reviewer".to_string());
+        }
+    }
+    reconciliation.updated_at = time();
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        storage
+            .account_reconciliations
+            .insert(reconciliation.id, reconciliation.clone());
+        storage.trial_balance_accounts.insert(account.id, account);
+    });
+
+    log_activity(
+        caller,
+        "sign_off_reconciliation".to_string(),
+        "account_reconciliation".to_string(),
+        reconciliation.id.to_string(),
+        format!("Reconciliation of account {} signed off as {:?}", reconciliation.account_number, role),
+        encode_args((reconciliation.clone(),)).ok(),
+    );
+
+    Ok(reconciliation)
+}
+
+pub fn get_reconciliation(caller: Principal, reconciliation_id: u64) -> Result<AccountReconciliation> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().account_reconciliations.get(&reconciliation_id))
+        .ok_or_else(|| "Reconciliation not found".to_string())
+}
+
+pub fn list_reconciliations(caller: Principal, trial_balance_id: u64) -> Result<Vec<AccountReconciliation>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut reconciliations: Vec<AccountReconciliation> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .account_reconciliations
+            .iter()
+            .filter(|(_, reconciliation)| reconciliation.trial_balance_id == trial_balance_id)
+            .map(|(_, reconciliation)| reconciliation)
+            .collect()
+    });
+    reconciliations.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+    Ok(reconciliations)
+}
+
+// Accounts with a balance of at least the threshold that are not reviewed
+// and reconciled at their current balance, largest first
+pub fn get_unreconciled_accounts(
+    caller: Principal,
+    trial_balance_id: u64,
+    threshold: i64,
+) -> Result<Vec<UnreconciledAccount>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let (accounts, reconciliations): (Vec<TrialBalanceAccount>, Vec<AccountReconciliation>) =
+        STORAGE.with(|storage| {
+            let storage = storage.borrow();
+            (
+                storage
+                    .trial_balance_accounts
+                    .iter()
+                    .map(|(_, account)| account)
+                    .filter(|account| account.trial_balance_id == trial_balance_id)
+                    .collect(),
+                storage
+                    .account_reconciliations
+                    .iter()
+                    .map(|(_, reconciliation)| reconciliation)
+                    .filter(|reconciliation| reconciliation.trial_balance_id == trial_balance_id)
+                    .collect(),
+            )
+        });
+
+    let mut unreconciled: Vec<UnreconciledAccount> = accounts
+        .iter()
+        .filter_map(|account| {
+            let balance = account.debit_balance - account.credit_balance;
+            if balance.abs() < threshold.abs() {
+                return None;
+            }
+            let reconciliation = reconciliations.iter().find(|r| r.account_id == account.id).cloned();
+            let difference = reconciliation.map(|mut r| {
+                recalculate(&mut r, account);
+                (r.id, r.status, r.unreconciled_difference)
+            });
+            if matches!(difference, Some((_, ReconciliationStatus::Reviewed, 0))) {
+                return None;
+            }
+            Some(UnreconciledAccount {
+                account_id: account.id,
+                account_number: account.account_number.clone(),
+                account_name: account.account_name.clone(),
+                balance,
+                reconciliation_id: difference.as_ref().map(|(id, _, _)| *id),
+                status: difference.as_ref().map(|(_, status, _)| status.clone()),
+                unreconciled_difference: difference.map(|(_, _, amount)| amount),
+            })
+        })
+        .collect();
+    unreconciled.sort_by_key(|account| std::cmp::Reverse(account.balance.abs()));
+
+    Ok(unreconciled)
+}
+
+fn reconciliation_for_account(account_id: u64) -> Option<AccountReconciliation> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .account_reconciliations
+            .iter()
+            .map(|(_, reconciliation)| reconciliation)
+            .find(|reconciliation| reconciliation.account_id == account_id)
+    })
+}
+
+fn reconciling_items(inputs: Vec<ReconcilingItemInput>) -> Result<Vec<ReconcilingItem>> {
+    inputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| {
+            if input.description.trim().is_empty() {
+                return Err(format!("Reconciling item {} needs a description", index + 1));
+            }
+            Ok(ReconcilingItem {
+                id: index as u32 + 1,
+                description: input.description.trim().to_string(),
+                amount: input.amount,
+                item_date: input.item_date,
+                document_id: input.document_id,
+            })
+        })
+        .collect()
+}
+
+fn validate_documents(document_ids: &[u64], items: &[ReconcilingItemInput]) -> Result<()> {
+    let ids = document_ids.iter().chain(items.iter().filter_map(|item| item.document_id.as_ref()));
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        for id in ids {
+            if !storage.documents.contains_key(id) {
+                return Err(format!("Document {} not found", id));
+            }
+        }
+        Ok(())
+    })
+}
+
+fn recalculate(reconciliation: &mut AccountReconciliation, account: &TrialBalanceAccount) {
+    let items: i64 = reconciliation.reconciling_items.iter().map(|item| item.amount).sum();
+    reconciliation.account_balance = account.debit_balance - account.credit_balance;
+    reconciliation.unreconciled_difference =
+        reconciliation.account_balance - reconciliation.supporting_balance - items;
+}
@@ -0,0 +1,645 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::HashMap;
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::data_import;
+use crate::general_ledger::parse_amount_cents;
+use crate::storage::{next_audit_sample_id, STORAGE};
+use crate::types::{
+    AuditSample, CreateAuditSampleRequest, EvaluateAuditSampleRequest, Result, SampleEvaluation,
+    SampleItem, SamplingMethod, StratumSummary,
+};
+
+const DEFAULT_CONFIDENCE: f64 = 0.95;
+
+// AICPA expansion factors for expected misstatement, by confidence level
+const EXPANSION_FACTORS: &[(f64, f64)] = &[
+    (0.70, 1.2),
+    (0.75, 1.25),
+    (0.80, 1.3),
+    (0.85, 1.4),
+    (0.90, 1.5),
+    (0.95, 1.6),
+    (0.99, 1.9),
+];
+
+// Selected population indexes with their stratum and selection note
+type Selection = Vec<(usize, Option<u32>, String)>;
+
+// One population item: row index, reference and book amount
+struct PopulationItem {
+    row_index: usize,
+    reference: String,
+    amount: f64,
+}
+
+// Select a sample from a dataset sheet and store it with the engagement
+pub fn create_audit_sample(caller: Principal, req: CreateAuditSampleRequest) -> Result<AuditSample> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to create audit samples".to_string());
+    }
+
+    STORAGE
+        .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let dataset = data_import::get_dataset(caller, req.dataset_id)?;
+    let sheet_name = match &req.sheet_name {
+        Some(name) => name.clone(),
+        None => dataset
+            .sheets
+            .first()
+            .map(|s| s.name.clone())
+            .ok_or_else(|| "Dataset has no sheets".to_string())?,
+    };
+
+    let population =
+        data_import::load_population(caller, &dataset, &sheet_name, req.file_data.as_deref())?;
+    let column = |name: &str| {
+        population
+            .headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| format!("Column '{}' not found", name))
+    };
+    let amount_idx = column(&req.amount_column)?;
+    let reference_idx = req.reference_column.as_deref().map(column).transpose()?;
+
+    let is_mus = matches!(req.method, SamplingMethod::MonetaryUnit { .. });
+    let mut items = Vec::new();
+    let mut excluded_count = 0;
+    for (row_index, row) in population.rows.iter().enumerate() {
+        let amount = row
+            .get(amount_idx)
+            .and_then(|v| parse_amount_cents(v))
+            .map(|cents| cents as f64 / 100.0);
+        match amount {
+            // MUS samples monetary units, so only positive balances take part
+            Some(amount) if !is_mus || amount > 0.0 => items.push(PopulationItem {
+                row_index,
+                reference: reference_idx
+                    .and_then(|idx| row.get(idx))
+                    .cloned()
+                    .unwrap_or_else(|| format!("Row {}", row_index + 1)),
+                amount,
+            }),
+            _ => excluded_count += 1,
+        }
+    }
+
+    if items.is_empty() {
+        return Err("No sampleable amounts in this column".to_string());
+    }
+
+    let seed = req.seed.unwrap_or_else(|| time() ^ 0x5DEE_CE66_D1CE_4E5B);
+    let mut rng = SplitMix64::new(seed);
+
+    let (selected, strata, sampling_interval) = match &req.method {
+        SamplingMethod::MonetaryUnit {
+            confidence,
+            tolerable_misstatement,
+            expected_misstatement,
+        } => {
+            let (selected, interval) = select_monetary_unit(
+                &items,
+                *confidence,
+                *tolerable_misstatement,
+                *expected_misstatement,
+                &mut rng,
+            )?;
+            (selected, Vec::new(), Some(interval))
+        }
+        SamplingMethod::SimpleRandom { sample_size } => {
+            let all: Vec<usize> = (0..items.len()).collect();
+            let picks = pick_random(&all, *sample_size as usize, &mut rng);
+            let selected = picks
+                .into_iter()
+                .map(|idx| (idx, None, "Random selection".to_string()))
+                .collect();
+            (selected, Vec::new(), None)
+        }
+        SamplingMethod::Stratified {
+            band_upper_bounds,
+            sample_sizes,
+        } => {
+            let (selected, strata) =
+                select_stratified(&items, band_upper_bounds, sample_sizes, &mut rng)?;
+            (selected, strata, None)
+        }
+    };
+
+    let mut sample_items: Vec<SampleItem> = selected
+        .into_iter()
+        .map(|(idx, stratum, note)| SampleItem {
+            row_index: items[idx].row_index as u64,
+            reference: items[idx].reference.clone(),
+            book_amount: items[idx].amount,
+            stratum,
+            selection_note: note,
+            audited_amount: None,
+        })
+        .collect();
+    sample_items.sort_by_key(|item| item.row_index);
+
+    let sample = AuditSample {
+        id: next_audit_sample_id(),
+        engagement_id: req.engagement_id,
+        name: req.name.clone(),
+        dataset_id: dataset.id,
+        dataset_version: dataset.version,
+        sheet_name,
+        amount_column: req.amount_column.clone(),
+        reference_column: req.reference_column.clone(),
+        method: req.method.clone(),
+        seed,
+        population_count: items.len() as u64,
+        population_value: items.iter().map(|i| i.amount).sum(),
+        excluded_count,
+        sampling_interval,
+        strata,
+        items: sample_items,
+        complete_population: population.complete,
+        evaluation: None,
+        created_at: time(),
+        created_by: caller,
+    };
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .audit_samples
+            .insert(sample.id, sample.clone());
+    });
+
+    log_activity(
+        caller,
+        "create_audit_sample".to_string(),
+        "audit_sample".to_string(),
+        sample.id.to_string(),
+        format!(
+            "Sample {} selected {} of {} items (seed {})",
+            sample.name,
+            sample.items.len(),
+            sample.population_count,
+            sample.seed
+        ),
+        encode_args((sample.clone(),)).ok(),
+    );
+
+    Ok(sample)
+}
+
+// Record audited amounts and project the misstatement to the population
+pub fn evaluate_audit_sample(
+    caller: Principal,
+    req: EvaluateAuditSampleRequest,
+) -> Result<AuditSample> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to evaluate audit samples".to_string());
+    }
+
+    let mut sample = STORAGE
+        .with(|storage| storage.borrow().audit_samples.get(&req.sample_id))
+        .ok_or_else(|| "Audit sample not found".to_string())?;
+
+    let results: HashMap<u64, f64> = req
+        .results
+        .iter()
+        .map(|r| (r.row_index, r.audited_amount))
+        .collect();
+    for row_index in results.keys() {
+        if !sample.items.iter().any(|item| item.row_index == *row_index) {
+            return Err(format!("Row {} is not part of this sample", row_index));
+        }
+    }
+    for item in sample.items.iter_mut() {
+        item.audited_amount = Some(results.get(&item.row_index).copied().unwrap_or(item.book_amount));
+    }
+
+    let evaluation = match &sample.method {
+        SamplingMethod::MonetaryUnit {
+            confidence,
+            tolerable_misstatement,
+            ..
+        } => evaluate_monetary_unit(
+            &sample,
+            *confidence,
+            req.tolerable_misstatement.or(Some(*tolerable_misstatement)),
+            caller,
+        ),
+        _ => evaluate_difference(
+            &sample,
+            req.confidence.unwrap_or(DEFAULT_CONFIDENCE),
+            req.tolerable_misstatement,
+            caller,
+        ),
+    }?;
+
+    sample.evaluation = Some(evaluation.clone());
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .audit_samples
+            .insert(sample.id, sample.clone());
+    });
+
+    log_activity(
+        caller,
+        "evaluate_audit_sample".to_string(),
+        "audit_sample".to_string(),
+        sample.id.to_string(),
+        format!(
+            "Sample {} evaluated: {} misstatement(s), projected {:.2}, upper limit {:.2}",
+            sample.name,
+            evaluation.misstatements_found,
+            evaluation.projected_misstatement,
+            evaluation.upper_misstatement_limit
+        ),
+        encode_args((sample.clone(),)).ok(),
+    );
+
+    Ok(sample)
+}
+
+// Get audit sample by ID
+pub fn get_audit_sample(caller: Principal, id: u64) -> Result<AuditSample> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    STORAGE
+        .with(|storage| storage.borrow().audit_samples.get(&id))
+        .ok_or_else(|| "Audit sample not found".to_string())
+}
+
+// List audit samples by engagement
+pub fn list_audit_samples_by_engagement(
+    caller: Principal,
+    engagement_id: u64,
+) -> Result<Vec<AuditSample>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let samples = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .audit_samples
+            .iter()
+            .filter(|(_, s)| s.engagement_id == engagement_id)
+            .map(|(_, s)| s)
+            .collect()
+    });
+
+    Ok(samples)
+}
+
+// Fixed-interval MUS with a seeded random start. Items at or above the
+// interval are always selected (top stratum).
+fn select_monetary_unit(
+    items: &[PopulationItem],
+    confidence: f64,
+    tolerable: f64,
+    expected: f64,
+    rng: &mut SplitMix64,
+) -> Result<(Selection, f64)> {
+    if !(0.5..1.0).contains(&confidence) {
+        return Err("Confidence must be between 0.5 and 1".to_string());
+    }
+    if tolerable <= 0.0 || expected < 0.0 {
+        return Err("Tolerable misstatement must be positive and expected misstatement non-negative".to_string());
+    }
+
+    let population_value: f64 = items.iter().map(|i| i.amount).sum();
+    let denominator = tolerable - expected * expansion_factor(confidence);
+    if denominator <= 0.0 {
+        return Err("Expected misstatement is too close to tolerable misstatement".to_string());
+    }
+
+    let sample_size = (population_value * poisson_upper_limit(0, confidence) / denominator).ceil();
+    let interval = population_value / sample_size.max(1.0);
+    let start = rng.next_f64() * interval;
+
+    let mut selected = Vec::new();
+    let mut cumulative = 0.0;
+    let mut next_hit = start;
+    for (idx, item) in items.iter().enumerate() {
+        let end = cumulative + item.amount;
+        if next_hit < end {
+            let note = if item.amount >= interval {
+                "Top stratum (amount at or above the interval)".to_string()
+            } else {
+                format!("Monetary unit {:.2}", next_hit)
+            };
+            selected.push((idx, None, note));
+            while next_hit < end {
+                next_hit += interval;
+            }
+        }
+        cumulative = end;
+    }
+
+    Ok((selected, interval))
+}
+
+fn select_stratified(
+    items: &[PopulationItem],
+    bounds: &[f64],
+    sample_sizes: &[u32],
+    rng: &mut SplitMix64,
+) -> Result<(Selection, Vec<StratumSummary>)> {
+    if sample_sizes.len() != bounds.len() + 1 {
+        return Err("Give one sample size per stratum (band bounds + 1)".to_string());
+    }
+    if bounds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err("Band upper bounds must be ascending".to_string());
+    }
+
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); sample_sizes.len()];
+    for (idx, item) in items.iter().enumerate() {
+        let stratum = bounds
+            .iter()
+            .position(|bound| item.amount <= *bound)
+            .unwrap_or(bounds.len());
+        members[stratum].push(idx);
+    }
+
+    let mut selected = Vec::new();
+    let mut strata = Vec::new();
+    for (stratum, indexes) in members.iter().enumerate() {
+        let size = sample_sizes[stratum] as usize;
+        let picks = pick_random(indexes, size, rng);
+        let note = if picks.len() == indexes.len() {
+            format!("Stratum {} examined in full", stratum + 1)
+        } else {
+            format!("Random selection in stratum {}", stratum + 1)
+        };
+        strata.push(StratumSummary {
+            index: stratum as u32 + 1,
+            lower_bound: stratum.checked_sub(1).map(|prev| bounds[prev]),
+            upper_bound: bounds.get(stratum).copied(),
+            population_count: indexes.len() as u64,
+            population_value: indexes.iter().map(|idx| items[*idx].amount).sum(),
+            sample_size: picks.len() as u64,
+        });
+        selected.extend(picks.into_iter().map(|idx| (idx, Some(stratum as u32 + 1), note.clone())));
+    }
+
+    Ok((selected, strata))
+}
+
+// Partial Fisher-Yates shuffle; the whole set if the size covers it
+fn pick_random(indexes: &[usize], size: usize, rng: &mut SplitMix64) -> Vec<usize> {
+    let mut pool = indexes.to_vec();
+    let size = size.min(pool.len());
+    for i in 0..size {
+        let j = i + rng.next_below(pool.len() - i);
+        pool.swap(i, j);
+    }
+    pool.truncate(size);
+    pool
+}
+
+// Top-stratum items count at their actual misstatement. Others are projected
+// as tainting x interval; overstatements are ranked by tainting for the
+// incremental allowance, understatements only net into the projection.
+fn evaluate_monetary_unit(
+    sample: &AuditSample,
+    confidence: f64,
+    tolerable: Option<f64>,
+    caller: Principal,
+) -> Result<SampleEvaluation> {
+    let interval = sample
+        .sampling_interval
+        .ok_or_else(|| "Sample has no sampling interval".to_string())?;
+
+    let mut known = 0.0;
+    let mut top_stratum_known = 0.0;
+    let mut projected_over = Vec::new();
+    let mut projected_under = 0.0;
+    let mut misstatements_found = 0;
+
+    for item in &sample.items {
+        let audited = item.audited_amount.unwrap_or(item.book_amount);
+        let misstatement = item.book_amount - audited;
+        if misstatement.abs() < 0.005 {
+            continue;
+        }
+        misstatements_found += 1;
+        known += misstatement;
+
+        if item.book_amount >= interval {
+            top_stratum_known += misstatement;
+        } else {
+            let tainting = (misstatement / item.book_amount).min(1.0);
+            if tainting > 0.0 {
+                projected_over.push(tainting * interval);
+            } else {
+                projected_under += tainting * interval;
+            }
+        }
+    }
+
+    projected_over.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    let basic_precision = poisson_upper_limit(0, confidence) * interval;
+    let incremental_allowance: f64 = projected_over
+        .iter()
+        .enumerate()
+        .map(|(i, projected)| {
+            let step = poisson_upper_limit(i as u32 + 1, confidence) - poisson_upper_limit(i as u32, confidence);
+            projected * (step - 1.0)
+        })
+        .sum();
+    let projected_over_total: f64 = projected_over.iter().sum();
+
+    let projected_misstatement = top_stratum_known + projected_over_total + projected_under;
+    let upper_misstatement_limit =
+        top_stratum_known + basic_precision + projected_over_total + incremental_allowance;
+
+    Ok(SampleEvaluation {
+        items_tested: sample.items.len() as u64,
+        misstatements_found,
+        known_misstatement: known,
+        projected_misstatement,
+        basic_precision,
+        incremental_allowance,
+        upper_misstatement_limit,
+        tolerable_misstatement: tolerable,
+        exceeds_tolerable: tolerable.map(|t| upper_misstatement_limit > t),
+        confidence,
+        evaluated_at: time(),
+        evaluated_by: caller,
+    })
+}
+
+// Difference estimation per stratum (one stratum for simple random samples),
+// with a one-sided upper limit from the combined standard error
+fn evaluate_difference(
+    sample: &AuditSample,
+    confidence: f64,
+    tolerable: Option<f64>,
+    caller: Principal,
+) -> Result<SampleEvaluation> {
+    if !(0.5..1.0).contains(&confidence) {
+        return Err("Confidence must be between 0.5 and 1".to_string());
+    }
+
+    let strata: Vec<(Option<u32>, f64)> = if sample.strata.is_empty() {
+        vec![(None, sample.population_count as f64)]
+    } else {
+        sample
+            .strata
+            .iter()
+            .map(|s| (Some(s.index), s.population_count as f64))
+            .collect()
+    };
+
+    let mut known = 0.0;
+    let mut misstatements_found = 0;
+    let mut projected = 0.0;
+    let mut variance = 0.0;
+
+    for (stratum, population_count) in strata {
+        let differences: Vec<f64> = sample
+            .items
+            .iter()
+            .filter(|item| item.stratum == stratum)
+            .map(|item| item.book_amount - item.audited_amount.unwrap_or(item.book_amount))
+            .collect();
+        let n = differences.len() as f64;
+        if n == 0.0 {
+            continue;
+        }
+
+        let total: f64 = differences.iter().sum();
+        known += total;
+        misstatements_found += differences.iter().filter(|d| d.abs() >= 0.005).count() as u64;
+
+        let mean = total / n;
+        projected += mean * population_count;
+
+        if n > 1.0 && n < population_count {
+            let sample_variance =
+                differences.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let finite_correction = 1.0 - n / population_count;
+            variance += population_count.powi(2) * sample_variance / n * finite_correction;
+        }
+    }
+
+    let basic_precision = inverse_normal(confidence) * variance.sqrt();
+    let upper_misstatement_limit = projected + basic_precision;
+
+    Ok(SampleEvaluation {
+        items_tested: sample.items.len() as u64,
+        misstatements_found,
+        known_misstatement: known,
+        projected_misstatement: projected,
+        basic_precision,
+        incremental_allowance: 0.0,
+        upper_misstatement_limit,
+        tolerable_misstatement: tolerable,
+        exceeds_tolerable: tolerable.map(|t| upper_misstatement_limit > t),
+        confidence,
+        evaluated_at: time(),
+        evaluated_by: caller,
+    })
+}
+
+// Smallest table factor at or above the requested confidence
+fn expansion_factor(confidence: f64) -> f64 {
+    EXPANSION_FACTORS
+        .iter()
+        .find(|(level, _)| *level >= confidence - 1e-9)
+        .map(|(_, factor)| *factor)
+        .unwrap_or(1.9)
+}
+
+// Poisson upper limit: the mean at which k or fewer errors occur with
+// probability 1 - confidence (3.00 for zero errors at 95%)
+fn poisson_upper_limit(k: u32, confidence: f64) -> f64 {
+    let target = 1.0 - confidence;
+    let (mut low, mut high) = (0.0, 50.0 + 5.0 * k as f64);
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if poisson_cdf(k, mid) > target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+fn poisson_cdf(k: u32, lambda: f64) -> f64 {
+    let mut term = (-lambda).exp();
+    let mut sum = term;
+    for i in 1..=k {
+        term *= lambda / i as f64;
+        sum += term;
+    }
+    sum
+}
+
+// One-sided z for a confidence level (Acklam's approximation)
+fn inverse_normal(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2,
+        1.38357751867269e2, -3.066479806614716e1, 2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2,
+        6.680131188771972e1, -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838,
+        -2.549732539343734, 4.374664141464968, 2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    }
+}
+
+// Small deterministic generator so a recorded seed reproduces the selection
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
@@ -347,6 +347,234 @@ impl Storable for TimeEntry {
     const BOUND: Bound = Bound::Unbounded;
 }
 
+impl Storable for PIIVaultEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for DatasetVersion {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ImportMappingProfile {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for GlImport {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for JournalEntry {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for JournalLine {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for JetRun {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for DigitAnalysis {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AuditSample {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AnalyticalExpectation {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for ReviewNote {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CrossReference {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for TickMark {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Annotation {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for FxRate {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for Consolidation {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for CoaAccount {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AccountReconciliation {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+impl Storable for AccountMapping {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
 // Storable for String keys
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct StorableString(pub String);
@@ -391,6 +619,27 @@ pub struct Storage {
     pub engagement_milestones: StableBTreeMap<u64, EngagementMilestone, Memory>,
     pub engagement_budgets: StableBTreeMap<u64, EngagementBudget, Memory>,
     pub time_entries: StableBTreeMap<u64, TimeEntry, Memory>,
+    pub pii_vault: StableBTreeMap<u64, PIIVaultEntry, Memory>,
+    pub pii_salts: StableBTreeMap<u64, StorableString, Memory>, // engagement_id -> salt
+    pub dataset_versions: StableBTreeMap<u64, DatasetVersion, Memory>,
+    pub mapping_profiles: StableBTreeMap<u64, ImportMappingProfile, Memory>,
+    pub gl_imports: StableBTreeMap<u64, GlImport, Memory>,
+    pub journal_entries: StableBTreeMap<u64, JournalEntry, Memory>,
+    pub journal_lines: StableBTreeMap<u64, JournalLine, Memory>,
+    pub jet_runs: StableBTreeMap<u64, JetRun, Memory>,
+    pub digit_analyses: StableBTreeMap<u64, DigitAnalysis, Memory>,
+    pub audit_samples: StableBTreeMap<u64, AuditSample, Memory>,
+    pub analytical_expectations: StableBTreeMap<u64, AnalyticalExpectation, Memory>,
+    pub review_notes: StableBTreeMap<u64, ReviewNote, Memory>,
+    pub cross_references: StableBTreeMap<u64, CrossReference, Memory>,
+    pub tick_marks: StableBTreeMap<u64, TickMark, Memory>,
+    pub annotations: StableBTreeMap<u64, Annotation, Memory>,
+    pub fx_rates: StableBTreeMap<u64, FxRate, Memory>,
+    pub consolidations: StableBTreeMap<u64, Consolidation, Memory>,
+    pub coa_accounts: StableBTreeMap<u64, CoaAccount, Memory>,
+    pub account_reconciliations: StableBTreeMap<u64, AccountReconciliation, Memory>,
+    pub account_mappings: StableBTreeMap<u64, AccountMapping, Memory>,
+    pub dataset_pii_salts: StableBTreeMap<u64, StorableString, Memory>, // dataset_id -> salt, for unlinked datasets
 }
 
 thread_local! {
@@ -427,6 +676,27 @@ thread_local! {
                 engagement_milestones: StableBTreeMap::init(m.get(MemoryId::new(22))),
                 engagement_budgets: StableBTreeMap::init(m.get(MemoryId::new(23))),
                 time_entries: StableBTreeMap::init(m.get(MemoryId::new(24))),
+                pii_vault: StableBTreeMap::init(m.get(MemoryId::new(26))),
+                pii_salts: StableBTreeMap::init(m.get(MemoryId::new(27))),
+                dataset_versions: StableBTreeMap::init(m.get(MemoryId::new(28))),
+                mapping_profiles: StableBTreeMap::init(m.get(MemoryId::new(29))),
+                gl_imports: StableBTreeMap::init(m.get(MemoryId::new(30))),
+                journal_entries: StableBTreeMap::init(m.get(MemoryId::new(31))),
+                journal_lines: StableBTreeMap::init(m.get(MemoryId::new(32))),
+                jet_runs: StableBTreeMap::init(m.get(MemoryId::new(33))),
+                digit_analyses: StableBTreeMap::init(m.get(MemoryId::new(34))),
+                audit_samples: StableBTreeMap::init(m.get(MemoryId::new(35))),
+                analytical_expectations: StableBTreeMap::init(m.get(MemoryId::new(36))),
+                review_notes: StableBTreeMap::init(m.get(MemoryId::new(37))),
+                cross_references: StableBTreeMap::init(m.get(MemoryId::new(38))),
+                tick_marks: StableBTreeMap::init(m.get(MemoryId::new(39))),
+                annotations: StableBTreeMap::init(m.get(MemoryId::new(40))),
+                fx_rates: StableBTreeMap::init(m.get(MemoryId::new(41))),
+                consolidations: StableBTreeMap::init(m.get(MemoryId::new(42))),
+                coa_accounts: StableBTreeMap::init(m.get(MemoryId::new(43))),
+                account_reconciliations: StableBTreeMap::init(m.get(MemoryId::new(44))),
+                account_mappings: StableBTreeMap::init(m.get(MemoryId::new(45))),
+                dataset_pii_salts: StableBTreeMap::init(m.get(MemoryId::new(46))),
             }
         })
     );
@@ -445,6 +715,25 @@ thread_local! {
     pub static NEXT_AJE_ID: RefCell<u64> = RefCell::new(1);
     pub static NEXT_AJE_LINE_ITEM_ID: RefCell<u64> = RefCell::new(1);
     pub static NEXT_FS_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_PII_VAULT_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_DATASET_VERSION_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_MAPPING_PROFILE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_GL_IMPORT_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_JOURNAL_ENTRY_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_JOURNAL_LINE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_JET_RUN_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_DIGIT_ANALYSIS_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_AUDIT_SAMPLE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_EXPECTATION_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_REVIEW_NOTE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_CROSS_REFERENCE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_TICK_MARK_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_ANNOTATION_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_FX_RATE_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_CONSOLIDATION_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_COA_ACCOUNT_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_RECONCILIATION_ID: RefCell<u64> = RefCell::new(1);
+    pub static NEXT_ACCOUNT_MAPPING_ID: RefCell<u64> = RefCell::new(1);
 }
 
 // Helper functions for ID generation
@@ -565,3 +854,173 @@ pub fn next_fs_id() -> u64 {
     })
 }
 
+pub fn next_pii_vault_id() -> u64 {
+    NEXT_PII_VAULT_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_dataset_version_id() -> u64 {
+    NEXT_DATASET_VERSION_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_mapping_profile_id() -> u64 {
+    NEXT_MAPPING_PROFILE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_gl_import_id() -> u64 {
+    NEXT_GL_IMPORT_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_journal_entry_id() -> u64 {
+    NEXT_JOURNAL_ENTRY_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_journal_line_id() -> u64 {
+    NEXT_JOURNAL_LINE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_jet_run_id() -> u64 {
+    NEXT_JET_RUN_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_digit_analysis_id() -> u64 {
+    NEXT_DIGIT_ANALYSIS_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_audit_sample_id() -> u64 {
+    NEXT_AUDIT_SAMPLE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_expectation_id() -> u64 {
+    NEXT_EXPECTATION_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_review_note_id() -> u64 {
+    NEXT_REVIEW_NOTE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_cross_reference_id() -> u64 {
+    NEXT_CROSS_REFERENCE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_tick_mark_id() -> u64 {
+    NEXT_TICK_MARK_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_annotation_id() -> u64 {
+    NEXT_ANNOTATION_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_fx_rate_id() -> u64 {
+    NEXT_FX_RATE_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_consolidation_id() -> u64 {
+    NEXT_CONSOLIDATION_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_coa_account_id() -> u64 {
+    NEXT_COA_ACCOUNT_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_reconciliation_id() -> u64 {
+    NEXT_RECONCILIATION_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
+
+pub fn next_account_mapping_id() -> u64 {
+    NEXT_ACCOUNT_MAPPING_ID.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let id = *counter;
+        *counter += 1;
+        id
+    })
+}
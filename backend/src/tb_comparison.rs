@@ -0,0 +1,437 @@
+use candid::{encode_args, Principal};
+use ic_cdk::api::time;
+use std::collections::{HashMap, HashSet};
+
+use crate::activity_log::log_activity;
+use crate::auth;
+use crate::chart_of_accounts;
+use crate::storage::{next_account_mapping_id, STORAGE};
+use crate::trend_analysis;
+use crate::types::{
+    AccountChangeKind, AccountComparison, AccountMapping, AccountMappingInput, AccountMatchMethod, CoaOwner,
+    ComparedAccount, Result, TrialBalanceAccount, TrialBalanceComparison,
+};
+
+// Names at least this similar are suggested as the same account
+const MIN_NAME_SIMILARITY: f64 = 0.6;
+
+// A link between an account of the base and one of the compared trial balance
+struct Link {
+    base: usize,
+    compare: usize,
+    method: AccountMatchMethod,
+    similarity: Option<f64>,
+}
+
+// Match the accounts of two trial balances, usually last year's against this
+// year's: first through confirmed mappings, then by number, then by similar
+// names among accounts of the same type. Accounts linked to several on the
+// other side are reported as splits or merges.
+pub fn compare_trial_balances(
+    caller: Principal,
+    base_trial_balance_id: u64,
+    compare_trial_balance_id: u64,
+) -> Result<TrialBalanceComparison> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let compare_tb = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&compare_trial_balance_id))
+        .ok_or("Trial balance not found")?;
+    STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&base_trial_balance_id))
+        .ok_or("Base trial balance not found")?;
+
+    let mut base = trend_analysis::accounts_for(base_trial_balance_id);
+    let mut compare = trend_analysis::accounts_for(compare_trial_balance_id);
+    base.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+    compare.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+
+    let owner = chart_of_accounts::owner_for_engagement(compare_tb.engagement_id);
+    let mappings = owner.as_ref().map(mappings_for).unwrap_or_default();
+
+    let mut links: Vec<Link> = Vec::new();
+    let mut base_linked = vec![false; base.len()];
+    let mut compare_linked = vec![false; compare.len()];
+
+    for mapping in &mappings {
+        for (b, base_account) in base.iter().enumerate() {
+            if base_account.account_number.trim() != mapping.prior_account_number {
+                continue;
+            }
+            for (c, compare_account) in compare.iter().enumerate() {
+                if compare_account.account_number.trim() == mapping.current_account_number {
+                    links.push(Link {
+                        base: b,
+                        compare: c,
+                        method: AccountMatchMethod::Confirmed,
+                        similarity: None,
+                    });
+                    base_linked[b] = true;
+                    compare_linked[c] = true;
+                }
+            }
+        }
+    }
+
+    for (b, base_account) in base.iter().enumerate() {
+        if base_linked[b] {
+            continue;
+        }
+        let found = compare.iter().enumerate().position(|(c, compare_account)| {
+            !compare_linked[c] && compare_account.account_number.trim() == base_account.account_number.trim()
+        });
+        if let Some(c) = found {
+            links.push(Link {
+                base: b,
+                compare: c,
+                method: AccountMatchMethod::Number,
+                similarity: None,
+            });
+            base_linked[b] = true;
+            compare_linked[c] = true;
+        }
+    }
+
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (b, base_account) in base.iter().enumerate().filter(|(b, _)| !base_linked[*b]) {
+        for (c, compare_account) in compare.iter().enumerate().filter(|(c, _)| !compare_linked[*c]) {
+            if base_account.account_type != compare_account.account_type {
+                continue;
+            }
+            let similarity = name_similarity(&base_account.account_name, &compare_account.account_name);
+            if similarity >= MIN_NAME_SIMILARITY {
+                candidates.push((similarity, b, c));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    for (similarity, b, c) in candidates {
+        if base_linked[b] || compare_linked[c] {
+            continue;
+        }
+        links.push(Link {
+            base: b,
+            compare: c,
+            method: AccountMatchMethod::Name,
+            similarity: Some(similarity),
+        });
+        base_linked[b] = true;
+        compare_linked[c] = true;
+    }
+
+    let mut rows: Vec<AccountComparison> = components(&links, base.len())
+        .into_iter()
+        .map(|group| {
+            let group_links: Vec<&Link> = links.iter().filter(|link| group.contains(&link.base)).collect();
+            let mut base_ids: Vec<usize> = group_links.iter().map(|link| link.base).collect();
+            let mut compare_ids: Vec<usize> = group_links.iter().map(|link| link.compare).collect();
+            base_ids.sort();
+            base_ids.dedup();
+            compare_ids.sort();
+            compare_ids.dedup();
+
+            let method = [AccountMatchMethod::Confirmed, AccountMatchMethod::Name]
+                .into_iter()
+                .find(|method| group_links.iter().any(|link| &link.method == method))
+                .unwrap_or(AccountMatchMethod::Number);
+            let similarity = group_links.iter().filter_map(|link| link.similarity).reduce(f64::min);
+            let kind = match (base_ids.len(), compare_ids.len()) {
+                (1, 1) => {
+                    let (a, b) = (&base[base_ids[0]], &compare[compare_ids[0]]);
+                    if a.account_number.trim() != b.account_number.trim() {
+                        AccountChangeKind::Renumbered
+                    } else if normalise_name(&a.account_name) != normalise_name(&b.account_name) {
+                        AccountChangeKind::Renamed
+                    } else {
+                        AccountChangeKind::Unchanged
+                    }
+                }
+                (bases, compares) if compares > bases => AccountChangeKind::Split,
+                _ => AccountChangeKind::Merged,
+            };
+
+            comparison(
+                kind,
+                Some(method),
+                base_ids.iter().map(|&b| &base[b]).collect(),
+                compare_ids.iter().map(|&c| &compare[c]).collect(),
+                similarity,
+            )
+        })
+        .collect();
+
+    rows.extend(base.iter().enumerate().filter(|(b, _)| !base_linked[*b]).map(|(_, account)| {
+        comparison(AccountChangeKind::Removed, None, vec![account], Vec::new(), None)
+    }));
+    rows.extend(compare.iter().enumerate().filter(|(c, _)| !compare_linked[*c]).map(|(_, account)| {
+        comparison(AccountChangeKind::New, None, Vec::new(), vec![account], None)
+    }));
+    rows.sort_by(|a, b| sort_key(a).cmp(sort_key(b)));
+
+    let count = |kind: AccountChangeKind| rows.iter().filter(|row| row.kind == kind).count() as u32;
+
+    Ok(TrialBalanceComparison {
+        base_trial_balance_id,
+        compare_trial_balance_id,
+        owner,
+        new_count: count(AccountChangeKind::New),
+        removed_count: count(AccountChangeKind::Removed),
+        renamed_count: count(AccountChangeKind::Renamed),
+        renumbered_count: count(AccountChangeKind::Renumbered),
+        split_count: count(AccountChangeKind::Split),
+        merged_count: count(AccountChangeKind::Merged),
+        rows,
+        generated_at: time(),
+    })
+}
+
+// Save prior-to-current account number mappings for the client or entity
+// behind a trial balance. A prior account mapped to several current ones is
+// a split; several prior accounts mapped to one current one are a merge.
+pub fn confirm_account_mappings(
+    caller: Principal,
+    trial_balance_id: u64,
+    mappings: Vec<AccountMappingInput>,
+) -> Result<Vec<AccountMapping>> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to confirm account mappings".to_string());
+    }
+
+    let trial_balance = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&trial_balance_id))
+        .ok_or("Trial balance not found")?;
+    let owner = chart_of_accounts::owner_for_engagement(trial_balance.engagement_id)
+        .ok_or("The engagement is not linked to a client or entity")?;
+    if mappings.is_empty() {
+        return Err("No mappings given".to_string());
+    }
+
+    let existing: HashSet<(String, String)> = mappings_for(&owner)
+        .into_iter()
+        .map(|mapping| (mapping.prior_account_number, mapping.current_account_number))
+        .collect();
+    let mut saved: Vec<AccountMapping> = Vec::new();
+    let now = time();
+    for input in mappings {
+        let prior = input.prior_account_number.trim().to_string();
+        let current = input.current_account_number.trim().to_string();
+        if prior.is_empty() || current.is_empty() {
+            return Err("Both account numbers are required".to_string());
+        }
+        if prior == current || existing.contains(&(prior.clone(), current.clone())) {
+            continue;
+        }
+        if saved.iter().any(|m| m.prior_account_number == prior && m.current_account_number == current) {
+            continue;
+        }
+        saved.push(AccountMapping {
+            id: next_account_mapping_id(),
+            owner: owner.clone(),
+            prior_account_number: prior,
+            current_account_number: current,
+            confirmed_at: now,
+            confirmed_by: caller,
+        });
+    }
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for mapping in &saved {
+            storage.account_mappings.insert(mapping.id, mapping.clone());
+        }
+    });
+
+    log_activity(
+        caller,
+        "confirm_account_mappings".to_string(),
+        "account_mapping".to_string(),
+        trial_balance_id.to_string(),
+        format!("Confirmed {} account mappings", saved.len()),
+        encode_args((saved.clone(),)).ok(),
+    );
+
+    Ok(saved)
+}
+
+pub fn list_account_mappings(caller: Principal, owner: CoaOwner) -> Result<Vec<AccountMapping>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut mappings = mappings_for(&owner);
+    mappings.sort_by(|a, b| {
+        a.prior_account_number
+            .cmp(&b.prior_account_number)
+            .then(a.current_account_number.cmp(&b.current_account_number))
+    });
+    Ok(mappings)
+}
+
+pub fn delete_account_mapping(caller: Principal, mapping_id: u64) -> Result<()> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_edit_engagement(&user) {
+        return Err("Insufficient permissions to delete account mappings".to_string());
+    }
+
+    let mapping = STORAGE
+        .with(|storage| storage.borrow_mut().account_mappings.remove(&mapping_id))
+        .ok_or("Account mapping not found")?;
+
+    log_activity(
+        caller,
+        "delete_account_mapping".to_string(),
+        "account_mapping".to_string(),
+        mapping.id.to_string(),
+        format!(
+            "Deleted mapping of account {} to {}",
+            mapping.prior_account_number, mapping.current_account_number
+        ),
+        encode_args((mapping,)).ok(),
+    );
+
+    Ok(())
+}
+
+// Prior account numbers mapped to exactly one current number. Splits are left
+// out since a single prior balance cannot be divided between the new accounts.
+pub fn renumbering(owner: &CoaOwner) -> HashMap<String, String> {
+    let mut targets: HashMap<String, Vec<String>> = HashMap::new();
+    for mapping in mappings_for(owner) {
+        targets
+            .entry(mapping.prior_account_number)
+            .or_default()
+            .push(mapping.current_account_number);
+    }
+    targets
+        .into_iter()
+        .filter(|(_, current)| current.len() == 1)
+        .map(|(prior, mut current)| (prior, current.remove(0)))
+        .collect()
+}
+
+fn mappings_for(owner: &CoaOwner) -> Vec<AccountMapping> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .account_mappings
+            .iter()
+            .map(|(_, mapping)| mapping)
+            .filter(|mapping| &mapping.owner == owner)
+            .collect()
+    })
+}
+
+// Groups of base account indices connected through links
+fn components(links: &[Link], base_count: usize) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..base_count).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    let mut first_base_of: HashMap<usize, usize> = HashMap::new();
+    for link in links {
+        if let Some(&other) = first_base_of.get(&link.compare) {
+            let (a, b) = (find(&mut parent, link.base), find(&mut parent, other));
+            parent[a] = b;
+        } else {
+            first_base_of.insert(link.compare, link.base);
+        }
+    }
+
+    let linked: HashSet<usize> = links.iter().map(|link| link.base).collect();
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for base in (0..base_count).filter(|b| linked.contains(b)) {
+        let root = find(&mut parent, base);
+        groups.entry(root).or_default().push(base);
+    }
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    groups.sort();
+    groups
+}
+
+fn comparison(
+    kind: AccountChangeKind,
+    match_method: Option<AccountMatchMethod>,
+    base_accounts: Vec<&TrialBalanceAccount>,
+    compare_accounts: Vec<&TrialBalanceAccount>,
+    name_similarity: Option<f64>,
+) -> AccountComparison {
+    let compared = |accounts: Vec<&TrialBalanceAccount>| -> Vec<ComparedAccount> {
+        accounts
+            .into_iter()
+            .map(|account| ComparedAccount {
+                account_number: account.account_number.clone(),
+                account_name: account.account_name.clone(),
+                balance: account.debit_balance - account.credit_balance,
+            })
+            .collect()
+    };
+    let base_accounts = compared(base_accounts);
+    let compare_accounts = compared(compare_accounts);
+    let base_balance: i64 = base_accounts.iter().map(|account| account.balance).sum();
+    let compare_balance: i64 = compare_accounts.iter().map(|account| account.balance).sum();
+    let change = compare_balance - base_balance;
+
+    AccountComparison {
+        kind,
+        match_method,
+        base_accounts,
+        compare_accounts,
+        base_balance,
+        compare_balance,
+        change,
+        change_percent: (base_balance != 0).then(|| change as f64 / base_balance.abs() as f64 * 100.0),
+        name_similarity,
+    }
+}
+
+fn sort_key(row: &AccountComparison) -> &str {
+    row.compare_accounts
+        .first()
+        .or(row.base_accounts.first())
+        .map(|account| account.account_number.as_str())
+        .unwrap_or_default()
+}
+
+fn normalise_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Dice coefficient over the character pairs of each word, so reordered or
+// slightly reworded names ("Trade receivables", "Receivables - trade") match
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let bigrams = |name: &str| -> Vec<(char, char)> {
+        normalise_name(name)
+            .split(' ')
+            .flat_map(|word| {
+                let chars: Vec<char> = word.chars().collect();
+                chars.windows(2).map(|pair| (pair[0], pair[1])).collect::<Vec<_>>()
+            })
+            .collect()
+    };
+    let a = bigrams(a);
+    let mut b = bigrams(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let total = a.len() + b.len();
+    let mut shared = 0;
+    for pair in &a {
+        if let Some(pos) = b.iter().position(|other| other == pair) {
+            b.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    2.0 * shared as f64 / total as f64
+}
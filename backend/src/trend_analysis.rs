@@ -0,0 +1,275 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use std::collections::BTreeMap;
+
+use crate::auth;
+use crate::chart_of_accounts;
+use crate::dates;
+use crate::engagements;
+use crate::financial_statements;
+use crate::storage::STORAGE;
+use crate::tb_comparison;
+use crate::types::{
+    MultiPeriodTrend, MultiPeriodTrendRequest, Result, SignificanceThresholds, TrendChange,
+    TrendPeriod, TrendRow, TrialBalance, TrialBalanceAccount, XBRLTaxonomy,
+};
+
+pub const MAX_YEARS: usize = 5;
+const DEFAULT_SIGNIFICANT_PERCENT: f64 = 10.0;
+
+// Trend for a trial balance over its engagement's prior-year chain
+pub fn get_multi_period_trend(caller: Principal, req: MultiPeriodTrendRequest) -> Result<MultiPeriodTrend> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    build_multi_period_trend(req.trial_balance_id, req.years, req.thresholds)
+}
+
+// Per-account and per-FS-line amounts for up to five years, current first
+pub fn build_multi_period_trend(
+    trial_balance_id: u64,
+    years: Option<u32>,
+    thresholds: Option<SignificanceThresholds>,
+) -> Result<MultiPeriodTrend> {
+    let trial_balance = STORAGE
+        .with(|storage| storage.borrow().trial_balances.get(&trial_balance_id))
+        .ok_or_else(|| "Trial balance not found".to_string())?;
+    let engagement = STORAGE
+        .with(|storage| storage.borrow().engagements.get(&trial_balance.engagement_id))
+        .ok_or_else(|| "Engagement not found".to_string())?;
+
+    let years = years.map(|y| y as usize).unwrap_or(MAX_YEARS).clamp(1, MAX_YEARS);
+    let thresholds = match thresholds {
+        Some(t) if t.amount.is_some() || t.percent.is_some() => t,
+        _ => SignificanceThresholds {
+            amount: None,
+            percent: Some(DEFAULT_SIGNIFICANT_PERCENT),
+        },
+    };
+
+    let current = (
+        TrendPeriod {
+            engagement_id: engagement.id,
+            engagement_name: engagement.name.clone(),
+            trial_balance_id: trial_balance.id,
+            period_end_date: trial_balance.period_end_date.clone(),
+        },
+        accounts_for(trial_balance.id),
+    );
+    let (prior, gaps) = prior_periods(&trial_balance, years - 1);
+    let periods: Vec<(TrendPeriod, Vec<TrialBalanceAccount>)> =
+        std::iter::once(current).chain(prior).collect();
+    let period_count = periods.len();
+
+    // Accounts matched across years by account number, following confirmed
+    // renumberings so a prior year's balance lands on today's account
+    let renumbering = chart_of_accounts::owner_for_engagement(engagement.id)
+        .map(|owner| tb_comparison::renumbering(&owner))
+        .unwrap_or_default();
+    let current_number = |number: &str| {
+        let mut number = number.trim().to_string();
+        for _ in 0..MAX_YEARS {
+            match renumbering.get(&number) {
+                Some(next) => number = next.clone(),
+                None => break,
+            }
+        }
+        number
+    };
+    let mut account_amounts: BTreeMap<String, (String, Vec<Option<i64>>)> = BTreeMap::new();
+    for (idx, (_, accounts)) in periods.iter().enumerate() {
+        for account in accounts {
+            let number = if idx == 0 {
+                account.account_number.clone()
+            } else {
+                current_number(&account.account_number)
+            };
+            let (_, amounts) = account_amounts
+                .entry(number)
+                .or_insert_with(|| (account.account_name.clone(), vec![None; period_count]));
+            *amounts[idx].get_or_insert(0) += financial_statements::net_balance(account);
+        }
+    }
+    let accounts = account_amounts
+        .into_iter()
+        .map(|(number, (name, amounts))| trend_row(number, name, amounts, &thresholds))
+        .collect();
+
+    let line_totals: Vec<_> = periods
+        .iter()
+        .map(|(_, accounts)| financial_statements::fs_line_totals(accounts))
+        .collect();
+    let mut fs_lines: Vec<TrendRow> = fs_line_order()
+        .into_iter()
+        .filter_map(|(code, name)| {
+            let amounts: Vec<Option<i64>> =
+                line_totals.iter().map(|totals| totals.get(&code).copied()).collect();
+            amounts
+                .iter()
+                .any(|a| a.unwrap_or(0) != 0)
+                .then(|| trend_row(code, name, amounts, &thresholds))
+        })
+        .collect();
+    // Codes outside the standard line items still get a row
+    let mut other_codes: Vec<&String> = line_totals
+        .iter()
+        .flat_map(|totals| totals.keys())
+        .filter(|code| !fs_lines.iter().any(|row| row.key == **code))
+        .collect();
+    other_codes.sort();
+    other_codes.dedup();
+    for code in other_codes {
+        let amounts: Vec<Option<i64>> =
+            line_totals.iter().map(|totals| totals.get(code).copied()).collect();
+        if amounts.iter().any(|a| a.unwrap_or(0) != 0) {
+            fs_lines.push(trend_row(code.clone(), code.clone(), amounts, &thresholds));
+        }
+    }
+
+    Ok(MultiPeriodTrend {
+        engagement_id: engagement.id,
+        trial_balance_id: trial_balance.id,
+        periods: periods.into_iter().map(|(period, _)| period).collect(),
+        accounts,
+        fs_lines,
+        thresholds,
+        gaps,
+        generated_at: time(),
+    })
+}
+
+// One trial balance per prior-year engagement, most recent first. Engagements
+// without a trial balance are reported as gaps and do not count as a year.
+pub fn prior_periods(
+    trial_balance: &TrialBalance,
+    limit: usize,
+) -> (Vec<(TrendPeriod, Vec<TrialBalanceAccount>)>, Vec<String>) {
+    let mut periods = Vec::new();
+    let mut gaps = Vec::new();
+
+    for engagement in engagements::prior_year_chain(trial_balance.engagement_id, MAX_YEARS * 2) {
+        if periods.len() >= limit {
+            break;
+        }
+        match latest_trial_balance(engagement.id) {
+            Some(prior) => periods.push((
+                TrendPeriod {
+                    engagement_id: engagement.id,
+                    engagement_name: engagement.name.clone(),
+                    trial_balance_id: prior.id,
+                    period_end_date: prior.period_end_date.clone(),
+                },
+                accounts_for(prior.id),
+            )),
+            None => gaps.push(format!(
+                "Engagement {} ({}) has no trial balance",
+                engagement.id, engagement.name
+            )),
+        }
+    }
+
+    (periods, gaps)
+}
+
+// The engagement's year-end trial balance: latest period end, adjusted first
+fn latest_trial_balance(engagement_id: u64) -> Option<TrialBalance> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .trial_balances
+            .iter()
+            .filter(|(_, tb)| tb.engagement_id == engagement_id)
+            .map(|(_, tb)| tb)
+            .max_by_key(|tb| {
+                (
+                    dates::parse_date(&tb.period_end_date).unwrap_or(i64::MIN),
+                    tb.is_adjusted,
+                    tb.created_at,
+                )
+            })
+    })
+}
+
+pub fn accounts_for(trial_balance_id: u64) -> Vec<TrialBalanceAccount> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .trial_balance_accounts
+            .iter()
+            .filter(|(_, account)| account.trial_balance_id == trial_balance_id)
+            .map(|(_, account)| account)
+            .collect()
+    })
+}
+
+// Standard FS line codes and names in presentation order
+pub fn fs_line_order() -> Vec<(String, String)> {
+    let mut items = financial_statements::get_line_items_for_taxonomy(&XBRLTaxonomy::Custom(String::new()));
+    items.sort_by_key(|item| item.order);
+    items.into_iter().map(|item| (item.code, item.name)).collect()
+}
+
+fn trend_row(
+    key: String,
+    name: String,
+    amounts: Vec<Option<i64>>,
+    thresholds: &SignificanceThresholds,
+) -> TrendRow {
+    let changes: Vec<TrendChange> = amounts
+        .windows(2)
+        .map(|pair| trend_change(pair[0], pair[1]))
+        .collect();
+    let significance_reason = changes.first().and_then(|change| significance(change, thresholds));
+
+    TrendRow {
+        key,
+        name,
+        amounts,
+        changes,
+        is_significant: significance_reason.is_some(),
+        significance_reason,
+    }
+}
+
+fn trend_change(current: Option<i64>, prior: Option<i64>) -> TrendChange {
+    if current.is_none() && prior.is_none() {
+        return TrendChange {
+            change: None,
+            change_percent: None,
+        };
+    }
+
+    let prior = prior.unwrap_or(0);
+    let change = current.unwrap_or(0) - prior;
+
+    TrendChange {
+        change: Some(change),
+        change_percent: (prior != 0).then(|| change as f64 / prior.abs() as f64 * 100.0),
+    }
+}
+
+// Every configured threshold must be met; a balance that is new this year
+// meets the percentage threshold by definition
+fn significance(change: &TrendChange, thresholds: &SignificanceThresholds) -> Option<String> {
+    let amount = change.change.filter(|c| *c != 0)?;
+
+    let meets_amount = thresholds.amount.is_none_or(|limit| amount.abs() >= limit);
+    let meets_percent = match (thresholds.percent, change.change_percent) {
+        (Some(limit), Some(percent)) => percent.abs() >= limit,
+        _ => true,
+    };
+    if !(meets_amount && meets_percent) {
+        return None;
+    }
+
+    Some(match change.change_percent {
+        Some(percent) => format!(
+            "Changed by {:.2} ({:.1}%) against the prior year",
+            amount as f64 / 100.0,
+            percent
+        ),
+        None => format!(
+            "Changed by {:.2} from a nil prior-year balance",
+            amount as f64 / 100.0
+        ),
+    })
+}
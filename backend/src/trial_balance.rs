@@ -1,11 +1,25 @@
 use candid::{encode_args, CandidType, Principal};
 use ic_cdk::api::time;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::activity_log::log_activity;
+use crate::adjustments;
 use crate::auth;
+use crate::chart_of_accounts;
+use crate::data_import;
+use crate::dates;
+use crate::fx;
 use crate::storage::{next_trial_balance_id, next_account_id, STORAGE};
-use crate::types::{Result, TrialBalance, TrialBalanceAccount, AccountType, CreateTrialBalanceRequest, UpdateAccountRequest};
+use crate::types::{
+    AdjustedAccountRow, AdjustedTrialBalance, AjeColumn, AjeEntryType, AjeStatus, ColumnMapping, Result, TrialBalance,
+    TrialBalanceAccount, AccountType, CreateTrialBalanceRequest, PeriodType, RollforwardRequest,
+    UpdateAccountRequest,
+};
+use crate::working_papers;
+
+const RETAINED_EARNINGS_LINE: &str = "BS_RETAINED";
+const RETAINED_EARNINGS_NUMBER: &str = "3999";
 
 pub fn create_trial_balance(caller: Principal, req: CreateTrialBalanceRequest) -> Result<TrialBalance> {
     let user = auth::get_user(caller).ok_or("User not found")?;
@@ -18,12 +32,33 @@ pub fn create_trial_balance(caller: Principal, req: CreateTrialBalanceRequest) -
         .with(|storage| storage.borrow().engagements.get(&req.engagement_id))
         .ok_or("Engagement not found")?;
 
+    if let Some(prior_id) = req.prior_trial_balance_id {
+        let prior = STORAGE
+            .with(|storage| storage.borrow().trial_balances.get(&prior_id))
+            .ok_or("Prior trial balance not found")?;
+        let prior_end = dates::parse_date(&prior.period_end_date);
+        let period_end = dates::parse_date(&req.period_end_date);
+        if let (Some(prior_end), Some(period_end)) = (prior_end, period_end) {
+            if period_end <= prior_end {
+                return Err("Period end must be after the prior trial balance's period end".to_string());
+            }
+        }
+        if let Some(next) = next_in_chain(prior_id) {
+            return Err(format!("Trial balance {} already follows trial balance {}", next.id, prior_id));
+        }
+    }
+
     let trial_balance = TrialBalance {
         id: next_trial_balance_id(),
         engagement_id: req.engagement_id,
         period_end_date: req.period_end_date,
+        period_type: Some(req.period_type.unwrap_or(PeriodType::YearEnd)),
+        prior_trial_balance_id: req.prior_trial_balance_id,
         description: req.description,
-        currency: req.currency.unwrap_or_else(|| "USD".to_string()),
+        currency: req
+            .currency
+            .map(|code| fx::normalise_currency(&code))
+            .unwrap_or_else(|| "USD".to_string()),
         is_adjusted: false,
         created_at: time(),
         created_by: caller,
@@ -64,6 +99,9 @@ fn build_trial_balance_account(
         credit_balance: req.credit_balance,
         fs_line_item: req.fs_line_item,
         notes: req.notes.unwrap_or_default(),
+        opening_balance: None,
+        currency: req.currency.map(|code| fx::normalise_currency(&code)),
+        coa_account_id: None,
         is_reconciled: false,
         created_at,
         created_by: caller,
@@ -77,12 +115,13 @@ pub fn add_account(caller: Principal, trial_balance_id: u64, req: UpdateAccountR
         return Err("Insufficient permissions to modify trial balance".to_string());
     }
 
-    let _tb = STORAGE
+    let tb = STORAGE
         .with(|storage| storage.borrow().trial_balances.get(&trial_balance_id))
         .ok_or("Trial balance not found")?;
 
     let created_at = time();
-    let account = build_trial_balance_account(trial_balance_id, req, caller, created_at);
+    let mut account = build_trial_balance_account(trial_balance_id, req, caller, created_at);
+    chart_of_accounts::apply_chart_of_accounts(tb.engagement_id, std::slice::from_mut(&mut account));
 
     STORAGE.with(|storage| {
         storage.borrow_mut().trial_balance_accounts.insert(account.id, account.clone());
@@ -174,6 +213,352 @@ pub fn validate_trial_balance(caller: Principal, trial_balance_id: u64) -> Resul
     })
 }
 
+// Unadjusted, per-AJE and adjusted balances for every account. Posting
+// updates the stored balances, so the unadjusted column backs posted AJEs out.
+// With include_proposed, unposted AJEs that were not rejected or passed are
+// shown as a what-if on top of the adjusted balance.
+pub fn get_adjusted_trial_balance(
+    caller: Principal,
+    trial_balance_id: u64,
+    include_proposed: bool,
+) -> Result<AdjustedTrialBalance> {
+    let trial_balance = get_trial_balance(caller, trial_balance_id)?;
+    let mut accounts = get_accounts(caller, trial_balance_id)?;
+    accounts.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+
+    let (mut ajes, line_items) = STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let ajes: Vec<_> = storage
+            .adjusting_entries
+            .iter()
+            .map(|(_, aje)| aje)
+            .filter(|aje| aje.trial_balance_id == trial_balance_id)
+            .filter(|aje| match aje.status {
+                AjeStatus::Posted => true,
+                AjeStatus::Rejected | AjeStatus::Passed => false,
+                _ => include_proposed,
+            })
+            .collect();
+        let line_items: Vec<_> = storage
+            .aje_line_items
+            .iter()
+            .map(|(_, item)| item)
+            .filter(|item| ajes.iter().any(|aje| aje.id == item.aje_id))
+            .collect();
+        (ajes, line_items)
+    });
+    ajes.sort_by_key(|aje| aje.id);
+
+    let columns: Vec<AjeColumn> = ajes
+        .iter()
+        .map(|aje| AjeColumn {
+            aje_id: aje.id,
+            aje_number: aje.aje_number.clone(),
+            description: aje.description.clone(),
+            entry_type: adjustments::entry_type_of(aje),
+            status: aje.status.clone(),
+            is_posted: matches!(aje.status, AjeStatus::Posted),
+        })
+        .collect();
+
+    let rows: Vec<AdjustedAccountRow> = accounts
+        .iter()
+        .map(|account| {
+            let adjustments: Vec<i64> = columns
+                .iter()
+                .map(|column| {
+                    line_items
+                        .iter()
+                        .filter(|item| item.aje_id == column.aje_id && item.account_id == account.id)
+                        .map(|item| item.debit_amount - item.credit_amount)
+                        .sum()
+                })
+                .collect();
+            let sum_where = |include: &dyn Fn(&AjeColumn) -> bool| -> i64 {
+                columns
+                    .iter()
+                    .zip(&adjustments)
+                    .filter(|(column, _)| include(column))
+                    .map(|(_, amount)| amount)
+                    .sum()
+            };
+            let posted_of = |entry_type: AjeEntryType| {
+                sum_where(&|column: &AjeColumn| column.is_posted && column.entry_type == entry_type)
+            };
+            let posted_adjustments = sum_where(&|column: &AjeColumn| column.is_posted);
+            let proposed_adjustments = sum_where(&|column: &AjeColumn| !column.is_posted);
+            let adjusting_adjustments = posted_of(AjeEntryType::Adjusting);
+            let reclassification_adjustments = posted_of(AjeEntryType::Reclassification);
+            let reversing_adjustments = posted_of(AjeEntryType::AutoReversing);
+            let adjusted_balance = account.debit_balance - account.credit_balance;
+
+            AdjustedAccountRow {
+                account_id: account.id,
+                account_number: account.account_number.clone(),
+                account_name: account.account_name.clone(),
+                account_type: account.account_type.clone(),
+                fs_line_item: account.fs_line_item.clone(),
+                unadjusted_balance: adjusted_balance - posted_adjustments,
+                adjustments,
+                posted_adjustments,
+                adjusting_adjustments,
+                reclassification_adjustments,
+                reversing_adjustments,
+                proposed_adjustments,
+                adjusted_balance,
+                pro_forma_balance: include_proposed.then_some(adjusted_balance + proposed_adjustments),
+            }
+        })
+        .collect();
+
+    let unadjusted_total = rows.iter().map(|row| row.unadjusted_balance).sum();
+    let posted_total = rows.iter().map(|row| row.posted_adjustments).sum();
+    let adjusting_total = rows.iter().map(|row| row.adjusting_adjustments).sum();
+    let reclassification_total = rows.iter().map(|row| row.reclassification_adjustments).sum();
+    let reversing_total = rows.iter().map(|row| row.reversing_adjustments).sum();
+    let proposed_total = rows.iter().map(|row| row.proposed_adjustments).sum();
+    let adjusted_total: i64 = rows.iter().map(|row| row.adjusted_balance).sum();
+
+    Ok(AdjustedTrialBalance {
+        trial_balance_id,
+        engagement_id: trial_balance.engagement_id,
+        include_proposed,
+        columns,
+        accounts: rows,
+        unadjusted_total,
+        posted_total,
+        adjusting_total,
+        reclassification_total,
+        reversing_total,
+        proposed_total,
+        adjusted_total,
+        is_balanced: adjusted_total == 0,
+        generated_at: time(),
+    })
+}
+
+// The following period: the trial balance chained after this one, else the
+// next period end in the same engagement, else the earliest trial balance of
+// an engagement that has this one as its prior year
+pub fn next_period_trial_balance(trial_balance: &TrialBalance) -> Option<TrialBalance> {
+    if let Some(next) = next_in_chain(trial_balance.id) {
+        return Some(next);
+    }
+
+    let period_end = |tb: &TrialBalance| dates::parse_date(&tb.period_end_date).unwrap_or(i64::MIN);
+    let current_end = period_end(trial_balance);
+
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let later_in_engagement = storage
+            .trial_balances
+            .iter()
+            .map(|(_, tb)| tb)
+            .filter(|tb| tb.engagement_id == trial_balance.engagement_id && period_end(tb) > current_end)
+            .min_by_key(|tb| (period_end(tb), tb.id));
+        later_in_engagement.or_else(|| {
+            storage
+                .trial_balances
+                .iter()
+                .map(|(_, tb)| tb)
+                .filter(|tb| {
+                    storage
+                        .engagements
+                        .get(&tb.engagement_id)
+                        .is_some_and(|e| e.prior_year_engagement_id == Some(trial_balance.engagement_id))
+                })
+                .min_by_key(|tb| (period_end(tb), tb.id))
+        })
+    })
+}
+
+fn next_in_chain(trial_balance_id: u64) -> Option<TrialBalance> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .trial_balances
+            .iter()
+            .map(|(_, tb)| tb)
+            .find(|tb| tb.prior_trial_balance_id == Some(trial_balance_id))
+    })
+}
+
+// Trial balances linked to this one through the period chain, earliest first
+pub fn get_period_chain(caller: Principal, trial_balance_id: u64) -> Result<Vec<TrialBalance>> {
+    let trial_balance = get_trial_balance(caller, trial_balance_id)?;
+
+    let mut earlier = Vec::new();
+    let mut prior_id = trial_balance.prior_trial_balance_id;
+    while let Some(id) = prior_id {
+        let Some(prior) = STORAGE.with(|storage| storage.borrow().trial_balances.get(&id)) else {
+            break;
+        };
+        if earlier.iter().any(|tb: &TrialBalance| tb.id == prior.id) {
+            break;
+        }
+        prior_id = prior.prior_trial_balance_id;
+        earlier.push(prior);
+    }
+    earlier.reverse();
+
+    let mut chain = earlier;
+    let mut current = Some(trial_balance);
+    while let Some(tb) = current {
+        if chain.iter().any(|existing| existing.id == tb.id) {
+            break;
+        }
+        current = next_in_chain(tb.id);
+        chain.push(tb);
+    }
+
+    Ok(chain)
+}
+
+// Open the next period from a trial balance's adjusted closing balances.
+// Revenue and expense accounts are closed into retained earnings and carried
+// at nil so their mappings and notes survive.
+pub fn rollforward_trial_balance(caller: Principal, req: RollforwardRequest) -> Result<TrialBalance> {
+    let source = get_trial_balance(caller, req.trial_balance_id)?;
+    let accounts = get_accounts(caller, source.id)?;
+
+    let new_tb = create_trial_balance(
+        caller,
+        CreateTrialBalanceRequest {
+            engagement_id: req.engagement_id.unwrap_or(source.engagement_id),
+            period_end_date: req.period_end_date.clone(),
+            description: req.description.unwrap_or_else(|| {
+                format!("Rolled forward from {} to {}", source.period_end_date, req.period_end_date)
+            }),
+            currency: Some(source.currency.clone()),
+            period_type: req.period_type,
+            prior_trial_balance_id: Some(source.id),
+        },
+    )?;
+
+    let is_income_statement = |account: &TrialBalanceAccount| {
+        matches!(account.account_type, AccountType::Revenue | AccountType::Expense)
+    };
+    let profit_close: i64 = accounts
+        .iter()
+        .filter(|account| is_income_statement(account))
+        .map(|account| account.debit_balance - account.credit_balance)
+        .sum();
+    let retained_earnings_id = retained_earnings_account(&accounts).map(|account| account.id);
+
+    let timestamp = time();
+    let mut new_accounts: Vec<TrialBalanceAccount> = accounts
+        .iter()
+        .map(|account| {
+            let mut opening = if is_income_statement(account) {
+                0
+            } else {
+                account.debit_balance - account.credit_balance
+            };
+            if Some(account.id) == retained_earnings_id {
+                opening += profit_close;
+            }
+            rolled_account(new_tb.id, account, opening, caller, timestamp)
+        })
+        .collect();
+    if retained_earnings_id.is_none() && profit_close != 0 {
+        new_accounts.push(TrialBalanceAccount {
+            id: next_account_id(),
+            trial_balance_id: new_tb.id,
+            account_number: RETAINED_EARNINGS_NUMBER.to_string(),
+            account_name: "Retained earnings".to_string(),
+            account_type: AccountType::Equity,
+            debit_balance: profit_close.max(0),
+            credit_balance: (-profit_close).max(0),
+            fs_line_item: Some(RETAINED_EARNINGS_LINE.to_string()),
+            notes: "Created on rollforward to receive the closed income statement".to_string(),
+            opening_balance: Some(profit_close),
+            currency: None,
+            coa_account_id: None,
+            is_reconciled: false,
+            created_at: timestamp,
+            created_by: caller,
+        });
+    }
+
+    STORAGE.with(|storage| {
+        let mut storage = storage.borrow_mut();
+        for account in &new_accounts {
+            storage.trial_balance_accounts.insert(account.id, account.clone());
+        }
+    });
+
+    let snapshot = encode_args((new_tb.clone(), new_accounts.clone())).ok();
+    log_activity(
+        caller,
+        "rollforward_trial_balance".to_string(),
+        "trial_balance".to_string(),
+        new_tb.id.to_string(),
+        format!(
+            "Rolled trial balance {} forward to {} with {} accounts; {:.2} closed to retained earnings",
+            source.id,
+            new_tb.period_end_date,
+            new_accounts.len(),
+            -profit_close as f64 / 100.0
+        ),
+        snapshot,
+    );
+
+    adjustments::release_pending_reversals(caller, &new_tb);
+
+    Ok(new_tb)
+}
+
+// The account mapped to retained earnings, else an equity account named so
+fn retained_earnings_account(accounts: &[TrialBalanceAccount]) -> Option<&TrialBalanceAccount> {
+    accounts
+        .iter()
+        .find(|account| account.fs_line_item.as_deref() == Some(RETAINED_EARNINGS_LINE))
+        .or_else(|| {
+            accounts.iter().find(|account| {
+                matches!(account.account_type, AccountType::Equity)
+                    && account.account_name.to_lowercase().contains("retained")
+            })
+        })
+}
+
+fn rolled_account(
+    trial_balance_id: u64,
+    account: &TrialBalanceAccount,
+    opening: i64,
+    caller: Principal,
+    created_at: u64,
+) -> TrialBalanceAccount {
+    TrialBalanceAccount {
+        id: next_account_id(),
+        trial_balance_id,
+        account_number: account.account_number.clone(),
+        account_name: account.account_name.clone(),
+        account_type: account.account_type.clone(),
+        debit_balance: opening.max(0),
+        credit_balance: (-opening).max(0),
+        fs_line_item: account.fs_line_item.clone(),
+        notes: account.notes.clone(),
+        opening_balance: Some(opening),
+        currency: account.currency.clone(),
+        coa_account_id: account.coa_account_id,
+        is_reconciled: false,
+        created_at,
+        created_by: caller,
+    }
+}
+
+pub fn accounts_by_number(trial_balance_id: u64) -> HashMap<String, TrialBalanceAccount> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .trial_balance_accounts
+            .iter()
+            .filter(|(_, account)| account.trial_balance_id == trial_balance_id)
+            .map(|(_, account)| (account.account_number.clone(), account))
+            .collect()
+    })
+}
+
 pub fn map_to_fs_line(caller: Principal, account_id: u64, fs_line_item: String) -> Result<TrialBalanceAccount> {
     let user = auth::get_user(caller).ok_or("User not found")?;
 
@@ -186,6 +571,9 @@ pub fn map_to_fs_line(caller: Principal, account_id: u64, fs_line_item: String)
         .ok_or("Account not found")?;
 
     account.fs_line_item = Some(fs_line_item.clone());
+    if let Some(tb) = STORAGE.with(|storage| storage.borrow().trial_balances.get(&account.trial_balance_id)) {
+        chart_of_accounts::carry_mapping(caller, tb.engagement_id, &mut account);
+    }
 
     STORAGE.with(|storage| {
         storage.borrow_mut().trial_balance_accounts.insert(account.id, account.clone());
@@ -209,6 +597,7 @@ pub fn import_trial_balance_csv(
     engagement_id: u64,
     period_end_date: String,
     csv_data: Vec<CsvAccountRow>,
+    currency: Option<String>,
 ) -> Result<TrialBalance> {
     let user = auth::get_user(caller).ok_or("User not found")?;
 
@@ -222,7 +611,9 @@ pub fn import_trial_balance_csv(
             engagement_id,
             period_end_date: period_end_date.clone(),
             description: format!("Imported trial balance for period {}", period_end_date),
-            currency: Some("USD".to_string()),
+            currency,
+            period_type: None,
+            prior_trial_balance_id: None,
         },
     )?;
 
@@ -239,10 +630,12 @@ pub fn import_trial_balance_csv(
                 credit_balance: row.credit_balance,
                 fs_line_item: None,
                 notes: None,
+                currency: row.currency,
         };
 
         accounts.push(build_trial_balance_account(tb.id, account_request, caller.clone(), import_timestamp));
     }
+    let matched = chart_of_accounts::apply_chart_of_accounts(engagement_id, &mut accounts);
 
     let accounts_snapshot = accounts.clone();
     STORAGE.with(|storage| {
@@ -260,13 +653,91 @@ pub fn import_trial_balance_csv(
         "import_trial_balance_csv".to_string(),
         "trial_balance".to_string(),
         tb.id.to_string(),
-        format!("Imported trial balance with {} accounts", account_count),
+        format!(
+            "Imported trial balance with {} accounts, {} matched to the chart of accounts",
+            account_count, matched
+        ),
         snapshot,
     );
 
+    adjustments::release_pending_reversals(caller, &tb);
+
     Ok(tb)
 }
 
+// Build a trial balance from an imported dataset using a column mapping,
+// by default the one pre-filled from the client's mapping profile. Without a
+// currency the trial balance takes the dataset's most common account currency.
+pub fn import_trial_balance_from_dataset(
+    caller: Principal,
+    engagement_id: u64,
+    dataset_id: u64,
+    period_end_date: String,
+    column_mapping: Option<ColumnMapping>,
+    currency: Option<String>,
+    file_data: Option<Vec<u8>>,
+) -> Result<TrialBalance> {
+    let dataset = data_import::get_dataset(caller, dataset_id)?;
+
+    let mapping = column_mapping
+        .or_else(|| dataset.suggested_mapping.as_ref().map(|m| m.column_mapping.clone()))
+        .ok_or_else(|| "No column mapping given and no mapping profile matched the dataset".to_string())?;
+
+    // Every account is needed, so a dataset stored truncated needs the original file
+    let sheet = working_papers::source_sheet(&dataset)?;
+    let population = data_import::load_population(caller, &dataset, &sheet.name, file_data.as_deref())?;
+    if !population.complete {
+        return Err(format!(
+            "Dataset {} stores only the first {} of {} rows of sheet '{}'; pass the original file to import the whole trial balance",
+            dataset.id,
+            sheet.data.len(),
+            sheet.row_count,
+            sheet.name
+        ));
+    }
+    let accounts = working_papers::extract_account_rows(sheet, &population.rows, &mapping, &[])?;
+
+    let account_currency = |code: &str| {
+        let code = fx::normalise_currency(code);
+        (!code.is_empty()).then_some(code)
+    };
+    let currency = currency.map(|code| fx::normalise_currency(&code)).or_else(|| {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for code in accounts.iter().filter_map(|a| account_currency(&a.currency)) {
+            *counts.entry(code).or_insert(0) += 1;
+        }
+        counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0))).map(|(code, _)| code)
+    });
+
+    // Closing balance from YTD columns when mapped, else opening plus period movement
+    let use_ytd = mapping.ytd_debit.is_some() || mapping.ytd_credit.is_some();
+    let csv_data: Vec<CsvAccountRow> = accounts
+        .into_iter()
+        .filter(|a| !a.account_number.trim().is_empty())
+        .map(|a| {
+            let net = if use_ytd {
+                a.ytd_debit - a.ytd_credit
+            } else {
+                a.opening_debit - a.opening_credit + a.period_debit - a.period_credit
+            };
+            let cents = (net * 100.0).round() as i64;
+            CsvAccountRow {
+                account_number: a.account_number.trim().to_string(),
+                account_name: a.account_name.trim().to_string(),
+                debit_balance: cents.max(0),
+                credit_balance: (-cents).max(0),
+                currency: account_currency(&a.currency).filter(|code| Some(code) != currency.as_ref()),
+            }
+        })
+        .collect();
+
+    if csv_data.is_empty() {
+        return Err("No accounts found in dataset".to_string());
+    }
+
+    import_trial_balance_csv(caller, engagement_id, period_end_date, csv_data, currency)
+}
+
 fn infer_account_type(account_number: &str, account_name: &str) -> AccountType {
     let number = account_number.parse::<u32>().unwrap_or(0);
     let name_lower = account_name.to_lowercase();
@@ -308,6 +779,7 @@ pub struct CsvAccountRow {
     pub account_name: String,
     pub debit_balance: i64,
     pub credit_balance: i64,
+    pub currency: Option<String>, // Only where it differs from the trial balance's
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -101,10 +101,32 @@ pub struct Engagement {
     pub start_date: u64,
     pub end_date: u64,
     pub status: String,
+    pub prior_year_engagement_id: Option<u64>,
+    pub materiality: Option<Materiality>,
     pub created_at: u64,
     pub created_by: Principal,
 }
 
+// Amounts in cents
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Materiality {
+    pub overall: i64,
+    pub performance: i64,
+    pub clearly_trivial: i64,
+    pub basis: String, // e.g. "5% of profit before tax"
+    pub set_at: u64,
+    pub set_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SetMaterialityRequest {
+    pub engagement_id: u64,
+    pub overall: i64,
+    pub performance: i64,
+    pub clearly_trivial: Option<i64>, // Defaults to 5% of overall
+    pub basis: String,
+}
+
 // Column Data Type
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum ColumnType {
@@ -115,6 +137,26 @@ pub enum ColumnType {
     Currency,
 }
 
+// PII Kinds recognised by the detectors
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PIIKind {
+    PersonName,
+    Email,
+    PhoneNumber,
+    EgyptianNationalId,
+    SaudiNationalId,
+    UaeEmiratesId,
+    Iban,
+}
+
+// Single detector outcome for a column
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PIIFinding {
+    pub kind: PIIKind,
+    pub confidence: f64,   // 0.0 - 1.0
+    pub matched_rows: u64, // Rows in the analysed sample that matched
+}
+
 // PII Detection Result
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct PIIDetection {
@@ -122,6 +164,39 @@ pub struct PIIDetection {
     pub has_emails: bool,
     pub has_phone_numbers: bool,
     pub has_national_ids: bool,
+    // None on datasets stored before these detectors existed
+    pub has_ibans: Option<bool>,
+    pub findings: Option<Vec<PIIFinding>>,
+}
+
+// PII Masking Method
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum MaskingMethod {
+    Redact,
+    Hash, // Salted per engagement (per dataset if unlinked), so equal values still join
+    PartialMask { visible_prefix: u32, visible_suffix: u32 },
+}
+
+// Masking policy for one column
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ColumnMaskingPolicy {
+    pub sheet_name: Option<String>, // None applies to every sheet with the column
+    pub column_name: String,
+    pub method: MaskingMethod,
+}
+
+// Raw values of a masked column, readable by managers and above only
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PIIVaultEntry {
+    pub id: u64,
+    pub dataset_id: u64,
+    pub dataset_version: u32,
+    pub sheet_name: String,
+    pub column_name: String,
+    pub raw_values: Vec<String>,        // Aligned with SheetData.data rows
+    pub raw_sample_values: Vec<String>,
+    pub created_at: u64,
+    pub created_by: Principal,
 }
 
 // Column Metadata
@@ -136,6 +211,7 @@ pub struct ColumnMetadata {
     pub max_value: String,
     pub sample_values: Vec<String>,
     pub pii_detection: PIIDetection,
+    pub masking: Option<MaskingMethod>, // Set once raw values moved to the PII vault
 }
 
 // Sheet Data
@@ -144,6 +220,7 @@ pub struct SheetData {
     pub name: String,
     pub columns: Vec<ColumnMetadata>,
     pub row_count: u64,
+    pub header_row: Option<u32>, // 1-based row the headers were read from; None means row 1
     pub data: Vec<Vec<String>>, // Simplified: row x column string data
 }
 
@@ -156,11 +233,137 @@ pub struct ImportedDataset {
     pub file_name: String,
     pub file_size: u64,
     pub sheets: Vec<SheetData>,
+    pub masking_policies: Option<Vec<ColumnMaskingPolicy>>, // None on datasets stored before masking
+    pub suggested_mapping: Option<MappingProfileMatch>, // Best client profile match at import
+    pub quality_report: Option<DataQualityReport>,
     pub version: u32,
     pub created_at: u64,
     pub created_by: Principal,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RuleSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+// Declarative checks evaluated against one dataset sheet
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum DataQualityCheck {
+    Unique { column: String },
+    NotBlank { column: String },
+    NotBothNonZero { first_column: String, second_column: String }, // e.g. debit and credit
+    ExcludeTotalsRow { label_columns: Vec<String> },
+    DateWithinPeriod { column: String, period_start: String, period_end: String }, // ISO dates
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DataQualityRule {
+    pub name: String,
+    pub check: DataQualityCheck,
+    pub severity: RuleSeverity,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RuleViolation {
+    pub row_number: u64, // 1-based spreadsheet row, as shown in Excel
+    pub column_name: String,
+    pub value: String,
+    pub message: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RuleResult {
+    pub rule_name: String,
+    pub severity: RuleSeverity,
+    pub passed: bool,
+    pub violation_count: u64,
+    pub violations: Vec<RuleViolation>, // Capped; violation_count has the full number
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DataQualityReport {
+    pub dataset_id: u64,
+    pub dataset_version: u32,
+    pub sheet_name: String,
+    pub rows_checked: u64,
+    pub total_rows: u64, // Rows in the source sheet; fewer are checked without the original file
+    pub results: Vec<RuleResult>,
+    pub critical_failures: u32,
+    pub warnings: u32,
+    pub block_on_critical: bool,
+    pub evaluated_at: u64,
+    pub evaluated_by: Principal,
+}
+
+// Superseded copy of a dataset, kept when a corrected file is re-imported
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DatasetVersion {
+    pub id: u64,
+    pub dataset_id: u64,
+    pub version: u32,
+    pub file_name: String,
+    pub file_size: u64,
+    pub sheets: Vec<SheetData>,
+    pub masking_policies: Vec<ColumnMaskingPolicy>,
+    pub created_at: u64,
+    pub created_by: Principal,
+    pub superseded_at: u64,
+    pub superseded_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RowChangeType {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CellChange {
+    pub column_name: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RowChange {
+    pub key: String,
+    pub change_type: RowChangeType,
+    pub old_row_index: Option<u64>,
+    pub new_row_index: Option<u64>,
+    pub cell_changes: Vec<CellChange>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SheetDiff {
+    pub sheet_name: String,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub added_count: u64,
+    pub removed_count: u64,
+    pub changed_count: u64,
+    pub unchanged_count: u64,
+    pub rows: Vec<RowChange>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DatasetDiff {
+    pub dataset_id: u64,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub key_column: String,
+    pub sheets: Vec<SheetDiff>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReimportResult {
+    pub dataset: ImportedDataset,
+    pub diff: DatasetDiff,
+    pub stale_working_paper_ids: Vec<u64>,
+}
+
 // Working Paper Field Mapping
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct ColumnMapping {
@@ -177,6 +380,76 @@ pub struct ColumnMapping {
     pub department: Option<String>,
     pub project: Option<String>,
     pub notes: Option<String>,
+    pub sign_convention: Option<SignConvention>, // Defaults to DebitCreditColumns
+}
+
+// Client ERP systems with known export layouts
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ErpSystem {
+    Sap,
+    Oracle,
+    Odoo,
+    Dynamics,
+    Other(String),
+}
+
+// How balances are laid out in the export
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum SignConvention {
+    DebitCreditColumns,   // Separate debit and credit columns
+    SignedDebitPositive,  // One signed column mapped as debit, debits positive
+    SignedCreditPositive, // One signed column mapped as debit, credits positive
+}
+
+// ColumnMapping fields a profile can hold aliases for
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum MappingField {
+    AccountNumber,
+    AccountName,
+    Currency,
+    OpeningDebit,
+    OpeningCredit,
+    PeriodDebit,
+    PeriodCredit,
+    YtdDebit,
+    YtdCredit,
+    Entity,
+    Department,
+    Project,
+    Notes,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct HeaderAlias {
+    pub field: MappingField,
+    pub aliases: Vec<String>, // Header texts seen in this client's exports
+}
+
+// Saved import mapping for one client's ERP export
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ImportMappingProfile {
+    pub id: u64,
+    pub client_id: u64,
+    pub name: String,
+    pub erp_system: ErpSystem,
+    pub sheet_name: Option<String>, // Applies to every sheet if None
+    pub header_row: u32,            // 1-based row holding the headers
+    pub sign_convention: SignConvention,
+    pub header_aliases: Vec<HeaderAlias>,
+    pub created_at: u64,
+    pub created_by: Principal,
+    pub updated_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MappingProfileMatch {
+    pub profile_id: u64,
+    pub profile_name: String,
+    pub sheet_name: String,
+    pub score: f64, // Share of profile fields found in the headers, 0-1
+    pub matched_fields: u32,
+    pub unmatched_fields: Vec<MappingField>,
+    pub column_mapping: ColumnMapping,
 }
 
 // Account Data
@@ -200,19 +473,70 @@ pub struct AccountData {
 // Leadsheet
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Leadsheet {
-    pub accounts: Vec<AccountData>,
-    pub opening_balance: f64,
-    pub adjustments: f64,
+    // None on leadsheets stored before trial balance leadsheets; read as dataset
+    pub source: Option<LeadsheetSource>,
+    pub accounts: Vec<AccountData>, // Dataset rows
+    pub lines: Option<Vec<LeadsheetLine>>, // Trial balance accounts
+    pub opening_balance: Option<f64>, // Dataset only
+    pub period_activity: Option<f64>, // Dataset only: period debits less credits
+    pub unadjusted_balance: Option<f64>,
+    pub adjustments: f64, // Posted AJEs
     pub closing_balance: f64,
     pub created_at: u64,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum LeadsheetSource {
+    Dataset,
+    TrialBalance,
+}
+
+// Amounts in cents, debit positive
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct LeadsheetLine {
+    pub account_id: u64,
+    pub account_number: String,
+    pub account_name: String,
+    pub fs_line_item: Option<String>,
+    pub unadjusted_balance: i64,
+    pub adjustments: i64,
+    pub adjusted_balance: i64,
+    pub aje_ids: Vec<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct LeadsheetChange {
+    pub account_number: String,
+    pub account_name: String,
+    pub previous_balance: Option<f64>, // None for an account new to the leadsheet
+    pub current_balance: Option<f64>,  // None for an account that dropped off
+    pub change: f64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct LeadsheetRefresh {
+    pub refreshed_at: u64,
+    pub refreshed_by: Principal,
+    pub previous_closing_balance: f64,
+    pub closing_balance: f64,
+    pub changes: Vec<LeadsheetChange>,
+}
+
 // Financial Ratio
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct FinancialRatio {
     pub name: String,
-    pub value: f64,
+    pub value: Option<f64>, // None when an input is missing or a denominator is zero
     pub formula: String,
+    pub inputs: Option<Vec<RatioInput>>, // None on ratios stored before inputs were recorded
+    pub unavailable_reason: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RatioInput {
+    pub name: String,
+    pub value: f64,
+    pub source: String, // FS line code or account type the value was taken from
 }
 
 // Trend Analysis
@@ -240,16 +564,112 @@ pub struct VarianceAnalysis {
 pub struct WorkingPaper {
     pub id: u64,
     pub engagement_id: u64,
-    pub dataset_id: u64,
+    pub dataset_id: Option<u64>, // None when the leadsheet comes from the trial balance
     pub name: String,
+    pub reference_code: Option<String>, // e.g. "B-3.2", unique within the engagement
     pub column_mapping: ColumnMapping,
+    pub selected_accounts: Option<Vec<String>>, // Empty for all accounts
     pub leadsheet: Option<Leadsheet>,
+    pub refresh_history: Option<Vec<LeadsheetRefresh>>, // None on working papers stored before refresh diffs
+    pub cells: Option<Vec<WorkingPaperCell>>, // None on working papers stored before the grid
     pub ratios: Vec<FinancialRatio>,
     pub trend_analysis: Vec<TrendAnalysis>,
     pub variance_analysis: Vec<VarianceAnalysis>,
     pub linked_document_ids: Vec<u64>,
+    pub trial_balance_id: Option<u64>, // Leadsheet source without a dataset; classification for ratios
+    pub multi_period_trend: Option<MultiPeriodTrend>, // Needs a linked trial balance
+    // None on working papers stored before dataset versioning
+    pub dataset_version: Option<u32>,
+    pub is_stale: Option<bool>, // Set when the source dataset gets a new version
+    pub stale_reason: Option<String>,
+    // None on working papers stored before sign-offs; read as draft
+    pub status: Option<WorkingPaperStatus>,
+    pub prepared_by: Option<SignOff>,
+    pub reviewed_by: Option<SignOff>,
+    pub approved_by: Option<SignOff>,
     pub created_at: u64,
     pub created_by: Principal,
+    pub last_modified_at: Option<u64>,
+    pub last_modified_by: Option<Principal>,
+}
+
+// A grid cell; inputs starting with "=" are formulas
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct WorkingPaperCell {
+    pub address: String, // e.g. "B4"
+    pub input: String,
+    pub value: CellValue,
+    pub error: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum CellValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CellInput {
+    pub address: String,
+    pub input: String, // Empty to clear the cell
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SetCellsRequest {
+    pub working_paper_id: u64,
+    pub cells: Vec<CellInput>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum WorkingPaperStatus {
+    Draft,
+    Prepared,
+    Reviewed,
+    Approved,
+    Locked, // Set when the engagement is archived
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum SignOffRole {
+    Preparer,
+    Reviewer,
+    Approver,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SignOff {
+    pub signed_by: Principal,
+    pub signed_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReviewNoteTarget {
+    WorkingPaper,
+    Cell(String),    // Cell reference, e.g. "C12"
+    Account(String), // Account number
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReviewNoteStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReviewNote {
+    pub id: u64,
+    pub working_paper_id: u64,
+    pub engagement_id: u64,
+    pub target: ReviewNoteTarget,
+    pub note: String,
+    pub status: ReviewNoteStatus,
+    pub response: Option<String>,
+    pub raised_by: Principal,
+    pub raised_at: u64,
+    pub resolved_by: Option<Principal>,
+    pub resolved_at: Option<u64>,
 }
 
 // Document
@@ -354,6 +774,7 @@ pub struct CreateEngagementRequest {
     pub start_date: u64,
     pub end_date: u64,
     pub status: String,
+    pub prior_year_engagement_id: Option<u64>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -364,6 +785,7 @@ pub struct UpdateEngagementRequest {
     pub start_date: u64,
     pub end_date: u64,
     pub status: String,
+    pub prior_year_engagement_id: Option<u64>, // None unlinks the prior year
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -372,15 +794,93 @@ pub struct ImportExcelRequest {
     pub engagement_id: Option<u64>,
     pub file_name: String,
     pub file_data: Vec<u8>,
+    pub masking_policies: Option<Vec<ColumnMaskingPolicy>>,
+    pub auto_mask_pii: Option<bool>, // Mask every column the PII detectors flag
+    pub mapping_profile_id: Option<u64>, // Auto-matched against the client's profiles if None
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MaskDatasetColumnsRequest {
+    pub dataset_id: u64,
+    pub policies: Vec<ColumnMaskingPolicy>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReimportDatasetRequest {
+    pub dataset_id: u64,
+    pub file_name: String,
+    pub file_data: Vec<u8>,
+    pub key_column: String,         // e.g. the account number column
+    pub sheet_name: Option<String>, // Diff every sheet with the key column if None
+    pub auto_mask_pii: Option<bool>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RunDataQualityRequest {
+    pub dataset_id: u64,
+    pub sheet_name: Option<String>, // First sheet if None
+    pub rules: Vec<DataQualityRule>,
+    pub block_on_critical: bool, // Stop create_working_paper while critical rules fail
+    pub file_data: Option<Vec<u8>>, // Original file, to check beyond the stored rows
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CreateMappingProfileRequest {
+    pub client_id: u64,
+    pub name: String,
+    pub erp_system: ErpSystem,
+    pub sheet_name: Option<String>,
+    pub header_row: Option<u32>,
+    pub sign_convention: SignConvention,
+    pub header_aliases: Vec<HeaderAlias>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateMappingProfileRequest {
+    pub id: u64,
+    pub name: String,
+    pub erp_system: ErpSystem,
+    pub sheet_name: Option<String>,
+    pub header_row: Option<u32>,
+    pub sign_convention: SignConvention,
+    pub header_aliases: Vec<HeaderAlias>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct CreateWorkingPaperRequest {
     pub engagement_id: u64,
-    pub dataset_id: u64,
+    pub dataset_id: Option<u64>,
     pub name: String,
+    pub reference_code: Option<String>,
     pub column_mapping: ColumnMapping,
     pub selected_accounts: Vec<String>, // Account numbers to include
+    pub trial_balance_id: Option<u64>,
+    pub trend_thresholds: Option<SignificanceThresholds>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateWorkingPaperRequest {
+    pub id: u64,
+    pub dataset_id: Option<u64>,
+    pub name: String,
+    pub reference_code: Option<String>,
+    pub column_mapping: ColumnMapping,
+    pub selected_accounts: Vec<String>,
+    pub trial_balance_id: Option<u64>,
+    pub trend_thresholds: Option<SignificanceThresholds>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AddReviewNoteRequest {
+    pub working_paper_id: u64,
+    pub target: ReviewNoteTarget,
+    pub note: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ResolveReviewNoteRequest {
+    pub note_id: u64,
+    pub response: String,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -428,6 +928,8 @@ pub struct TrialBalance {
     pub id: u64,
     pub engagement_id: u64,
     pub period_end_date: String,
+    pub period_type: Option<PeriodType>, // None on trial balances stored before period types; read as year end
+    pub prior_trial_balance_id: Option<u64>, // Previous period in the chain
     pub description: String,
     pub currency: String,
     pub is_adjusted: bool,
@@ -437,7 +939,14 @@ pub struct TrialBalance {
     pub last_modified_by: Principal,
 }
 
-#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PeriodType {
+    PriorYear,
+    Interim,
+    YearEnd,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum AccountType {
     Asset,
     Liability,
@@ -457,6 +966,9 @@ pub struct TrialBalanceAccount {
     pub credit_balance: i64,
     pub fs_line_item: Option<String>,
     pub notes: String,
+    pub opening_balance: Option<i64>, // Debit positive; set when rolled forward
+    pub currency: Option<String>,     // Defaults to the trial balance's currency
+    pub coa_account_id: Option<u64>,  // Chart of accounts entry it was matched to
     pub is_reconciled: bool,
     pub created_at: u64,
     pub created_by: Principal,
@@ -468,6 +980,17 @@ pub struct CreateTrialBalanceRequest {
     pub period_end_date: String,
     pub description: String,
     pub currency: Option<String>,
+    pub period_type: Option<PeriodType>, // Defaults to YearEnd
+    pub prior_trial_balance_id: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RollforwardRequest {
+    pub trial_balance_id: u64,
+    pub engagement_id: Option<u64>, // Defaults to the source trial balance's engagement
+    pub period_end_date: String,
+    pub period_type: Option<PeriodType>,
+    pub description: Option<String>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -479,6 +1002,381 @@ pub struct UpdateAccountRequest {
     pub credit_balance: i64,
     pub fs_line_item: Option<String>,
     pub notes: Option<String>,
+    pub currency: Option<String>,
+}
+
+// Chart of Accounts Types
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum CoaOwner {
+    Client(u64),
+    Entity(u64),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum NormalBalance {
+    Debit,
+    Credit,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CoaAccount {
+    pub id: u64,
+    pub owner: CoaOwner,
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub normal_balance: NormalBalance,
+    pub fs_line_item: Option<String>, // Default mapping for imported accounts
+    pub is_active: bool,
+    pub created_at: u64,
+    pub created_by: Principal,
+    pub updated_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CoaAccountInput {
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub normal_balance: Option<NormalBalance>, // Defaults from the account type
+    pub fs_line_item: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CoaMappingReport {
+    pub trial_balance_id: u64,
+    pub owner: Option<CoaOwner>, // None when the engagement has no client or entity
+    pub matched_accounts: u32,
+    pub new_accounts: Vec<TrialBalanceAccount>,       // Not in the chart of accounts
+    pub unmapped_accounts: Vec<TrialBalanceAccount>,  // No FS line
+    pub type_mismatches: Vec<TrialBalanceAccount>,    // Type differs from the chart
+    pub abnormal_balances: Vec<TrialBalanceAccount>,  // Balance against the normal side
+    pub missing_accounts: Vec<CoaAccount>,            // Active in the chart, absent from the trial balance
+}
+
+// Trial Balance Comparison Types
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountMapping {
+    pub id: u64,
+    pub owner: CoaOwner,
+    pub prior_account_number: String,
+    pub current_account_number: String,
+    pub confirmed_at: u64,
+    pub confirmed_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountMappingInput {
+    pub prior_account_number: String,
+    pub current_account_number: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum AccountMatchMethod {
+    Number,
+    Name,      // Suggested from similar names; confirm to keep
+    Confirmed, // From the saved mapping table
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum AccountChangeKind {
+    Unchanged,
+    Renamed,
+    Renumbered,
+    Split,
+    Merged,
+    New,
+    Removed,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ComparedAccount {
+    pub account_number: String,
+    pub account_name: String,
+    pub balance: i64, // Debit positive
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountComparison {
+    pub kind: AccountChangeKind,
+    pub match_method: Option<AccountMatchMethod>,
+    pub base_accounts: Vec<ComparedAccount>,
+    pub compare_accounts: Vec<ComparedAccount>,
+    pub base_balance: i64,
+    pub compare_balance: i64,
+    pub change: i64,
+    pub change_percent: Option<f64>, // None when the base balance is nil
+    pub name_similarity: Option<f64>, // 0-1, for name matches
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TrialBalanceComparison {
+    pub base_trial_balance_id: u64,
+    pub compare_trial_balance_id: u64,
+    pub owner: Option<CoaOwner>, // Whose saved mappings were applied
+    pub rows: Vec<AccountComparison>,
+    pub new_count: u32,
+    pub removed_count: u32,
+    pub renamed_count: u32,
+    pub renumbered_count: u32,
+    pub split_count: u32,
+    pub merged_count: u32,
+    pub generated_at: u64,
+}
+
+// Account Reconciliation Types
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReconciliationSource {
+    BankStatement,
+    Subledger,
+    Confirmation,
+    Other,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReconciliationStatus {
+    Open,
+    Prepared,
+    Reviewed,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReconcilingItem {
+    pub id: u32,
+    pub description: String,
+    pub amount: i64, // Added to the supporting balance to reach the account balance
+    pub item_date: Option<String>,
+    pub document_id: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReconcilingItemInput {
+    pub description: String,
+    pub amount: i64,
+    pub item_date: Option<String>,
+    pub document_id: Option<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountReconciliation {
+    pub id: u64,
+    pub trial_balance_id: u64,
+    pub account_id: u64,
+    pub account_number: String,
+    pub account_name: String,
+    pub source: ReconciliationSource,
+    pub source_reference: String, // e.g. statement number or subledger report
+    pub supporting_balance: i64,  // Debit positive, like the account balance
+    pub account_balance: i64,     // As at the last save
+    pub reconciling_items: Vec<ReconcilingItem>,
+    pub unreconciled_difference: i64, // Account balance less supporting balance and items
+    pub document_ids: Vec<u64>,
+    pub status: ReconciliationStatus,
+    pub prepared_by: Option<SignOff>,
+    pub reviewed_by: Option<SignOff>,
+    pub created_at: u64,
+    pub created_by: Principal,
+    pub updated_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CreateReconciliationRequest {
+    pub account_id: u64,
+    pub source: ReconciliationSource,
+    pub source_reference: String,
+    pub supporting_balance: i64,
+    pub reconciling_items: Vec<ReconcilingItemInput>,
+    pub document_ids: Vec<u64>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateReconciliationRequest {
+    pub reconciliation_id: u64,
+    pub source: Option<ReconciliationSource>,
+    pub source_reference: Option<String>,
+    pub supporting_balance: Option<i64>,
+    pub reconciling_items: Option<Vec<ReconcilingItemInput>>, // Replaces all items
+    pub document_ids: Option<Vec<u64>>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UnreconciledAccount {
+    pub account_id: u64,
+    pub account_number: String,
+    pub account_name: String,
+    pub balance: i64,
+    pub reconciliation_id: Option<u64>,
+    pub status: Option<ReconciliationStatus>,
+    pub unreconciled_difference: Option<i64>, // Against the current balance
+}
+
+// Foreign Exchange Types
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum FxRateType {
+    Closing,
+    Average,
+    Historical,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum FxRateSource {
+    Manual,
+    Imported,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct FxRate {
+    pub id: u64,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate_type: FxRateType,
+    pub rate: f64,              // Units of to_currency per unit of from_currency
+    pub effective_date: String, // Average rates are dated at the end of their period
+    pub source: FxRateSource,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SetFxRateRequest {
+    pub from_currency: String,
+    pub to_currency: String,
+    pub rate_type: FxRateType,
+    pub rate: f64,
+    pub effective_date: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountRate {
+    pub account_number: String,
+    pub rate: f64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TranslateTrialBalanceRequest {
+    pub trial_balance_id: u64,
+    pub presentation_currency: String,
+    pub engagement_id: Option<u64>,       // Defaults to the source trial balance's engagement
+    pub historical_rates: Vec<AccountRate>, // Per equity account, overriding the rate table
+    pub description: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TranslationLine {
+    pub account_id: u64,
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub currency: String,
+    pub balance: i64, // Debit positive, in the account's currency
+    pub rate_type: FxRateType,
+    pub rate: f64,
+    pub translated_balance: i64, // Debit positive, in the presentation currency
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TranslationResult {
+    pub source_trial_balance_id: u64,
+    pub trial_balance: TrialBalance,
+    pub lines: Vec<TranslationLine>,
+    pub translation_difference: i64, // Posted to the CTA account, debit positive
+}
+
+// Group Consolidation Types
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ConsolidationEntityInput {
+    pub entity_id: u64,
+    pub trial_balance_id: u64,
+    pub ownership_percentage: f64, // 100 for the parent and wholly owned subsidiaries
+    pub historical_rates: Vec<AccountRate>, // Equity rates for a foreign entity
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct IntercompanyPair {
+    pub entity_id: u64,
+    pub account_number: String,
+    pub counterparty_entity_id: u64,
+    pub counterparty_account_number: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct EliminationLine {
+    pub account_number: String,
+    pub debit_amount: i64,
+    pub credit_amount: i64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct EliminationEntry {
+    pub description: String,
+    pub lines: Vec<EliminationLine>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ConsolidateRequest {
+    pub organization_id: u64,
+    pub engagement_id: u64, // The group engagement, linked to the organization
+    pub period_end_date: String,
+    pub presentation_currency: String,
+    pub entities: Vec<ConsolidationEntityInput>,
+    pub intercompany_pairs: Vec<IntercompanyPair>,
+    pub eliminations: Vec<EliminationEntry>, // e.g. investment in subsidiaries against their share capital
+    pub description: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ConsolidationColumn {
+    pub entity_id: u64,
+    pub entity_name: String,
+    pub trial_balance_id: u64,
+    pub currency: String,
+    pub ownership_percentage: f64,
+    pub translation_difference: i64,
+    pub nci_share_of_equity: i64, // Debit positive, so an NCI credit is negative
+    pub nci_share_of_profit: i64, // Debit positive, negative for a share of a profit
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ConsolidationRow {
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub fs_line_item: Option<String>,
+    pub entity_balances: Vec<i64>, // Aligned with columns, translated, debit positive
+    pub aggregated_balance: i64,
+    pub eliminations: i64,
+    pub nci_adjustment: i64,
+    pub consolidated_balance: i64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct IntercompanyMatch {
+    pub entity_id: u64,
+    pub account_number: String,
+    pub balance: i64,
+    pub counterparty_entity_id: u64,
+    pub counterparty_account_number: String,
+    pub counterparty_balance: i64,
+    pub eliminated: i64,
+    pub difference: i64, // Left in the consolidated balances for follow-up
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Consolidation {
+    pub id: u64,
+    pub organization_id: u64,
+    pub engagement_id: u64,
+    pub trial_balance_id: u64, // The consolidated trial balance
+    pub period_end_date: String,
+    pub presentation_currency: String,
+    pub columns: Vec<ConsolidationColumn>,
+    pub rows: Vec<ConsolidationRow>,
+    pub intercompany: Vec<IntercompanyMatch>,
+    pub eliminations: Vec<EliminationEntry>, // Intercompany entries followed by the manual ones
+    pub non_controlling_interest: i64, // Share of equity plus share of profit, debit positive like the NCI account
+    pub nci_share_of_profit: i64,
+    pub created_at: u64,
+    pub created_by: Principal,
 }
 
 // Adjusting Journal Entry Types
@@ -490,6 +1388,22 @@ pub enum AjeStatus {
     Approved,
     Rejected,
     Posted,
+    Passed, // Client declined to book; carried to the uncorrected misstatements summary
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum AjeEntryType {
+    Adjusting,
+    Reclassification, // Presentation only; not part of the client's book adjustments
+    AutoReversing,    // Mirrored into the next period's trial balance once posted
+}
+
+// ISA 450 classification of a misstatement
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum MisstatementClassification {
+    Factual,
+    Judgmental,
+    Projected,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -499,6 +1413,7 @@ pub struct AdjustingJournalEntry {
     pub trial_balance_id: u64,
     pub aje_number: String,
     pub description: String,
+    pub entry_type: Option<AjeEntryType>, // None on AJEs stored before entry types; read as adjusting
     pub status: AjeStatus,
     pub amount: i64,
     pub created_at: u64,
@@ -507,8 +1422,23 @@ pub struct AdjustingJournalEntry {
     pub reviewed_by: Option<Principal>,
     pub approved_at: Option<u64>,
     pub approved_by: Option<Principal>,
+    pub classification: Option<MisstatementClassification>, // Set when passed
+    pub passed_reason: Option<String>,
+    pub passed_at: Option<u64>,
+    pub passed_by: Option<Principal>,
+    pub reverses_aje_id: Option<u64>, // Set on a generated reversal
+    pub reversal_aje_id: Option<u64>,
+    pub reversal_pending: Option<bool>, // Posted auto-reversing entry waiting for the next period's trial balance
     pub blockchain_hash: String,
     pub blockchain_signature: String,
+    pub proof_version: Option<u32>, // Format the hash was signed under; None for the original format
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PassAjeRequest {
+    pub aje_id: u64,
+    pub classification: MisstatementClassification,
+    pub reason: String,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -527,11 +1457,27 @@ pub struct AjeLineItem {
 pub struct CreateAjeRequest {
     pub engagement_id: u64,
     pub trial_balance_id: u64,
-    pub aje_number: String,
     pub description: String,
+    pub entry_type: Option<AjeEntryType>, // Defaults to Adjusting
+    pub line_items: Vec<CreateAjeLineItemRequest>,
+}
+
+// Draft AJEs only; the line items replace the existing ones
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateAjeRequest {
+    pub aje_id: u64,
+    pub description: String,
+    pub entry_type: Option<AjeEntryType>, // Unchanged when None
     pub line_items: Vec<CreateAjeLineItemRequest>,
 }
 
+// A posted entry the client should record in its books
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BookAdjustment {
+    pub aje: AdjustingJournalEntry,
+    pub line_items: Vec<AjeLineItem>,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct CreateAjeLineItemRequest {
     pub account_id: u64,
@@ -540,6 +1486,93 @@ pub struct CreateAjeLineItemRequest {
     pub description: String,
 }
 
+// Debit positive, in cents
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct FsLineEffect {
+    pub fs_line_item: String, // "UNMAPPED" when the account has no FS line
+    pub amount: i64,
+}
+
+// Effects on profit and equity are positive when they overstate them
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct UncorrectedMisstatement {
+    pub aje_id: u64,
+    pub engagement_id: u64,
+    pub aje_number: String,
+    pub description: String,
+    pub classification: MisstatementClassification,
+    pub is_prior_year_turnaround: bool,
+    pub fs_line_effects: Vec<FsLineEffect>,
+    pub profit_before_tax_effect: i64,
+    pub equity_effect: i64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MisstatementSummary {
+    pub engagement_id: u64,
+    pub materiality: Option<Materiality>,
+    pub items: Vec<UncorrectedMisstatement>,
+    pub fs_lines: Vec<FsLineEffect>,
+    pub current_year_profit_effect: i64,
+    pub turnaround_profit_effect: i64,
+    pub profit_before_tax_effect: i64,
+    pub equity_effect: i64,
+    pub factual_total: i64,
+    pub judgmental_total: i64,
+    pub projected_total: i64,
+    pub exceeds_performance_materiality: Option<bool>, // None until materiality is set
+    pub exceeds_overall_materiality: Option<bool>,
+    pub conclusion: String,
+    pub generated_at: u64,
+}
+
+// Amounts in cents, debit positive
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AdjustedTrialBalance {
+    pub trial_balance_id: u64,
+    pub engagement_id: u64,
+    pub include_proposed: bool,
+    pub columns: Vec<AjeColumn>, // One per AJE, in entry order
+    pub accounts: Vec<AdjustedAccountRow>,
+    pub unadjusted_total: i64,
+    pub posted_total: i64,
+    pub adjusting_total: i64,
+    pub reclassification_total: i64,
+    pub reversing_total: i64,
+    pub proposed_total: i64,
+    pub adjusted_total: i64,
+    pub is_balanced: bool,
+    pub generated_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AjeColumn {
+    pub aje_id: u64,
+    pub aje_number: String,
+    pub description: String,
+    pub entry_type: AjeEntryType,
+    pub status: AjeStatus,
+    pub is_posted: bool,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AdjustedAccountRow {
+    pub account_id: u64,
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: AccountType,
+    pub fs_line_item: Option<String>,
+    pub unadjusted_balance: i64, // Debit positive, like the AJE columns
+    pub adjustments: Vec<i64>, // Aligned with columns
+    pub posted_adjustments: i64,
+    pub adjusting_adjustments: i64, // Posted, split by entry type
+    pub reclassification_adjustments: i64,
+    pub reversing_adjustments: i64,
+    pub proposed_adjustments: i64,
+    pub adjusted_balance: i64,           // Unadjusted plus posted AJEs
+    pub pro_forma_balance: Option<i64>, // Adjusted plus proposed AJEs, when requested
+}
+
 // Financial Statements
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum FSCategory {
@@ -566,6 +1599,7 @@ pub struct FSLine {
     pub line_item: FSLineItem,
     pub amount: i64, // in cents
     pub mapped_accounts: Vec<u64>, // trial balance account IDs
+    pub prior_amounts: Option<Vec<Option<i64>>>, // One per comparative period, latest first; None on older statements
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -585,6 +1619,7 @@ pub struct FinancialStatement {
     pub taxonomy: XBRLTaxonomy,
     pub period_end_date: String,
     pub lines: Vec<FSLine>,
+    pub comparative_periods: Option<Vec<TrendPeriod>>, // Prior years from the engagement chain; None on older statements
     pub notes: Vec<FSNote>,
     pub created_at: u64,
     pub created_by: Principal,
@@ -937,6 +1972,7 @@ pub struct EngagementDashboard {
     pub on_schedule: bool,
     pub at_risk_milestones: Vec<EngagementMilestone>,
     pub recent_time_entries: Vec<TimeEntry>,
+    pub unexplained_variances: Vec<AnalyticalExpectation>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -954,6 +1990,532 @@ pub struct CreateEngagementFromTemplateRequest {
     pub manager_in_charge: Option<Principal>,
 }
 
+// ============================================================================
+// GENERAL LEDGER & JOURNAL ENTRY TESTING
+// ============================================================================
+
+// Dataset columns holding the journal fields; amounts come either from one
+// signed column (debits positive) or from separate debit and credit columns
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GlColumnMapping {
+    pub entry_number: String,
+    pub posting_date: String,
+    pub account_number: String,
+    pub account_name: Option<String>,
+    pub amount: Option<String>,
+    pub debit: Option<String>,
+    pub credit: Option<String>,
+    pub posted_by: Option<String>,
+    pub entry_date: Option<String>, // Date the entry was keyed, if exported
+    pub description: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct GlImport {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub dataset_id: u64,
+    pub dataset_version: u32,
+    pub sheet_name: String,
+    pub mapping: GlColumnMapping,
+    pub entry_count: u64,
+    pub line_count: u64,
+    pub total_debits: i64,  // In cents
+    pub total_credits: i64, // In cents
+    pub unbalanced_entries: Vec<String>,
+    pub skipped_rows: Vec<u64>,
+    pub complete_population: bool, // False if only the stored preview rows were imported
+    pub imported_at: u64,
+    pub imported_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JournalEntry {
+    pub id: u64,
+    pub gl_import_id: u64,
+    pub engagement_id: u64,
+    pub entry_number: String,
+    pub posting_date: String, // ISO date
+    pub entry_date: Option<String>,
+    pub posted_by: String,
+    pub description: String,
+    pub total_debit: i64,  // In cents
+    pub total_credit: i64, // In cents
+    pub line_count: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JournalLine {
+    pub id: u64,
+    pub journal_entry_id: u64,
+    pub gl_import_id: u64,
+    pub line_number: u32,
+    pub account_number: String,
+    pub account_name: String,
+    pub debit: i64,  // In cents
+    pub credit: i64, // In cents
+    pub description: String,
+    pub row_index: u64, // Row in the source sheet, below the header
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ImportGlJournalRequest {
+    pub engagement_id: u64,
+    pub dataset_id: u64,
+    pub sheet_name: Option<String>, // First sheet if None
+    pub mapping: GlColumnMapping,
+    pub file_data: Option<Vec<u8>>, // Original file, to import beyond the stored rows
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum JetFlag {
+    WeekendPosting,
+    HolidayPosting,
+    RoundAmount,
+    JustBelowThreshold,
+    UnusualUserAccount,
+    PostClose,
+    Duplicate,
+    Unbalanced,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JetConfig {
+    pub period_end_date: String,         // ISO date
+    pub close_date: Option<String>,      // Entries keyed after this are post-close; period end if None
+    pub weekend_days: Option<Vec<u32>>,  // ISO weekdays (1 = Monday); Friday and Saturday if None
+    pub holidays: Vec<String>,           // ISO dates
+    pub round_amount_unit: Option<i64>,  // Cents; 1,000.00 if None
+    pub approval_thresholds: Vec<i64>,   // Cents
+    pub just_below_percent: Option<f64>, // Band below each threshold; 5% if None
+    pub rare_pair_max_count: Option<u32>, // User/account pairs seen this often or less; 1 if None
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JetFinding {
+    pub journal_entry_id: u64,
+    pub entry_number: String,
+    pub posting_date: String,
+    pub posted_by: String,
+    pub amount: i64, // Total debit in cents
+    pub flags: Vec<JetFlag>,
+    pub risk_score: u32,
+    pub details: Vec<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JetFlagCount {
+    pub flag: JetFlag,
+    pub count: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct JetRun {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub gl_import_id: u64,
+    pub config: JetConfig,
+    pub entries_tested: u64,
+    pub flagged_entries: u64,
+    pub flag_counts: Vec<JetFlagCount>,
+    pub findings: Vec<JetFinding>, // Highest risk first
+    pub run_at: u64,
+    pub run_by: Principal,
+}
+
+// ============================================================================
+// DIGIT ANALYSIS (BENFORD'S LAW)
+// ============================================================================
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum DigitTest {
+    FirstDigit,
+    FirstTwoDigits,
+    LastDigit,
+}
+
+// Nigrini's MAD conformity ranges
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ConformityLevel {
+    CloseConformity,
+    AcceptableConformity,
+    MarginallyAcceptable,
+    Nonconformity,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DigitFrequency {
+    pub digits: String,
+    pub expected_proportion: f64,
+    pub observed_proportion: f64,
+    pub expected_count: f64,
+    pub observed_count: u64,
+    pub z_statistic: f64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DigitDeviation {
+    pub digits: String,
+    pub expected_count: f64,
+    pub observed_count: u64,
+    pub z_statistic: f64,
+    pub row_indexes: Vec<u64>, // Rows below the header, for drill-down
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DigitAnalysis {
+    pub id: u64,
+    pub working_paper_id: u64,
+    pub engagement_id: u64,
+    pub dataset_id: u64,
+    pub dataset_version: u32,
+    pub sheet_name: String,
+    pub column_name: String,
+    pub test: DigitTest,
+    pub records_tested: u64,
+    pub records_excluded: u64, // Blank, non-numeric or below the minimum value
+    pub complete_population: bool,
+    pub frequencies: Vec<DigitFrequency>,
+    pub chi_square: f64,
+    pub degrees_of_freedom: u32,
+    pub chi_square_critical: f64, // 95% confidence
+    pub mad: f64,
+    pub conformity: ConformityLevel,
+    pub top_deviations: Vec<DigitDeviation>,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RunDigitAnalysisRequest {
+    pub working_paper_id: u64,
+    pub dataset_id: u64,
+    pub sheet_name: Option<String>, // First sheet if None
+    pub column_name: String,
+    pub test: DigitTest,
+    pub min_value: Option<f64>,     // Amounts below this are excluded; 10 if None
+    pub file_data: Option<Vec<u8>>, // Original file, to test beyond the stored rows
+}
+
+// ============================================================================
+// AUDIT SAMPLING
+// ============================================================================
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum SamplingMethod {
+    MonetaryUnit {
+        confidence: f64, // e.g. 0.95
+        tolerable_misstatement: f64,
+        expected_misstatement: f64,
+    },
+    SimpleRandom {
+        sample_size: u32,
+    },
+    Stratified {
+        band_upper_bounds: Vec<f64>, // Ascending; the last stratum is open-ended
+        sample_sizes: Vec<u32>,      // One per stratum, i.e. bounds + 1
+    },
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct StratumSummary {
+    pub index: u32,
+    pub lower_bound: Option<f64>,
+    pub upper_bound: Option<f64>,
+    pub population_count: u64,
+    pub population_value: f64,
+    pub sample_size: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SampleItem {
+    pub row_index: u64, // Row below the header in the source sheet
+    pub reference: String,
+    pub book_amount: f64,
+    pub stratum: Option<u32>,
+    pub selection_note: String,
+    pub audited_amount: Option<f64>, // Filled in on evaluation
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SampleEvaluation {
+    pub items_tested: u64,
+    pub misstatements_found: u64,
+    pub known_misstatement: f64,     // Sampled items' actual misstatement
+    pub projected_misstatement: f64, // Best estimate for the population
+    pub basic_precision: f64,
+    pub incremental_allowance: f64,
+    pub upper_misstatement_limit: f64,
+    pub tolerable_misstatement: Option<f64>,
+    pub exceeds_tolerable: Option<bool>,
+    pub confidence: f64,
+    pub evaluated_at: u64,
+    pub evaluated_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AuditSample {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub name: String,
+    pub dataset_id: u64,
+    pub dataset_version: u32,
+    pub sheet_name: String,
+    pub amount_column: String,
+    pub reference_column: Option<String>,
+    pub method: SamplingMethod,
+    pub seed: u64,
+    pub population_count: u64,
+    pub population_value: f64,
+    pub excluded_count: u64, // Blank or non-numeric amounts (and non-positive ones for MUS)
+    pub sampling_interval: Option<f64>, // MUS only
+    pub strata: Vec<StratumSummary>,
+    pub items: Vec<SampleItem>,
+    pub complete_population: bool,
+    pub evaluation: Option<SampleEvaluation>,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CreateAuditSampleRequest {
+    pub engagement_id: u64,
+    pub name: String,
+    pub dataset_id: u64,
+    pub sheet_name: Option<String>, // First sheet if None
+    pub amount_column: String,
+    pub reference_column: Option<String>,
+    pub method: SamplingMethod,
+    pub seed: Option<u64>,          // Generated and recorded if None
+    pub file_data: Option<Vec<u8>>, // Original file, to sample beyond the stored rows
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SampleItemResult {
+    pub row_index: u64,
+    pub audited_amount: f64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct EvaluateAuditSampleRequest {
+    pub sample_id: u64,
+    pub results: Vec<SampleItemResult>, // Items not listed are taken as correct
+    pub confidence: Option<f64>,        // MUS uses its design confidence; 0.95 otherwise
+    pub tolerable_misstatement: Option<f64>,
+}
+
+// ============================================================================
+// MULTI-PERIOD TREND ANALYSIS
+// ============================================================================
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct SignificanceThresholds {
+    pub amount: Option<i64>,  // Absolute change in cents
+    pub percent: Option<f64>, // Absolute change in percent
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TrendPeriod {
+    pub engagement_id: u64,
+    pub engagement_name: String,
+    pub trial_balance_id: u64,
+    pub period_end_date: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TrendChange {
+    pub change: Option<i64>,         // Against the next older period
+    pub change_percent: Option<f64>, // None when the older amount is zero or missing
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TrendRow {
+    pub key: String, // Account number or FS line code
+    pub name: String,
+    pub amounts: Vec<Option<i64>>, // One per period, current first; None if absent that year
+    pub changes: Vec<TrendChange>, // One per pair of adjacent periods
+    pub is_significant: bool,      // Current vs prior year against the thresholds
+    pub significance_reason: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MultiPeriodTrend {
+    pub engagement_id: u64,
+    pub trial_balance_id: u64,
+    pub periods: Vec<TrendPeriod>, // Current first
+    pub accounts: Vec<TrendRow>,
+    pub fs_lines: Vec<TrendRow>,
+    pub thresholds: SignificanceThresholds,
+    pub gaps: Vec<String>, // Prior-year engagements skipped for lack of a trial balance
+    pub generated_at: u64,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct MultiPeriodTrendRequest {
+    pub trial_balance_id: u64,
+    pub years: Option<u32>, // Including the current year; at most 5
+    pub thresholds: Option<SignificanceThresholds>,
+}
+
+// ============================================================================
+// ANALYTICAL PROCEDURES
+// ============================================================================
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ExpectationTarget {
+    Account(String), // Account number
+    FsLine(String),  // FS line code, e.g. IS_REVENUE
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct RegressionPoint {
+    pub driver: f64,
+    pub amount: i64, // in cents
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum ExpectationMethod {
+    PriorYearGrowth {
+        growth_percent: f64,
+    },
+    Budget {
+        dataset_id: u64,
+        sheet_name: Option<String>,
+        key_column: String, // Holds the account number or FS line code
+        amount_column: String,
+    },
+    Regression {
+        driver_name: String,
+        observations: Vec<RegressionPoint>, // At least three periods
+        current_driver: f64,
+    },
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AnalyticalExpectation {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub trial_balance_id: u64,
+    pub target: ExpectationTarget,
+    pub target_name: String,
+    pub method: ExpectationMethod,
+    pub basis: String, // How the expectation was derived
+    pub r_squared: Option<f64>, // Regression only
+    pub expected: i64,
+    pub actual: i64,
+    pub variance: i64, // actual - expected
+    pub variance_percent: Option<f64>,
+    pub threshold_amount: Option<i64>,
+    pub threshold_percent: Option<f64>, // Of the expected amount
+    pub outside_threshold: bool,
+    pub explanation: Option<String>,
+    pub explained_by: Option<Principal>,
+    pub explained_at: Option<u64>,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CreateExpectationRequest {
+    pub trial_balance_id: u64,
+    pub target: ExpectationTarget,
+    pub method: ExpectationMethod,
+    pub threshold_amount: Option<i64>,
+    pub threshold_percent: Option<f64>, // The tighter of the two applies when both are set
+    pub file_data: Option<Vec<u8>>,     // Original budget file, beyond the stored rows
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ExplainVarianceRequest {
+    pub expectation_id: u64,
+    pub explanation: String,
+}
+
+// ============================================================================
+// CROSS-REFERENCES & TICK MARKS
+// ============================================================================
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ReferenceTarget {
+    WorkingPaper(u64),
+    Aje(u64),
+    Document(u64),
+    FsLine(String), // FS line code
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct CrossReference {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub working_paper_id: u64,
+    pub account_number: Option<String>, // Leadsheet account the reference is made from
+    pub target: ReferenceTarget,
+    pub description: String, // e.g. "Agreed to bank confirmation"
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct TickMark {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub symbol: String,
+    pub meaning: String,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Annotation {
+    pub id: u64,
+    pub engagement_id: u64,
+    pub working_paper_id: u64,
+    pub account_number: String,
+    pub tick_mark_id: u64,
+    pub comment: Option<String>,
+    pub created_at: u64,
+    pub created_by: Principal,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AddCrossReferenceRequest {
+    pub working_paper_id: u64,
+    pub account_number: Option<String>,
+    pub target: ReferenceTarget,
+    pub description: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct DefineTickMarkRequest {
+    pub engagement_id: u64,
+    pub symbol: String,
+    pub meaning: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AddAnnotationRequest {
+    pub working_paper_id: u64,
+    pub account_number: String,
+    pub tick_mark_id: u64,
+    pub comment: Option<String>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BrokenReference {
+    pub working_paper_id: u64,
+    pub source: String, // What holds the reference, e.g. "Cross-reference 12"
+    pub target: String,
+    pub reason: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ReferenceIntegrityReport {
+    pub engagement_id: u64,
+    pub references_checked: u64,
+    pub broken: Vec<BrokenReference>,
+    pub checked_at: u64,
+}
+
 // Result types
 pub type Result<T> = std::result::Result<T, String>;
 
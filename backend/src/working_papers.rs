@@ -1,15 +1,32 @@
 use candid::{encode_args, Principal};
 use ic_cdk::api::time;
+use std::collections::{HashMap, HashSet};
 
 use crate::activity_log::log_activity;
+use crate::adjustments;
+use crate::analytical_procedures;
 use crate::auth;
+use crate::cross_references;
 use crate::data_import;
-use crate::storage::{next_working_paper_id, STORAGE};
+use crate::data_quality;
+use crate::financial_statements;
+use crate::formulas::{self, FormulaContext};
+use crate::engagements;
+use crate::storage::{next_review_note_id, next_working_paper_id, STORAGE};
+use crate::trend_analysis;
 use crate::types::{
-    AccountData, CreateWorkingPaperRequest, FinancialRatio, Leadsheet, Result, TrendAnalysis,
-    VarianceAnalysis, WorkingPaper,
+    AccountData, AccountType, AddReviewNoteRequest, CellValue, CreateWorkingPaperRequest,
+    FinancialRatio, Leadsheet, LeadsheetChange, LeadsheetLine, LeadsheetRefresh, LeadsheetSource,
+    RatioInput, ResolveReviewNoteRequest, Result, ReviewNote, ReviewNoteStatus, SetCellsRequest,
+    SignConvention, SignOff, SignOffRole, SignificanceThresholds, TrialBalanceAccount,
+    TrendAnalysis, UpdateWorkingPaperRequest, WorkingPaper, WorkingPaperCell, WorkingPaperStatus,
 };
 
+const DAYS_IN_YEAR: f64 = 365.0;
+const CURRENT_ASSET_LINES: &[&str] = &["BS_CASH", "BS_AR", "BS_INVENTORY", "BS_PREPAID"];
+const CURRENT_LIABILITY_LINES: &[&str] = &["BS_AP", "BS_ACCRUED"];
+const NO_TRIAL_BALANCE: &str = "No trial balance linked to this working paper";
+
 // Create working paper
 pub fn create_working_paper(
     caller: Principal,
@@ -21,38 +38,43 @@ pub fn create_working_paper(
         return Err("Insufficient permissions to create working paper".to_string());
     }
 
-    // Get the dataset
-    let dataset = data_import::get_dataset(caller, req.dataset_id)?;
-
-    // Extract account data based on mapping
-    let accounts = extract_accounts(&dataset, &req.column_mapping, &req.selected_accounts)?;
-
-    // Generate leadsheet
-    let leadsheet = generate_leadsheet(&accounts)?;
-
-    // Calculate ratios
-    let ratios = calculate_ratios(&accounts)?;
-
-    // Generate trend analysis
-    let trend_analysis = generate_trend_analysis(&accounts)?;
-
-    // Generate variance analysis
-    let variance_analysis = generate_variance_analysis(&accounts)?;
+    if req.dataset_id.is_none() && req.trial_balance_id.is_none() {
+        return Err("A working paper needs a dataset or a trial balance".to_string());
+    }
+    let reference_code =
+        cross_references::validate_reference_code(req.engagement_id, req.reference_code, None)?;
 
-    let working_paper = WorkingPaper {
-        id: next_working_paper_id(),
+    let mut working_paper = WorkingPaper {
+        id: 0,
         engagement_id: req.engagement_id,
         dataset_id: req.dataset_id,
         name: req.name.clone(),
+        reference_code,
         column_mapping: req.column_mapping,
-        leadsheet: Some(leadsheet),
-        ratios,
-        trend_analysis,
-        variance_analysis,
+        selected_accounts: Some(req.selected_accounts),
+        leadsheet: None,
+        refresh_history: Some(Vec::new()),
+        cells: Some(Vec::new()),
+        ratios: Vec::new(),
+        trend_analysis: Vec::new(),
+        variance_analysis: Vec::new(),
         linked_document_ids: Vec::new(),
+        trial_balance_id: req.trial_balance_id,
+        multi_period_trend: None,
+        dataset_version: None,
+        is_stale: Some(false),
+        stale_reason: None,
+        status: Some(WorkingPaperStatus::Draft),
+        prepared_by: None,
+        reviewed_by: None,
+        approved_by: None,
         created_at: time(),
         created_by: caller,
+        last_modified_at: Some(time()),
+        last_modified_by: Some(caller),
     };
+    analyse_working_paper(caller, &mut working_paper, req.trend_thresholds)?;
+    working_paper.id = next_working_paper_id();
 
     STORAGE.with(|storage| {
         storage
@@ -73,19 +95,670 @@ pub fn create_working_paper(
     Ok(working_paper)
 }
 
+// Update a working paper's mapping and sources and rebuild its analysis.
+// Any sign-offs are cleared and the paper goes back to draft.
+pub fn update_working_paper(
+    caller: Principal,
+    req: UpdateWorkingPaperRequest,
+) -> Result<WorkingPaper> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to update working paper".to_string());
+    }
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&req.id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    wp.reference_code =
+        cross_references::validate_reference_code(wp.engagement_id, req.reference_code, Some(wp.id))?;
+    if req.dataset_id.is_none() && req.trial_balance_id.is_none() {
+        return Err("A working paper needs a dataset or a trial balance".to_string());
+    }
+    wp.name = req.name;
+    wp.dataset_id = req.dataset_id;
+    wp.column_mapping = req.column_mapping;
+    wp.selected_accounts = Some(req.selected_accounts);
+    wp.trial_balance_id = req.trial_balance_id;
+    analyse_working_paper(caller, &mut wp, req.trend_thresholds)?;
+    wp.is_stale = Some(false);
+    wp.stale_reason = None;
+    let cleared = record_edit(&mut wp, caller);
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .working_papers
+            .insert(wp.id, wp.clone());
+    });
+
+    log_activity(
+        caller,
+        "update_working_paper".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        if cleared {
+            format!("Working paper {} updated; sign-offs cleared", wp.name)
+        } else {
+            format!("Working paper {} updated", wp.name)
+        },
+        encode_args((wp.clone(),)).ok(),
+    );
+
+    Ok(wp)
+}
+
+// Recompute a working paper from its current sources and record what moved
+// on the leadsheet since the last build
+pub fn refresh_working_paper(caller: Principal, working_paper_id: u64) -> Result<WorkingPaper> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to refresh working paper".to_string());
+    }
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    let previous = wp.leadsheet.clone();
+    let thresholds = wp.multi_period_trend.as_ref().map(|trend| trend.thresholds.clone());
+    analyse_working_paper(caller, &mut wp, thresholds)?;
+    wp.is_stale = Some(false);
+    wp.stale_reason = None;
+
+    let changes = match (&previous, &wp.leadsheet) {
+        (Some(before), Some(after)) => leadsheet_changes(before, after),
+        _ => Vec::new(),
+    };
+    let cleared = !changes.is_empty() && record_edit(&mut wp, caller);
+    wp.refresh_history.get_or_insert_with(Vec::new).push(LeadsheetRefresh {
+        refreshed_at: time(),
+        refreshed_by: caller,
+        previous_closing_balance: previous.map(|l| l.closing_balance).unwrap_or(0.0),
+        closing_balance: wp.leadsheet.as_ref().map(|l| l.closing_balance).unwrap_or(0.0),
+        changes,
+    });
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .working_papers
+            .insert(wp.id, wp.clone());
+    });
+
+    let change_count = wp
+        .refresh_history
+        .iter()
+        .flatten()
+        .last()
+        .map(|r| r.changes.len())
+        .unwrap_or(0);
+    log_activity(
+        caller,
+        "refresh_working_paper".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        if cleared {
+            format!(
+                "Working paper {} refreshed; {} accounts changed, sign-offs cleared",
+                wp.name, change_count
+            )
+        } else {
+            format!("Working paper {} refreshed; {} accounts changed", wp.name, change_count)
+        },
+        encode_args((wp.clone(),)).ok(),
+    );
+
+    Ok(wp)
+}
+
+// Accounts whose closing balance moved, appeared or dropped off
+fn leadsheet_changes(before: &Leadsheet, after: &Leadsheet) -> Vec<LeadsheetChange> {
+    let before = leadsheet_balances(before);
+    let after = leadsheet_balances(after);
+
+    let mut numbers: Vec<&String> = before.keys().chain(after.keys()).collect();
+    numbers.sort();
+    numbers.dedup();
+
+    numbers
+        .into_iter()
+        .filter_map(|number| {
+            let previous = before.get(number);
+            let current = after.get(number);
+            let change = current.map_or(0.0, |(_, b)| *b) - previous.map_or(0.0, |(_, b)| *b);
+            if previous.is_some() && current.is_some() && change.abs() < 0.005 {
+                return None;
+            }
+            Some(LeadsheetChange {
+                account_number: number.clone(),
+                account_name: current.or(previous).map(|(name, _)| name.clone()).unwrap_or_default(),
+                previous_balance: previous.map(|(_, b)| *b),
+                current_balance: current.map(|(_, b)| *b),
+                change,
+            })
+        })
+        .collect()
+}
+
+// Rebuild the leadsheet and analytics from the dataset and linked trial
+// balance. Without a dataset the leadsheet comes from the trial balance.
+fn analyse_working_paper(
+    caller: Principal,
+    wp: &mut WorkingPaper,
+    trend_thresholds: Option<SignificanceThresholds>,
+) -> Result<()> {
+    let trial_balance_accounts = match wp.trial_balance_id {
+        Some(trial_balance_id) => Some(trial_balance_accounts(wp.engagement_id, trial_balance_id)?),
+        None => None,
+    };
+
+    let (leadsheet, accounts, dataset_version) = match (wp.dataset_id, wp.trial_balance_id) {
+        (Some(dataset_id), _) => {
+            // Get the dataset
+            let dataset = data_import::get_dataset(caller, dataset_id)?;
+            data_quality::ensure_not_blocked(&dataset)?;
+
+            // Extract account data based on mapping
+            let accounts = extract_accounts(&dataset, &wp.column_mapping, selected_accounts(wp))?;
+
+            (generate_leadsheet(&accounts)?, accounts, dataset.version)
+        }
+        (None, Some(trial_balance_id)) => {
+            let leadsheet = generate_trial_balance_leadsheet(
+                trial_balance_id,
+                trial_balance_accounts.as_deref().unwrap_or_default(),
+                selected_accounts(wp),
+            );
+            (leadsheet, Vec::new(), 0)
+        }
+        (None, None) => return Err("A working paper needs a dataset or a trial balance".to_string()),
+    };
+
+    // Calculate ratios from the linked trial balance's classification
+    let ratios = calculate_ratios(trial_balance_accounts.as_deref())?;
+
+    // Multi-period trend over the prior-year engagement chain
+    let multi_period_trend = match wp.trial_balance_id {
+        Some(trial_balance_id) => Some(trend_analysis::build_multi_period_trend(
+            trial_balance_id,
+            None,
+            trend_thresholds,
+        )?),
+        None => None,
+    };
+
+    // Generate trend analysis
+    let trend_analysis = generate_trend_analysis(&accounts)?;
+
+    // Variances against the expectations set on the linked trial balance
+    let variance_analysis = wp
+        .trial_balance_id
+        .map(analytical_procedures::variance_rows_for_trial_balance)
+        .unwrap_or_default();
+
+    wp.leadsheet = Some(leadsheet);
+    wp.ratios = ratios;
+    wp.multi_period_trend = multi_period_trend;
+    wp.trend_analysis = trend_analysis;
+    wp.variance_analysis = variance_analysis;
+    wp.dataset_version = Some(dataset_version);
+    recalculate_cells(wp, trial_balance_accounts.as_deref());
+
+    Ok(())
+}
+
+// Set or clear grid cells and recalculate every formula on the paper
+pub fn set_working_paper_cells(caller: Principal, req: SetCellsRequest) -> Result<WorkingPaper> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to edit working paper".to_string());
+    }
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&req.working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    let mut inputs: Vec<(String, String)> = wp
+        .cells
+        .iter()
+        .flatten()
+        .map(|cell| (cell.address.clone(), cell.input.clone()))
+        .collect();
+    for cell in &req.cells {
+        let address = formulas::normalise_address(&cell.address)?;
+        inputs.retain(|(existing, _)| *existing != address);
+        if !cell.input.trim().is_empty() {
+            inputs.push((address, cell.input.clone()));
+        }
+    }
+    if inputs.len() > formulas::MAX_CELLS {
+        return Err(format!(
+            "A working paper can hold at most {} cells",
+            formulas::MAX_CELLS
+        ));
+    }
+
+    wp.cells = Some(
+        inputs
+            .into_iter()
+            .map(|(address, input)| WorkingPaperCell {
+                address,
+                input,
+                value: CellValue::Empty,
+                error: None,
+            })
+            .collect(),
+    );
+    let trial_balance_accounts = match wp.trial_balance_id {
+        Some(trial_balance_id) => Some(trial_balance_accounts(wp.engagement_id, trial_balance_id)?),
+        None => None,
+    };
+    recalculate_cells(&mut wp, trial_balance_accounts.as_deref());
+    let cleared = record_edit(&mut wp, caller);
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .working_papers
+            .insert(wp.id, wp.clone());
+    });
+
+    let errors = wp.cells.iter().flatten().filter(|cell| cell.error.is_some()).count();
+    log_activity(
+        caller,
+        "set_working_paper_cells".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        format!(
+            "{} cells set on working paper {}; {} in error{}",
+            req.cells.len(),
+            wp.name,
+            errors,
+            if cleared { "; sign-offs cleared" } else { "" }
+        ),
+        encode_args((wp.cells.clone(),)).ok(),
+    );
+
+    Ok(wp)
+}
+
+// Re-evaluate the grid against the current leadsheet and trial balance
+fn recalculate_cells(wp: &mut WorkingPaper, trial_balance_accounts: Option<&[TrialBalanceAccount]>) {
+    if wp.cells.as_ref().is_none_or(|cells| cells.is_empty()) {
+        return;
+    }
+
+    let leadsheet = wp
+        .leadsheet
+        .as_ref()
+        .map(|leadsheet| {
+            leadsheet_balances(leadsheet)
+                .into_iter()
+                .map(|(number, (_, balance))| (number, balance))
+                .collect()
+        })
+        .unwrap_or_default();
+    let trial_balance = trial_balance_accounts.map(|accounts| {
+        let mut balances: HashMap<String, f64> = HashMap::new();
+        for account in accounts {
+            *balances.entry(account.account_number.clone()).or_insert(0.0) +=
+                (account.debit_balance - account.credit_balance) as f64 / 100.0;
+        }
+        balances
+    });
+
+    let inputs: Vec<(String, String)> = wp
+        .cells
+        .iter()
+        .flatten()
+        .map(|cell| (cell.address.clone(), cell.input.clone()))
+        .collect();
+    wp.cells = Some(formulas::evaluate_cells(
+        &inputs,
+        &FormulaContext {
+            leadsheet,
+            trial_balance,
+        },
+    ));
+}
+
+// Sign off a working paper as preparer, reviewer or approver, in that order
+pub fn sign_off_working_paper(
+    caller: Principal,
+    working_paper_id: u64,
+    role: SignOffRole,
+) -> Result<WorkingPaper> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    let mut wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    let sign_off = Some(SignOff {
+        signed_by: caller,
+        signed_at: time(),
+    });
+    let open_notes = review_notes_for(working_paper_id)
+        .iter()
+        .filter(|n| n.status == ReviewNoteStatus::Open)
+        .count();
+
+    match role {
+        SignOffRole::Preparer => {
+            if !auth::can_create_working_paper(&user) {
+                return Err("Insufficient permissions to sign off as preparer".to_string());
+            }
+            if status_of(&wp) != WorkingPaperStatus::Draft {
+                return Err("Only draft working papers can be signed off as prepared".to_string());
+            }
+            wp.prepared_by = sign_off;
+            wp.status = Some(WorkingPaperStatus::Prepared);
+        }
+        SignOffRole::Reviewer => {
+            if !auth::can_review_work(&user) {
+                return Err("Insufficient permissions to sign off as reviewer".to_string());
+            }
+            if status_of(&wp) != WorkingPaperStatus::Prepared {
+                return Err("Working paper must be prepared before review".to_string());
+            }
+            if wp.prepared_by.as_ref().is_some_and(|s| s.signed_by == caller) {
+                return Err("The preparer cannot also review the working paper".to_string());
+            }
+            if open_notes > 0 {
+                return Err(format!("{} review note(s) are still open", open_notes));
+            }
+            wp.reviewed_by = sign_off;
+            wp.status = Some(WorkingPaperStatus::Reviewed);
+        }
+        SignOffRole::Approver => {
+            if !auth::can_approve(&user) {
+                return Err("Insufficient permissions to approve working papers".to_string());
+            }
+            if status_of(&wp) != WorkingPaperStatus::Reviewed {
+                return Err("Working paper must be reviewed before approval".to_string());
+            }
+            let signed_before = [&wp.prepared_by, &wp.reviewed_by]
+                .into_iter()
+                .flatten()
+                .any(|s| s.signed_by == caller);
+            if signed_before {
+                return Err(
+                    "The preparer or reviewer cannot also approve the working paper".to_string(),
+                );
+            }
+            if open_notes > 0 {
+                return Err(format!("{} review note(s) are still open", open_notes));
+            }
+            wp.approved_by = sign_off;
+            wp.status = Some(WorkingPaperStatus::Approved);
+        }
+    }
+
+    STORAGE.with(|storage| {
+        storage
+            .borrow_mut()
+            .working_papers
+            .insert(wp.id, wp.clone());
+    });
+
+    log_activity(
+        caller,
+        "sign_off_working_paper".to_string(),
+        "working_paper".to_string(),
+        wp.id.to_string(),
+        format!("Working paper {} signed off as {:?}", wp.name, role),
+        encode_args((wp.clone(),)).ok(),
+    );
+
+    Ok(wp)
+}
+
+// Lock every working paper of an engagement, e.g. when it is archived
+pub fn lock_working_papers_for_engagement(caller: Principal, engagement_id: u64) -> Vec<u64> {
+    let working_papers: Vec<WorkingPaper> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .working_papers
+            .iter()
+            .filter(|(_, wp)| {
+                wp.engagement_id == engagement_id && status_of(wp) != WorkingPaperStatus::Locked
+            })
+            .map(|(_, wp)| wp)
+            .collect()
+    });
+
+    let mut locked_ids = Vec::new();
+    for mut wp in working_papers {
+        wp.status = Some(WorkingPaperStatus::Locked);
+
+        STORAGE.with(|storage| {
+            storage
+                .borrow_mut()
+                .working_papers
+                .insert(wp.id, wp.clone());
+        });
+
+        log_activity(
+            caller,
+            "lock_working_paper".to_string(),
+            "working_paper".to_string(),
+            wp.id.to_string(),
+            format!("Working paper {} locked", wp.name),
+            encode_args((wp.clone(),)).ok(),
+        );
+
+        locked_ids.push(wp.id);
+    }
+
+    locked_ids
+}
+
+// Raise a review note on a working paper, one of its cells or an account
+pub fn add_review_note(caller: Principal, req: AddReviewNoteRequest) -> Result<ReviewNote> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_review_work(&user) {
+        return Err("Insufficient permissions to add review notes".to_string());
+    }
+
+    if req.note.trim().is_empty() {
+        return Err("Review note cannot be empty".to_string());
+    }
+
+    let wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&req.working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    let note = ReviewNote {
+        id: next_review_note_id(),
+        working_paper_id: wp.id,
+        engagement_id: wp.engagement_id,
+        target: req.target,
+        note: req.note.trim().to_string(),
+        status: ReviewNoteStatus::Open,
+        response: None,
+        raised_by: caller,
+        raised_at: time(),
+        resolved_by: None,
+        resolved_at: None,
+    };
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().review_notes.insert(note.id, note.clone());
+    });
+
+    log_activity(
+        caller,
+        "add_review_note".to_string(),
+        "review_note".to_string(),
+        note.id.to_string(),
+        format!("Review note raised on working paper {}", wp.name),
+        encode_args((note.clone(),)).ok(),
+    );
+
+    Ok(note)
+}
+
+// Resolve a review note with a response
+pub fn resolve_review_note(caller: Principal, req: ResolveReviewNoteRequest) -> Result<ReviewNote> {
+    let user = auth::get_user(caller).ok_or("User not found")?;
+
+    if !auth::can_create_working_paper(&user) {
+        return Err("Insufficient permissions to resolve review notes".to_string());
+    }
+
+    let mut note = STORAGE
+        .with(|storage| storage.borrow().review_notes.get(&req.note_id))
+        .ok_or_else(|| "Review note not found".to_string())?;
+
+    if note.status == ReviewNoteStatus::Resolved {
+        return Err("Review note is already resolved".to_string());
+    }
+
+    let wp = STORAGE
+        .with(|storage| storage.borrow().working_papers.get(&note.working_paper_id))
+        .ok_or_else(|| "Working paper not found".to_string())?;
+    ensure_editable(&wp)?;
+
+    note.status = ReviewNoteStatus::Resolved;
+    note.response = Some(req.response.trim().to_string()).filter(|r| !r.is_empty());
+    note.resolved_by = Some(caller);
+    note.resolved_at = Some(time());
+
+    STORAGE.with(|storage| {
+        storage.borrow_mut().review_notes.insert(note.id, note.clone());
+    });
+
+    log_activity(
+        caller,
+        "resolve_review_note".to_string(),
+        "review_note".to_string(),
+        note.id.to_string(),
+        format!("Review note on working paper {} resolved", wp.name),
+        encode_args((note.clone(),)).ok(),
+    );
+
+    Ok(note)
+}
+
+// List review notes by working paper
+pub fn list_review_notes(caller: Principal, working_paper_id: u64) -> Result<Vec<ReviewNote>> {
+    let _user = auth::get_user(caller).ok_or("User not found")?;
+
+    Ok(review_notes_for(working_paper_id))
+}
+
+fn review_notes_for(working_paper_id: u64) -> Vec<ReviewNote> {
+    STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .review_notes
+            .iter()
+            .filter(|(_, note)| note.working_paper_id == working_paper_id)
+            .map(|(_, note)| note)
+            .collect()
+    })
+}
+
+// Papers stored before sign-offs read as draft
+pub fn status_of(wp: &WorkingPaper) -> WorkingPaperStatus {
+    wp.status.clone().unwrap_or(WorkingPaperStatus::Draft)
+}
+
+fn selected_accounts(wp: &WorkingPaper) -> &[String] {
+    wp.selected_accounts.as_deref().unwrap_or_default()
+}
+
+// Locked papers, and any paper of an archived engagement, cannot change
+pub fn ensure_editable(wp: &WorkingPaper) -> Result<()> {
+    if status_of(wp) == WorkingPaperStatus::Locked {
+        return Err(format!("Working paper {} is locked", wp.name));
+    }
+
+    let archived = STORAGE
+        .with(|storage| storage.borrow().engagements.get(&wp.engagement_id))
+        .is_some_and(|engagement| engagements::is_archived(&engagement));
+    if archived {
+        return Err("The engagement is archived; its working papers are locked".to_string());
+    }
+
+    Ok(())
+}
+
+// Stamp an edit; a signed-off paper goes back to draft. Returns whether
+// sign-offs were cleared.
+pub fn record_edit(wp: &mut WorkingPaper, caller: Principal) -> bool {
+    let cleared = status_of(wp) != WorkingPaperStatus::Draft;
+
+    wp.status = Some(WorkingPaperStatus::Draft);
+    wp.prepared_by = None;
+    wp.reviewed_by = None;
+    wp.approved_by = None;
+    wp.last_modified_at = Some(time());
+    wp.last_modified_by = Some(caller);
+
+    cleared
+}
+
+// Accounts of a trial balance that belongs to the engagement
+fn trial_balance_accounts(engagement_id: u64, trial_balance_id: u64) -> Result<Vec<TrialBalanceAccount>> {
+    STORAGE.with(|storage| {
+        let storage = storage.borrow();
+        let trial_balance = storage
+            .trial_balances
+            .get(&trial_balance_id)
+            .ok_or_else(|| "Trial balance not found".to_string())?;
+        if trial_balance.engagement_id != engagement_id {
+            return Err("Trial balance belongs to a different engagement".to_string());
+        }
+        Ok(storage
+            .trial_balance_accounts
+            .iter()
+            .filter(|(_, account)| account.trial_balance_id == trial_balance_id)
+            .map(|(_, account)| account)
+            .collect())
+    })
+}
+
 // Extract accounts from dataset based on column mapping
-fn extract_accounts(
+pub fn extract_accounts(
     dataset: &crate::types::ImportedDataset,
     mapping: &crate::types::ColumnMapping,
     selected_accounts: &[String],
 ) -> Result<Vec<AccountData>> {
+    let sheet = source_sheet(dataset)?;
+    extract_account_rows(sheet, &sheet.data, mapping, selected_accounts)
+}
+
+// The sheet the mapping profile matched, else the first sheet
+pub fn source_sheet(dataset: &crate::types::ImportedDataset) -> Result<&crate::types::SheetData> {
     if dataset.sheets.is_empty() {
         return Err("Dataset has no sheets".to_string());
     }
 
-    // Use first sheet by default
-    let sheet = &dataset.sheets[0];
+    Ok(dataset
+        .suggested_mapping
+        .as_ref()
+        .and_then(|m| dataset.sheets.iter().find(|s| s.name == m.sheet_name))
+        .unwrap_or(&dataset.sheets[0]))
+}
 
+// Extract accounts from rows laid out like the sheet's columns
+pub fn extract_account_rows(
+    sheet: &crate::types::SheetData,
+    rows: &[Vec<String>],
+    mapping: &crate::types::ColumnMapping,
+    selected_accounts: &[String],
+) -> Result<Vec<AccountData>> {
     // Find column indices based on mapping
     let account_num_idx = find_column_index(&sheet, &mapping.account_number)?;
     let account_name_idx = find_column_index(&sheet, &mapping.account_name)?;
@@ -102,9 +775,14 @@ fn extract_accounts(
     let project_idx = find_column_index_opt(&sheet, &mapping.project);
     let notes_idx = find_column_index_opt(&sheet, &mapping.notes);
 
+    let sign_convention = mapping
+        .sign_convention
+        .as_ref()
+        .unwrap_or(&SignConvention::DebitCreditColumns);
+
     let mut accounts = Vec::new();
 
-    for row in &sheet.data {
+    for row in rows {
         let account_number = row.get(account_num_idx).unwrap_or(&String::new()).clone();
 
         // Filter by selected accounts
@@ -114,16 +792,32 @@ fn extract_accounts(
 
         let account_name = row.get(account_name_idx).unwrap_or(&String::new()).clone();
 
+        let (opening_debit, opening_credit) = split_signed_amounts(
+            parse_amount(get_value_or_default(row, opening_debit_idx)),
+            parse_amount(get_value_or_default(row, opening_credit_idx)),
+            sign_convention,
+        );
+        let (period_debit, period_credit) = split_signed_amounts(
+            parse_amount(get_value_or_default(row, period_debit_idx)),
+            parse_amount(get_value_or_default(row, period_credit_idx)),
+            sign_convention,
+        );
+        let (ytd_debit, ytd_credit) = split_signed_amounts(
+            parse_amount(get_value_or_default(row, ytd_debit_idx)),
+            parse_amount(get_value_or_default(row, ytd_credit_idx)),
+            sign_convention,
+        );
+
         let account = AccountData {
             account_number,
             account_name,
             currency: get_value_or_default(row, currency_idx),
-            opening_debit: parse_amount(get_value_or_default(row, opening_debit_idx)),
-            opening_credit: parse_amount(get_value_or_default(row, opening_credit_idx)),
-            period_debit: parse_amount(get_value_or_default(row, period_debit_idx)),
-            period_credit: parse_amount(get_value_or_default(row, period_credit_idx)),
-            ytd_debit: parse_amount(get_value_or_default(row, ytd_debit_idx)),
-            ytd_credit: parse_amount(get_value_or_default(row, ytd_credit_idx)),
+            opening_debit,
+            opening_credit,
+            period_debit,
+            period_credit,
+            ytd_debit,
+            ytd_credit,
             entity: get_value_or_default(row, entity_idx),
             department: get_value_or_default(row, department_idx),
             project: get_value_or_default(row, project_idx),
@@ -173,6 +867,20 @@ fn get_value_or_default(row: &[String], index: Option<usize>) -> String {
         .unwrap_or_default()
 }
 
+// Signed exports carry one balance in the debit column; split it into debit/credit
+fn split_signed_amounts(debit: f64, credit: f64, convention: &SignConvention) -> (f64, f64) {
+    let signed = match convention {
+        SignConvention::DebitCreditColumns => return (debit, credit),
+        SignConvention::SignedDebitPositive => debit - credit,
+        SignConvention::SignedCreditPositive => credit - debit,
+    };
+    if signed >= 0.0 {
+        (signed, 0.0)
+    } else {
+        (0.0, -signed)
+    }
+}
+
 // Parse amount string to f64
 fn parse_amount(s: String) -> f64 {
     s.replace(",", "")
@@ -188,69 +896,332 @@ fn parse_amount(s: String) -> f64 {
 // Generate leadsheet
 fn generate_leadsheet(accounts: &[AccountData]) -> Result<Leadsheet> {
     let mut opening_balance = 0.0;
-    let mut adjustments = 0.0;
+    let mut period_activity = 0.0;
     let mut closing_balance = 0.0;
 
     for account in accounts {
         // Opening balance = Opening Debit - Opening Credit
         opening_balance += account.opening_debit - account.opening_credit;
 
-        // Adjustments = Period Debit - Period Credit
-        adjustments += account.period_debit - account.period_credit;
+        // Period activity = Period Debit - Period Credit
+        period_activity += account.period_debit - account.period_credit;
 
         // Closing balance = YTD Debit - YTD Credit
         closing_balance += account.ytd_debit - account.ytd_credit;
     }
 
+    // A dataset carries no audit adjustments
     Ok(Leadsheet {
+        source: Some(LeadsheetSource::Dataset),
         accounts: accounts.to_vec(),
-        opening_balance,
-        adjustments,
+        lines: Some(Vec::new()),
+        opening_balance: Some(opening_balance),
+        period_activity: Some(period_activity),
+        unadjusted_balance: Some(closing_balance),
+        adjustments: 0.0,
         closing_balance,
         created_at: time(),
     })
 }
 
-// Calculate financial ratios
-fn calculate_ratios(accounts: &[AccountData]) -> Result<Vec<FinancialRatio>> {
-    let mut ratios = Vec::new();
+// Leadsheet from the live trial balance. Posting an AJE updates the account
+// balances, so the unadjusted balance is the current one less posted AJEs.
+fn generate_trial_balance_leadsheet(
+    trial_balance_id: u64,
+    accounts: &[TrialBalanceAccount],
+    selected_accounts: &[String],
+) -> Leadsheet {
+    let effects = adjustments::posted_effects_by_account(trial_balance_id);
+
+    let mut lines: Vec<LeadsheetLine> = accounts
+        .iter()
+        .filter(|account| {
+            selected_accounts.is_empty() || selected_accounts.contains(&account.account_number)
+        })
+        .map(|account| {
+            let adjusted_balance = account.debit_balance - account.credit_balance;
+            let (adjustments, aje_ids) = effects.get(&account.id).cloned().unwrap_or_default();
+            LeadsheetLine {
+                account_id: account.id,
+                account_number: account.account_number.clone(),
+                account_name: account.account_name.clone(),
+                fs_line_item: account.fs_line_item.clone(),
+                unadjusted_balance: adjusted_balance - adjustments,
+                adjustments,
+                adjusted_balance,
+                aje_ids,
+            }
+        })
+        .collect();
+    lines.sort_by(|a, b| a.account_number.cmp(&b.account_number));
+
+    let total = |f: fn(&LeadsheetLine) -> i64| lines.iter().map(f).sum::<i64>() as f64 / 100.0;
+
+    Leadsheet {
+        source: Some(LeadsheetSource::TrialBalance),
+        accounts: Vec::new(),
+        opening_balance: None,
+        period_activity: None,
+        unadjusted_balance: Some(total(|line| line.unadjusted_balance)),
+        adjustments: total(|line| line.adjustments),
+        closing_balance: total(|line| line.adjusted_balance),
+        lines: Some(lines),
+        created_at: time(),
+    }
+}
 
-    // Calculate total assets, liabilities, equity, revenue, expenses
-    let mut total_debits = 0.0;
-    let mut total_credits = 0.0;
+// Account numbers on a working paper's leadsheet
+pub fn leadsheet_account_numbers(wp: &WorkingPaper) -> HashSet<String> {
+    wp.leadsheet
+        .as_ref()
+        .map(|leadsheet| leadsheet_balances(leadsheet).into_keys().collect())
+        .unwrap_or_default()
+}
 
-    for account in accounts {
-        total_debits += account.ytd_debit;
-        total_credits += account.ytd_credit;
+// Closing balance and name per account, in currency units
+fn leadsheet_balances(leadsheet: &Leadsheet) -> HashMap<String, (String, f64)> {
+    let mut balances: HashMap<String, (String, f64)> = HashMap::new();
+    for account in &leadsheet.accounts {
+        let (_, balance) = balances
+            .entry(account.account_number.clone())
+            .or_insert_with(|| (account.account_name.clone(), 0.0));
+        *balance += account.ytd_debit - account.ytd_credit;
     }
+    for line in leadsheet.lines.iter().flatten() {
+        let (_, balance) = balances
+            .entry(line.account_number.clone())
+            .or_insert_with(|| (line.account_name.clone(), 0.0));
+        *balance += line.adjusted_balance as f64 / 100.0;
+    }
+    balances
+}
 
-    // Net movement
-    let net_movement = total_debits - total_credits;
-    ratios.push(FinancialRatio {
-        name: "Net Movement".to_string(),
-        value: net_movement,
-        formula: "Total YTD Debits - Total YTD Credits".to_string(),
-    });
+// Calculate financial ratios from the accounts of the linked trial balance
+fn calculate_ratios(accounts: Option<&[TrialBalanceAccount]>) -> Result<Vec<FinancialRatio>> {
+    let tb = ClassifiedBalances::new(accounts);
+
+    let current_assets = || tb.fs_line("Current Assets", "BS_CURRENT_ASSETS", CURRENT_ASSET_LINES);
+    let current_liabilities =
+        || tb.fs_line("Current Liabilities", "BS_CURRENT_LIAB", CURRENT_LIABILITY_LINES);
+    let cogs = || tb.fs_line("Cost of Sales", "IS_COGS", &["IS_COGS"]);
+    let inventory = || tb.fs_line("Inventory", "BS_INVENTORY", &["BS_INVENTORY"]);
+
+    let ratios = vec![
+        ratio(
+            "Current Ratio",
+            "Current Assets / Current Liabilities",
+            vec![current_assets(), current_liabilities()],
+            |v| divide(v[0], v[1], "Current Liabilities"),
+        ),
+        ratio(
+            "Quick Ratio",
+            "(Current Assets - Inventory) / Current Liabilities",
+            // No inventory line just means there is nothing to strip out
+            vec![
+                current_assets(),
+                inventory().or_else(|_| tb.zero("Inventory", "BS_INVENTORY")),
+                current_liabilities(),
+            ],
+            |v| divide(v[0] - v[1], v[2], "Current Liabilities"),
+        ),
+        ratio(
+            "Debt to Equity",
+            "Total Liabilities / Total Equity",
+            vec![tb.liabilities(), tb.equity()],
+            |v| divide(v[0], v[1], "Total Equity"),
+        ),
+        ratio(
+            "Gross Margin %",
+            "(Revenue - Cost of Sales) / Revenue x 100",
+            vec![tb.revenue(), cogs()],
+            |v| divide(v[0] - v[1], v[0], "Revenue").map(|r| r * 100.0),
+        ),
+        ratio(
+            "Net Margin %",
+            "Net Income / Revenue x 100",
+            vec![tb.net_income(), tb.revenue()],
+            |v| divide(v[0], v[1], "Revenue").map(|r| r * 100.0),
+        ),
+        ratio(
+            "Days Sales Outstanding",
+            "Trade Receivables / Revenue x 365",
+            vec![tb.fs_line("Trade Receivables", "BS_AR", &["BS_AR"]), tb.revenue()],
+            |v| divide(v[0], v[1], "Revenue").map(|r| r * DAYS_IN_YEAR),
+        ),
+        ratio(
+            "Days Payable Outstanding",
+            "Trade Payables / Cost of Sales x 365",
+            vec![tb.fs_line("Trade Payables", "BS_AP", &["BS_AP"]), cogs()],
+            |v| divide(v[0], v[1], "Cost of Sales").map(|r| r * DAYS_IN_YEAR),
+        ),
+        ratio(
+            "Inventory Turnover",
+            "Cost of Sales / Inventory",
+            vec![cogs(), inventory()],
+            |v| divide(v[0], v[1], "Inventory"),
+        ),
+        ratio(
+            "Interest Cover",
+            "(Net Income + Interest Expense) / Interest Expense",
+            vec![
+                tb.net_income(),
+                tb.fs_line("Interest Expense", "IS_INTEREST", &["IS_INTEREST"]),
+            ],
+            |v| divide(v[0] + v[1], v[1], "Interest Expense"),
+        ),
+        ratio(
+            "Return on Assets %",
+            "Net Income / Total Assets x 100",
+            vec![tb.net_income(), tb.assets()],
+            |v| divide(v[0], v[1], "Total Assets").map(|r| r * 100.0),
+        ),
+    ];
 
-    // Activity ratio (transaction volume)
-    let total_activity = total_debits + total_credits;
-    ratios.push(FinancialRatio {
-        name: "Total Activity".to_string(),
-        value: total_activity,
-        formula: "Total YTD Debits + Total YTD Credits".to_string(),
-    });
+    Ok(ratios)
+}
 
-    // Average balance per account
-    if !accounts.is_empty() {
-        let avg_balance = net_movement / accounts.len() as f64;
-        ratios.push(FinancialRatio {
-            name: "Average Balance per Account".to_string(),
-            value: avg_balance,
-            formula: "Net Movement / Number of Accounts".to_string(),
-        });
+type RatioInputResult = std::result::Result<RatioInput, String>;
+
+// Trial balance totals by FS line and by account type, in natural sign
+struct ClassifiedBalances {
+    linked: bool,
+    lines: HashMap<String, i64>,
+    mapped_lines: HashSet<String>,
+    assets: Option<i64>, // None when the trial balance has no account of the type
+    liabilities: Option<i64>,
+    equity: Option<i64>,
+    revenue: Option<i64>,
+    expenses: Option<i64>,
+}
+
+impl ClassifiedBalances {
+    fn new(accounts: Option<&[TrialBalanceAccount]>) -> Self {
+        let accounts = accounts.unwrap_or_default();
+        let type_total = |wanted: fn(&AccountType) -> bool| {
+            let matching: Vec<&TrialBalanceAccount> =
+                accounts.iter().filter(|a| wanted(&a.account_type)).collect();
+            (!matching.is_empty())
+                .then(|| matching.iter().map(|a| financial_statements::net_balance(a)).sum())
+        };
+
+        ClassifiedBalances {
+            linked: !accounts.is_empty(),
+            lines: financial_statements::fs_line_totals(accounts),
+            mapped_lines: accounts
+                .iter()
+                .filter_map(|a| a.fs_line_item.clone())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            assets: type_total(|t| matches!(t, AccountType::Asset)),
+            liabilities: type_total(|t| matches!(t, AccountType::Liability)),
+            equity: type_total(|t| matches!(t, AccountType::Equity)),
+            revenue: type_total(|t| matches!(t, AccountType::Revenue)),
+            expenses: type_total(|t| matches!(t, AccountType::Expense)),
+        }
     }
 
-    Ok(ratios)
+    // An FS line (or subtotal) that needs at least one of its component lines mapped
+    fn fs_line(&self, name: &str, code: &str, components: &[&str]) -> RatioInputResult {
+        if !self.linked {
+            return Err(NO_TRIAL_BALANCE.to_string());
+        }
+        if !components.iter().any(|c| self.mapped_lines.contains(*c)) {
+            return Err(format!("No accounts mapped to {}", components.join(" / ")));
+        }
+        Ok(ratio_input(name, self.lines.get(code).copied().unwrap_or(0), code))
+    }
+
+    fn zero(&self, name: &str, code: &str) -> RatioInputResult {
+        if !self.linked {
+            return Err(NO_TRIAL_BALANCE.to_string());
+        }
+        Ok(ratio_input(name, 0, &format!("{} (no accounts mapped)", code)))
+    }
+
+    fn account_type(&self, name: &str, total: Option<i64>, label: &str) -> RatioInputResult {
+        if !self.linked {
+            return Err(NO_TRIAL_BALANCE.to_string());
+        }
+        total
+            .map(|value| ratio_input(name, value, &format!("{} accounts", label)))
+            .ok_or_else(|| format!("No {} accounts in the trial balance", label.to_lowercase()))
+    }
+
+    fn assets(&self) -> RatioInputResult {
+        self.account_type("Total Assets", self.assets, "Asset")
+    }
+
+    fn liabilities(&self) -> RatioInputResult {
+        self.account_type("Total Liabilities", self.liabilities, "Liability")
+    }
+
+    // Revenue mapped on the income statement, else all revenue accounts
+    fn revenue(&self) -> RatioInputResult {
+        self.fs_line("Revenue", "IS_REVENUE", &["IS_REVENUE"])
+            .or_else(|_| self.account_type("Revenue", self.revenue, "Revenue"))
+    }
+
+    fn net_income(&self) -> RatioInputResult {
+        if self.revenue.is_none() && self.expenses.is_none() {
+            self.account_type("Net Income", None, "Revenue or Expense")
+        } else {
+            let value = self.revenue.unwrap_or(0) - self.expenses.unwrap_or(0);
+            self.account_type("Net Income", Some(value), "Revenue - Expense")
+        }
+    }
+
+    // Unclosed trial balance: the current-year result is still outside equity
+    fn equity(&self) -> RatioInputResult {
+        let equity = self.account_type("Total Equity", self.equity, "Equity")?;
+        let result = self.net_income().map(|r| r.value).unwrap_or(0.0);
+        Ok(RatioInput {
+            value: equity.value + result,
+            source: "Equity accounts + current-year result".to_string(),
+            ..equity
+        })
+    }
+}
+
+fn ratio_input(name: &str, cents: i64, source: &str) -> RatioInput {
+    RatioInput {
+        name: name.to_string(),
+        value: cents as f64 / 100.0,
+        source: source.to_string(),
+    }
+}
+
+fn ratio(
+    name: &str,
+    formula: &str,
+    inputs: Vec<RatioInputResult>,
+    compute: impl Fn(&[f64]) -> std::result::Result<f64, String>,
+) -> FinancialRatio {
+    let mut missing: Vec<String> = inputs.iter().filter_map(|i| i.clone().err()).collect();
+    missing.dedup();
+    let inputs: Vec<RatioInput> = inputs.into_iter().filter_map(|i| i.ok()).collect();
+
+    let value = if missing.is_empty() {
+        let values: Vec<f64> = inputs.iter().map(|i| i.value).collect();
+        compute(&values).map_err(|reason| missing.push(reason)).ok()
+    } else {
+        None
+    };
+
+    FinancialRatio {
+        name: name.to_string(),
+        value,
+        formula: formula.to_string(),
+        inputs: Some(inputs),
+        unavailable_reason: (!missing.is_empty()).then(|| missing.join("; ")),
+    }
+}
+
+fn divide(numerator: f64, denominator: f64, denominator_name: &str) -> std::result::Result<f64, String> {
+    if denominator.abs() < 0.005 {
+        Err(format!("{} is zero", denominator_name))
+    } else {
+        Ok(numerator / denominator)
+    }
 }
 
 // Generate trend analysis
@@ -284,37 +1255,6 @@ fn generate_trend_analysis(accounts: &[AccountData]) -> Result<Vec<TrendAnalysis
     Ok(analyses)
 }
 
-// Generate variance analysis
-fn generate_variance_analysis(accounts: &[AccountData]) -> Result<Vec<VarianceAnalysis>> {
-    let mut analyses = Vec::new();
-
-    // Period movements vs expected (using period as actual, opening as baseline)
-    let mut total_period_movement = 0.0;
-    let mut total_opening = 0.0;
-
-    for account in accounts {
-        total_period_movement += account.period_debit - account.period_credit;
-        total_opening += account.opening_debit - account.opening_credit;
-    }
-
-    let variance = total_period_movement - total_opening;
-    let variance_percent = if total_opening != 0.0 {
-        (variance / total_opening) * 100.0
-    } else {
-        0.0
-    };
-
-    analyses.push(VarianceAnalysis {
-        item_name: "Period Activity vs Opening Balance".to_string(),
-        actual: total_period_movement,
-        expected: total_opening,
-        variance,
-        variance_percent,
-    });
-
-    Ok(analyses)
-}
-
 // Get working paper by ID
 pub fn get_working_paper(caller: Principal, id: u64) -> Result<WorkingPaper> {
     let _user = auth::get_user(caller).ok_or("User not found")?;
@@ -344,6 +1284,58 @@ pub fn list_working_papers_by_engagement(
     Ok(working_papers)
 }
 
+// Flag every working paper built on an older version of the dataset
+pub fn mark_working_papers_stale(caller: Principal, dataset_id: u64, reason: &str) -> Vec<u64> {
+    mark_stale(caller, |wp| wp.dataset_id == Some(dataset_id), reason)
+}
+
+// Flag every working paper whose leadsheet comes from the trial balance
+pub fn mark_trial_balance_papers_stale(caller: Principal, trial_balance_id: u64, reason: &str) -> Vec<u64> {
+    mark_stale(
+        caller,
+        |wp| wp.dataset_id.is_none() && wp.trial_balance_id == Some(trial_balance_id),
+        reason,
+    )
+}
+
+fn mark_stale(caller: Principal, affects: impl Fn(&WorkingPaper) -> bool, reason: &str) -> Vec<u64> {
+    let working_papers: Vec<WorkingPaper> = STORAGE.with(|storage| {
+        storage
+            .borrow()
+            .working_papers
+            .iter()
+            .filter(|(_, wp)| affects(wp) && status_of(wp) != WorkingPaperStatus::Locked)
+            .map(|(_, wp)| wp)
+            .collect()
+    });
+
+    let mut stale_ids = Vec::new();
+    for mut wp in working_papers {
+        wp.is_stale = Some(true);
+        wp.stale_reason = Some(reason.to_string());
+
+        STORAGE.with(|storage| {
+            storage
+                .borrow_mut()
+                .working_papers
+                .insert(wp.id, wp.clone());
+        });
+
+        log_activity(
+            caller,
+            "mark_working_paper_stale".to_string(),
+            "working_paper".to_string(),
+            wp.id.to_string(),
+            format!("Working paper {} marked stale: {}", wp.name, reason),
+            encode_args((wp.clone(),)).ok(),
+        );
+
+        stale_ids.push(wp.id);
+    }
+
+    stale_ids
+}
+
 // Link document to working paper
 pub fn link_document_to_working_paper(
     caller: Principal,
@@ -360,8 +1352,11 @@ pub fn link_document_to_working_paper(
         .with(|storage| storage.borrow().working_papers.get(&working_paper_id))
         .ok_or_else(|| "Working paper not found".to_string())?;
 
+    ensure_editable(&wp)?;
+
     if !wp.linked_document_ids.contains(&document_id) {
         wp.linked_document_ids.push(document_id);
+        record_edit(&mut wp, caller);
     }
 
     let snapshot_wp = wp.clone();
@@ -7,6 +7,24 @@ export type AcceptanceDecision = { 'Rejected' : null } |
   { 'Accepted' : null } |
   { 'RequiresPartnerReview' : null } |
   { 'Pending' : null };
+export type AccountChangeKind = { 'New' : null } |
+  { 'Split' : null } |
+  { 'Merged' : null } |
+  { 'Renamed' : null } |
+  { 'Unchanged' : null } |
+  { 'Renumbered' : null } |
+  { 'Removed' : null };
+export interface AccountComparison {
+  'compare_accounts' : Array<ComparedAccount>,
+  'change_percent' : [] | [number],
+  'base_accounts' : Array<ComparedAccount>,
+  'match_method' : [] | [AccountMatchMethod],
+  'kind' : AccountChangeKind,
+  'compare_balance' : bigint,
+  'change' : bigint,
+  'name_similarity' : [] | [number],
+  'base_balance' : bigint,
+}
 export interface AccountData {
   'entity' : string,
   'opening_debit' : number,
@@ -22,6 +40,42 @@ export interface AccountData {
   'account_name' : string,
   'project' : string,
 }
+export interface AccountMapping {
+  'id' : bigint,
+  'owner' : CoaOwner,
+  'current_account_number' : string,
+  'prior_account_number' : string,
+  'confirmed_at' : bigint,
+  'confirmed_by' : Principal,
+}
+export interface AccountMappingInput {
+  'current_account_number' : string,
+  'prior_account_number' : string,
+}
+export type AccountMatchMethod = { 'Name' : null } |
+  { 'Confirmed' : null } |
+  { 'Number' : null };
+export interface AccountRate { 'rate' : number, 'account_number' : string }
+export interface AccountReconciliation {
+  'id' : bigint,
+  'account_id' : bigint,
+  'prepared_by' : [] | [SignOff],
+  'status' : ReconciliationStatus,
+  'updated_at' : bigint,
+  'source_reference' : string,
+  'document_ids' : BigUint64Array | bigint[],
+  'trial_balance_id' : bigint,
+  'source' : ReconciliationSource,
+  'unreconciled_difference' : bigint,
+  'reviewed_by' : [] | [SignOff],
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'supporting_balance' : bigint,
+  'account_number' : string,
+  'account_balance' : bigint,
+  'reconciling_items' : Array<ReconcilingItem>,
+  'account_name' : string,
+}
 export type AccountType = { 'Asset' : null } |
   { 'Liability' : null } |
   { 'Revenue' : null } |
@@ -41,16 +95,69 @@ export interface ActivityLogEntry {
   'details' : string,
   'block_height' : bigint,
 }
+export interface AddAnnotationRequest {
+  'working_paper_id' : bigint,
+  'comment' : [] | [string],
+  'account_number' : string,
+  'tick_mark_id' : bigint,
+}
+export interface AddCrossReferenceRequest {
+  'working_paper_id' : bigint,
+  'description' : string,
+  'target' : ReferenceTarget,
+  'account_number' : [] | [string],
+}
 export interface AddFSNoteRequest {
   'fs_id' : bigint,
   'title' : string,
   'content' : string,
 }
+export interface AddReviewNoteRequest {
+  'working_paper_id' : bigint,
+  'note' : string,
+  'target' : ReviewNoteTarget,
+}
+export interface AdjustedAccountRow {
+  'account_id' : bigint,
+  'reclassification_adjustments' : bigint,
+  'fs_line_item' : [] | [string],
+  'adjusting_adjustments' : bigint,
+  'unadjusted_balance' : bigint,
+  'adjustments' : BigInt64Array | bigint[],
+  'account_number' : string,
+  'proposed_adjustments' : bigint,
+  'reversing_adjustments' : bigint,
+  'pro_forma_balance' : [] | [bigint],
+  'adjusted_balance' : bigint,
+  'posted_adjustments' : bigint,
+  'account_name' : string,
+  'account_type' : FSCategory,
+}
+export interface AdjustedTrialBalance {
+  'unadjusted_total' : bigint,
+  'trial_balance_id' : bigint,
+  'proposed_total' : bigint,
+  'generated_at' : bigint,
+  'adjusting_total' : bigint,
+  'posted_total' : bigint,
+  'is_balanced' : boolean,
+  'accounts' : Array<AdjustedAccountRow>,
+  'engagement_id' : bigint,
+  'include_proposed' : boolean,
+  'reversing_total' : bigint,
+  'reclassification_total' : bigint,
+  'columns' : Array<AjeColumn>,
+  'adjusted_total' : bigint,
+}
 export interface AdjustingJournalEntry {
   'id' : bigint,
   'status' : AjeStatus,
+  'passed_at' : [] | [bigint],
+  'passed_by' : [] | [Principal],
   'trial_balance_id' : bigint,
   'blockchain_signature' : string,
+  'reversal_pending' : [] | [boolean],
+  'entry_type' : [] | [AjeEntryType],
   'approved_at' : [] | [bigint],
   'approved_by' : [] | [Principal],
   'reviewed_at' : [] | [bigint],
@@ -59,9 +166,14 @@ export interface AdjustingJournalEntry {
   'created_at' : bigint,
   'created_by' : Principal,
   'blockchain_hash' : string,
+  'reverses_aje_id' : [] | [bigint],
+  'reversal_aje_id' : [] | [bigint],
   'engagement_id' : bigint,
+  'passed_reason' : [] | [string],
+  'proof_version' : [] | [number],
   'amount' : bigint,
   'aje_number' : string,
+  'classification' : [] | [MisstatementClassification],
 }
 export interface AjeBlockchainVerification {
   'status' : string,
@@ -77,6 +189,17 @@ export interface AjeBlockchainVerification {
   'verified_at' : bigint,
   'verified_by' : Principal,
 }
+export interface AjeColumn {
+  'status' : AjeStatus,
+  'aje_id' : bigint,
+  'entry_type' : AjeEntryType,
+  'description' : string,
+  'is_posted' : boolean,
+  'aje_number' : string,
+}
+export type AjeEntryType = { 'AutoReversing' : null } |
+  { 'Adjusting' : null } |
+  { 'Reclassification' : null };
 export interface AjeLineItem {
   'id' : bigint,
   'account_id' : bigint,
@@ -87,12 +210,45 @@ export interface AjeLineItem {
   'credit_amount' : bigint,
   'account_name' : string,
 }
-export type AjeStatus = { 'Posted' : null } |
+export type AjeStatus = { 'Passed' : null } |
+  { 'Posted' : null } |
   { 'Approved' : null } |
   { 'Draft' : null } |
   { 'Rejected' : null } |
   { 'Proposed' : null } |
   { 'Reviewed' : null };
+export interface AnalyticalExpectation {
+  'id' : bigint,
+  'method' : ExpectationMethod,
+  'actual' : bigint,
+  'trial_balance_id' : bigint,
+  'variance_percent' : [] | [number],
+  'expected' : bigint,
+  'explanation' : [] | [string],
+  'variance' : bigint,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'outside_threshold' : boolean,
+  'target' : ExpectationTarget,
+  'threshold_percent' : [] | [number],
+  'explained_at' : [] | [bigint],
+  'explained_by' : [] | [Principal],
+  'r_squared' : [] | [number],
+  'engagement_id' : bigint,
+  'basis' : string,
+  'threshold_amount' : [] | [bigint],
+  'target_name' : string,
+}
+export interface Annotation {
+  'id' : bigint,
+  'working_paper_id' : bigint,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'comment' : [] | [string],
+  'account_number' : string,
+  'engagement_id' : bigint,
+  'tick_mark_id' : bigint,
+}
 export interface ApplyTemplateRequest {
   'name' : [] | [string],
   'template_id' : bigint,
@@ -103,6 +259,28 @@ export interface ApproveDocumentInput {
   'approved' : boolean,
   'rejection_reason' : [] | [string],
 }
+export interface AuditSample {
+  'id' : bigint,
+  'method' : SamplingMethod,
+  'strata' : Array<StratumSummary>,
+  'dataset_id' : bigint,
+  'evaluation' : [] | [SampleEvaluation],
+  'name' : string,
+  'seed' : bigint,
+  'sheet_name' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'sampling_interval' : [] | [number],
+  'dataset_version' : number,
+  'engagement_id' : bigint,
+  'reference_column' : [] | [string],
+  'excluded_count' : bigint,
+  'items' : Array<SampleItem>,
+  'complete_population' : boolean,
+  'population_value' : number,
+  'amount_column' : string,
+  'population_count' : bigint,
+}
 export interface AuditTemplate {
   'id' : bigint,
   'is_public' : boolean,
@@ -124,6 +302,26 @@ export interface BlockchainProof {
   'entry_id' : bigint,
   'block_height' : bigint,
 }
+export interface BookAdjustment {
+  'aje' : AdjustingJournalEntry,
+  'line_items' : Array<AjeLineItem>,
+}
+export interface BrokenReference {
+  'working_paper_id' : bigint,
+  'source' : string,
+  'target' : string,
+  'reason' : string,
+}
+export interface CellChange {
+  'old_value' : string,
+  'column_name' : string,
+  'new_value' : string,
+}
+export interface CellInput { 'address' : string, 'input' : string }
+export type CellValue = { 'Empty' : null } |
+  { 'Text' : string } |
+  { 'Boolean' : boolean } |
+  { 'Number' : number };
 export interface ChecklistItem {
   'id' : string,
   'title' : string,
@@ -202,10 +400,43 @@ export interface ClientAccess {
 export type ClientAccessLevel = { 'Full' : null } |
   { 'UploadDocuments' : null } |
   { 'ViewOnly' : null };
+export interface CoaAccount {
+  'id' : bigint,
+  'updated_at' : bigint,
+  'fs_line_item' : [] | [string],
+  'owner' : CoaOwner,
+  'normal_balance' : NormalBalance,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'account_number' : string,
+  'is_active' : boolean,
+  'account_name' : string,
+  'account_type' : FSCategory,
+}
+export interface CoaAccountInput {
+  'fs_line_item' : [] | [string],
+  'normal_balance' : [] | [NormalBalance],
+  'account_number' : string,
+  'account_name' : string,
+  'account_type' : FSCategory,
+}
+export interface CoaMappingReport {
+  'trial_balance_id' : bigint,
+  'owner' : [] | [CoaOwner],
+  'missing_accounts' : Array<CoaAccount>,
+  'new_accounts' : Array<TrialBalanceAccount>,
+  'type_mismatches' : Array<TrialBalanceAccount>,
+  'abnormal_balances' : Array<TrialBalanceAccount>,
+  'unmapped_accounts' : Array<TrialBalanceAccount>,
+  'matched_accounts' : number,
+}
+export type CoaOwner = { 'Entity' : bigint } |
+  { 'Client' : bigint };
 export interface ColumnMapping {
   'entity' : [] | [string],
   'opening_debit' : [] | [string],
   'period_credit' : [] | [string],
+  'sign_convention' : [] | [SignConvention],
   'account_number' : [] | [string],
   'currency' : [] | [string],
   'notes' : [] | [string],
@@ -217,6 +448,11 @@ export interface ColumnMapping {
   'account_name' : [] | [string],
   'project' : [] | [string],
 }
+export interface ColumnMaskingPolicy {
+  'column_name' : string,
+  'method' : MaskingMethod,
+  'sheet_name' : [] | [string],
+}
 export interface ColumnMetadata {
   'max_value' : string,
   'pii_detection' : PIIDetection,
@@ -227,12 +463,18 @@ export interface ColumnMetadata {
   'sample_values' : Array<string>,
   'original_name' : string,
   'null_percent' : number,
+  'masking' : [] | [MaskingMethod],
 }
 export type ColumnType = { 'Date' : null } |
   { 'Text' : null } |
   { 'Boolean' : null } |
   { 'Currency' : null } |
   { 'Numeric' : null };
+export interface ComparedAccount {
+  'balance' : bigint,
+  'account_number' : string,
+  'account_name' : string,
+}
 export interface CompleteProfileRequest {
   'requested_role' : UserRole,
   'name' : string,
@@ -251,6 +493,63 @@ export interface ConflictCheck {
   'resolution_notes' : string,
   'client_id' : bigint,
 }
+export type ConformityLevel = { 'Nonconformity' : null } |
+  { 'AcceptableConformity' : null } |
+  { 'MarginallyAcceptable' : null } |
+  { 'CloseConformity' : null };
+export interface ConsolidateRequest {
+  'presentation_currency' : string,
+  'eliminations' : Array<EliminationEntry>,
+  'description' : [] | [string],
+  'entities' : Array<ConsolidationEntityInput>,
+  'engagement_id' : bigint,
+  'period_end_date' : string,
+  'organization_id' : bigint,
+  'intercompany_pairs' : Array<IntercompanyPair>,
+}
+export interface Consolidation {
+  'id' : bigint,
+  'presentation_currency' : string,
+  'trial_balance_id' : bigint,
+  'eliminations' : Array<EliminationEntry>,
+  'nci_share_of_profit' : bigint,
+  'rows' : Array<ConsolidationRow>,
+  'non_controlling_interest' : bigint,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'engagement_id' : bigint,
+  'period_end_date' : string,
+  'intercompany' : Array<IntercompanyMatch>,
+  'organization_id' : bigint,
+  'columns' : Array<ConsolidationColumn>,
+}
+export interface ConsolidationColumn {
+  'trial_balance_id' : bigint,
+  'ownership_percentage' : number,
+  'nci_share_of_profit' : bigint,
+  'nci_share_of_equity' : bigint,
+  'currency' : string,
+  'entity_name' : string,
+  'entity_id' : bigint,
+  'translation_difference' : bigint,
+}
+export interface ConsolidationEntityInput {
+  'trial_balance_id' : bigint,
+  'ownership_percentage' : number,
+  'historical_rates' : Array<AccountRate>,
+  'entity_id' : bigint,
+}
+export interface ConsolidationRow {
+  'fs_line_item' : [] | [string],
+  'eliminations' : bigint,
+  'nci_adjustment' : bigint,
+  'entity_balances' : BigInt64Array | bigint[],
+  'account_number' : string,
+  'consolidated_balance' : bigint,
+  'aggregated_balance' : bigint,
+  'account_name' : string,
+  'account_type' : FSCategory,
+}
 export interface CreateAjeLineItemRequest {
   'account_id' : bigint,
   'description' : string,
@@ -259,10 +558,21 @@ export interface CreateAjeLineItemRequest {
 }
 export interface CreateAjeRequest {
   'trial_balance_id' : bigint,
+  'entry_type' : [] | [AjeEntryType],
   'line_items' : Array<CreateAjeLineItemRequest>,
   'description' : string,
   'engagement_id' : bigint,
-  'aje_number' : string,
+}
+export interface CreateAuditSampleRequest {
+  'method' : SamplingMethod,
+  'dataset_id' : bigint,
+  'name' : string,
+  'seed' : [] | [bigint],
+  'file_data' : [] | [Uint8Array | number[]],
+  'sheet_name' : [] | [string],
+  'engagement_id' : bigint,
+  'reference_column' : [] | [string],
+  'amount_column' : string,
 }
 export interface CreateBudgetRequest {
   'total_budgeted_hours' : number,
@@ -336,6 +646,7 @@ export interface CreateEngagementRequest {
   'description' : string,
   'end_date' : bigint,
   'start_date' : bigint,
+  'prior_year_engagement_id' : [] | [bigint],
 }
 export interface CreateEngagementSetupTemplateRequest {
   'estimated_hours' : number,
@@ -353,12 +664,29 @@ export interface CreateEntityRequest {
   'organization_id' : bigint,
   'taxonomy' : [] | [XBRLTaxonomy],
 }
+export interface CreateExpectationRequest {
+  'method' : ExpectationMethod,
+  'trial_balance_id' : bigint,
+  'file_data' : [] | [Uint8Array | number[]],
+  'target' : ExpectationTarget,
+  'threshold_percent' : [] | [number],
+  'threshold_amount' : [] | [bigint],
+}
 export interface CreateInvitationRequest {
   'access_level' : ClientAccessLevel,
   'message' : [] | [string],
   'engagement_id' : bigint,
   'invited_email' : string,
 }
+export interface CreateMappingProfileRequest {
+  'erp_system' : ErpSystem,
+  'name' : string,
+  'sheet_name' : [] | [string],
+  'sign_convention' : SignConvention,
+  'header_row' : [] | [number],
+  'header_aliases' : Array<HeaderAlias>,
+  'client_id' : bigint,
+}
 export interface CreateMilestoneRequest {
   'estimated_hours' : number,
   'name' : string,
@@ -371,6 +699,14 @@ export interface CreateOrganizationRequest {
   'name' : string,
   'description' : string,
 }
+export interface CreateReconciliationRequest {
+  'account_id' : bigint,
+  'source_reference' : string,
+  'document_ids' : BigUint64Array | bigint[],
+  'source' : ReconciliationSource,
+  'supporting_balance' : bigint,
+  'reconciling_items' : Array<ReconcilingItemInput>,
+}
 export interface CreateTemplateRequest {
   'is_public' : boolean,
   'name' : string,
@@ -388,24 +724,134 @@ export interface CreateTimeEntryRequest {
   'milestone_id' : [] | [bigint],
 }
 export interface CreateTrialBalanceRequest {
+  'prior_trial_balance_id' : [] | [bigint],
   'description' : string,
   'currency' : [] | [string],
   'engagement_id' : bigint,
+  'period_type' : [] | [PeriodType],
   'period_end_date' : string,
 }
 export interface CreateWorkingPaperRequest {
-  'dataset_id' : bigint,
+  'reference_code' : [] | [string],
+  'trial_balance_id' : [] | [bigint],
+  'dataset_id' : [] | [bigint],
   'column_mapping' : ColumnMapping,
   'name' : string,
   'selected_accounts' : Array<string>,
   'engagement_id' : bigint,
+  'trend_thresholds' : [] | [SignificanceThresholds],
+}
+export interface CrossReference {
+  'id' : bigint,
+  'working_paper_id' : bigint,
+  'description' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'target' : ReferenceTarget,
+  'account_number' : [] | [string],
+  'engagement_id' : bigint,
 }
 export interface CsvAccountRow {
   'debit_balance' : bigint,
   'credit_balance' : bigint,
   'account_number' : string,
+  'currency' : [] | [string],
   'account_name' : string,
 }
+export type DataQualityCheck = { 'NotBlank' : { 'column' : string } } |
+  { 'NotBothNonZero' : { 'first_column' : string, 'second_column' : string } } |
+  { 'Unique' : { 'column' : string } } |
+  { 'ExcludeTotalsRow' : { 'label_columns' : Array<string> } } |
+  {
+    'DateWithinPeriod' : {
+      'period_end' : string,
+      'period_start' : string,
+      'column' : string,
+    }
+  };
+export interface DataQualityReport {
+  'block_on_critical' : boolean,
+  'dataset_id' : bigint,
+  'sheet_name' : string,
+  'results' : Array<RuleResult>,
+  'total_rows' : bigint,
+  'dataset_version' : number,
+  'warnings' : number,
+  'rows_checked' : bigint,
+  'evaluated_at' : bigint,
+  'evaluated_by' : Principal,
+  'critical_failures' : number,
+}
+export interface DataQualityRule {
+  'check' : DataQualityCheck,
+  'name' : string,
+  'severity' : RuleSeverity,
+}
+export interface DatasetDiff {
+  'sheets' : Array<SheetDiff>,
+  'dataset_id' : bigint,
+  'to_version' : number,
+  'from_version' : number,
+  'key_column' : string,
+}
+export interface DatasetVersion {
+  'id' : bigint,
+  'superseded_at' : bigint,
+  'superseded_by' : Principal,
+  'sheets' : Array<SheetData>,
+  'dataset_id' : bigint,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'file_name' : string,
+  'file_size' : bigint,
+  'version' : number,
+  'masking_policies' : Array<ColumnMaskingPolicy>,
+}
+export interface DefineTickMarkRequest {
+  'meaning' : string,
+  'engagement_id' : bigint,
+  'symbol' : string,
+}
+export interface DigitAnalysis {
+  'id' : bigint,
+  'mad' : number,
+  'column_name' : string,
+  'working_paper_id' : bigint,
+  'dataset_id' : bigint,
+  'degrees_of_freedom' : number,
+  'conformity' : ConformityLevel,
+  'chi_square' : number,
+  'test' : DigitTest,
+  'sheet_name' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'records_excluded' : bigint,
+  'frequencies' : Array<DigitFrequency>,
+  'records_tested' : bigint,
+  'dataset_version' : number,
+  'engagement_id' : bigint,
+  'top_deviations' : Array<DigitDeviation>,
+  'complete_population' : boolean,
+  'chi_square_critical' : number,
+}
+export interface DigitDeviation {
+  'expected_count' : number,
+  'z_statistic' : number,
+  'observed_count' : bigint,
+  'digits' : string,
+  'row_indexes' : BigUint64Array | bigint[],
+}
+export interface DigitFrequency {
+  'observed_proportion' : number,
+  'expected_count' : number,
+  'z_statistic' : number,
+  'observed_count' : bigint,
+  'digits' : string,
+  'expected_proportion' : number,
+}
+export type DigitTest = { 'FirstDigit' : null } |
+  { 'LastDigit' : null } |
+  { 'FirstTwoDigits' : null };
 export interface Document {
   'id' : bigint,
   'name' : string,
@@ -439,6 +885,15 @@ export type DocumentRequestStatus = { 'Approved' : null } |
   { 'Uploaded' : null } |
   { 'Cancelled' : null } |
   { 'Pending' : null };
+export interface EliminationEntry {
+  'description' : string,
+  'lines' : Array<EliminationLine>,
+}
+export interface EliminationLine {
+  'account_number' : string,
+  'debit_amount' : bigint,
+  'credit_amount' : bigint,
+}
 export interface Engagement {
   'id' : bigint,
   'status' : string,
@@ -449,6 +904,8 @@ export interface Engagement {
   'created_at' : bigint,
   'created_by' : Principal,
   'start_date' : bigint,
+  'materiality' : [] | [Materiality],
+  'prior_year_engagement_id' : [] | [bigint],
 }
 export interface EngagementBudget {
   'id' : bigint,
@@ -480,6 +937,7 @@ export interface EngagementChecklist {
 }
 export interface EngagementDashboard {
   'at_risk_milestones' : Array<EngagementMilestone>,
+  'unexplained_variances' : Array<AnalyticalExpectation>,
   'budget_utilization' : number,
   'completion_percentage' : number,
   'recent_time_entries' : Array<TimeEntry>,
@@ -576,6 +1034,39 @@ export interface Entity {
   'organization_id' : bigint,
   'taxonomy' : [] | [XBRLTaxonomy],
 }
+export type ErpSystem = { 'Sap' : null } |
+  { 'Odoo' : null } |
+  { 'Oracle' : null } |
+  { 'Other' : string } |
+  { 'Dynamics' : null };
+export interface EvaluateAuditSampleRequest {
+  'sample_id' : bigint,
+  'results' : Array<SampleItemResult>,
+  'tolerable_misstatement' : [] | [number],
+  'confidence' : [] | [number],
+}
+export type ExpectationMethod = {
+    'Budget' : {
+      'dataset_id' : bigint,
+      'sheet_name' : [] | [string],
+      'key_column' : string,
+      'amount_column' : string,
+    }
+  } |
+  {
+    'Regression' : {
+      'current_driver' : number,
+      'driver_name' : string,
+      'observations' : Array<RegressionPoint>,
+    }
+  } |
+  { 'PriorYearGrowth' : { 'growth_percent' : number } };
+export type ExpectationTarget = { 'FsLine' : string } |
+  { 'Account' : string };
+export interface ExplainVarianceRequest {
+  'explanation' : string,
+  'expectation_id' : bigint,
+}
 export type FSCategory = { 'Asset' : null } |
   { 'Liability' : null } |
   { 'Revenue' : null } |
@@ -584,6 +1075,7 @@ export type FSCategory = { 'Asset' : null } |
 export interface FSLine {
   'mapped_accounts' : BigUint64Array | bigint[],
   'line_item' : FSLineItem,
+  'prior_amounts' : [] | [Array<[] | [bigint]>],
   'amount' : bigint,
 }
 export interface FSLineItem {
@@ -603,8 +1095,10 @@ export interface FSNote {
   'note_number' : bigint,
 }
 export interface FinancialRatio {
-  'value' : number,
+  'value' : [] | [number],
   'name' : string,
+  'unavailable_reason' : [] | [string],
+  'inputs' : [] | [Array<RatioInput>],
   'formula' : string,
 }
 export interface FinancialStatement {
@@ -613,12 +1107,14 @@ export interface FinancialStatement {
   'created_at' : bigint,
   'created_by' : Principal,
   'lines' : Array<FSLine>,
+  'comparative_periods' : [] | [Array<TrendPeriod>],
   'notes' : Array<FSNote>,
   'engagement_id' : bigint,
   'period_end_date' : string,
   'last_modified' : bigint,
   'taxonomy' : XBRLTaxonomy,
 }
+export interface FsLineEffect { 'fs_line_item' : string, 'amount' : bigint }
 export interface FulfillDocumentRequestInput {
   'request_id' : bigint,
   'document_name' : string,
@@ -626,44 +1122,268 @@ export interface FulfillDocumentRequestInput {
   'file_type' : string,
   'category' : string,
 }
+export interface FxRate {
+  'id' : bigint,
+  'source' : FxRateSource,
+  'rate' : number,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'effective_date' : string,
+  'to_currency' : string,
+  'from_currency' : string,
+  'rate_type' : FxRateType,
+}
+export type FxRateSource = { 'Imported' : null } |
+  { 'Manual' : null };
+export type FxRateType = { 'Average' : null } |
+  { 'Closing' : null } |
+  { 'Historical' : null };
 export interface GenerateFSRequest {
   'trial_balance_id' : bigint,
   'taxonomy' : XBRLTaxonomy,
 }
+export interface GlColumnMapping {
+  'posting_date' : string,
+  'entry_date' : [] | [string],
+  'description' : [] | [string],
+  'entry_number' : string,
+  'credit' : [] | [string],
+  'account_number' : string,
+  'posted_by' : [] | [string],
+  'amount' : [] | [string],
+  'debit' : [] | [string],
+  'account_name' : [] | [string],
+}
+export interface GlImport {
+  'id' : bigint,
+  'imported_at' : bigint,
+  'imported_by' : Principal,
+  'dataset_id' : bigint,
+  'mapping' : GlColumnMapping,
+  'sheet_name' : string,
+  'entry_count' : bigint,
+  'dataset_version' : number,
+  'engagement_id' : bigint,
+  'total_credits' : bigint,
+  'total_debits' : bigint,
+  'complete_population' : boolean,
+  'line_count' : bigint,
+  'skipped_rows' : BigUint64Array | bigint[],
+  'unbalanced_entries' : Array<string>,
+}
 export interface GrantClientAccessRequest {
   'access_level' : ClientAccessLevel,
   'client_principal' : Principal,
   'engagement_id' : bigint,
 }
+export interface HeaderAlias {
+  'field' : MappingField,
+  'aliases' : Array<string>,
+}
 export interface ImportExcelRequest {
   'name' : string,
   'file_data' : Uint8Array | number[],
   'file_name' : string,
+  'auto_mask_pii' : [] | [boolean],
   'engagement_id' : [] | [bigint],
+  'mapping_profile_id' : [] | [bigint],
+  'masking_policies' : [] | [Array<ColumnMaskingPolicy>],
+}
+export interface ImportGlJournalRequest {
+  'dataset_id' : bigint,
+  'mapping' : GlColumnMapping,
+  'file_data' : [] | [Uint8Array | number[]],
+  'sheet_name' : [] | [string],
+  'engagement_id' : bigint,
+}
+export interface ImportMappingProfile {
+  'id' : bigint,
+  'updated_at' : bigint,
+  'erp_system' : ErpSystem,
+  'name' : string,
+  'sheet_name' : [] | [string],
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'sign_convention' : SignConvention,
+  'header_row' : number,
+  'header_aliases' : Array<HeaderAlias>,
+  'client_id' : bigint,
 }
 export interface ImportedDataset {
   'id' : bigint,
+  'quality_report' : [] | [DataQualityReport],
   'sheets' : Array<SheetData>,
   'name' : string,
+  'suggested_mapping' : [] | [MappingProfileMatch],
   'created_at' : bigint,
   'created_by' : Principal,
   'file_name' : string,
   'file_size' : bigint,
   'version' : number,
   'engagement_id' : [] | [bigint],
+  'masking_policies' : [] | [Array<ColumnMaskingPolicy>],
+}
+export interface IntercompanyMatch {
+  'balance' : bigint,
+  'counterparty_balance' : bigint,
+  'difference' : bigint,
+  'counterparty_entity_id' : bigint,
+  'account_number' : string,
+  'eliminated' : bigint,
+  'entity_id' : bigint,
+  'counterparty_account_number' : string,
+}
+export interface IntercompanyPair {
+  'counterparty_entity_id' : bigint,
+  'account_number' : string,
+  'entity_id' : bigint,
+  'counterparty_account_number' : string,
 }
 export type InvitationStatus = { 'Rejected' : null } |
   { 'Accepted' : null } |
   { 'Cancelled' : null } |
   { 'Expired' : null } |
   { 'Pending' : null };
+export interface JetConfig {
+  'approval_thresholds' : BigInt64Array | bigint[],
+  'holidays' : Array<string>,
+  'close_date' : [] | [string],
+  'round_amount_unit' : [] | [bigint],
+  'period_end_date' : string,
+  'weekend_days' : [] | [Uint32Array | number[]],
+  'just_below_percent' : [] | [number],
+  'rare_pair_max_count' : [] | [number],
+}
+export interface JetFinding {
+  'flags' : Array<JetFlag>,
+  'posting_date' : string,
+  'journal_entry_id' : bigint,
+  'entry_number' : string,
+  'posted_by' : string,
+  'details' : Array<string>,
+  'risk_score' : number,
+  'amount' : bigint,
+}
+export type JetFlag = { 'WeekendPosting' : null } |
+  { 'Duplicate' : null } |
+  { 'Unbalanced' : null } |
+  { 'HolidayPosting' : null } |
+  { 'PostClose' : null } |
+  { 'JustBelowThreshold' : null } |
+  { 'UnusualUserAccount' : null } |
+  { 'RoundAmount' : null };
+export interface JetFlagCount { 'flag' : JetFlag, 'count' : bigint }
+export interface JetRun {
+  'id' : bigint,
+  'entries_tested' : bigint,
+  'flagged_entries' : bigint,
+  'run_at' : bigint,
+  'run_by' : Principal,
+  'gl_import_id' : bigint,
+  'flag_counts' : Array<JetFlagCount>,
+  'engagement_id' : bigint,
+  'findings' : Array<JetFinding>,
+  'config' : JetConfig,
+}
+export interface JournalEntry {
+  'id' : bigint,
+  'posting_date' : string,
+  'entry_date' : [] | [string],
+  'description' : string,
+  'total_debit' : bigint,
+  'entry_number' : string,
+  'posted_by' : string,
+  'gl_import_id' : bigint,
+  'engagement_id' : bigint,
+  'total_credit' : bigint,
+  'line_count' : number,
+}
+export interface JournalLine {
+  'id' : bigint,
+  'journal_entry_id' : bigint,
+  'description' : string,
+  'credit' : bigint,
+  'line_number' : number,
+  'account_number' : string,
+  'gl_import_id' : bigint,
+  'row_index' : bigint,
+  'debit' : bigint,
+  'account_name' : string,
+}
 export interface Leadsheet {
+  'period_activity' : [] | [number],
+  'source' : [] | [LeadsheetSource],
   'closing_balance' : number,
-  'opening_balance' : number,
+  'opening_balance' : [] | [number],
+  'unadjusted_balance' : [] | [number],
   'created_at' : bigint,
+  'lines' : [] | [Array<LeadsheetLine>],
   'adjustments' : number,
   'accounts' : Array<AccountData>,
 }
+export interface LeadsheetChange {
+  'previous_balance' : [] | [number],
+  'current_balance' : [] | [number],
+  'account_number' : string,
+  'change' : number,
+  'account_name' : string,
+}
+export interface LeadsheetLine {
+  'account_id' : bigint,
+  'fs_line_item' : [] | [string],
+  'unadjusted_balance' : bigint,
+  'adjustments' : bigint,
+  'aje_ids' : BigUint64Array | bigint[],
+  'account_number' : string,
+  'adjusted_balance' : bigint,
+  'account_name' : string,
+}
+export interface LeadsheetRefresh {
+  'closing_balance' : number,
+  'changes' : Array<LeadsheetChange>,
+  'previous_closing_balance' : number,
+  'refreshed_at' : bigint,
+  'refreshed_by' : Principal,
+}
+export type LeadsheetSource = { 'TrialBalance' : null } |
+  { 'Dataset' : null };
+export type MappingField = { 'AccountNumber' : null } |
+  { 'OpeningDebit' : null } |
+  { 'YtdDebit' : null } |
+  { 'YtdCredit' : null } |
+  { 'Notes' : null } |
+  { 'Entity' : null } |
+  { 'PeriodCredit' : null } |
+  { 'OpeningCredit' : null } |
+  { 'AccountName' : null } |
+  { 'Currency' : null } |
+  { 'Department' : null } |
+  { 'Project' : null } |
+  { 'PeriodDebit' : null };
+export interface MappingProfileMatch {
+  'column_mapping' : ColumnMapping,
+  'sheet_name' : string,
+  'score' : number,
+  'matched_fields' : number,
+  'unmatched_fields' : Array<MappingField>,
+  'profile_id' : bigint,
+  'profile_name' : string,
+}
+export interface MaskDatasetColumnsRequest {
+  'dataset_id' : bigint,
+  'policies' : Array<ColumnMaskingPolicy>,
+}
+export type MaskingMethod = { 'Hash' : null } |
+  { 'Redact' : null } |
+  { 'PartialMask' : { 'visible_prefix' : number, 'visible_suffix' : number } };
+export interface Materiality {
+  'set_at' : bigint,
+  'set_by' : Principal,
+  'performance' : bigint,
+  'overall' : bigint,
+  'basis' : string,
+  'clearly_trivial' : bigint,
+}
 export type MilestoneStatus = { 'Blocked' : null } |
   { 'Cancelled' : null } |
   { 'InProgress' : null } |
@@ -675,6 +1395,43 @@ export interface MilestoneTemplate {
   'description' : string,
   'days_from_start' : bigint,
 }
+export type MisstatementClassification = { 'Judgmental' : null } |
+  { 'Projected' : null } |
+  { 'Factual' : null };
+export interface MisstatementSummary {
+  'judgmental_total' : bigint,
+  'projected_total' : bigint,
+  'current_year_profit_effect' : bigint,
+  'exceeds_performance_materiality' : [] | [boolean],
+  'generated_at' : bigint,
+  'conclusion' : string,
+  'factual_total' : bigint,
+  'materiality' : [] | [Materiality],
+  'exceeds_overall_materiality' : [] | [boolean],
+  'engagement_id' : bigint,
+  'equity_effect' : bigint,
+  'items' : Array<UncorrectedMisstatement>,
+  'turnaround_profit_effect' : bigint,
+  'fs_lines' : Array<FsLineEffect>,
+  'profit_before_tax_effect' : bigint,
+}
+export interface MultiPeriodTrend {
+  'trial_balance_id' : bigint,
+  'generated_at' : bigint,
+  'gaps' : Array<string>,
+  'periods' : Array<TrendPeriod>,
+  'accounts' : Array<TrendRow>,
+  'engagement_id' : bigint,
+  'thresholds' : SignificanceThresholds,
+  'fs_lines' : Array<TrendRow>,
+}
+export interface MultiPeriodTrendRequest {
+  'trial_balance_id' : bigint,
+  'thresholds' : [] | [SignificanceThresholds],
+  'years' : [] | [number],
+}
+export type NormalBalance = { 'Debit' : null } |
+  { 'Credit' : null };
 export interface Organization {
   'id' : bigint,
   'name' : string,
@@ -687,149 +1444,490 @@ export interface PIIDetection {
   'has_national_ids' : boolean,
   'has_emails' : boolean,
   'has_names' : boolean,
+  'findings' : [] | [Array<PIIFinding>],
   'has_phone_numbers' : boolean,
+  'has_ibans' : [] | [boolean],
+}
+export interface PIIFinding {
+  'matched_rows' : bigint,
+  'kind' : PIIKind,
+  'confidence' : number,
+}
+export type PIIKind = { 'Email' : null } |
+  { 'Iban' : null } |
+  { 'UaeEmiratesId' : null } |
+  { 'PersonName' : null } |
+  { 'EgyptianNationalId' : null } |
+  { 'PhoneNumber' : null } |
+  { 'SaudiNationalId' : null };
+export interface PIIVaultEntry {
+  'id' : bigint,
+  'column_name' : string,
+  'dataset_id' : bigint,
+  'sheet_name' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'dataset_version' : number,
+  'raw_sample_values' : Array<string>,
+  'raw_values' : Array<string>,
+}
+export interface PassAjeRequest {
+  'aje_id' : bigint,
+  'reason' : string,
+  'classification' : MisstatementClassification,
+}
+export type PeriodType = { 'PriorYear' : null } |
+  { 'Interim' : null } |
+  { 'YearEnd' : null };
+export interface RatioInput {
+  'value' : number,
+  'source' : string,
+  'name' : string,
+}
+export type ReconciliationSource = { 'Subledger' : null } |
+  { 'Confirmation' : null } |
+  { 'BankStatement' : null } |
+  { 'Other' : null };
+export type ReconciliationStatus = { 'Open' : null } |
+  { 'Prepared' : null } |
+  { 'Reviewed' : null };
+export interface ReconcilingItem {
+  'id' : number,
+  'document_id' : [] | [bigint],
+  'description' : string,
+  'item_date' : [] | [string],
+  'amount' : bigint,
+}
+export interface ReconcilingItemInput {
+  'document_id' : [] | [bigint],
+  'description' : string,
+  'item_date' : [] | [string],
+  'amount' : bigint,
+}
+export interface ReferenceIntegrityReport {
+  'broken' : Array<BrokenReference>,
+  'references_checked' : bigint,
+  'engagement_id' : bigint,
+  'checked_at' : bigint,
+}
+export type ReferenceTarget = { 'Aje' : bigint } |
+  { 'FsLine' : string } |
+  { 'Document' : bigint } |
+  { 'WorkingPaper' : bigint };
+export interface RegressionPoint { 'amount' : bigint, 'driver' : number }
+export interface ReimportDatasetRequest {
+  'dataset_id' : bigint,
+  'file_data' : Uint8Array | number[],
+  'sheet_name' : [] | [string],
+  'file_name' : string,
+  'auto_mask_pii' : [] | [boolean],
+  'key_column' : string,
+}
+export interface ReimportResult {
+  'dataset' : ImportedDataset,
+  'diff' : DatasetDiff,
+  'stale_working_paper_ids' : BigUint64Array | bigint[],
 }
 export interface RejectInvitationRequest {
   'invitation_id' : bigint,
   'reason' : [] | [string],
 }
+export interface ResolveReviewNoteRequest {
+  'note_id' : bigint,
+  'response' : string,
+}
 export type Result = { 'Ok' : ClientAccess } |
   { 'Err' : string };
-export type Result_1 = { 'Ok' : null } |
+export type Result_1 = { 'Ok' : Annotation } |
+  { 'Err' : string };
+export type Result_10 = { 'Ok' : DocumentRequest } |
+  { 'Err' : string };
+export type Result_100 = { 'Ok' : PIIVaultEntry } |
+  { 'Err' : string };
+export type Result_101 = { 'Ok' : TrialBalanceValidation } |
+  { 'Err' : string };
+export type Result_102 = { 'Ok' : VerificationResult } |
+  { 'Err' : string };
+export type Result_103 = { 'Ok' : AjeBlockchainVerification } |
+  { 'Err' : string };
+export type Result_104 = { 'Ok' : boolean } |
+  { 'Err' : string };
+export type Result_11 = { 'Ok' : ReferenceIntegrityReport } |
+  { 'Err' : string };
+export type Result_12 = { 'Ok' : TrialBalanceComparison } |
+  { 'Err' : string };
+export type Result_13 = { 'Ok' : User } |
+  { 'Err' : string };
+export type Result_14 = { 'Ok' : Array<AccountMapping> } |
+  { 'Err' : string };
+export type Result_15 = { 'Ok' : Consolidation } |
+  { 'Err' : string };
+export type Result_16 = { 'Ok' : AnalyticalExpectation } |
+  { 'Err' : string };
+export type Result_17 = { 'Ok' : AuditSample } |
+  { 'Err' : string };
+export type Result_18 = { 'Ok' : EngagementBudget } |
+  { 'Err' : string };
+export type Result_19 = { 'Ok' : Client } |
+  { 'Err' : string };
+export type Result_2 = { 'Ok' : CrossReference } |
+  { 'Err' : string };
+export type Result_20 = { 'Ok' : ConflictCheck } |
+  { 'Err' : string };
+export type Result_21 = { 'Ok' : Engagement } |
+  { 'Err' : string };
+export type Result_22 = { 'Ok' : [Engagement, Array<EngagementMilestone>] } |
+  { 'Err' : string };
+export type Result_23 = { 'Ok' : EngagementLetter } |
+  { 'Err' : string };
+export type Result_24 = { 'Ok' : EngagementSetupTemplate } |
+  { 'Err' : string };
+export type Result_25 = { 'Ok' : Entity } |
+  { 'Err' : string };
+export type Result_26 = { 'Ok' : EngagementInvitation } |
+  { 'Err' : string };
+export type Result_27 = { 'Ok' : ImportMappingProfile } |
+  { 'Err' : string };
+export type Result_28 = { 'Ok' : EngagementMilestone } |
+  { 'Err' : string };
+export type Result_29 = { 'Ok' : Organization } |
+  { 'Err' : string };
+export type Result_3 = { 'Ok' : null } |
+  { 'Err' : string };
+export type Result_30 = { 'Ok' : AccountReconciliation } |
+  { 'Err' : string };
+export type Result_31 = { 'Ok' : AuditTemplate } |
+  { 'Err' : string };
+export type Result_32 = { 'Ok' : TimeEntry } |
+  { 'Err' : string };
+export type Result_33 = { 'Ok' : TrialBalance } |
+  { 'Err' : string };
+export type Result_34 = { 'Ok' : WorkingPaper } |
+  { 'Err' : string };
+export type Result_35 = { 'Ok' : CoaAccount } |
+  { 'Err' : string };
+export type Result_36 = { 'Ok' : TickMark } |
+  { 'Err' : string };
+export type Result_37 = { 'Ok' : Uint8Array | number[] } |
+  { 'Err' : string };
+export type Result_38 = { 'Ok' : FinancialStatement } |
+  { 'Err' : string };
+export type Result_39 = { 'Ok' : Array<ActivityLogEntry> } |
+  { 'Err' : string };
+export type Result_4 = { 'Ok' : ReviewNote } |
+  { 'Err' : string };
+export type Result_40 = { 'Ok' : AdjustedTrialBalance } |
+  { 'Err' : string };
+export type Result_41 = { 'Ok' : Array<AjeLineItem> } |
+  { 'Err' : string };
+export type Result_42 = { 'Ok' : BlockchainProof } |
+  { 'Err' : string };
+export type Result_43 = { 'Ok' : Array<ClientAccess> } |
+  { 'Err' : string };
+export type Result_44 = { 'Ok' : CoaMappingReport } |
+  { 'Err' : string };
+export type Result_45 = { 'Ok' : DataQualityReport } |
+  { 'Err' : string };
+export type Result_46 = { 'Ok' : ImportedDataset } |
+  { 'Err' : string };
+export type Result_47 = { 'Ok' : DatasetDiff } |
   { 'Err' : string };
-export type Result_10 = { 'Ok' : ConflictCheck } |
+export type Result_48 = { 'Ok' : DigitAnalysis } |
   { 'Err' : string };
-export type Result_11 = { 'Ok' : Engagement } |
+export type Result_49 = { 'Ok' : Document } |
   { 'Err' : string };
-export type Result_12 = { 'Ok' : [Engagement, Array<EngagementMilestone>] } |
+export type Result_5 = { 'Ok' : TrialBalanceAccount } |
   { 'Err' : string };
-export type Result_13 = { 'Ok' : EngagementLetter } |
+export type Result_50 = { 'Ok' : Array<DocumentRequest> } |
   { 'Err' : string };
-export type Result_14 = { 'Ok' : EngagementSetupTemplate } |
+export type Result_51 = { 'Ok' : Array<EngagementChecklist> } |
   { 'Err' : string };
-export type Result_15 = { 'Ok' : Entity } |
+export type Result_52 = { 'Ok' : EngagementDashboard } |
   { 'Err' : string };
-export type Result_16 = { 'Ok' : EngagementInvitation } |
+export type Result_53 = { 'Ok' : GlImport } |
   { 'Err' : string };
-export type Result_17 = { 'Ok' : EngagementMilestone } |
+export type Result_54 = { 'Ok' : Array<EngagementInvitation> } |
   { 'Err' : string };
-export type Result_18 = { 'Ok' : Organization } |
+export type Result_55 = { 'Ok' : JetRun } |
   { 'Err' : string };
-export type Result_19 = { 'Ok' : AuditTemplate } |
+export type Result_56 = { 'Ok' : Array<JournalEntry> } |
   { 'Err' : string };
-export type Result_2 = { 'Ok' : TrialBalanceAccount } |
+export type Result_57 = { 'Ok' : Array<JournalLine> } |
   { 'Err' : string };
-export type Result_20 = { 'Ok' : TimeEntry } |
+export type Result_58 = { 'Ok' : MultiPeriodTrend } |
   { 'Err' : string };
-export type Result_21 = { 'Ok' : TrialBalance } |
+export type Result_59 = { 'Ok' : Array<[bigint, string, string]> } |
   { 'Err' : string };
-export type Result_22 = { 'Ok' : WorkingPaper } |
+export type Result_6 = { 'Ok' : Array<CoaAccount> } |
   { 'Err' : string };
-export type Result_23 = { 'Ok' : Uint8Array | number[] } |
+export type Result_60 = { 'Ok' : Array<TrialBalanceAccount> } |
   { 'Err' : string };
-export type Result_24 = { 'Ok' : FinancialStatement } |
+export type Result_61 = { 'Ok' : Array<TrialBalance> } |
   { 'Err' : string };
-export type Result_25 = { 'Ok' : Array<ActivityLogEntry> } |
+export type Result_62 = { 'Ok' : MisstatementSummary } |
   { 'Err' : string };
-export type Result_26 = { 'Ok' : Array<AjeLineItem> } |
+export type Result_63 = { 'Ok' : Array<UnreconciledAccount> } |
   { 'Err' : string };
-export type Result_27 = { 'Ok' : BlockchainProof } |
+export type Result_64 = { 'Ok' : Array<FxRate> } |
   { 'Err' : string };
-export type Result_28 = { 'Ok' : Array<ClientAccess> } |
+export type Result_65 = { 'Ok' : Array<AdjustingJournalEntry> } |
   { 'Err' : string };
-export type Result_29 = { 'Ok' : ImportedDataset } |
+export type Result_66 = { 'Ok' : Array<Annotation> } |
   { 'Err' : string };
-export type Result_3 = { 'Ok' : EngagementChecklist } |
+export type Result_67 = { 'Ok' : Array<AuditSample> } |
   { 'Err' : string };
-export type Result_30 = { 'Ok' : Document } |
+export type Result_68 = { 'Ok' : Array<BookAdjustment> } |
   { 'Err' : string };
-export type Result_31 = { 'Ok' : Array<DocumentRequest> } |
+export type Result_69 = { 'Ok' : Array<ClientAcceptance> } |
   { 'Err' : string };
-export type Result_32 = { 'Ok' : Array<EngagementChecklist> } |
+export type Result_7 = { 'Ok' : EngagementChecklist } |
   { 'Err' : string };
-export type Result_33 = { 'Ok' : EngagementDashboard } |
+export type Result_70 = { 'Ok' : Array<Client> } |
   { 'Err' : string };
-export type Result_34 = { 'Ok' : Array<EngagementInvitation> } |
+export type Result_71 = { 'Ok' : Array<ConflictCheck> } |
   { 'Err' : string };
-export type Result_35 = { 'Ok' : Array<[bigint, string, string]> } |
+export type Result_72 = { 'Ok' : Array<Consolidation> } |
   { 'Err' : string };
-export type Result_36 = { 'Ok' : Array<TrialBalanceAccount> } |
+export type Result_73 = { 'Ok' : Array<CrossReference> } |
   { 'Err' : string };
-export type Result_37 = { 'Ok' : Array<AdjustingJournalEntry> } |
+export type Result_74 = { 'Ok' : Array<DatasetVersion> } |
   { 'Err' : string };
-export type Result_38 = { 'Ok' : Array<ClientAcceptance> } |
+export type Result_75 = { 'Ok' : Array<ImportedDataset> } |
   { 'Err' : string };
-export type Result_39 = { 'Ok' : Array<Client> } |
+export type Result_76 = { 'Ok' : Array<DigitAnalysis> } |
   { 'Err' : string };
-export type Result_4 = { 'Ok' : AdjustingJournalEntry } |
+export type Result_77 = { 'Ok' : Array<Document> } |
   { 'Err' : string };
-export type Result_40 = { 'Ok' : Array<ConflictCheck> } |
+export type Result_78 = { 'Ok' : Array<EngagementLetter> } |
   { 'Err' : string };
-export type Result_41 = { 'Ok' : Array<ImportedDataset> } |
+export type Result_79 = { 'Ok' : Array<EngagementSetupTemplate> } |
   { 'Err' : string };
-export type Result_42 = { 'Ok' : Array<Document> } |
+export type Result_8 = { 'Ok' : AdjustingJournalEntry } |
   { 'Err' : string };
-export type Result_43 = { 'Ok' : Array<EngagementLetter> } |
+export type Result_80 = { 'Ok' : Array<Engagement> } |
   { 'Err' : string };
-export type Result_44 = { 'Ok' : Array<EngagementSetupTemplate> } |
+export type Result_81 = { 'Ok' : Array<Entity> } |
   { 'Err' : string };
-export type Result_45 = { 'Ok' : Array<Engagement> } |
+export type Result_82 = { 'Ok' : Array<AnalyticalExpectation> } |
   { 'Err' : string };
-export type Result_46 = { 'Ok' : Array<Entity> } |
+export type Result_83 = { 'Ok' : Array<FinancialStatement> } |
   { 'Err' : string };
-export type Result_47 = { 'Ok' : Array<FinancialStatement> } |
+export type Result_84 = { 'Ok' : Array<GlImport> } |
   { 'Err' : string };
-export type Result_48 = { 'Ok' : Array<EngagementMilestone> } |
+export type Result_85 = { 'Ok' : Array<JetRun> } |
   { 'Err' : string };
-export type Result_49 = { 'Ok' : Array<Organization> } |
+export type Result_86 = { 'Ok' : Array<ImportMappingProfile> } |
   { 'Err' : string };
-export type Result_5 = { 'Ok' : ClientAcceptance } |
+export type Result_87 = { 'Ok' : Array<EngagementMilestone> } |
   { 'Err' : string };
-export type Result_50 = { 'Ok' : Array<AuditTemplate> } |
+export type Result_88 = { 'Ok' : Array<Organization> } |
   { 'Err' : string };
-export type Result_51 = { 'Ok' : Array<TimeEntry> } |
+export type Result_89 = { 'Ok' : Array<AccountReconciliation> } |
   { 'Err' : string };
-export type Result_52 = { 'Ok' : Array<TrialBalance> } |
+export type Result_9 = { 'Ok' : ClientAcceptance } |
   { 'Err' : string };
-export type Result_53 = { 'Ok' : Array<User> } |
+export type Result_90 = { 'Ok' : Array<ReviewNote> } |
   { 'Err' : string };
-export type Result_54 = { 'Ok' : Array<WorkingPaper> } |
+export type Result_91 = { 'Ok' : Array<AuditTemplate> } |
   { 'Err' : string };
-export type Result_55 = { 'Ok' : TrialBalanceValidation } |
+export type Result_92 = { 'Ok' : Array<TickMark> } |
   { 'Err' : string };
-export type Result_56 = { 'Ok' : VerificationResult } |
+export type Result_93 = { 'Ok' : Array<TimeEntry> } |
   { 'Err' : string };
-export type Result_57 = { 'Ok' : AjeBlockchainVerification } |
+export type Result_94 = { 'Ok' : Array<User> } |
   { 'Err' : string };
-export type Result_58 = { 'Ok' : boolean } |
+export type Result_95 = { 'Ok' : Array<WorkingPaper> } |
   { 'Err' : string };
-export type Result_6 = { 'Ok' : DocumentRequest } |
+export type Result_96 = { 'Ok' : ReimportResult } |
   { 'Err' : string };
-export type Result_7 = { 'Ok' : User } |
+export type Result_97 = { 'Ok' : FxRate } |
   { 'Err' : string };
-export type Result_8 = { 'Ok' : EngagementBudget } |
+export type Result_98 = { 'Ok' : Array<MappingProfileMatch> } |
   { 'Err' : string };
-export type Result_9 = { 'Ok' : Client } |
+export type Result_99 = { 'Ok' : TranslationResult } |
   { 'Err' : string };
+export interface ReviewNote {
+  'id' : bigint,
+  'status' : ReviewNoteStatus,
+  'working_paper_id' : bigint,
+  'raised_at' : bigint,
+  'raised_by' : Principal,
+  'note' : string,
+  'target' : ReviewNoteTarget,
+  'response' : [] | [string],
+  'engagement_id' : bigint,
+  'resolved_at' : [] | [bigint],
+  'resolved_by' : [] | [Principal],
+}
+export type ReviewNoteStatus = { 'Open' : null } |
+  { 'Resolved' : null };
+export type ReviewNoteTarget = { 'Cell' : string } |
+  { 'Account' : string } |
+  { 'WorkingPaper' : null };
 export type RiskLevel = { 'Low' : null } |
   { 'High' : null } |
   { 'Medium' : null } |
   { 'Unacceptable' : null };
+export interface RollforwardRequest {
+  'trial_balance_id' : bigint,
+  'description' : [] | [string],
+  'engagement_id' : [] | [bigint],
+  'period_type' : [] | [PeriodType],
+  'period_end_date' : string,
+}
+export interface RowChange {
+  'key' : string,
+  'cell_changes' : Array<CellChange>,
+  'old_row_index' : [] | [bigint],
+  'change_type' : RowChangeType,
+  'new_row_index' : [] | [bigint],
+}
+export type RowChangeType = { 'Changed' : null } |
+  { 'Added' : null } |
+  { 'Removed' : null };
+export interface RuleResult {
+  'violations' : Array<RuleViolation>,
+  'severity' : RuleSeverity,
+  'violation_count' : bigint,
+  'passed' : boolean,
+  'rule_name' : string,
+}
+export type RuleSeverity = { 'Info' : null } |
+  { 'Critical' : null } |
+  { 'Warning' : null };
+export interface RuleViolation {
+  'column_name' : string,
+  'value' : string,
+  'message' : string,
+  'row_number' : bigint,
+}
+export interface RunDataQualityRequest {
+  'block_on_critical' : boolean,
+  'dataset_id' : bigint,
+  'file_data' : [] | [Uint8Array | number[]],
+  'sheet_name' : [] | [string],
+  'rules' : Array<DataQualityRule>,
+}
+export interface RunDigitAnalysisRequest {
+  'column_name' : string,
+  'working_paper_id' : bigint,
+  'dataset_id' : bigint,
+  'test' : DigitTest,
+  'min_value' : [] | [number],
+  'file_data' : [] | [Uint8Array | number[]],
+  'sheet_name' : [] | [string],
+}
+export interface SampleEvaluation {
+  'exceeds_tolerable' : [] | [boolean],
+  'incremental_allowance' : number,
+  'known_misstatement' : number,
+  'tolerable_misstatement' : [] | [number],
+  'misstatements_found' : bigint,
+  'confidence' : number,
+  'basic_precision' : number,
+  'projected_misstatement' : number,
+  'evaluated_at' : bigint,
+  'evaluated_by' : Principal,
+  'upper_misstatement_limit' : number,
+  'items_tested' : bigint,
+}
+export interface SampleItem {
+  'audited_amount' : [] | [number],
+  'book_amount' : number,
+  'reference' : string,
+  'row_index' : bigint,
+  'stratum' : [] | [number],
+  'selection_note' : string,
+}
+export interface SampleItemResult {
+  'audited_amount' : number,
+  'row_index' : bigint,
+}
+export type SamplingMethod = {
+    'Stratified' : {
+      'sample_sizes' : Uint32Array | number[],
+      'band_upper_bounds' : Array<number>,
+    }
+  } |
+  {
+    'MonetaryUnit' : {
+      'expected_misstatement' : number,
+      'tolerable_misstatement' : number,
+      'confidence' : number,
+    }
+  } |
+  { 'SimpleRandom' : { 'sample_size' : number } };
+export interface SetCellsRequest {
+  'working_paper_id' : bigint,
+  'cells' : Array<CellInput>,
+}
+export interface SetFxRateRequest {
+  'rate' : number,
+  'effective_date' : string,
+  'to_currency' : string,
+  'from_currency' : string,
+  'rate_type' : FxRateType,
+}
+export interface SetMaterialityRequest {
+  'performance' : bigint,
+  'engagement_id' : bigint,
+  'overall' : bigint,
+  'basis' : string,
+  'clearly_trivial' : [] | [bigint],
+}
 export interface SheetData {
   'data' : Array<Array<string>>,
   'name' : string,
   'row_count' : bigint,
+  'header_row' : [] | [number],
   'columns' : Array<ColumnMetadata>,
 }
+export interface SheetDiff {
+  'changed_count' : bigint,
+  'removed_columns' : Array<string>,
+  'rows' : Array<RowChange>,
+  'sheet_name' : string,
+  'added_count' : bigint,
+  'removed_count' : bigint,
+  'unchanged_count' : bigint,
+  'added_columns' : Array<string>,
+}
+export type SignConvention = { 'SignedCreditPositive' : null } |
+  { 'SignedDebitPositive' : null } |
+  { 'DebitCreditColumns' : null };
 export interface SignEngagementLetterRequest {
   'letter_id' : bigint,
   'client_name' : string,
 }
+export interface SignOff { 'signed_at' : bigint, 'signed_by' : Principal }
+export type SignOffRole = { 'Approver' : null } |
+  { 'Preparer' : null } |
+  { 'Reviewer' : null };
+export interface SignificanceThresholds {
+  'percent' : [] | [number],
+  'amount' : [] | [bigint],
+}
+export interface StratumSummary {
+  'upper_bound' : [] | [number],
+  'sample_size' : bigint,
+  'lower_bound' : [] | [number],
+  'index' : number,
+  'population_value' : number,
+  'population_count' : bigint,
+}
 export type TemplateType = { 'Review' : null } |
   { 'Compilation' : null } |
   { 'Custom' : null } |
   { 'Audit' : null } |
   { 'TaxPreparation' : null };
+export interface TickMark {
+  'id' : bigint,
+  'meaning' : string,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'engagement_id' : bigint,
+  'symbol' : string,
+}
 export interface TimeEntry {
   'id' : bigint,
   'hours' : number,
@@ -841,6 +1939,30 @@ export interface TimeEntry {
   'engagement_id' : bigint,
   'milestone_id' : [] | [bigint],
 }
+export interface TranslateTrialBalanceRequest {
+  'presentation_currency' : string,
+  'trial_balance_id' : bigint,
+  'description' : [] | [string],
+  'historical_rates' : Array<AccountRate>,
+  'engagement_id' : [] | [bigint],
+}
+export interface TranslationLine {
+  'account_id' : bigint,
+  'balance' : bigint,
+  'rate' : number,
+  'account_number' : string,
+  'currency' : string,
+  'rate_type' : FxRateType,
+  'account_name' : string,
+  'translated_balance' : bigint,
+  'account_type' : FSCategory,
+}
+export interface TranslationResult {
+  'source_trial_balance_id' : bigint,
+  'lines' : Array<TranslationLine>,
+  'trial_balance' : TrialBalance,
+  'translation_difference' : bigint,
+}
 export interface TrendAnalysis {
   'prior_value' : number,
   'change_percent' : number,
@@ -848,16 +1970,36 @@ export interface TrendAnalysis {
   'change' : number,
   'current_value' : number,
 }
+export interface TrendChange {
+  'change_percent' : [] | [number],
+  'change' : [] | [bigint],
+}
+export interface TrendPeriod {
+  'trial_balance_id' : bigint,
+  'engagement_id' : bigint,
+  'period_end_date' : string,
+  'engagement_name' : string,
+}
+export interface TrendRow {
+  'key' : string,
+  'name' : string,
+  'is_significant' : boolean,
+  'amounts' : Array<[] | [bigint]>,
+  'changes' : Array<TrendChange>,
+  'significance_reason' : [] | [string],
+}
 export interface TrialBalance {
   'id' : bigint,
   'last_modified_at' : bigint,
   'last_modified_by' : Principal,
+  'prior_trial_balance_id' : [] | [bigint],
   'description' : string,
   'created_at' : bigint,
   'created_by' : Principal,
   'is_adjusted' : boolean,
   'currency' : string,
   'engagement_id' : bigint,
+  'period_type' : [] | [PeriodType],
   'period_end_date' : string,
 }
 export interface TrialBalanceAccount {
@@ -865,15 +2007,31 @@ export interface TrialBalanceAccount {
   'trial_balance_id' : bigint,
   'fs_line_item' : [] | [string],
   'is_reconciled' : boolean,
+  'opening_balance' : [] | [bigint],
+  'coa_account_id' : [] | [bigint],
   'debit_balance' : bigint,
   'created_at' : bigint,
   'created_by' : Principal,
   'credit_balance' : bigint,
   'account_number' : string,
+  'currency' : [] | [string],
   'notes' : string,
   'account_name' : string,
   'account_type' : FSCategory,
 }
+export interface TrialBalanceComparison {
+  'compare_trial_balance_id' : bigint,
+  'base_trial_balance_id' : bigint,
+  'renamed_count' : number,
+  'generated_at' : bigint,
+  'renumbered_count' : number,
+  'owner' : [] | [CoaOwner],
+  'rows' : Array<AccountComparison>,
+  'merged_count' : number,
+  'removed_count' : number,
+  'split_count' : number,
+  'new_count' : number,
+}
 export interface TrialBalanceValidation {
   'trial_balance_id' : bigint,
   'account_count' : bigint,
@@ -885,15 +2043,42 @@ export interface TrialBalanceValidation {
   'validated_by' : Principal,
   'total_debits' : bigint,
 }
+export interface UncorrectedMisstatement {
+  'aje_id' : bigint,
+  'description' : string,
+  'is_prior_year_turnaround' : boolean,
+  'fs_line_effects' : Array<FsLineEffect>,
+  'engagement_id' : bigint,
+  'equity_effect' : bigint,
+  'aje_number' : string,
+  'profit_before_tax_effect' : bigint,
+  'classification' : MisstatementClassification,
+}
+export interface UnreconciledAccount {
+  'account_id' : bigint,
+  'status' : [] | [ReconciliationStatus],
+  'balance' : bigint,
+  'unreconciled_difference' : [] | [bigint],
+  'reconciliation_id' : [] | [bigint],
+  'account_number' : string,
+  'account_name' : string,
+}
 export interface UpdateAccountRequest {
   'fs_line_item' : [] | [string],
   'debit_balance' : bigint,
   'credit_balance' : bigint,
   'account_number' : string,
+  'currency' : [] | [string],
   'notes' : [] | [string],
   'account_name' : string,
   'account_type' : AccountType,
 }
+export interface UpdateAjeRequest {
+  'aje_id' : bigint,
+  'entry_type' : [] | [AjeEntryType],
+  'line_items' : Array<CreateAjeLineItemRequest>,
+  'description' : string,
+}
 export interface UpdateChecklistItemRequest {
   'status' : [] | [ChecklistItemStatus],
   'checklist_id' : bigint,
@@ -922,6 +2107,7 @@ export interface UpdateEngagementRequest {
   'description' : string,
   'end_date' : bigint,
   'start_date' : bigint,
+  'prior_year_engagement_id' : [] | [bigint],
 }
 export interface UpdateEntityRequest {
   'id' : bigint,
@@ -934,6 +2120,15 @@ export interface UpdateFSLineMappingRequest {
   'account_id' : bigint,
   'fs_line_item_code' : string,
 }
+export interface UpdateMappingProfileRequest {
+  'id' : bigint,
+  'erp_system' : ErpSystem,
+  'name' : string,
+  'sheet_name' : [] | [string],
+  'sign_convention' : SignConvention,
+  'header_row' : [] | [number],
+  'header_aliases' : Array<HeaderAlias>,
+}
 export interface UpdateMilestoneRequest {
   'status' : [] | [MilestoneStatus],
   'assigned_to' : [] | [Principal],
@@ -945,6 +2140,24 @@ export interface UpdateOrganizationRequest {
   'name' : string,
   'description' : string,
 }
+export interface UpdateReconciliationRequest {
+  'source_reference' : [] | [string],
+  'document_ids' : [] | [BigUint64Array | bigint[]],
+  'source' : [] | [ReconciliationSource],
+  'supporting_balance' : [] | [bigint],
+  'reconciliation_id' : bigint,
+  'reconciling_items' : [] | [Array<ReconcilingItemInput>],
+}
+export interface UpdateWorkingPaperRequest {
+  'id' : bigint,
+  'reference_code' : [] | [string],
+  'trial_balance_id' : [] | [bigint],
+  'dataset_id' : [] | [bigint],
+  'column_mapping' : ColumnMapping,
+  'name' : string,
+  'selected_accounts' : Array<string>,
+  'trend_thresholds' : [] | [SignificanceThresholds],
+}
 export interface UploadDocumentRequest {
   'name' : string,
   'file_data' : Uint8Array | number[],
@@ -987,183 +2200,330 @@ export interface VerificationResult {
 }
 export interface WorkingPaper {
   'id' : bigint,
+  'prepared_by' : [] | [SignOff],
+  'last_modified_at' : [] | [bigint],
+  'last_modified_by' : [] | [Principal],
   'linked_document_ids' : BigUint64Array | bigint[],
-  'dataset_id' : bigint,
+  'status' : [] | [WorkingPaperStatus],
+  'reference_code' : [] | [string],
+  'trial_balance_id' : [] | [bigint],
+  'dataset_id' : [] | [bigint],
   'column_mapping' : ColumnMapping,
+  'cells' : [] | [Array<WorkingPaperCell>],
   'name' : string,
+  'approved_by' : [] | [SignOff],
+  'reviewed_by' : [] | [SignOff],
   'created_at' : bigint,
   'created_by' : Principal,
   'variance_analysis' : Array<VarianceAnalysis>,
+  'dataset_version' : [] | [number],
+  'selected_accounts' : [] | [Array<string>],
+  'is_stale' : [] | [boolean],
   'engagement_id' : bigint,
   'ratios' : Array<FinancialRatio>,
+  'refresh_history' : [] | [Array<LeadsheetRefresh>],
+  'stale_reason' : [] | [string],
   'leadsheet' : [] | [Leadsheet],
   'trend_analysis' : Array<TrendAnalysis>,
+  'multi_period_trend' : [] | [MultiPeriodTrend],
 }
+export interface WorkingPaperCell {
+  'value' : CellValue,
+  'error' : [] | [string],
+  'address' : string,
+  'input' : string,
+}
+export type WorkingPaperStatus = { 'Approved' : null } |
+  { 'Draft' : null } |
+  { 'Prepared' : null } |
+  { 'Locked' : null } |
+  { 'Reviewed' : null };
 export type XBRLTaxonomy = { 'EAS' : null } |
   { 'GCC' : null } |
   { 'IFRS' : null } |
   { 'Custom' : string };
 export interface _SERVICE {
   'accept_invitation' : ActorMethod<[AcceptInvitationRequest], Result>,
-  'add_fs_note' : ActorMethod<[AddFSNoteRequest], Result_1>,
+  'add_annotation' : ActorMethod<[AddAnnotationRequest], Result_1>,
+  'add_cross_reference' : ActorMethod<[AddCrossReferenceRequest], Result_2>,
+  'add_fs_note' : ActorMethod<[AddFSNoteRequest], Result_3>,
+  'add_review_note' : ActorMethod<[AddReviewNoteRequest], Result_4>,
   'add_trial_balance_account' : ActorMethod<
     [bigint, UpdateAccountRequest],
-    Result_2
+    Result_5
   >,
+  'adopt_trial_balance_accounts' : ActorMethod<[bigint], Result_6>,
   'apply_template_to_engagement' : ActorMethod<
     [ApplyTemplateRequest],
-    Result_3
+    Result_7
+  >,
+  'approve_aje' : ActorMethod<[bigint], Result_8>,
+  'approve_client_acceptance' : ActorMethod<[bigint], Result_9>,
+  'approve_document_request' : ActorMethod<[ApproveDocumentInput], Result_10>,
+  'check_reference_integrity' : ActorMethod<[bigint], Result_11>,
+  'compare_trial_balances' : ActorMethod<[bigint, bigint], Result_12>,
+  'complete_user_profile' : ActorMethod<[CompleteProfileRequest], Result_13>,
+  'confirm_account_mappings' : ActorMethod<
+    [bigint, Array<AccountMappingInput>],
+    Result_14
   >,
-  'approve_aje' : ActorMethod<[bigint], Result_4>,
-  'approve_client_acceptance' : ActorMethod<[bigint], Result_5>,
-  'approve_document_request' : ActorMethod<[ApproveDocumentInput], Result_6>,
-  'complete_user_profile' : ActorMethod<[CompleteProfileRequest], Result_7>,
-  'create_aje' : ActorMethod<[CreateAjeRequest], Result_4>,
-  'create_budget' : ActorMethod<[CreateBudgetRequest], Result_8>,
-  'create_client' : ActorMethod<[CreateClientRequest], Result_9>,
+  'consolidate_trial_balances' : ActorMethod<[ConsolidateRequest], Result_15>,
+  'create_aje' : ActorMethod<[CreateAjeRequest], Result_8>,
+  'create_analytical_expectation' : ActorMethod<
+    [CreateExpectationRequest],
+    Result_16
+  >,
+  'create_audit_sample' : ActorMethod<[CreateAuditSampleRequest], Result_17>,
+  'create_budget' : ActorMethod<[CreateBudgetRequest], Result_18>,
+  'create_client' : ActorMethod<[CreateClientRequest], Result_19>,
   'create_client_acceptance' : ActorMethod<
     [CreateClientAcceptanceRequest],
-    Result_5
+    Result_9
   >,
   'create_conflict_check' : ActorMethod<
     [CreateConflictCheckRequest],
-    Result_10
+    Result_20
   >,
   'create_document_request' : ActorMethod<
     [CreateDocumentRequestInput],
-    Result_6
+    Result_10
   >,
-  'create_engagement' : ActorMethod<[CreateEngagementRequest], Result_11>,
+  'create_engagement' : ActorMethod<[CreateEngagementRequest], Result_21>,
   'create_engagement_from_template' : ActorMethod<
     [CreateEngagementFromTemplateRequest],
-    Result_12
+    Result_22
   >,
   'create_engagement_letter' : ActorMethod<
     [CreateEngagementLetterRequest],
-    Result_13
+    Result_23
   >,
   'create_engagement_setup_template' : ActorMethod<
     [CreateEngagementSetupTemplateRequest],
-    Result_14
+    Result_24
+  >,
+  'create_entity' : ActorMethod<[CreateEntityRequest], Result_25>,
+  'create_invitation' : ActorMethod<[CreateInvitationRequest], Result_26>,
+  'create_mapping_profile' : ActorMethod<
+    [CreateMappingProfileRequest],
+    Result_27
+  >,
+  'create_milestone' : ActorMethod<[CreateMilestoneRequest], Result_28>,
+  'create_organization' : ActorMethod<[CreateOrganizationRequest], Result_29>,
+  'create_reconciliation' : ActorMethod<
+    [CreateReconciliationRequest],
+    Result_30
+  >,
+  'create_template' : ActorMethod<[CreateTemplateRequest], Result_31>,
+  'create_time_entry' : ActorMethod<[CreateTimeEntryRequest], Result_32>,
+  'create_trial_balance' : ActorMethod<[CreateTrialBalanceRequest], Result_33>,
+  'create_working_paper' : ActorMethod<[CreateWorkingPaperRequest], Result_34>,
+  'deactivate_coa_account' : ActorMethod<[bigint], Result_35>,
+  'define_tick_mark' : ActorMethod<[DefineTickMarkRequest], Result_36>,
+  'delete_account_mapping' : ActorMethod<[bigint], Result_3>,
+  'delete_client' : ActorMethod<[bigint], Result_3>,
+  'delete_document' : ActorMethod<[bigint], Result_3>,
+  'delete_engagement' : ActorMethod<[bigint], Result_3>,
+  'delete_entity' : ActorMethod<[bigint], Result_3>,
+  'delete_mapping_profile' : ActorMethod<[bigint], Result_3>,
+  'delete_organization' : ActorMethod<[bigint], Result_3>,
+  'download_document' : ActorMethod<[bigint], Result_37>,
+  'evaluate_audit_sample' : ActorMethod<
+    [EvaluateAuditSampleRequest],
+    Result_17
   >,
-  'create_entity' : ActorMethod<[CreateEntityRequest], Result_15>,
-  'create_invitation' : ActorMethod<[CreateInvitationRequest], Result_16>,
-  'create_milestone' : ActorMethod<[CreateMilestoneRequest], Result_17>,
-  'create_organization' : ActorMethod<[CreateOrganizationRequest], Result_18>,
-  'create_template' : ActorMethod<[CreateTemplateRequest], Result_19>,
-  'create_time_entry' : ActorMethod<[CreateTimeEntryRequest], Result_20>,
-  'create_trial_balance' : ActorMethod<[CreateTrialBalanceRequest], Result_21>,
-  'create_working_paper' : ActorMethod<[CreateWorkingPaperRequest], Result_22>,
-  'delete_client' : ActorMethod<[bigint], Result_1>,
-  'delete_document' : ActorMethod<[bigint], Result_1>,
-  'delete_engagement' : ActorMethod<[bigint], Result_1>,
-  'delete_entity' : ActorMethod<[bigint], Result_1>,
-  'delete_organization' : ActorMethod<[bigint], Result_1>,
-  'download_document' : ActorMethod<[bigint], Result_23>,
+  'explain_variance' : ActorMethod<[ExplainVarianceRequest], Result_16>,
   'fulfill_document_request' : ActorMethod<
     [FulfillDocumentRequestInput],
-    Result_6
+    Result_10
+  >,
+  'generate_financial_statements' : ActorMethod<[GenerateFSRequest], Result_38>,
+  'get_activity_logs' : ActorMethod<[[] | [bigint]], Result_39>,
+  'get_adjusted_trial_balance' : ActorMethod<[bigint, boolean], Result_40>,
+  'get_aje' : ActorMethod<[bigint], Result_8>,
+  'get_aje_line_items' : ActorMethod<[bigint], Result_41>,
+  'get_analytical_expectation' : ActorMethod<[bigint], Result_16>,
+  'get_audit_sample' : ActorMethod<[bigint], Result_17>,
+  'get_blockchain_proof' : ActorMethod<[bigint], Result_42>,
+  'get_client' : ActorMethod<[bigint], Result_19>,
+  'get_client_access_for_engagement' : ActorMethod<[bigint], Result_43>,
+  'get_coa_mapping_report' : ActorMethod<[bigint], Result_44>,
+  'get_consolidation' : ActorMethod<[bigint], Result_15>,
+  'get_current_user' : ActorMethod<[], Result_13>,
+  'get_data_quality_report' : ActorMethod<[bigint], Result_45>,
+  'get_dataset' : ActorMethod<[bigint], Result_46>,
+  'get_dataset_diff' : ActorMethod<
+    [bigint, number, number, string, [] | [string]],
+    Result_47
   >,
-  'generate_financial_statements' : ActorMethod<[GenerateFSRequest], Result_24>,
-  'get_activity_logs' : ActorMethod<[[] | [bigint]], Result_25>,
-  'get_aje' : ActorMethod<[bigint], Result_4>,
-  'get_aje_line_items' : ActorMethod<[bigint], Result_26>,
-  'get_blockchain_proof' : ActorMethod<[bigint], Result_27>,
-  'get_client' : ActorMethod<[bigint], Result_9>,
-  'get_client_access_for_engagement' : ActorMethod<[bigint], Result_28>,
-  'get_current_user' : ActorMethod<[], Result_7>,
-  'get_dataset' : ActorMethod<[bigint], Result_29>,
-  'get_document' : ActorMethod<[bigint], Result_30>,
-  'get_document_requests_for_engagement' : ActorMethod<[bigint], Result_31>,
-  'get_engagement' : ActorMethod<[bigint], Result_11>,
-  'get_engagement_checklists' : ActorMethod<[bigint], Result_32>,
-  'get_engagement_dashboard' : ActorMethod<[bigint], Result_33>,
-  'get_entity' : ActorMethod<[bigint], Result_15>,
-  'get_financial_statement' : ActorMethod<[bigint], Result_24>,
-  'get_invitations_for_engagement' : ActorMethod<[bigint], Result_34>,
+  'get_digit_analysis' : ActorMethod<[bigint], Result_48>,
+  'get_document' : ActorMethod<[bigint], Result_49>,
+  'get_document_requests_for_engagement' : ActorMethod<[bigint], Result_50>,
+  'get_engagement' : ActorMethod<[bigint], Result_21>,
+  'get_engagement_checklists' : ActorMethod<[bigint], Result_51>,
+  'get_engagement_dashboard' : ActorMethod<[bigint], Result_52>,
+  'get_entity' : ActorMethod<[bigint], Result_25>,
+  'get_financial_statement' : ActorMethod<[bigint], Result_38>,
+  'get_gl_import' : ActorMethod<[bigint], Result_53>,
+  'get_invitations_for_engagement' : ActorMethod<[bigint], Result_54>,
+  'get_jet_run' : ActorMethod<[bigint], Result_55>,
+  'get_journal_entries' : ActorMethod<[bigint], Result_56>,
+  'get_journal_lines' : ActorMethod<[bigint], Result_57>,
   'get_line_items_for_taxonomy' : ActorMethod<
     [XBRLTaxonomy],
     Array<FSLineItem>
   >,
-  'get_my_document_requests' : ActorMethod<[], Result_31>,
-  'get_my_engagements' : ActorMethod<[], Result_35>,
-  'get_my_invitations' : ActorMethod<[], Result_34>,
-  'get_organization' : ActorMethod<[bigint], Result_18>,
+  'get_mapping_profile' : ActorMethod<[bigint], Result_27>,
+  'get_multi_period_trend' : ActorMethod<[MultiPeriodTrendRequest], Result_58>,
+  'get_my_document_requests' : ActorMethod<[], Result_50>,
+  'get_my_engagements' : ActorMethod<[], Result_59>,
+  'get_my_invitations' : ActorMethod<[], Result_54>,
+  'get_organization' : ActorMethod<[bigint], Result_29>,
+  'get_reconciliation' : ActorMethod<[bigint], Result_30>,
   'get_resource_activity_logs' : ActorMethod<
     [string, string, [] | [bigint]],
-    Result_25
+    Result_39
   >,
-  'get_template' : ActorMethod<[bigint], Result_19>,
-  'get_trial_balance' : ActorMethod<[bigint], Result_21>,
-  'get_trial_balance_accounts' : ActorMethod<[bigint], Result_36>,
-  'get_user_activity_logs' : ActorMethod<[Principal, [] | [bigint]], Result_25>,
-  'get_working_paper' : ActorMethod<[bigint], Result_22>,
+  'get_template' : ActorMethod<[bigint], Result_31>,
+  'get_trial_balance' : ActorMethod<[bigint], Result_33>,
+  'get_trial_balance_accounts' : ActorMethod<[bigint], Result_60>,
+  'get_trial_balance_period_chain' : ActorMethod<[bigint], Result_61>,
+  'get_uncorrected_misstatements' : ActorMethod<[bigint], Result_62>,
+  'get_unreconciled_accounts' : ActorMethod<[bigint, bigint], Result_63>,
+  'get_user_activity_logs' : ActorMethod<[Principal, [] | [bigint]], Result_39>,
+  'get_working_paper' : ActorMethod<[bigint], Result_34>,
   'grant_client_access' : ActorMethod<[GrantClientAccessRequest], Result>,
-  'grant_document_access' : ActorMethod<[bigint, Principal], Result_1>,
-  'import_excel' : ActorMethod<[ImportExcelRequest], Result_29>,
+  'grant_document_access' : ActorMethod<[bigint, Principal], Result_3>,
+  'import_excel' : ActorMethod<[ImportExcelRequest], Result_46>,
+  'import_fx_rates' : ActorMethod<[Array<SetFxRateRequest>], Result_64>,
+  'import_gl_journal' : ActorMethod<[ImportGlJournalRequest], Result_53>,
   'import_trial_balance_csv' : ActorMethod<
-    [bigint, string, Array<CsvAccountRow>],
+    [bigint, string, Array<CsvAccountRow>, [] | [string]],
+    Result_33
+  >,
+  'import_trial_balance_from_dataset' : ActorMethod<
+    [
+      bigint,
+      bigint,
+      string,
+      [] | [ColumnMapping],
+      [] | [string],
+      [] | [Uint8Array | number[]],
+    ],
+    Result_33
+  >,
+  'link_document_to_working_paper' : ActorMethod<[bigint, bigint], Result_3>,
+  'list_account_mappings' : ActorMethod<[CoaOwner], Result_14>,
+  'list_ajes_by_engagement' : ActorMethod<[bigint], Result_65>,
+  'list_annotations' : ActorMethod<[bigint], Result_66>,
+  'list_audit_samples_by_engagement' : ActorMethod<[bigint], Result_67>,
+  'list_book_adjustments' : ActorMethod<[bigint], Result_68>,
+  'list_client_acceptances_by_client' : ActorMethod<[bigint], Result_69>,
+  'list_clients' : ActorMethod<[], Result_70>,
+  'list_clients_by_entity' : ActorMethod<[bigint], Result_70>,
+  'list_clients_by_organization' : ActorMethod<[bigint], Result_70>,
+  'list_coa_accounts' : ActorMethod<[CoaOwner], Result_6>,
+  'list_conflict_checks_by_client' : ActorMethod<[bigint], Result_71>,
+  'list_consolidations' : ActorMethod<[bigint], Result_72>,
+  'list_cross_references' : ActorMethod<[bigint], Result_73>,
+  'list_dataset_versions' : ActorMethod<[bigint], Result_74>,
+  'list_datasets' : ActorMethod<[], Result_75>,
+  'list_datasets_by_engagement' : ActorMethod<[bigint], Result_75>,
+  'list_digit_analyses_by_working_paper' : ActorMethod<[bigint], Result_76>,
+  'list_documents' : ActorMethod<[], Result_77>,
+  'list_documents_by_entity' : ActorMethod<[bigint], Result_77>,
+  'list_documents_by_organization' : ActorMethod<[bigint], Result_77>,
+  'list_engagement_letters_by_client' : ActorMethod<[bigint], Result_78>,
+  'list_engagement_templates' : ActorMethod<[], Result_79>,
+  'list_engagements' : ActorMethod<[], Result_80>,
+  'list_engagements_by_client' : ActorMethod<[bigint], Result_80>,
+  'list_engagements_by_entity' : ActorMethod<[bigint], Result_80>,
+  'list_engagements_by_organization' : ActorMethod<[bigint], Result_80>,
+  'list_entities' : ActorMethod<[], Result_81>,
+  'list_entities_by_organization' : ActorMethod<[bigint], Result_81>,
+  'list_expectations_by_engagement' : ActorMethod<[bigint], Result_82>,
+  'list_financial_statements_by_engagement' : ActorMethod<[bigint], Result_83>,
+  'list_fx_rates' : ActorMethod<[[] | [string]], Result_64>,
+  'list_gl_imports_by_engagement' : ActorMethod<[bigint], Result_84>,
+  'list_jet_runs_by_engagement' : ActorMethod<[bigint], Result_85>,
+  'list_mapping_profiles_by_client' : ActorMethod<[bigint], Result_86>,
+  'list_milestones_by_engagement' : ActorMethod<[bigint], Result_87>,
+  'list_organizations' : ActorMethod<[], Result_88>,
+  'list_reconciliations' : ActorMethod<[bigint], Result_89>,
+  'list_review_notes' : ActorMethod<[bigint], Result_90>,
+  'list_templates' : ActorMethod<[], Result_91>,
+  'list_tick_marks' : ActorMethod<[bigint], Result_92>,
+  'list_time_entries_by_engagement' : ActorMethod<[bigint], Result_93>,
+  'list_trial_balances_by_engagement' : ActorMethod<[bigint], Result_61>,
+  'list_users' : ActorMethod<[], Result_94>,
+  'list_working_papers_by_engagement' : ActorMethod<[bigint], Result_95>,
+  'map_account_to_fs_line' : ActorMethod<[bigint, string], Result_5>,
+  'mask_dataset_columns' : ActorMethod<[MaskDatasetColumnsRequest], Result_46>,
+  'pass_aje' : ActorMethod<[PassAjeRequest], Result_8>,
+  'post_aje' : ActorMethod<[bigint], Result_8>,
+  'refresh_working_paper' : ActorMethod<[bigint], Result_34>,
+  'reimport_dataset' : ActorMethod<[ReimportDatasetRequest], Result_96>,
+  'reject_invitation' : ActorMethod<[RejectInvitationRequest], Result_26>,
+  'resolve_review_note' : ActorMethod<[ResolveReviewNoteRequest], Result_4>,
+  'revert_activity_entry' : ActorMethod<[bigint], Result_3>,
+  'review_aje' : ActorMethod<[bigint, boolean], Result_8>,
+  'revoke_document_access' : ActorMethod<[bigint, Principal], Result_3>,
+  'rollforward_trial_balance' : ActorMethod<[RollforwardRequest], Result_33>,
+  'run_data_quality_checks' : ActorMethod<[RunDataQualityRequest], Result_45>,
+  'run_digit_analysis' : ActorMethod<[RunDigitAnalysisRequest], Result_48>,
+  'run_journal_entry_tests' : ActorMethod<[bigint, JetConfig], Result_55>,
+  'send_engagement_letter' : ActorMethod<[bigint], Result_23>,
+  'set_coa_accounts' : ActorMethod<
+    [CoaOwner, Array<CoaAccountInput>],
+    Result_6
+  >,
+  'set_engagement_materiality' : ActorMethod<
+    [SetMaterialityRequest],
     Result_21
   >,
-  'link_document_to_working_paper' : ActorMethod<[bigint, bigint], Result_1>,
-  'list_ajes_by_engagement' : ActorMethod<[bigint], Result_37>,
-  'list_client_acceptances_by_client' : ActorMethod<[bigint], Result_38>,
-  'list_clients' : ActorMethod<[], Result_39>,
-  'list_clients_by_entity' : ActorMethod<[bigint], Result_39>,
-  'list_clients_by_organization' : ActorMethod<[bigint], Result_39>,
-  'list_conflict_checks_by_client' : ActorMethod<[bigint], Result_40>,
-  'list_datasets' : ActorMethod<[], Result_41>,
-  'list_datasets_by_engagement' : ActorMethod<[bigint], Result_41>,
-  'list_documents' : ActorMethod<[], Result_42>,
-  'list_documents_by_entity' : ActorMethod<[bigint], Result_42>,
-  'list_documents_by_organization' : ActorMethod<[bigint], Result_42>,
-  'list_engagement_letters_by_client' : ActorMethod<[bigint], Result_43>,
-  'list_engagement_templates' : ActorMethod<[], Result_44>,
-  'list_engagements' : ActorMethod<[], Result_45>,
-  'list_engagements_by_client' : ActorMethod<[bigint], Result_45>,
-  'list_engagements_by_entity' : ActorMethod<[bigint], Result_45>,
-  'list_engagements_by_organization' : ActorMethod<[bigint], Result_45>,
-  'list_entities' : ActorMethod<[], Result_46>,
-  'list_entities_by_organization' : ActorMethod<[bigint], Result_46>,
-  'list_financial_statements_by_engagement' : ActorMethod<[bigint], Result_47>,
-  'list_milestones_by_engagement' : ActorMethod<[bigint], Result_48>,
-  'list_organizations' : ActorMethod<[], Result_49>,
-  'list_templates' : ActorMethod<[], Result_50>,
-  'list_time_entries_by_engagement' : ActorMethod<[bigint], Result_51>,
-  'list_trial_balances_by_engagement' : ActorMethod<[bigint], Result_52>,
-  'list_users' : ActorMethod<[], Result_53>,
-  'list_working_papers_by_engagement' : ActorMethod<[bigint], Result_54>,
-  'map_account_to_fs_line' : ActorMethod<[bigint, string], Result_2>,
-  'post_aje' : ActorMethod<[bigint], Result_4>,
-  'reject_invitation' : ActorMethod<[RejectInvitationRequest], Result_16>,
-  'revert_activity_entry' : ActorMethod<[bigint], Result_1>,
-  'review_aje' : ActorMethod<[bigint, boolean], Result_4>,
-  'revoke_document_access' : ActorMethod<[bigint, Principal], Result_1>,
-  'send_engagement_letter' : ActorMethod<[bigint], Result_13>,
+  'set_fx_rate' : ActorMethod<[SetFxRateRequest], Result_97>,
+  'set_working_paper_cells' : ActorMethod<[SetCellsRequest], Result_34>,
   'sign_engagement_letter' : ActorMethod<
     [SignEngagementLetterRequest],
-    Result_13
+    Result_23
+  >,
+  'sign_off_reconciliation' : ActorMethod<[bigint, SignOffRole], Result_30>,
+  'sign_off_working_paper' : ActorMethod<[bigint, SignOffRole], Result_34>,
+  'submit_aje' : ActorMethod<[bigint], Result_8>,
+  'suggest_mapping_profiles' : ActorMethod<[bigint, [] | [bigint]], Result_98>,
+  'translate_trial_balance' : ActorMethod<
+    [TranslateTrialBalanceRequest],
+    Result_99
   >,
-  'submit_aje' : ActorMethod<[bigint], Result_4>,
-  'update_checklist_item' : ActorMethod<[UpdateChecklistItemRequest], Result_3>,
-  'update_client' : ActorMethod<[UpdateClientRequest], Result_9>,
-  'update_engagement' : ActorMethod<[UpdateEngagementRequest], Result_11>,
-  'update_entity' : ActorMethod<[UpdateEntityRequest], Result_15>,
+  'unmask_dataset_column' : ActorMethod<[bigint, string, string], Result_100>,
+  'update_aje' : ActorMethod<[UpdateAjeRequest], Result_8>,
+  'update_checklist_item' : ActorMethod<[UpdateChecklistItemRequest], Result_7>,
+  'update_client' : ActorMethod<[UpdateClientRequest], Result_19>,
+  'update_engagement' : ActorMethod<[UpdateEngagementRequest], Result_21>,
+  'update_entity' : ActorMethod<[UpdateEntityRequest], Result_25>,
   'update_fs_line_mapping' : ActorMethod<
     [UpdateFSLineMappingRequest],
-    Result_1
+    Result_3
+  >,
+  'update_mapping_profile' : ActorMethod<
+    [UpdateMappingProfileRequest],
+    Result_27
+  >,
+  'update_milestone' : ActorMethod<[UpdateMilestoneRequest], Result_28>,
+  'update_organization' : ActorMethod<[UpdateOrganizationRequest], Result_29>,
+  'update_reconciliation' : ActorMethod<
+    [UpdateReconciliationRequest],
+    Result_30
   >,
-  'update_milestone' : ActorMethod<[UpdateMilestoneRequest], Result_17>,
-  'update_organization' : ActorMethod<[UpdateOrganizationRequest], Result_18>,
-  'update_user_email' : ActorMethod<[string], Result_1>,
-  'update_user_language' : ActorMethod<[string], Result_1>,
-  'update_user_name' : ActorMethod<[string], Result_1>,
-  'update_user_role' : ActorMethod<[Principal, UserRole], Result_1>,
-  'upload_document' : ActorMethod<[UploadDocumentRequest], Result_30>,
-  'validate_trial_balance' : ActorMethod<[bigint], Result_55>,
-  'verify_activity_log' : ActorMethod<[bigint], Result_56>,
-  'verify_aje_blockchain' : ActorMethod<[bigint], Result_57>,
-  'verify_blockchain_chain' : ActorMethod<[], Result_58>,
+  'update_user_email' : ActorMethod<[string], Result_3>,
+  'update_user_language' : ActorMethod<[string], Result_3>,
+  'update_user_name' : ActorMethod<[string], Result_3>,
+  'update_user_role' : ActorMethod<[Principal, UserRole], Result_3>,
+  'update_working_paper' : ActorMethod<[UpdateWorkingPaperRequest], Result_34>,
+  'upload_document' : ActorMethod<[UploadDocumentRequest], Result_49>,
+  'validate_trial_balance' : ActorMethod<[bigint], Result_101>,
+  'verify_activity_log' : ActorMethod<[bigint], Result_102>,
+  'verify_aje_blockchain' : ActorMethod<[bigint], Result_103>,
+  'verify_blockchain_chain' : ActorMethod<[], Result_104>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
@@ -13,12 +13,80 @@ export const idlFactory = ({ IDL }) => {
     'engagement_id' : IDL.Nat64,
   });
   const Result = IDL.Variant({ 'Ok' : ClientAccess, 'Err' : IDL.Text });
+  const AddAnnotationRequest = IDL.Record({
+    'working_paper_id' : IDL.Nat64,
+    'comment' : IDL.Opt(IDL.Text),
+    'account_number' : IDL.Text,
+    'tick_mark_id' : IDL.Nat64,
+  });
+  const Annotation = IDL.Record({
+    'id' : IDL.Nat64,
+    'working_paper_id' : IDL.Nat64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'comment' : IDL.Opt(IDL.Text),
+    'account_number' : IDL.Text,
+    'engagement_id' : IDL.Nat64,
+    'tick_mark_id' : IDL.Nat64,
+  });
+  const Result_1 = IDL.Variant({ 'Ok' : Annotation, 'Err' : IDL.Text });
+  const ReferenceTarget = IDL.Variant({
+    'Aje' : IDL.Nat64,
+    'FsLine' : IDL.Text,
+    'Document' : IDL.Nat64,
+    'WorkingPaper' : IDL.Nat64,
+  });
+  const AddCrossReferenceRequest = IDL.Record({
+    'working_paper_id' : IDL.Nat64,
+    'description' : IDL.Text,
+    'target' : ReferenceTarget,
+    'account_number' : IDL.Opt(IDL.Text),
+  });
+  const CrossReference = IDL.Record({
+    'id' : IDL.Nat64,
+    'working_paper_id' : IDL.Nat64,
+    'description' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'target' : ReferenceTarget,
+    'account_number' : IDL.Opt(IDL.Text),
+    'engagement_id' : IDL.Nat64,
+  });
+  const Result_2 = IDL.Variant({ 'Ok' : CrossReference, 'Err' : IDL.Text });
   const AddFSNoteRequest = IDL.Record({
     'fs_id' : IDL.Nat64,
     'title' : IDL.Text,
     'content' : IDL.Text,
   });
-  const Result_1 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const Result_3 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const ReviewNoteTarget = IDL.Variant({
+    'Cell' : IDL.Text,
+    'Account' : IDL.Text,
+    'WorkingPaper' : IDL.Null,
+  });
+  const AddReviewNoteRequest = IDL.Record({
+    'working_paper_id' : IDL.Nat64,
+    'note' : IDL.Text,
+    'target' : ReviewNoteTarget,
+  });
+  const ReviewNoteStatus = IDL.Variant({
+    'Open' : IDL.Null,
+    'Resolved' : IDL.Null,
+  });
+  const ReviewNote = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : ReviewNoteStatus,
+    'working_paper_id' : IDL.Nat64,
+    'raised_at' : IDL.Nat64,
+    'raised_by' : IDL.Principal,
+    'note' : IDL.Text,
+    'target' : ReviewNoteTarget,
+    'response' : IDL.Opt(IDL.Text),
+    'engagement_id' : IDL.Nat64,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'resolved_by' : IDL.Opt(IDL.Principal),
+  });
+  const Result_4 = IDL.Variant({ 'Ok' : ReviewNote, 'Err' : IDL.Text });
   const AccountType = IDL.Variant({
     'Asset' : IDL.Null,
     'Liability' : IDL.Null,
@@ -31,6 +99,7 @@ export const idlFactory = ({ IDL }) => {
     'debit_balance' : IDL.Int64,
     'credit_balance' : IDL.Int64,
     'account_number' : IDL.Text,
+    'currency' : IDL.Opt(IDL.Text),
     'notes' : IDL.Opt(IDL.Text),
     'account_name' : IDL.Text,
     'account_type' : AccountType,
@@ -47,19 +116,44 @@ export const idlFactory = ({ IDL }) => {
     'trial_balance_id' : IDL.Nat64,
     'fs_line_item' : IDL.Opt(IDL.Text),
     'is_reconciled' : IDL.Bool,
+    'opening_balance' : IDL.Opt(IDL.Int64),
+    'coa_account_id' : IDL.Opt(IDL.Nat64),
     'debit_balance' : IDL.Int64,
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'credit_balance' : IDL.Int64,
     'account_number' : IDL.Text,
+    'currency' : IDL.Opt(IDL.Text),
     'notes' : IDL.Text,
     'account_name' : IDL.Text,
     'account_type' : FSCategory,
   });
-  const Result_2 = IDL.Variant({
+  const Result_5 = IDL.Variant({
     'Ok' : TrialBalanceAccount,
     'Err' : IDL.Text,
   });
+  const CoaOwner = IDL.Variant({ 'Entity' : IDL.Nat64, 'Client' : IDL.Nat64 });
+  const NormalBalance = IDL.Variant({
+    'Debit' : IDL.Null,
+    'Credit' : IDL.Null,
+  });
+  const CoaAccount = IDL.Record({
+    'id' : IDL.Nat64,
+    'updated_at' : IDL.Nat64,
+    'fs_line_item' : IDL.Opt(IDL.Text),
+    'owner' : CoaOwner,
+    'normal_balance' : NormalBalance,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'account_number' : IDL.Text,
+    'is_active' : IDL.Bool,
+    'account_name' : IDL.Text,
+    'account_type' : FSCategory,
+  });
+  const Result_6 = IDL.Variant({
+    'Ok' : IDL.Vec(CoaAccount),
+    'Err' : IDL.Text,
+  });
   const ApplyTemplateRequest = IDL.Record({
     'name' : IDL.Opt(IDL.Text),
     'template_id' : IDL.Nat64,
@@ -93,11 +187,12 @@ export const idlFactory = ({ IDL }) => {
     'engagement_id' : IDL.Nat64,
     'items' : IDL.Vec(ChecklistItemInstance),
   });
-  const Result_3 = IDL.Variant({
+  const Result_7 = IDL.Variant({
     'Ok' : EngagementChecklist,
     'Err' : IDL.Text,
   });
   const AjeStatus = IDL.Variant({
+    'Passed' : IDL.Null,
     'Posted' : IDL.Null,
     'Approved' : IDL.Null,
     'Draft' : IDL.Null,
@@ -105,11 +200,25 @@ export const idlFactory = ({ IDL }) => {
     'Proposed' : IDL.Null,
     'Reviewed' : IDL.Null,
   });
+  const AjeEntryType = IDL.Variant({
+    'AutoReversing' : IDL.Null,
+    'Adjusting' : IDL.Null,
+    'Reclassification' : IDL.Null,
+  });
+  const MisstatementClassification = IDL.Variant({
+    'Judgmental' : IDL.Null,
+    'Projected' : IDL.Null,
+    'Factual' : IDL.Null,
+  });
   const AdjustingJournalEntry = IDL.Record({
     'id' : IDL.Nat64,
     'status' : AjeStatus,
+    'passed_at' : IDL.Opt(IDL.Nat64),
+    'passed_by' : IDL.Opt(IDL.Principal),
     'trial_balance_id' : IDL.Nat64,
     'blockchain_signature' : IDL.Text,
+    'reversal_pending' : IDL.Opt(IDL.Bool),
+    'entry_type' : IDL.Opt(AjeEntryType),
     'approved_at' : IDL.Opt(IDL.Nat64),
     'approved_by' : IDL.Opt(IDL.Principal),
     'reviewed_at' : IDL.Opt(IDL.Nat64),
@@ -118,11 +227,16 @@ export const idlFactory = ({ IDL }) => {
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'blockchain_hash' : IDL.Text,
+    'reverses_aje_id' : IDL.Opt(IDL.Nat64),
+    'reversal_aje_id' : IDL.Opt(IDL.Nat64),
     'engagement_id' : IDL.Nat64,
+    'passed_reason' : IDL.Opt(IDL.Text),
+    'proof_version' : IDL.Opt(IDL.Nat32),
     'amount' : IDL.Int64,
     'aje_number' : IDL.Text,
+    'classification' : IDL.Opt(MisstatementClassification),
   });
-  const Result_4 = IDL.Variant({
+  const Result_8 = IDL.Variant({
     'Ok' : AdjustingJournalEntry,
     'Err' : IDL.Text,
   });
@@ -164,7 +278,7 @@ export const idlFactory = ({ IDL }) => {
     'client_id' : IDL.Nat64,
     'decision_rationale' : IDL.Text,
   });
-  const Result_5 = IDL.Variant({ 'Ok' : ClientAcceptance, 'Err' : IDL.Text });
+  const Result_9 = IDL.Variant({ 'Ok' : ClientAcceptance, 'Err' : IDL.Text });
   const ApproveDocumentInput = IDL.Record({
     'request_id' : IDL.Nat64,
     'approved' : IDL.Bool,
@@ -192,7 +306,70 @@ export const idlFactory = ({ IDL }) => {
     'due_date' : IDL.Opt(IDL.Nat64),
     'fulfilled_at' : IDL.Opt(IDL.Nat64),
   });
-  const Result_6 = IDL.Variant({ 'Ok' : DocumentRequest, 'Err' : IDL.Text });
+  const Result_10 = IDL.Variant({ 'Ok' : DocumentRequest, 'Err' : IDL.Text });
+  const BrokenReference = IDL.Record({
+    'working_paper_id' : IDL.Nat64,
+    'source' : IDL.Text,
+    'target' : IDL.Text,
+    'reason' : IDL.Text,
+  });
+  const ReferenceIntegrityReport = IDL.Record({
+    'broken' : IDL.Vec(BrokenReference),
+    'references_checked' : IDL.Nat64,
+    'engagement_id' : IDL.Nat64,
+    'checked_at' : IDL.Nat64,
+  });
+  const Result_11 = IDL.Variant({
+    'Ok' : ReferenceIntegrityReport,
+    'Err' : IDL.Text,
+  });
+  const ComparedAccount = IDL.Record({
+    'balance' : IDL.Int64,
+    'account_number' : IDL.Text,
+    'account_name' : IDL.Text,
+  });
+  const AccountMatchMethod = IDL.Variant({
+    'Name' : IDL.Null,
+    'Confirmed' : IDL.Null,
+    'Number' : IDL.Null,
+  });
+  const AccountChangeKind = IDL.Variant({
+    'New' : IDL.Null,
+    'Split' : IDL.Null,
+    'Merged' : IDL.Null,
+    'Renamed' : IDL.Null,
+    'Unchanged' : IDL.Null,
+    'Renumbered' : IDL.Null,
+    'Removed' : IDL.Null,
+  });
+  const AccountComparison = IDL.Record({
+    'compare_accounts' : IDL.Vec(ComparedAccount),
+    'change_percent' : IDL.Opt(IDL.Float64),
+    'base_accounts' : IDL.Vec(ComparedAccount),
+    'match_method' : IDL.Opt(AccountMatchMethod),
+    'kind' : AccountChangeKind,
+    'compare_balance' : IDL.Int64,
+    'change' : IDL.Int64,
+    'name_similarity' : IDL.Opt(IDL.Float64),
+    'base_balance' : IDL.Int64,
+  });
+  const TrialBalanceComparison = IDL.Record({
+    'compare_trial_balance_id' : IDL.Nat64,
+    'base_trial_balance_id' : IDL.Nat64,
+    'renamed_count' : IDL.Nat32,
+    'generated_at' : IDL.Nat64,
+    'renumbered_count' : IDL.Nat32,
+    'owner' : IDL.Opt(CoaOwner),
+    'rows' : IDL.Vec(AccountComparison),
+    'merged_count' : IDL.Nat32,
+    'removed_count' : IDL.Nat32,
+    'split_count' : IDL.Nat32,
+    'new_count' : IDL.Nat32,
+  });
+  const Result_12 = IDL.Variant({
+    'Ok' : TrialBalanceComparison,
+    'Err' : IDL.Text,
+  });
   const UserRole = IDL.Variant({
     'Staff' : IDL.Null,
     'ClientUser' : IDL.Null,
@@ -215,7 +392,106 @@ export const idlFactory = ({ IDL }) => {
     'email' : IDL.Text,
     'language_preference' : IDL.Text,
   });
-  const Result_7 = IDL.Variant({ 'Ok' : User, 'Err' : IDL.Text });
+  const Result_13 = IDL.Variant({ 'Ok' : User, 'Err' : IDL.Text });
+  const AccountMappingInput = IDL.Record({
+    'current_account_number' : IDL.Text,
+    'prior_account_number' : IDL.Text,
+  });
+  const AccountMapping = IDL.Record({
+    'id' : IDL.Nat64,
+    'owner' : CoaOwner,
+    'current_account_number' : IDL.Text,
+    'prior_account_number' : IDL.Text,
+    'confirmed_at' : IDL.Nat64,
+    'confirmed_by' : IDL.Principal,
+  });
+  const Result_14 = IDL.Variant({
+    'Ok' : IDL.Vec(AccountMapping),
+    'Err' : IDL.Text,
+  });
+  const EliminationLine = IDL.Record({
+    'account_number' : IDL.Text,
+    'debit_amount' : IDL.Int64,
+    'credit_amount' : IDL.Int64,
+  });
+  const EliminationEntry = IDL.Record({
+    'description' : IDL.Text,
+    'lines' : IDL.Vec(EliminationLine),
+  });
+  const AccountRate = IDL.Record({
+    'rate' : IDL.Float64,
+    'account_number' : IDL.Text,
+  });
+  const ConsolidationEntityInput = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'ownership_percentage' : IDL.Float64,
+    'historical_rates' : IDL.Vec(AccountRate),
+    'entity_id' : IDL.Nat64,
+  });
+  const IntercompanyPair = IDL.Record({
+    'counterparty_entity_id' : IDL.Nat64,
+    'account_number' : IDL.Text,
+    'entity_id' : IDL.Nat64,
+    'counterparty_account_number' : IDL.Text,
+  });
+  const ConsolidateRequest = IDL.Record({
+    'presentation_currency' : IDL.Text,
+    'eliminations' : IDL.Vec(EliminationEntry),
+    'description' : IDL.Opt(IDL.Text),
+    'entities' : IDL.Vec(ConsolidationEntityInput),
+    'engagement_id' : IDL.Nat64,
+    'period_end_date' : IDL.Text,
+    'organization_id' : IDL.Nat64,
+    'intercompany_pairs' : IDL.Vec(IntercompanyPair),
+  });
+  const ConsolidationRow = IDL.Record({
+    'fs_line_item' : IDL.Opt(IDL.Text),
+    'eliminations' : IDL.Int64,
+    'nci_adjustment' : IDL.Int64,
+    'entity_balances' : IDL.Vec(IDL.Int64),
+    'account_number' : IDL.Text,
+    'consolidated_balance' : IDL.Int64,
+    'aggregated_balance' : IDL.Int64,
+    'account_name' : IDL.Text,
+    'account_type' : FSCategory,
+  });
+  const IntercompanyMatch = IDL.Record({
+    'balance' : IDL.Int64,
+    'counterparty_balance' : IDL.Int64,
+    'difference' : IDL.Int64,
+    'counterparty_entity_id' : IDL.Nat64,
+    'account_number' : IDL.Text,
+    'eliminated' : IDL.Int64,
+    'entity_id' : IDL.Nat64,
+    'counterparty_account_number' : IDL.Text,
+  });
+  const ConsolidationColumn = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'ownership_percentage' : IDL.Float64,
+    'nci_share_of_profit' : IDL.Int64,
+    'nci_share_of_equity' : IDL.Int64,
+    'currency' : IDL.Text,
+    'entity_name' : IDL.Text,
+    'entity_id' : IDL.Nat64,
+    'translation_difference' : IDL.Int64,
+  });
+  const Consolidation = IDL.Record({
+    'id' : IDL.Nat64,
+    'presentation_currency' : IDL.Text,
+    'trial_balance_id' : IDL.Nat64,
+    'eliminations' : IDL.Vec(EliminationEntry),
+    'nci_share_of_profit' : IDL.Int64,
+    'rows' : IDL.Vec(ConsolidationRow),
+    'non_controlling_interest' : IDL.Int64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'engagement_id' : IDL.Nat64,
+    'period_end_date' : IDL.Text,
+    'intercompany' : IDL.Vec(IntercompanyMatch),
+    'organization_id' : IDL.Nat64,
+    'columns' : IDL.Vec(ConsolidationColumn),
+  });
+  const Result_15 = IDL.Variant({ 'Ok' : Consolidation, 'Err' : IDL.Text });
   const CreateAjeLineItemRequest = IDL.Record({
     'account_id' : IDL.Nat64,
     'description' : IDL.Text,
@@ -224,11 +500,143 @@ export const idlFactory = ({ IDL }) => {
   });
   const CreateAjeRequest = IDL.Record({
     'trial_balance_id' : IDL.Nat64,
+    'entry_type' : IDL.Opt(AjeEntryType),
     'line_items' : IDL.Vec(CreateAjeLineItemRequest),
     'description' : IDL.Text,
     'engagement_id' : IDL.Nat64,
-    'aje_number' : IDL.Text,
   });
+  const RegressionPoint = IDL.Record({
+    'amount' : IDL.Int64,
+    'driver' : IDL.Float64,
+  });
+  const ExpectationMethod = IDL.Variant({
+    'Budget' : IDL.Record({
+      'dataset_id' : IDL.Nat64,
+      'sheet_name' : IDL.Opt(IDL.Text),
+      'key_column' : IDL.Text,
+      'amount_column' : IDL.Text,
+    }),
+    'Regression' : IDL.Record({
+      'current_driver' : IDL.Float64,
+      'driver_name' : IDL.Text,
+      'observations' : IDL.Vec(RegressionPoint),
+    }),
+    'PriorYearGrowth' : IDL.Record({ 'growth_percent' : IDL.Float64 }),
+  });
+  const ExpectationTarget = IDL.Variant({
+    'FsLine' : IDL.Text,
+    'Account' : IDL.Text,
+  });
+  const CreateExpectationRequest = IDL.Record({
+    'method' : ExpectationMethod,
+    'trial_balance_id' : IDL.Nat64,
+    'file_data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'target' : ExpectationTarget,
+    'threshold_percent' : IDL.Opt(IDL.Float64),
+    'threshold_amount' : IDL.Opt(IDL.Int64),
+  });
+  const AnalyticalExpectation = IDL.Record({
+    'id' : IDL.Nat64,
+    'method' : ExpectationMethod,
+    'actual' : IDL.Int64,
+    'trial_balance_id' : IDL.Nat64,
+    'variance_percent' : IDL.Opt(IDL.Float64),
+    'expected' : IDL.Int64,
+    'explanation' : IDL.Opt(IDL.Text),
+    'variance' : IDL.Int64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'outside_threshold' : IDL.Bool,
+    'target' : ExpectationTarget,
+    'threshold_percent' : IDL.Opt(IDL.Float64),
+    'explained_at' : IDL.Opt(IDL.Nat64),
+    'explained_by' : IDL.Opt(IDL.Principal),
+    'r_squared' : IDL.Opt(IDL.Float64),
+    'engagement_id' : IDL.Nat64,
+    'basis' : IDL.Text,
+    'threshold_amount' : IDL.Opt(IDL.Int64),
+    'target_name' : IDL.Text,
+  });
+  const Result_16 = IDL.Variant({
+    'Ok' : AnalyticalExpectation,
+    'Err' : IDL.Text,
+  });
+  const SamplingMethod = IDL.Variant({
+    'Stratified' : IDL.Record({
+      'sample_sizes' : IDL.Vec(IDL.Nat32),
+      'band_upper_bounds' : IDL.Vec(IDL.Float64),
+    }),
+    'MonetaryUnit' : IDL.Record({
+      'expected_misstatement' : IDL.Float64,
+      'tolerable_misstatement' : IDL.Float64,
+      'confidence' : IDL.Float64,
+    }),
+    'SimpleRandom' : IDL.Record({ 'sample_size' : IDL.Nat32 }),
+  });
+  const CreateAuditSampleRequest = IDL.Record({
+    'method' : SamplingMethod,
+    'dataset_id' : IDL.Nat64,
+    'name' : IDL.Text,
+    'seed' : IDL.Opt(IDL.Nat64),
+    'file_data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'engagement_id' : IDL.Nat64,
+    'reference_column' : IDL.Opt(IDL.Text),
+    'amount_column' : IDL.Text,
+  });
+  const StratumSummary = IDL.Record({
+    'upper_bound' : IDL.Opt(IDL.Float64),
+    'sample_size' : IDL.Nat64,
+    'lower_bound' : IDL.Opt(IDL.Float64),
+    'index' : IDL.Nat32,
+    'population_value' : IDL.Float64,
+    'population_count' : IDL.Nat64,
+  });
+  const SampleEvaluation = IDL.Record({
+    'exceeds_tolerable' : IDL.Opt(IDL.Bool),
+    'incremental_allowance' : IDL.Float64,
+    'known_misstatement' : IDL.Float64,
+    'tolerable_misstatement' : IDL.Opt(IDL.Float64),
+    'misstatements_found' : IDL.Nat64,
+    'confidence' : IDL.Float64,
+    'basic_precision' : IDL.Float64,
+    'projected_misstatement' : IDL.Float64,
+    'evaluated_at' : IDL.Nat64,
+    'evaluated_by' : IDL.Principal,
+    'upper_misstatement_limit' : IDL.Float64,
+    'items_tested' : IDL.Nat64,
+  });
+  const SampleItem = IDL.Record({
+    'audited_amount' : IDL.Opt(IDL.Float64),
+    'book_amount' : IDL.Float64,
+    'reference' : IDL.Text,
+    'row_index' : IDL.Nat64,
+    'stratum' : IDL.Opt(IDL.Nat32),
+    'selection_note' : IDL.Text,
+  });
+  const AuditSample = IDL.Record({
+    'id' : IDL.Nat64,
+    'method' : SamplingMethod,
+    'strata' : IDL.Vec(StratumSummary),
+    'dataset_id' : IDL.Nat64,
+    'evaluation' : IDL.Opt(SampleEvaluation),
+    'name' : IDL.Text,
+    'seed' : IDL.Nat64,
+    'sheet_name' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'sampling_interval' : IDL.Opt(IDL.Float64),
+    'dataset_version' : IDL.Nat32,
+    'engagement_id' : IDL.Nat64,
+    'reference_column' : IDL.Opt(IDL.Text),
+    'excluded_count' : IDL.Nat64,
+    'items' : IDL.Vec(SampleItem),
+    'complete_population' : IDL.Bool,
+    'population_value' : IDL.Float64,
+    'amount_column' : IDL.Text,
+    'population_count' : IDL.Nat64,
+  });
+  const Result_17 = IDL.Variant({ 'Ok' : AuditSample, 'Err' : IDL.Text });
   const CreateBudgetRequest = IDL.Record({
     'total_budgeted_hours' : IDL.Float64,
     'partner_rate' : IDL.Float64,
@@ -260,7 +668,7 @@ export const idlFactory = ({ IDL }) => {
     'staff_rate' : IDL.Float64,
     'senior_hours' : IDL.Float64,
   });
-  const Result_8 = IDL.Variant({ 'Ok' : EngagementBudget, 'Err' : IDL.Text });
+  const Result_18 = IDL.Variant({ 'Ok' : EngagementBudget, 'Err' : IDL.Text });
   const CreateClientRequest = IDL.Record({
     'commercial_registration' : IDL.Opt(IDL.Text),
     'name' : IDL.Text,
@@ -288,7 +696,7 @@ export const idlFactory = ({ IDL }) => {
     'entity_id' : IDL.Opt(IDL.Nat64),
     'organization_id' : IDL.Opt(IDL.Nat64),
   });
-  const Result_9 = IDL.Variant({ 'Ok' : Client, 'Err' : IDL.Text });
+  const Result_19 = IDL.Variant({ 'Ok' : Client, 'Err' : IDL.Text });
   const CreateClientAcceptanceRequest = IDL.Record({
     'questionnaire' : ClientAcceptanceQuestionnaire,
     'client_id' : IDL.Nat64,
@@ -313,7 +721,7 @@ export const idlFactory = ({ IDL }) => {
     'resolution_notes' : IDL.Text,
     'client_id' : IDL.Nat64,
   });
-  const Result_10 = IDL.Variant({ 'Ok' : ConflictCheck, 'Err' : IDL.Text });
+  const Result_20 = IDL.Variant({ 'Ok' : ConflictCheck, 'Err' : IDL.Text });
   const CreateDocumentRequestInput = IDL.Record({
     'title' : IDL.Text,
     'is_required' : IDL.Bool,
@@ -335,6 +743,15 @@ export const idlFactory = ({ IDL }) => {
     'description' : IDL.Text,
     'end_date' : IDL.Nat64,
     'start_date' : IDL.Nat64,
+    'prior_year_engagement_id' : IDL.Opt(IDL.Nat64),
+  });
+  const Materiality = IDL.Record({
+    'set_at' : IDL.Nat64,
+    'set_by' : IDL.Principal,
+    'performance' : IDL.Int64,
+    'overall' : IDL.Int64,
+    'basis' : IDL.Text,
+    'clearly_trivial' : IDL.Int64,
   });
   const Engagement = IDL.Record({
     'id' : IDL.Nat64,
@@ -346,8 +763,10 @@ export const idlFactory = ({ IDL }) => {
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'start_date' : IDL.Nat64,
+    'materiality' : IDL.Opt(Materiality),
+    'prior_year_engagement_id' : IDL.Opt(IDL.Nat64),
   });
-  const Result_11 = IDL.Variant({ 'Ok' : Engagement, 'Err' : IDL.Text });
+  const Result_21 = IDL.Variant({ 'Ok' : Engagement, 'Err' : IDL.Text });
   const CreateEngagementFromTemplateRequest = IDL.Record({
     'manager_in_charge' : IDL.Opt(IDL.Principal),
     'client_acceptance_id' : IDL.Opt(IDL.Nat64),
@@ -384,7 +803,7 @@ export const idlFactory = ({ IDL }) => {
     'due_date' : IDL.Nat64,
     'completed_by' : IDL.Opt(IDL.Principal),
   });
-  const Result_12 = IDL.Variant({
+  const Result_22 = IDL.Variant({
     'Ok' : IDL.Tuple(Engagement, IDL.Vec(EngagementMilestone)),
     'Err' : IDL.Text,
   });
@@ -430,7 +849,7 @@ export const idlFactory = ({ IDL }) => {
     'client_id' : IDL.Nat64,
     'limitations_of_engagement' : IDL.Text,
   });
-  const Result_13 = IDL.Variant({ 'Ok' : EngagementLetter, 'Err' : IDL.Text });
+  const Result_23 = IDL.Variant({ 'Ok' : EngagementLetter, 'Err' : IDL.Text });
   const MilestoneTemplate = IDL.Record({
     'estimated_hours' : IDL.Float64,
     'name' : IDL.Text,
@@ -459,7 +878,7 @@ export const idlFactory = ({ IDL }) => {
     'engagement_type' : EngagementType,
     'default_procedures' : IDL.Vec(IDL.Text),
   });
-  const Result_14 = IDL.Variant({
+  const Result_24 = IDL.Variant({
     'Ok' : EngagementSetupTemplate,
     'Err' : IDL.Text,
   });
@@ -486,7 +905,7 @@ export const idlFactory = ({ IDL }) => {
     'organization_id' : IDL.Nat64,
     'taxonomy' : IDL.Opt(XBRLTaxonomy),
   });
-  const Result_15 = IDL.Variant({ 'Ok' : Entity, 'Err' : IDL.Text });
+  const Result_25 = IDL.Variant({ 'Ok' : Entity, 'Err' : IDL.Text });
   const CreateInvitationRequest = IDL.Record({
     'access_level' : ClientAccessLevel,
     'message' : IDL.Opt(IDL.Text),
@@ -516,10 +935,67 @@ export const idlFactory = ({ IDL }) => {
     'rejection_reason' : IDL.Opt(IDL.Text),
     'invited_email' : IDL.Text,
   });
-  const Result_16 = IDL.Variant({
+  const Result_26 = IDL.Variant({
     'Ok' : EngagementInvitation,
     'Err' : IDL.Text,
   });
+  const ErpSystem = IDL.Variant({
+    'Sap' : IDL.Null,
+    'Odoo' : IDL.Null,
+    'Oracle' : IDL.Null,
+    'Other' : IDL.Text,
+    'Dynamics' : IDL.Null,
+  });
+  const SignConvention = IDL.Variant({
+    'SignedCreditPositive' : IDL.Null,
+    'SignedDebitPositive' : IDL.Null,
+    'DebitCreditColumns' : IDL.Null,
+  });
+  const MappingField = IDL.Variant({
+    'AccountNumber' : IDL.Null,
+    'OpeningDebit' : IDL.Null,
+    'YtdDebit' : IDL.Null,
+    'YtdCredit' : IDL.Null,
+    'Notes' : IDL.Null,
+    'Entity' : IDL.Null,
+    'PeriodCredit' : IDL.Null,
+    'OpeningCredit' : IDL.Null,
+    'AccountName' : IDL.Null,
+    'Currency' : IDL.Null,
+    'Department' : IDL.Null,
+    'Project' : IDL.Null,
+    'PeriodDebit' : IDL.Null,
+  });
+  const HeaderAlias = IDL.Record({
+    'field' : MappingField,
+    'aliases' : IDL.Vec(IDL.Text),
+  });
+  const CreateMappingProfileRequest = IDL.Record({
+    'erp_system' : ErpSystem,
+    'name' : IDL.Text,
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'sign_convention' : SignConvention,
+    'header_row' : IDL.Opt(IDL.Nat32),
+    'header_aliases' : IDL.Vec(HeaderAlias),
+    'client_id' : IDL.Nat64,
+  });
+  const ImportMappingProfile = IDL.Record({
+    'id' : IDL.Nat64,
+    'updated_at' : IDL.Nat64,
+    'erp_system' : ErpSystem,
+    'name' : IDL.Text,
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'sign_convention' : SignConvention,
+    'header_row' : IDL.Nat32,
+    'header_aliases' : IDL.Vec(HeaderAlias),
+    'client_id' : IDL.Nat64,
+  });
+  const Result_27 = IDL.Variant({
+    'Ok' : ImportMappingProfile,
+    'Err' : IDL.Text,
+  });
   const CreateMilestoneRequest = IDL.Record({
     'estimated_hours' : IDL.Float64,
     'name' : IDL.Text,
@@ -528,7 +1004,7 @@ export const idlFactory = ({ IDL }) => {
     'engagement_id' : IDL.Nat64,
     'due_date' : IDL.Nat64,
   });
-  const Result_17 = IDL.Variant({
+  const Result_28 = IDL.Variant({
     'Ok' : EngagementMilestone,
     'Err' : IDL.Text,
   });
@@ -544,7 +1020,67 @@ export const idlFactory = ({ IDL }) => {
     'created_by' : IDL.Principal,
     'entity_ids' : IDL.Vec(IDL.Nat64),
   });
-  const Result_18 = IDL.Variant({ 'Ok' : Organization, 'Err' : IDL.Text });
+  const Result_29 = IDL.Variant({ 'Ok' : Organization, 'Err' : IDL.Text });
+  const ReconciliationSource = IDL.Variant({
+    'Subledger' : IDL.Null,
+    'Confirmation' : IDL.Null,
+    'BankStatement' : IDL.Null,
+    'Other' : IDL.Null,
+  });
+  const ReconcilingItemInput = IDL.Record({
+    'document_id' : IDL.Opt(IDL.Nat64),
+    'description' : IDL.Text,
+    'item_date' : IDL.Opt(IDL.Text),
+    'amount' : IDL.Int64,
+  });
+  const CreateReconciliationRequest = IDL.Record({
+    'account_id' : IDL.Nat64,
+    'source_reference' : IDL.Text,
+    'document_ids' : IDL.Vec(IDL.Nat64),
+    'source' : ReconciliationSource,
+    'supporting_balance' : IDL.Int64,
+    'reconciling_items' : IDL.Vec(ReconcilingItemInput),
+  });
+  const SignOff = IDL.Record({
+    'signed_at' : IDL.Nat64,
+    'signed_by' : IDL.Principal,
+  });
+  const ReconciliationStatus = IDL.Variant({
+    'Open' : IDL.Null,
+    'Prepared' : IDL.Null,
+    'Reviewed' : IDL.Null,
+  });
+  const ReconcilingItem = IDL.Record({
+    'id' : IDL.Nat32,
+    'document_id' : IDL.Opt(IDL.Nat64),
+    'description' : IDL.Text,
+    'item_date' : IDL.Opt(IDL.Text),
+    'amount' : IDL.Int64,
+  });
+  const AccountReconciliation = IDL.Record({
+    'id' : IDL.Nat64,
+    'account_id' : IDL.Nat64,
+    'prepared_by' : IDL.Opt(SignOff),
+    'status' : ReconciliationStatus,
+    'updated_at' : IDL.Nat64,
+    'source_reference' : IDL.Text,
+    'document_ids' : IDL.Vec(IDL.Nat64),
+    'trial_balance_id' : IDL.Nat64,
+    'source' : ReconciliationSource,
+    'unreconciled_difference' : IDL.Int64,
+    'reviewed_by' : IDL.Opt(SignOff),
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'supporting_balance' : IDL.Int64,
+    'account_number' : IDL.Text,
+    'account_balance' : IDL.Int64,
+    'reconciling_items' : IDL.Vec(ReconcilingItem),
+    'account_name' : IDL.Text,
+  });
+  const Result_30 = IDL.Variant({
+    'Ok' : AccountReconciliation,
+    'Err' : IDL.Text,
+  });
   const ChecklistItem = IDL.Record({
     'id' : IDL.Text,
     'title' : IDL.Text,
@@ -583,7 +1119,7 @@ export const idlFactory = ({ IDL }) => {
     'is_default' : IDL.Bool,
     'firm_id' : IDL.Opt(IDL.Nat64),
   });
-  const Result_19 = IDL.Variant({ 'Ok' : AuditTemplate, 'Err' : IDL.Text });
+  const Result_31 = IDL.Variant({ 'Ok' : AuditTemplate, 'Err' : IDL.Text });
   const CreateTimeEntryRequest = IDL.Record({
     'hours' : IDL.Float64,
     'date' : IDL.Nat64,
@@ -603,30 +1139,40 @@ export const idlFactory = ({ IDL }) => {
     'engagement_id' : IDL.Nat64,
     'milestone_id' : IDL.Opt(IDL.Nat64),
   });
-  const Result_20 = IDL.Variant({ 'Ok' : TimeEntry, 'Err' : IDL.Text });
+  const Result_32 = IDL.Variant({ 'Ok' : TimeEntry, 'Err' : IDL.Text });
+  const PeriodType = IDL.Variant({
+    'PriorYear' : IDL.Null,
+    'Interim' : IDL.Null,
+    'YearEnd' : IDL.Null,
+  });
   const CreateTrialBalanceRequest = IDL.Record({
+    'prior_trial_balance_id' : IDL.Opt(IDL.Nat64),
     'description' : IDL.Text,
     'currency' : IDL.Opt(IDL.Text),
     'engagement_id' : IDL.Nat64,
+    'period_type' : IDL.Opt(PeriodType),
     'period_end_date' : IDL.Text,
   });
   const TrialBalance = IDL.Record({
     'id' : IDL.Nat64,
     'last_modified_at' : IDL.Nat64,
     'last_modified_by' : IDL.Principal,
+    'prior_trial_balance_id' : IDL.Opt(IDL.Nat64),
     'description' : IDL.Text,
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'is_adjusted' : IDL.Bool,
     'currency' : IDL.Text,
     'engagement_id' : IDL.Nat64,
+    'period_type' : IDL.Opt(PeriodType),
     'period_end_date' : IDL.Text,
   });
-  const Result_21 = IDL.Variant({ 'Ok' : TrialBalance, 'Err' : IDL.Text });
+  const Result_33 = IDL.Variant({ 'Ok' : TrialBalance, 'Err' : IDL.Text });
   const ColumnMapping = IDL.Record({
     'entity' : IDL.Opt(IDL.Text),
     'opening_debit' : IDL.Opt(IDL.Text),
     'period_credit' : IDL.Opt(IDL.Text),
+    'sign_convention' : IDL.Opt(SignConvention),
     'account_number' : IDL.Opt(IDL.Text),
     'currency' : IDL.Opt(IDL.Text),
     'notes' : IDL.Opt(IDL.Text),
@@ -638,12 +1184,38 @@ export const idlFactory = ({ IDL }) => {
     'account_name' : IDL.Opt(IDL.Text),
     'project' : IDL.Opt(IDL.Text),
   });
+  const SignificanceThresholds = IDL.Record({
+    'percent' : IDL.Opt(IDL.Float64),
+    'amount' : IDL.Opt(IDL.Int64),
+  });
   const CreateWorkingPaperRequest = IDL.Record({
-    'dataset_id' : IDL.Nat64,
+    'reference_code' : IDL.Opt(IDL.Text),
+    'trial_balance_id' : IDL.Opt(IDL.Nat64),
+    'dataset_id' : IDL.Opt(IDL.Nat64),
     'column_mapping' : ColumnMapping,
     'name' : IDL.Text,
     'selected_accounts' : IDL.Vec(IDL.Text),
     'engagement_id' : IDL.Nat64,
+    'trend_thresholds' : IDL.Opt(SignificanceThresholds),
+  });
+  const WorkingPaperStatus = IDL.Variant({
+    'Approved' : IDL.Null,
+    'Draft' : IDL.Null,
+    'Prepared' : IDL.Null,
+    'Locked' : IDL.Null,
+    'Reviewed' : IDL.Null,
+  });
+  const CellValue = IDL.Variant({
+    'Empty' : IDL.Null,
+    'Text' : IDL.Text,
+    'Boolean' : IDL.Bool,
+    'Number' : IDL.Float64,
+  });
+  const WorkingPaperCell = IDL.Record({
+    'value' : CellValue,
+    'error' : IDL.Opt(IDL.Text),
+    'address' : IDL.Text,
+    'input' : IDL.Text,
   });
   const VarianceAnalysis = IDL.Record({
     'actual' : IDL.Float64,
@@ -652,11 +1224,46 @@ export const idlFactory = ({ IDL }) => {
     'item_name' : IDL.Text,
     'variance' : IDL.Float64,
   });
-  const FinancialRatio = IDL.Record({
+  const RatioInput = IDL.Record({
     'value' : IDL.Float64,
+    'source' : IDL.Text,
     'name' : IDL.Text,
+  });
+  const FinancialRatio = IDL.Record({
+    'value' : IDL.Opt(IDL.Float64),
+    'name' : IDL.Text,
+    'unavailable_reason' : IDL.Opt(IDL.Text),
+    'inputs' : IDL.Opt(IDL.Vec(RatioInput)),
     'formula' : IDL.Text,
   });
+  const LeadsheetChange = IDL.Record({
+    'previous_balance' : IDL.Opt(IDL.Float64),
+    'current_balance' : IDL.Opt(IDL.Float64),
+    'account_number' : IDL.Text,
+    'change' : IDL.Float64,
+    'account_name' : IDL.Text,
+  });
+  const LeadsheetRefresh = IDL.Record({
+    'closing_balance' : IDL.Float64,
+    'changes' : IDL.Vec(LeadsheetChange),
+    'previous_closing_balance' : IDL.Float64,
+    'refreshed_at' : IDL.Nat64,
+    'refreshed_by' : IDL.Principal,
+  });
+  const LeadsheetSource = IDL.Variant({
+    'TrialBalance' : IDL.Null,
+    'Dataset' : IDL.Null,
+  });
+  const LeadsheetLine = IDL.Record({
+    'account_id' : IDL.Nat64,
+    'fs_line_item' : IDL.Opt(IDL.Text),
+    'unadjusted_balance' : IDL.Int64,
+    'adjustments' : IDL.Int64,
+    'aje_ids' : IDL.Vec(IDL.Nat64),
+    'account_number' : IDL.Text,
+    'adjusted_balance' : IDL.Int64,
+    'account_name' : IDL.Text,
+  });
   const AccountData = IDL.Record({
     'entity' : IDL.Text,
     'opening_debit' : IDL.Float64,
@@ -673,9 +1280,13 @@ export const idlFactory = ({ IDL }) => {
     'project' : IDL.Text,
   });
   const Leadsheet = IDL.Record({
+    'period_activity' : IDL.Opt(IDL.Float64),
+    'source' : IDL.Opt(LeadsheetSource),
     'closing_balance' : IDL.Float64,
-    'opening_balance' : IDL.Float64,
+    'opening_balance' : IDL.Opt(IDL.Float64),
+    'unadjusted_balance' : IDL.Opt(IDL.Float64),
     'created_at' : IDL.Nat64,
+    'lines' : IDL.Opt(IDL.Vec(LeadsheetLine)),
     'adjustments' : IDL.Float64,
     'accounts' : IDL.Vec(AccountData),
   });
@@ -686,22 +1297,94 @@ export const idlFactory = ({ IDL }) => {
     'change' : IDL.Float64,
     'current_value' : IDL.Float64,
   });
+  const TrendPeriod = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'engagement_id' : IDL.Nat64,
+    'period_end_date' : IDL.Text,
+    'engagement_name' : IDL.Text,
+  });
+  const TrendChange = IDL.Record({
+    'change_percent' : IDL.Opt(IDL.Float64),
+    'change' : IDL.Opt(IDL.Int64),
+  });
+  const TrendRow = IDL.Record({
+    'key' : IDL.Text,
+    'name' : IDL.Text,
+    'is_significant' : IDL.Bool,
+    'amounts' : IDL.Vec(IDL.Opt(IDL.Int64)),
+    'changes' : IDL.Vec(TrendChange),
+    'significance_reason' : IDL.Opt(IDL.Text),
+  });
+  const MultiPeriodTrend = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'generated_at' : IDL.Nat64,
+    'gaps' : IDL.Vec(IDL.Text),
+    'periods' : IDL.Vec(TrendPeriod),
+    'accounts' : IDL.Vec(TrendRow),
+    'engagement_id' : IDL.Nat64,
+    'thresholds' : SignificanceThresholds,
+    'fs_lines' : IDL.Vec(TrendRow),
+  });
   const WorkingPaper = IDL.Record({
     'id' : IDL.Nat64,
+    'prepared_by' : IDL.Opt(SignOff),
+    'last_modified_at' : IDL.Opt(IDL.Nat64),
+    'last_modified_by' : IDL.Opt(IDL.Principal),
     'linked_document_ids' : IDL.Vec(IDL.Nat64),
-    'dataset_id' : IDL.Nat64,
+    'status' : IDL.Opt(WorkingPaperStatus),
+    'reference_code' : IDL.Opt(IDL.Text),
+    'trial_balance_id' : IDL.Opt(IDL.Nat64),
+    'dataset_id' : IDL.Opt(IDL.Nat64),
     'column_mapping' : ColumnMapping,
+    'cells' : IDL.Opt(IDL.Vec(WorkingPaperCell)),
     'name' : IDL.Text,
+    'approved_by' : IDL.Opt(SignOff),
+    'reviewed_by' : IDL.Opt(SignOff),
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'variance_analysis' : IDL.Vec(VarianceAnalysis),
+    'dataset_version' : IDL.Opt(IDL.Nat32),
+    'selected_accounts' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'is_stale' : IDL.Opt(IDL.Bool),
     'engagement_id' : IDL.Nat64,
     'ratios' : IDL.Vec(FinancialRatio),
+    'refresh_history' : IDL.Opt(IDL.Vec(LeadsheetRefresh)),
+    'stale_reason' : IDL.Opt(IDL.Text),
     'leadsheet' : IDL.Opt(Leadsheet),
     'trend_analysis' : IDL.Vec(TrendAnalysis),
+    'multi_period_trend' : IDL.Opt(MultiPeriodTrend),
+  });
+  const Result_34 = IDL.Variant({ 'Ok' : WorkingPaper, 'Err' : IDL.Text });
+  const Result_35 = IDL.Variant({ 'Ok' : CoaAccount, 'Err' : IDL.Text });
+  const DefineTickMarkRequest = IDL.Record({
+    'meaning' : IDL.Text,
+    'engagement_id' : IDL.Nat64,
+    'symbol' : IDL.Text,
+  });
+  const TickMark = IDL.Record({
+    'id' : IDL.Nat64,
+    'meaning' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'engagement_id' : IDL.Nat64,
+    'symbol' : IDL.Text,
+  });
+  const Result_36 = IDL.Variant({ 'Ok' : TickMark, 'Err' : IDL.Text });
+  const Result_37 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Nat8), 'Err' : IDL.Text });
+  const SampleItemResult = IDL.Record({
+    'audited_amount' : IDL.Float64,
+    'row_index' : IDL.Nat64,
+  });
+  const EvaluateAuditSampleRequest = IDL.Record({
+    'sample_id' : IDL.Nat64,
+    'results' : IDL.Vec(SampleItemResult),
+    'tolerable_misstatement' : IDL.Opt(IDL.Float64),
+    'confidence' : IDL.Opt(IDL.Float64),
+  });
+  const ExplainVarianceRequest = IDL.Record({
+    'explanation' : IDL.Text,
+    'expectation_id' : IDL.Nat64,
   });
-  const Result_22 = IDL.Variant({ 'Ok' : WorkingPaper, 'Err' : IDL.Text });
-  const Result_23 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Nat8), 'Err' : IDL.Text });
   const FulfillDocumentRequestInput = IDL.Record({
     'request_id' : IDL.Nat64,
     'document_name' : IDL.Text,
@@ -725,6 +1408,7 @@ export const idlFactory = ({ IDL }) => {
   const FSLine = IDL.Record({
     'mapped_accounts' : IDL.Vec(IDL.Nat64),
     'line_item' : FSLineItem,
+    'prior_amounts' : IDL.Opt(IDL.Vec(IDL.Opt(IDL.Int64))),
     'amount' : IDL.Int64,
   });
   const FSNote = IDL.Record({
@@ -740,13 +1424,14 @@ export const idlFactory = ({ IDL }) => {
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'lines' : IDL.Vec(FSLine),
+    'comparative_periods' : IDL.Opt(IDL.Vec(TrendPeriod)),
     'notes' : IDL.Vec(FSNote),
     'engagement_id' : IDL.Nat64,
     'period_end_date' : IDL.Text,
     'last_modified' : IDL.Nat64,
     'taxonomy' : XBRLTaxonomy,
   });
-  const Result_24 = IDL.Variant({
+  const Result_38 = IDL.Variant({
     'Ok' : FinancialStatement,
     'Err' : IDL.Text,
   });
@@ -764,10 +1449,54 @@ export const idlFactory = ({ IDL }) => {
     'details' : IDL.Text,
     'block_height' : IDL.Nat64,
   });
-  const Result_25 = IDL.Variant({
+  const Result_39 = IDL.Variant({
     'Ok' : IDL.Vec(ActivityLogEntry),
     'Err' : IDL.Text,
   });
+  const AdjustedAccountRow = IDL.Record({
+    'account_id' : IDL.Nat64,
+    'reclassification_adjustments' : IDL.Int64,
+    'fs_line_item' : IDL.Opt(IDL.Text),
+    'adjusting_adjustments' : IDL.Int64,
+    'unadjusted_balance' : IDL.Int64,
+    'adjustments' : IDL.Vec(IDL.Int64),
+    'account_number' : IDL.Text,
+    'proposed_adjustments' : IDL.Int64,
+    'reversing_adjustments' : IDL.Int64,
+    'pro_forma_balance' : IDL.Opt(IDL.Int64),
+    'adjusted_balance' : IDL.Int64,
+    'posted_adjustments' : IDL.Int64,
+    'account_name' : IDL.Text,
+    'account_type' : FSCategory,
+  });
+  const AjeColumn = IDL.Record({
+    'status' : AjeStatus,
+    'aje_id' : IDL.Nat64,
+    'entry_type' : AjeEntryType,
+    'description' : IDL.Text,
+    'is_posted' : IDL.Bool,
+    'aje_number' : IDL.Text,
+  });
+  const AdjustedTrialBalance = IDL.Record({
+    'unadjusted_total' : IDL.Int64,
+    'trial_balance_id' : IDL.Nat64,
+    'proposed_total' : IDL.Int64,
+    'generated_at' : IDL.Nat64,
+    'adjusting_total' : IDL.Int64,
+    'posted_total' : IDL.Int64,
+    'is_balanced' : IDL.Bool,
+    'accounts' : IDL.Vec(AdjustedAccountRow),
+    'engagement_id' : IDL.Nat64,
+    'include_proposed' : IDL.Bool,
+    'reversing_total' : IDL.Int64,
+    'reclassification_total' : IDL.Int64,
+    'columns' : IDL.Vec(AjeColumn),
+    'adjusted_total' : IDL.Int64,
+  });
+  const Result_40 = IDL.Variant({
+    'Ok' : AdjustedTrialBalance,
+    'Err' : IDL.Text,
+  });
   const AjeLineItem = IDL.Record({
     'id' : IDL.Nat64,
     'account_id' : IDL.Nat64,
@@ -778,7 +1507,7 @@ export const idlFactory = ({ IDL }) => {
     'credit_amount' : IDL.Int64,
     'account_name' : IDL.Text,
   });
-  const Result_26 = IDL.Variant({
+  const Result_41 = IDL.Variant({
     'Ok' : IDL.Vec(AjeLineItem),
     'Err' : IDL.Text,
   });
@@ -790,16 +1519,75 @@ export const idlFactory = ({ IDL }) => {
     'entry_id' : IDL.Nat64,
     'block_height' : IDL.Nat64,
   });
-  const Result_27 = IDL.Variant({ 'Ok' : BlockchainProof, 'Err' : IDL.Text });
-  const Result_28 = IDL.Variant({
+  const Result_42 = IDL.Variant({ 'Ok' : BlockchainProof, 'Err' : IDL.Text });
+  const Result_43 = IDL.Variant({
     'Ok' : IDL.Vec(ClientAccess),
     'Err' : IDL.Text,
   });
+  const CoaMappingReport = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'owner' : IDL.Opt(CoaOwner),
+    'missing_accounts' : IDL.Vec(CoaAccount),
+    'new_accounts' : IDL.Vec(TrialBalanceAccount),
+    'type_mismatches' : IDL.Vec(TrialBalanceAccount),
+    'abnormal_balances' : IDL.Vec(TrialBalanceAccount),
+    'unmapped_accounts' : IDL.Vec(TrialBalanceAccount),
+    'matched_accounts' : IDL.Nat32,
+  });
+  const Result_44 = IDL.Variant({ 'Ok' : CoaMappingReport, 'Err' : IDL.Text });
+  const RuleViolation = IDL.Record({
+    'column_name' : IDL.Text,
+    'value' : IDL.Text,
+    'message' : IDL.Text,
+    'row_number' : IDL.Nat64,
+  });
+  const RuleSeverity = IDL.Variant({
+    'Info' : IDL.Null,
+    'Critical' : IDL.Null,
+    'Warning' : IDL.Null,
+  });
+  const RuleResult = IDL.Record({
+    'violations' : IDL.Vec(RuleViolation),
+    'severity' : RuleSeverity,
+    'violation_count' : IDL.Nat64,
+    'passed' : IDL.Bool,
+    'rule_name' : IDL.Text,
+  });
+  const DataQualityReport = IDL.Record({
+    'block_on_critical' : IDL.Bool,
+    'dataset_id' : IDL.Nat64,
+    'sheet_name' : IDL.Text,
+    'results' : IDL.Vec(RuleResult),
+    'total_rows' : IDL.Nat64,
+    'dataset_version' : IDL.Nat32,
+    'warnings' : IDL.Nat32,
+    'rows_checked' : IDL.Nat64,
+    'evaluated_at' : IDL.Nat64,
+    'evaluated_by' : IDL.Principal,
+    'critical_failures' : IDL.Nat32,
+  });
+  const Result_45 = IDL.Variant({ 'Ok' : DataQualityReport, 'Err' : IDL.Text });
+  const PIIKind = IDL.Variant({
+    'Email' : IDL.Null,
+    'Iban' : IDL.Null,
+    'UaeEmiratesId' : IDL.Null,
+    'PersonName' : IDL.Null,
+    'EgyptianNationalId' : IDL.Null,
+    'PhoneNumber' : IDL.Null,
+    'SaudiNationalId' : IDL.Null,
+  });
+  const PIIFinding = IDL.Record({
+    'matched_rows' : IDL.Nat64,
+    'kind' : PIIKind,
+    'confidence' : IDL.Float64,
+  });
   const PIIDetection = IDL.Record({
     'has_national_ids' : IDL.Bool,
     'has_emails' : IDL.Bool,
     'has_names' : IDL.Bool,
+    'findings' : IDL.Opt(IDL.Vec(PIIFinding)),
     'has_phone_numbers' : IDL.Bool,
+    'has_ibans' : IDL.Opt(IDL.Bool),
   });
   const ColumnType = IDL.Variant({
     'Date' : IDL.Null,
@@ -808,6 +1596,14 @@ export const idlFactory = ({ IDL }) => {
     'Currency' : IDL.Null,
     'Numeric' : IDL.Null,
   });
+  const MaskingMethod = IDL.Variant({
+    'Hash' : IDL.Null,
+    'Redact' : IDL.Null,
+    'PartialMask' : IDL.Record({
+      'visible_prefix' : IDL.Nat32,
+      'visible_suffix' : IDL.Nat32,
+    }),
+  });
   const ColumnMetadata = IDL.Record({
     'max_value' : IDL.Text,
     'pii_detection' : PIIDetection,
@@ -818,25 +1614,128 @@ export const idlFactory = ({ IDL }) => {
     'sample_values' : IDL.Vec(IDL.Text),
     'original_name' : IDL.Text,
     'null_percent' : IDL.Float64,
+    'masking' : IDL.Opt(MaskingMethod),
   });
   const SheetData = IDL.Record({
     'data' : IDL.Vec(IDL.Vec(IDL.Text)),
     'name' : IDL.Text,
     'row_count' : IDL.Nat64,
+    'header_row' : IDL.Opt(IDL.Nat32),
     'columns' : IDL.Vec(ColumnMetadata),
   });
+  const MappingProfileMatch = IDL.Record({
+    'column_mapping' : ColumnMapping,
+    'sheet_name' : IDL.Text,
+    'score' : IDL.Float64,
+    'matched_fields' : IDL.Nat32,
+    'unmatched_fields' : IDL.Vec(MappingField),
+    'profile_id' : IDL.Nat64,
+    'profile_name' : IDL.Text,
+  });
+  const ColumnMaskingPolicy = IDL.Record({
+    'column_name' : IDL.Text,
+    'method' : MaskingMethod,
+    'sheet_name' : IDL.Opt(IDL.Text),
+  });
   const ImportedDataset = IDL.Record({
     'id' : IDL.Nat64,
+    'quality_report' : IDL.Opt(DataQualityReport),
     'sheets' : IDL.Vec(SheetData),
     'name' : IDL.Text,
+    'suggested_mapping' : IDL.Opt(MappingProfileMatch),
     'created_at' : IDL.Nat64,
     'created_by' : IDL.Principal,
     'file_name' : IDL.Text,
     'file_size' : IDL.Nat64,
     'version' : IDL.Nat32,
     'engagement_id' : IDL.Opt(IDL.Nat64),
+    'masking_policies' : IDL.Opt(IDL.Vec(ColumnMaskingPolicy)),
+  });
+  const Result_46 = IDL.Variant({ 'Ok' : ImportedDataset, 'Err' : IDL.Text });
+  const CellChange = IDL.Record({
+    'old_value' : IDL.Text,
+    'column_name' : IDL.Text,
+    'new_value' : IDL.Text,
+  });
+  const RowChangeType = IDL.Variant({
+    'Changed' : IDL.Null,
+    'Added' : IDL.Null,
+    'Removed' : IDL.Null,
+  });
+  const RowChange = IDL.Record({
+    'key' : IDL.Text,
+    'cell_changes' : IDL.Vec(CellChange),
+    'old_row_index' : IDL.Opt(IDL.Nat64),
+    'change_type' : RowChangeType,
+    'new_row_index' : IDL.Opt(IDL.Nat64),
+  });
+  const SheetDiff = IDL.Record({
+    'changed_count' : IDL.Nat64,
+    'removed_columns' : IDL.Vec(IDL.Text),
+    'rows' : IDL.Vec(RowChange),
+    'sheet_name' : IDL.Text,
+    'added_count' : IDL.Nat64,
+    'removed_count' : IDL.Nat64,
+    'unchanged_count' : IDL.Nat64,
+    'added_columns' : IDL.Vec(IDL.Text),
+  });
+  const DatasetDiff = IDL.Record({
+    'sheets' : IDL.Vec(SheetDiff),
+    'dataset_id' : IDL.Nat64,
+    'to_version' : IDL.Nat32,
+    'from_version' : IDL.Nat32,
+    'key_column' : IDL.Text,
+  });
+  const Result_47 = IDL.Variant({ 'Ok' : DatasetDiff, 'Err' : IDL.Text });
+  const ConformityLevel = IDL.Variant({
+    'Nonconformity' : IDL.Null,
+    'AcceptableConformity' : IDL.Null,
+    'MarginallyAcceptable' : IDL.Null,
+    'CloseConformity' : IDL.Null,
+  });
+  const DigitTest = IDL.Variant({
+    'FirstDigit' : IDL.Null,
+    'LastDigit' : IDL.Null,
+    'FirstTwoDigits' : IDL.Null,
+  });
+  const DigitFrequency = IDL.Record({
+    'observed_proportion' : IDL.Float64,
+    'expected_count' : IDL.Float64,
+    'z_statistic' : IDL.Float64,
+    'observed_count' : IDL.Nat64,
+    'digits' : IDL.Text,
+    'expected_proportion' : IDL.Float64,
+  });
+  const DigitDeviation = IDL.Record({
+    'expected_count' : IDL.Float64,
+    'z_statistic' : IDL.Float64,
+    'observed_count' : IDL.Nat64,
+    'digits' : IDL.Text,
+    'row_indexes' : IDL.Vec(IDL.Nat64),
+  });
+  const DigitAnalysis = IDL.Record({
+    'id' : IDL.Nat64,
+    'mad' : IDL.Float64,
+    'column_name' : IDL.Text,
+    'working_paper_id' : IDL.Nat64,
+    'dataset_id' : IDL.Nat64,
+    'degrees_of_freedom' : IDL.Nat32,
+    'conformity' : ConformityLevel,
+    'chi_square' : IDL.Float64,
+    'test' : DigitTest,
+    'sheet_name' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'records_excluded' : IDL.Nat64,
+    'frequencies' : IDL.Vec(DigitFrequency),
+    'records_tested' : IDL.Nat64,
+    'dataset_version' : IDL.Nat32,
+    'engagement_id' : IDL.Nat64,
+    'top_deviations' : IDL.Vec(DigitDeviation),
+    'complete_population' : IDL.Bool,
+    'chi_square_critical' : IDL.Float64,
   });
-  const Result_29 = IDL.Variant({ 'Ok' : ImportedDataset, 'Err' : IDL.Text });
+  const Result_48 = IDL.Variant({ 'Ok' : DigitAnalysis, 'Err' : IDL.Text });
   const Document = IDL.Record({
     'id' : IDL.Nat64,
     'name' : IDL.Text,
@@ -850,17 +1749,18 @@ export const idlFactory = ({ IDL }) => {
     'entity_id' : IDL.Opt(IDL.Nat64),
     'organization_id' : IDL.Opt(IDL.Nat64),
   });
-  const Result_30 = IDL.Variant({ 'Ok' : Document, 'Err' : IDL.Text });
-  const Result_31 = IDL.Variant({
+  const Result_49 = IDL.Variant({ 'Ok' : Document, 'Err' : IDL.Text });
+  const Result_50 = IDL.Variant({
     'Ok' : IDL.Vec(DocumentRequest),
     'Err' : IDL.Text,
   });
-  const Result_32 = IDL.Variant({
+  const Result_51 = IDL.Variant({
     'Ok' : IDL.Vec(EngagementChecklist),
     'Err' : IDL.Text,
   });
   const EngagementDashboard = IDL.Record({
     'at_risk_milestones' : IDL.Vec(EngagementMilestone),
+    'unexplained_variances' : IDL.Vec(AnalyticalExpectation),
     'budget_utilization' : IDL.Float64,
     'completion_percentage' : IDL.Float64,
     'recent_time_entries' : IDL.Vec(TimeEntry),
@@ -869,22 +1769,188 @@ export const idlFactory = ({ IDL }) => {
     'engagement' : Engagement,
     'milestones' : IDL.Vec(EngagementMilestone),
   });
-  const Result_33 = IDL.Variant({
+  const Result_52 = IDL.Variant({
     'Ok' : EngagementDashboard,
     'Err' : IDL.Text,
   });
-  const Result_34 = IDL.Variant({
-    'Ok' : IDL.Vec(EngagementInvitation),
-    'Err' : IDL.Text,
-  });
-  const Result_35 = IDL.Variant({
-    'Ok' : IDL.Vec(IDL.Tuple(IDL.Nat64, IDL.Text, IDL.Text)),
-    'Err' : IDL.Text,
+  const GlColumnMapping = IDL.Record({
+    'posting_date' : IDL.Text,
+    'entry_date' : IDL.Opt(IDL.Text),
+    'description' : IDL.Opt(IDL.Text),
+    'entry_number' : IDL.Text,
+    'credit' : IDL.Opt(IDL.Text),
+    'account_number' : IDL.Text,
+    'posted_by' : IDL.Opt(IDL.Text),
+    'amount' : IDL.Opt(IDL.Text),
+    'debit' : IDL.Opt(IDL.Text),
+    'account_name' : IDL.Opt(IDL.Text),
   });
-  const Result_36 = IDL.Variant({
+  const GlImport = IDL.Record({
+    'id' : IDL.Nat64,
+    'imported_at' : IDL.Nat64,
+    'imported_by' : IDL.Principal,
+    'dataset_id' : IDL.Nat64,
+    'mapping' : GlColumnMapping,
+    'sheet_name' : IDL.Text,
+    'entry_count' : IDL.Nat64,
+    'dataset_version' : IDL.Nat32,
+    'engagement_id' : IDL.Nat64,
+    'total_credits' : IDL.Int64,
+    'total_debits' : IDL.Int64,
+    'complete_population' : IDL.Bool,
+    'line_count' : IDL.Nat64,
+    'skipped_rows' : IDL.Vec(IDL.Nat64),
+    'unbalanced_entries' : IDL.Vec(IDL.Text),
+  });
+  const Result_53 = IDL.Variant({ 'Ok' : GlImport, 'Err' : IDL.Text });
+  const Result_54 = IDL.Variant({
+    'Ok' : IDL.Vec(EngagementInvitation),
+    'Err' : IDL.Text,
+  });
+  const JetFlag = IDL.Variant({
+    'WeekendPosting' : IDL.Null,
+    'Duplicate' : IDL.Null,
+    'Unbalanced' : IDL.Null,
+    'HolidayPosting' : IDL.Null,
+    'PostClose' : IDL.Null,
+    'JustBelowThreshold' : IDL.Null,
+    'UnusualUserAccount' : IDL.Null,
+    'RoundAmount' : IDL.Null,
+  });
+  const JetFlagCount = IDL.Record({ 'flag' : JetFlag, 'count' : IDL.Nat64 });
+  const JetFinding = IDL.Record({
+    'flags' : IDL.Vec(JetFlag),
+    'posting_date' : IDL.Text,
+    'journal_entry_id' : IDL.Nat64,
+    'entry_number' : IDL.Text,
+    'posted_by' : IDL.Text,
+    'details' : IDL.Vec(IDL.Text),
+    'risk_score' : IDL.Nat32,
+    'amount' : IDL.Int64,
+  });
+  const JetConfig = IDL.Record({
+    'approval_thresholds' : IDL.Vec(IDL.Int64),
+    'holidays' : IDL.Vec(IDL.Text),
+    'close_date' : IDL.Opt(IDL.Text),
+    'round_amount_unit' : IDL.Opt(IDL.Int64),
+    'period_end_date' : IDL.Text,
+    'weekend_days' : IDL.Opt(IDL.Vec(IDL.Nat32)),
+    'just_below_percent' : IDL.Opt(IDL.Float64),
+    'rare_pair_max_count' : IDL.Opt(IDL.Nat32),
+  });
+  const JetRun = IDL.Record({
+    'id' : IDL.Nat64,
+    'entries_tested' : IDL.Nat64,
+    'flagged_entries' : IDL.Nat64,
+    'run_at' : IDL.Nat64,
+    'run_by' : IDL.Principal,
+    'gl_import_id' : IDL.Nat64,
+    'flag_counts' : IDL.Vec(JetFlagCount),
+    'engagement_id' : IDL.Nat64,
+    'findings' : IDL.Vec(JetFinding),
+    'config' : JetConfig,
+  });
+  const Result_55 = IDL.Variant({ 'Ok' : JetRun, 'Err' : IDL.Text });
+  const JournalEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'posting_date' : IDL.Text,
+    'entry_date' : IDL.Opt(IDL.Text),
+    'description' : IDL.Text,
+    'total_debit' : IDL.Int64,
+    'entry_number' : IDL.Text,
+    'posted_by' : IDL.Text,
+    'gl_import_id' : IDL.Nat64,
+    'engagement_id' : IDL.Nat64,
+    'total_credit' : IDL.Int64,
+    'line_count' : IDL.Nat32,
+  });
+  const Result_56 = IDL.Variant({
+    'Ok' : IDL.Vec(JournalEntry),
+    'Err' : IDL.Text,
+  });
+  const JournalLine = IDL.Record({
+    'id' : IDL.Nat64,
+    'journal_entry_id' : IDL.Nat64,
+    'description' : IDL.Text,
+    'credit' : IDL.Int64,
+    'line_number' : IDL.Nat32,
+    'account_number' : IDL.Text,
+    'gl_import_id' : IDL.Nat64,
+    'row_index' : IDL.Nat64,
+    'debit' : IDL.Int64,
+    'account_name' : IDL.Text,
+  });
+  const Result_57 = IDL.Variant({
+    'Ok' : IDL.Vec(JournalLine),
+    'Err' : IDL.Text,
+  });
+  const MultiPeriodTrendRequest = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'thresholds' : IDL.Opt(SignificanceThresholds),
+    'years' : IDL.Opt(IDL.Nat32),
+  });
+  const Result_58 = IDL.Variant({ 'Ok' : MultiPeriodTrend, 'Err' : IDL.Text });
+  const Result_59 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Tuple(IDL.Nat64, IDL.Text, IDL.Text)),
+    'Err' : IDL.Text,
+  });
+  const Result_60 = IDL.Variant({
     'Ok' : IDL.Vec(TrialBalanceAccount),
     'Err' : IDL.Text,
   });
+  const Result_61 = IDL.Variant({
+    'Ok' : IDL.Vec(TrialBalance),
+    'Err' : IDL.Text,
+  });
+  const FsLineEffect = IDL.Record({
+    'fs_line_item' : IDL.Text,
+    'amount' : IDL.Int64,
+  });
+  const UncorrectedMisstatement = IDL.Record({
+    'aje_id' : IDL.Nat64,
+    'description' : IDL.Text,
+    'is_prior_year_turnaround' : IDL.Bool,
+    'fs_line_effects' : IDL.Vec(FsLineEffect),
+    'engagement_id' : IDL.Nat64,
+    'equity_effect' : IDL.Int64,
+    'aje_number' : IDL.Text,
+    'profit_before_tax_effect' : IDL.Int64,
+    'classification' : MisstatementClassification,
+  });
+  const MisstatementSummary = IDL.Record({
+    'judgmental_total' : IDL.Int64,
+    'projected_total' : IDL.Int64,
+    'current_year_profit_effect' : IDL.Int64,
+    'exceeds_performance_materiality' : IDL.Opt(IDL.Bool),
+    'generated_at' : IDL.Nat64,
+    'conclusion' : IDL.Text,
+    'factual_total' : IDL.Int64,
+    'materiality' : IDL.Opt(Materiality),
+    'exceeds_overall_materiality' : IDL.Opt(IDL.Bool),
+    'engagement_id' : IDL.Nat64,
+    'equity_effect' : IDL.Int64,
+    'items' : IDL.Vec(UncorrectedMisstatement),
+    'turnaround_profit_effect' : IDL.Int64,
+    'fs_lines' : IDL.Vec(FsLineEffect),
+    'profit_before_tax_effect' : IDL.Int64,
+  });
+  const Result_62 = IDL.Variant({
+    'Ok' : MisstatementSummary,
+    'Err' : IDL.Text,
+  });
+  const UnreconciledAccount = IDL.Record({
+    'account_id' : IDL.Nat64,
+    'status' : IDL.Opt(ReconciliationStatus),
+    'balance' : IDL.Int64,
+    'unreconciled_difference' : IDL.Opt(IDL.Int64),
+    'reconciliation_id' : IDL.Opt(IDL.Nat64),
+    'account_number' : IDL.Text,
+    'account_name' : IDL.Text,
+  });
+  const Result_63 = IDL.Variant({
+    'Ok' : IDL.Vec(UnreconciledAccount),
+    'Err' : IDL.Text,
+  });
   const GrantClientAccessRequest = IDL.Record({
     'access_level' : ClientAccessLevel,
     'client_principal' : IDL.Principal,
@@ -894,82 +1960,322 @@ export const idlFactory = ({ IDL }) => {
     'name' : IDL.Text,
     'file_data' : IDL.Vec(IDL.Nat8),
     'file_name' : IDL.Text,
+    'auto_mask_pii' : IDL.Opt(IDL.Bool),
     'engagement_id' : IDL.Opt(IDL.Nat64),
+    'mapping_profile_id' : IDL.Opt(IDL.Nat64),
+    'masking_policies' : IDL.Opt(IDL.Vec(ColumnMaskingPolicy)),
+  });
+  const FxRateType = IDL.Variant({
+    'Average' : IDL.Null,
+    'Closing' : IDL.Null,
+    'Historical' : IDL.Null,
+  });
+  const SetFxRateRequest = IDL.Record({
+    'rate' : IDL.Float64,
+    'effective_date' : IDL.Text,
+    'to_currency' : IDL.Text,
+    'from_currency' : IDL.Text,
+    'rate_type' : FxRateType,
+  });
+  const FxRateSource = IDL.Variant({
+    'Imported' : IDL.Null,
+    'Manual' : IDL.Null,
+  });
+  const FxRate = IDL.Record({
+    'id' : IDL.Nat64,
+    'source' : FxRateSource,
+    'rate' : IDL.Float64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'effective_date' : IDL.Text,
+    'to_currency' : IDL.Text,
+    'from_currency' : IDL.Text,
+    'rate_type' : FxRateType,
+  });
+  const Result_64 = IDL.Variant({ 'Ok' : IDL.Vec(FxRate), 'Err' : IDL.Text });
+  const ImportGlJournalRequest = IDL.Record({
+    'dataset_id' : IDL.Nat64,
+    'mapping' : GlColumnMapping,
+    'file_data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'engagement_id' : IDL.Nat64,
   });
   const CsvAccountRow = IDL.Record({
     'debit_balance' : IDL.Int64,
     'credit_balance' : IDL.Int64,
     'account_number' : IDL.Text,
+    'currency' : IDL.Opt(IDL.Text),
     'account_name' : IDL.Text,
   });
-  const Result_37 = IDL.Variant({
+  const Result_65 = IDL.Variant({
     'Ok' : IDL.Vec(AdjustingJournalEntry),
     'Err' : IDL.Text,
   });
-  const Result_38 = IDL.Variant({
+  const Result_66 = IDL.Variant({
+    'Ok' : IDL.Vec(Annotation),
+    'Err' : IDL.Text,
+  });
+  const Result_67 = IDL.Variant({
+    'Ok' : IDL.Vec(AuditSample),
+    'Err' : IDL.Text,
+  });
+  const BookAdjustment = IDL.Record({
+    'aje' : AdjustingJournalEntry,
+    'line_items' : IDL.Vec(AjeLineItem),
+  });
+  const Result_68 = IDL.Variant({
+    'Ok' : IDL.Vec(BookAdjustment),
+    'Err' : IDL.Text,
+  });
+  const Result_69 = IDL.Variant({
     'Ok' : IDL.Vec(ClientAcceptance),
     'Err' : IDL.Text,
   });
-  const Result_39 = IDL.Variant({ 'Ok' : IDL.Vec(Client), 'Err' : IDL.Text });
-  const Result_40 = IDL.Variant({
+  const Result_70 = IDL.Variant({ 'Ok' : IDL.Vec(Client), 'Err' : IDL.Text });
+  const Result_71 = IDL.Variant({
     'Ok' : IDL.Vec(ConflictCheck),
     'Err' : IDL.Text,
   });
-  const Result_41 = IDL.Variant({
+  const Result_72 = IDL.Variant({
+    'Ok' : IDL.Vec(Consolidation),
+    'Err' : IDL.Text,
+  });
+  const Result_73 = IDL.Variant({
+    'Ok' : IDL.Vec(CrossReference),
+    'Err' : IDL.Text,
+  });
+  const DatasetVersion = IDL.Record({
+    'id' : IDL.Nat64,
+    'superseded_at' : IDL.Nat64,
+    'superseded_by' : IDL.Principal,
+    'sheets' : IDL.Vec(SheetData),
+    'dataset_id' : IDL.Nat64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'file_name' : IDL.Text,
+    'file_size' : IDL.Nat64,
+    'version' : IDL.Nat32,
+    'masking_policies' : IDL.Vec(ColumnMaskingPolicy),
+  });
+  const Result_74 = IDL.Variant({
+    'Ok' : IDL.Vec(DatasetVersion),
+    'Err' : IDL.Text,
+  });
+  const Result_75 = IDL.Variant({
     'Ok' : IDL.Vec(ImportedDataset),
     'Err' : IDL.Text,
   });
-  const Result_42 = IDL.Variant({ 'Ok' : IDL.Vec(Document), 'Err' : IDL.Text });
-  const Result_43 = IDL.Variant({
+  const Result_76 = IDL.Variant({
+    'Ok' : IDL.Vec(DigitAnalysis),
+    'Err' : IDL.Text,
+  });
+  const Result_77 = IDL.Variant({ 'Ok' : IDL.Vec(Document), 'Err' : IDL.Text });
+  const Result_78 = IDL.Variant({
     'Ok' : IDL.Vec(EngagementLetter),
     'Err' : IDL.Text,
   });
-  const Result_44 = IDL.Variant({
+  const Result_79 = IDL.Variant({
     'Ok' : IDL.Vec(EngagementSetupTemplate),
     'Err' : IDL.Text,
   });
-  const Result_45 = IDL.Variant({
+  const Result_80 = IDL.Variant({
     'Ok' : IDL.Vec(Engagement),
     'Err' : IDL.Text,
   });
-  const Result_46 = IDL.Variant({ 'Ok' : IDL.Vec(Entity), 'Err' : IDL.Text });
-  const Result_47 = IDL.Variant({
+  const Result_81 = IDL.Variant({ 'Ok' : IDL.Vec(Entity), 'Err' : IDL.Text });
+  const Result_82 = IDL.Variant({
+    'Ok' : IDL.Vec(AnalyticalExpectation),
+    'Err' : IDL.Text,
+  });
+  const Result_83 = IDL.Variant({
     'Ok' : IDL.Vec(FinancialStatement),
     'Err' : IDL.Text,
   });
-  const Result_48 = IDL.Variant({
+  const Result_84 = IDL.Variant({ 'Ok' : IDL.Vec(GlImport), 'Err' : IDL.Text });
+  const Result_85 = IDL.Variant({ 'Ok' : IDL.Vec(JetRun), 'Err' : IDL.Text });
+  const Result_86 = IDL.Variant({
+    'Ok' : IDL.Vec(ImportMappingProfile),
+    'Err' : IDL.Text,
+  });
+  const Result_87 = IDL.Variant({
     'Ok' : IDL.Vec(EngagementMilestone),
     'Err' : IDL.Text,
   });
-  const Result_49 = IDL.Variant({
+  const Result_88 = IDL.Variant({
     'Ok' : IDL.Vec(Organization),
     'Err' : IDL.Text,
   });
-  const Result_50 = IDL.Variant({
-    'Ok' : IDL.Vec(AuditTemplate),
+  const Result_89 = IDL.Variant({
+    'Ok' : IDL.Vec(AccountReconciliation),
     'Err' : IDL.Text,
   });
-  const Result_51 = IDL.Variant({
-    'Ok' : IDL.Vec(TimeEntry),
+  const Result_90 = IDL.Variant({
+    'Ok' : IDL.Vec(ReviewNote),
     'Err' : IDL.Text,
   });
-  const Result_52 = IDL.Variant({
-    'Ok' : IDL.Vec(TrialBalance),
+  const Result_91 = IDL.Variant({
+    'Ok' : IDL.Vec(AuditTemplate),
     'Err' : IDL.Text,
   });
-  const Result_53 = IDL.Variant({ 'Ok' : IDL.Vec(User), 'Err' : IDL.Text });
-  const Result_54 = IDL.Variant({
+  const Result_92 = IDL.Variant({ 'Ok' : IDL.Vec(TickMark), 'Err' : IDL.Text });
+  const Result_93 = IDL.Variant({
+    'Ok' : IDL.Vec(TimeEntry),
+    'Err' : IDL.Text,
+  });
+  const Result_94 = IDL.Variant({ 'Ok' : IDL.Vec(User), 'Err' : IDL.Text });
+  const Result_95 = IDL.Variant({
     'Ok' : IDL.Vec(WorkingPaper),
     'Err' : IDL.Text,
   });
+  const MaskDatasetColumnsRequest = IDL.Record({
+    'dataset_id' : IDL.Nat64,
+    'policies' : IDL.Vec(ColumnMaskingPolicy),
+  });
+  const PassAjeRequest = IDL.Record({
+    'aje_id' : IDL.Nat64,
+    'reason' : IDL.Text,
+    'classification' : MisstatementClassification,
+  });
+  const ReimportDatasetRequest = IDL.Record({
+    'dataset_id' : IDL.Nat64,
+    'file_data' : IDL.Vec(IDL.Nat8),
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'file_name' : IDL.Text,
+    'auto_mask_pii' : IDL.Opt(IDL.Bool),
+    'key_column' : IDL.Text,
+  });
+  const ReimportResult = IDL.Record({
+    'dataset' : ImportedDataset,
+    'diff' : DatasetDiff,
+    'stale_working_paper_ids' : IDL.Vec(IDL.Nat64),
+  });
+  const Result_96 = IDL.Variant({ 'Ok' : ReimportResult, 'Err' : IDL.Text });
   const RejectInvitationRequest = IDL.Record({
     'invitation_id' : IDL.Nat64,
     'reason' : IDL.Opt(IDL.Text),
   });
+  const ResolveReviewNoteRequest = IDL.Record({
+    'note_id' : IDL.Nat64,
+    'response' : IDL.Text,
+  });
+  const RollforwardRequest = IDL.Record({
+    'trial_balance_id' : IDL.Nat64,
+    'description' : IDL.Opt(IDL.Text),
+    'engagement_id' : IDL.Opt(IDL.Nat64),
+    'period_type' : IDL.Opt(PeriodType),
+    'period_end_date' : IDL.Text,
+  });
+  const DataQualityCheck = IDL.Variant({
+    'NotBlank' : IDL.Record({ 'column' : IDL.Text }),
+    'NotBothNonZero' : IDL.Record({
+      'first_column' : IDL.Text,
+      'second_column' : IDL.Text,
+    }),
+    'Unique' : IDL.Record({ 'column' : IDL.Text }),
+    'ExcludeTotalsRow' : IDL.Record({ 'label_columns' : IDL.Vec(IDL.Text) }),
+    'DateWithinPeriod' : IDL.Record({
+      'period_end' : IDL.Text,
+      'period_start' : IDL.Text,
+      'column' : IDL.Text,
+    }),
+  });
+  const DataQualityRule = IDL.Record({
+    'check' : DataQualityCheck,
+    'name' : IDL.Text,
+    'severity' : RuleSeverity,
+  });
+  const RunDataQualityRequest = IDL.Record({
+    'block_on_critical' : IDL.Bool,
+    'dataset_id' : IDL.Nat64,
+    'file_data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'rules' : IDL.Vec(DataQualityRule),
+  });
+  const RunDigitAnalysisRequest = IDL.Record({
+    'column_name' : IDL.Text,
+    'working_paper_id' : IDL.Nat64,
+    'dataset_id' : IDL.Nat64,
+    'test' : DigitTest,
+    'min_value' : IDL.Opt(IDL.Float64),
+    'file_data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'sheet_name' : IDL.Opt(IDL.Text),
+  });
+  const CoaAccountInput = IDL.Record({
+    'fs_line_item' : IDL.Opt(IDL.Text),
+    'normal_balance' : IDL.Opt(NormalBalance),
+    'account_number' : IDL.Text,
+    'account_name' : IDL.Text,
+    'account_type' : FSCategory,
+  });
+  const SetMaterialityRequest = IDL.Record({
+    'performance' : IDL.Int64,
+    'engagement_id' : IDL.Nat64,
+    'overall' : IDL.Int64,
+    'basis' : IDL.Text,
+    'clearly_trivial' : IDL.Opt(IDL.Int64),
+  });
+  const Result_97 = IDL.Variant({ 'Ok' : FxRate, 'Err' : IDL.Text });
+  const CellInput = IDL.Record({ 'address' : IDL.Text, 'input' : IDL.Text });
+  const SetCellsRequest = IDL.Record({
+    'working_paper_id' : IDL.Nat64,
+    'cells' : IDL.Vec(CellInput),
+  });
   const SignEngagementLetterRequest = IDL.Record({
     'letter_id' : IDL.Nat64,
     'client_name' : IDL.Text,
   });
+  const SignOffRole = IDL.Variant({
+    'Approver' : IDL.Null,
+    'Preparer' : IDL.Null,
+    'Reviewer' : IDL.Null,
+  });
+  const Result_98 = IDL.Variant({
+    'Ok' : IDL.Vec(MappingProfileMatch),
+    'Err' : IDL.Text,
+  });
+  const TranslateTrialBalanceRequest = IDL.Record({
+    'presentation_currency' : IDL.Text,
+    'trial_balance_id' : IDL.Nat64,
+    'description' : IDL.Opt(IDL.Text),
+    'historical_rates' : IDL.Vec(AccountRate),
+    'engagement_id' : IDL.Opt(IDL.Nat64),
+  });
+  const TranslationLine = IDL.Record({
+    'account_id' : IDL.Nat64,
+    'balance' : IDL.Int64,
+    'rate' : IDL.Float64,
+    'account_number' : IDL.Text,
+    'currency' : IDL.Text,
+    'rate_type' : FxRateType,
+    'account_name' : IDL.Text,
+    'translated_balance' : IDL.Int64,
+    'account_type' : FSCategory,
+  });
+  const TranslationResult = IDL.Record({
+    'source_trial_balance_id' : IDL.Nat64,
+    'lines' : IDL.Vec(TranslationLine),
+    'trial_balance' : TrialBalance,
+    'translation_difference' : IDL.Int64,
+  });
+  const Result_99 = IDL.Variant({ 'Ok' : TranslationResult, 'Err' : IDL.Text });
+  const PIIVaultEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'column_name' : IDL.Text,
+    'dataset_id' : IDL.Nat64,
+    'sheet_name' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'dataset_version' : IDL.Nat32,
+    'raw_sample_values' : IDL.Vec(IDL.Text),
+    'raw_values' : IDL.Vec(IDL.Text),
+  });
+  const Result_100 = IDL.Variant({ 'Ok' : PIIVaultEntry, 'Err' : IDL.Text });
+  const UpdateAjeRequest = IDL.Record({
+    'aje_id' : IDL.Nat64,
+    'entry_type' : IDL.Opt(AjeEntryType),
+    'line_items' : IDL.Vec(CreateAjeLineItemRequest),
+    'description' : IDL.Text,
+  });
   const UpdateChecklistItemRequest = IDL.Record({
     'status' : IDL.Opt(ChecklistItemStatus),
     'checklist_id' : IDL.Nat64,
@@ -998,6 +2304,7 @@ export const idlFactory = ({ IDL }) => {
     'description' : IDL.Text,
     'end_date' : IDL.Nat64,
     'start_date' : IDL.Nat64,
+    'prior_year_engagement_id' : IDL.Opt(IDL.Nat64),
   });
   const UpdateEntityRequest = IDL.Record({
     'id' : IDL.Nat64,
@@ -1010,6 +2317,15 @@ export const idlFactory = ({ IDL }) => {
     'account_id' : IDL.Nat64,
     'fs_line_item_code' : IDL.Text,
   });
+  const UpdateMappingProfileRequest = IDL.Record({
+    'id' : IDL.Nat64,
+    'erp_system' : ErpSystem,
+    'name' : IDL.Text,
+    'sheet_name' : IDL.Opt(IDL.Text),
+    'sign_convention' : SignConvention,
+    'header_row' : IDL.Opt(IDL.Nat32),
+    'header_aliases' : IDL.Vec(HeaderAlias),
+  });
   const UpdateMilestoneRequest = IDL.Record({
     'status' : IDL.Opt(MilestoneStatus),
     'assigned_to' : IDL.Opt(IDL.Principal),
@@ -1021,6 +2337,24 @@ export const idlFactory = ({ IDL }) => {
     'name' : IDL.Text,
     'description' : IDL.Text,
   });
+  const UpdateReconciliationRequest = IDL.Record({
+    'source_reference' : IDL.Opt(IDL.Text),
+    'document_ids' : IDL.Opt(IDL.Vec(IDL.Nat64)),
+    'source' : IDL.Opt(ReconciliationSource),
+    'supporting_balance' : IDL.Opt(IDL.Int64),
+    'reconciliation_id' : IDL.Nat64,
+    'reconciling_items' : IDL.Opt(IDL.Vec(ReconcilingItemInput)),
+  });
+  const UpdateWorkingPaperRequest = IDL.Record({
+    'id' : IDL.Nat64,
+    'reference_code' : IDL.Opt(IDL.Text),
+    'trial_balance_id' : IDL.Opt(IDL.Nat64),
+    'dataset_id' : IDL.Opt(IDL.Nat64),
+    'column_mapping' : ColumnMapping,
+    'name' : IDL.Text,
+    'selected_accounts' : IDL.Vec(IDL.Text),
+    'trend_thresholds' : IDL.Opt(SignificanceThresholds),
+  });
   const UploadDocumentRequest = IDL.Record({
     'name' : IDL.Text,
     'file_data' : IDL.Vec(IDL.Nat8),
@@ -1040,7 +2374,7 @@ export const idlFactory = ({ IDL }) => {
     'validated_by' : IDL.Principal,
     'total_debits' : IDL.Int64,
   });
-  const Result_55 = IDL.Variant({
+  const Result_101 = IDL.Variant({
     'Ok' : TrialBalanceValidation,
     'Err' : IDL.Text,
   });
@@ -1054,7 +2388,7 @@ export const idlFactory = ({ IDL }) => {
     'block_height' : IDL.Nat64,
     'verification_timestamp' : IDL.Nat64,
   });
-  const Result_56 = IDL.Variant({
+  const Result_102 = IDL.Variant({
     'Ok' : VerificationResult,
     'Err' : IDL.Text,
   });
@@ -1072,314 +2406,551 @@ export const idlFactory = ({ IDL }) => {
     'verified_at' : IDL.Nat64,
     'verified_by' : IDL.Principal,
   });
-  const Result_57 = IDL.Variant({
+  const Result_103 = IDL.Variant({
     'Ok' : AjeBlockchainVerification,
     'Err' : IDL.Text,
   });
-  const Result_58 = IDL.Variant({ 'Ok' : IDL.Bool, 'Err' : IDL.Text });
+  const Result_104 = IDL.Variant({ 'Ok' : IDL.Bool, 'Err' : IDL.Text });
   return IDL.Service({
     'accept_invitation' : IDL.Func([AcceptInvitationRequest], [Result], []),
-    'add_fs_note' : IDL.Func([AddFSNoteRequest], [Result_1], []),
+    'add_annotation' : IDL.Func([AddAnnotationRequest], [Result_1], []),
+    'add_cross_reference' : IDL.Func(
+        [AddCrossReferenceRequest],
+        [Result_2],
+        [],
+      ),
+    'add_fs_note' : IDL.Func([AddFSNoteRequest], [Result_3], []),
+    'add_review_note' : IDL.Func([AddReviewNoteRequest], [Result_4], []),
     'add_trial_balance_account' : IDL.Func(
         [IDL.Nat64, UpdateAccountRequest],
-        [Result_2],
+        [Result_5],
         [],
       ),
+    'adopt_trial_balance_accounts' : IDL.Func([IDL.Nat64], [Result_6], []),
     'apply_template_to_engagement' : IDL.Func(
         [ApplyTemplateRequest],
-        [Result_3],
+        [Result_7],
         [],
       ),
-    'approve_aje' : IDL.Func([IDL.Nat64], [Result_4], []),
-    'approve_client_acceptance' : IDL.Func([IDL.Nat64], [Result_5], []),
+    'approve_aje' : IDL.Func([IDL.Nat64], [Result_8], []),
+    'approve_client_acceptance' : IDL.Func([IDL.Nat64], [Result_9], []),
     'approve_document_request' : IDL.Func(
         [ApproveDocumentInput],
-        [Result_6],
+        [Result_10],
         [],
       ),
+    'check_reference_integrity' : IDL.Func([IDL.Nat64], [Result_11], ['query']),
+    'compare_trial_balances' : IDL.Func(
+        [IDL.Nat64, IDL.Nat64],
+        [Result_12],
+        ['query'],
+      ),
     'complete_user_profile' : IDL.Func(
         [CompleteProfileRequest],
-        [Result_7],
+        [Result_13],
         [],
       ),
-    'create_aje' : IDL.Func([CreateAjeRequest], [Result_4], []),
-    'create_budget' : IDL.Func([CreateBudgetRequest], [Result_8], []),
-    'create_client' : IDL.Func([CreateClientRequest], [Result_9], []),
+    'confirm_account_mappings' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(AccountMappingInput)],
+        [Result_14],
+        [],
+      ),
+    'consolidate_trial_balances' : IDL.Func(
+        [ConsolidateRequest],
+        [Result_15],
+        [],
+      ),
+    'create_aje' : IDL.Func([CreateAjeRequest], [Result_8], []),
+    'create_analytical_expectation' : IDL.Func(
+        [CreateExpectationRequest],
+        [Result_16],
+        [],
+      ),
+    'create_audit_sample' : IDL.Func(
+        [CreateAuditSampleRequest],
+        [Result_17],
+        [],
+      ),
+    'create_budget' : IDL.Func([CreateBudgetRequest], [Result_18], []),
+    'create_client' : IDL.Func([CreateClientRequest], [Result_19], []),
     'create_client_acceptance' : IDL.Func(
         [CreateClientAcceptanceRequest],
-        [Result_5],
+        [Result_9],
         [],
       ),
     'create_conflict_check' : IDL.Func(
         [CreateConflictCheckRequest],
-        [Result_10],
+        [Result_20],
         [],
       ),
     'create_document_request' : IDL.Func(
         [CreateDocumentRequestInput],
-        [Result_6],
+        [Result_10],
         [],
       ),
-    'create_engagement' : IDL.Func([CreateEngagementRequest], [Result_11], []),
+    'create_engagement' : IDL.Func([CreateEngagementRequest], [Result_21], []),
     'create_engagement_from_template' : IDL.Func(
         [CreateEngagementFromTemplateRequest],
-        [Result_12],
+        [Result_22],
         [],
       ),
     'create_engagement_letter' : IDL.Func(
         [CreateEngagementLetterRequest],
-        [Result_13],
+        [Result_23],
         [],
       ),
     'create_engagement_setup_template' : IDL.Func(
         [CreateEngagementSetupTemplateRequest],
-        [Result_14],
+        [Result_24],
         [],
       ),
-    'create_entity' : IDL.Func([CreateEntityRequest], [Result_15], []),
-    'create_invitation' : IDL.Func([CreateInvitationRequest], [Result_16], []),
-    'create_milestone' : IDL.Func([CreateMilestoneRequest], [Result_17], []),
+    'create_entity' : IDL.Func([CreateEntityRequest], [Result_25], []),
+    'create_invitation' : IDL.Func([CreateInvitationRequest], [Result_26], []),
+    'create_mapping_profile' : IDL.Func(
+        [CreateMappingProfileRequest],
+        [Result_27],
+        [],
+      ),
+    'create_milestone' : IDL.Func([CreateMilestoneRequest], [Result_28], []),
     'create_organization' : IDL.Func(
         [CreateOrganizationRequest],
-        [Result_18],
+        [Result_29],
         [],
       ),
-    'create_template' : IDL.Func([CreateTemplateRequest], [Result_19], []),
-    'create_time_entry' : IDL.Func([CreateTimeEntryRequest], [Result_20], []),
+    'create_reconciliation' : IDL.Func(
+        [CreateReconciliationRequest],
+        [Result_30],
+        [],
+      ),
+    'create_template' : IDL.Func([CreateTemplateRequest], [Result_31], []),
+    'create_time_entry' : IDL.Func([CreateTimeEntryRequest], [Result_32], []),
     'create_trial_balance' : IDL.Func(
         [CreateTrialBalanceRequest],
-        [Result_21],
+        [Result_33],
         [],
       ),
     'create_working_paper' : IDL.Func(
         [CreateWorkingPaperRequest],
-        [Result_22],
+        [Result_34],
         [],
       ),
-    'delete_client' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'delete_document' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'delete_engagement' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'delete_entity' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'delete_organization' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'download_document' : IDL.Func([IDL.Nat64], [Result_23], ['query']),
+    'deactivate_coa_account' : IDL.Func([IDL.Nat64], [Result_35], []),
+    'define_tick_mark' : IDL.Func([DefineTickMarkRequest], [Result_36], []),
+    'delete_account_mapping' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_client' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_document' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_engagement' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_entity' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_mapping_profile' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'delete_organization' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'download_document' : IDL.Func([IDL.Nat64], [Result_37], ['query']),
+    'evaluate_audit_sample' : IDL.Func(
+        [EvaluateAuditSampleRequest],
+        [Result_17],
+        [],
+      ),
+    'explain_variance' : IDL.Func([ExplainVarianceRequest], [Result_16], []),
     'fulfill_document_request' : IDL.Func(
         [FulfillDocumentRequestInput],
-        [Result_6],
+        [Result_10],
         [],
       ),
     'generate_financial_statements' : IDL.Func(
         [GenerateFSRequest],
-        [Result_24],
+        [Result_38],
         [],
       ),
     'get_activity_logs' : IDL.Func(
         [IDL.Opt(IDL.Nat64)],
-        [Result_25],
+        [Result_39],
         ['query'],
       ),
-    'get_aje' : IDL.Func([IDL.Nat64], [Result_4], ['query']),
-    'get_aje_line_items' : IDL.Func([IDL.Nat64], [Result_26], ['query']),
-    'get_blockchain_proof' : IDL.Func([IDL.Nat64], [Result_27], ['query']),
-    'get_client' : IDL.Func([IDL.Nat64], [Result_9], ['query']),
+    'get_adjusted_trial_balance' : IDL.Func(
+        [IDL.Nat64, IDL.Bool],
+        [Result_40],
+        ['query'],
+      ),
+    'get_aje' : IDL.Func([IDL.Nat64], [Result_8], ['query']),
+    'get_aje_line_items' : IDL.Func([IDL.Nat64], [Result_41], ['query']),
+    'get_analytical_expectation' : IDL.Func(
+        [IDL.Nat64],
+        [Result_16],
+        ['query'],
+      ),
+    'get_audit_sample' : IDL.Func([IDL.Nat64], [Result_17], ['query']),
+    'get_blockchain_proof' : IDL.Func([IDL.Nat64], [Result_42], ['query']),
+    'get_client' : IDL.Func([IDL.Nat64], [Result_19], ['query']),
     'get_client_access_for_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_28],
+        [Result_43],
         ['query'],
       ),
-    'get_current_user' : IDL.Func([], [Result_7], []),
-    'get_dataset' : IDL.Func([IDL.Nat64], [Result_29], ['query']),
-    'get_document' : IDL.Func([IDL.Nat64], [Result_30], ['query']),
+    'get_coa_mapping_report' : IDL.Func([IDL.Nat64], [Result_44], ['query']),
+    'get_consolidation' : IDL.Func([IDL.Nat64], [Result_15], ['query']),
+    'get_current_user' : IDL.Func([], [Result_13], []),
+    'get_data_quality_report' : IDL.Func([IDL.Nat64], [Result_45], ['query']),
+    'get_dataset' : IDL.Func([IDL.Nat64], [Result_46], ['query']),
+    'get_dataset_diff' : IDL.Func(
+        [IDL.Nat64, IDL.Nat32, IDL.Nat32, IDL.Text, IDL.Opt(IDL.Text)],
+        [Result_47],
+        ['query'],
+      ),
+    'get_digit_analysis' : IDL.Func([IDL.Nat64], [Result_48], ['query']),
+    'get_document' : IDL.Func([IDL.Nat64], [Result_49], ['query']),
     'get_document_requests_for_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_31],
+        [Result_50],
         ['query'],
       ),
-    'get_engagement' : IDL.Func([IDL.Nat64], [Result_11], ['query']),
-    'get_engagement_checklists' : IDL.Func([IDL.Nat64], [Result_32], ['query']),
-    'get_engagement_dashboard' : IDL.Func([IDL.Nat64], [Result_33], ['query']),
-    'get_entity' : IDL.Func([IDL.Nat64], [Result_15], ['query']),
-    'get_financial_statement' : IDL.Func([IDL.Nat64], [Result_24], ['query']),
+    'get_engagement' : IDL.Func([IDL.Nat64], [Result_21], ['query']),
+    'get_engagement_checklists' : IDL.Func([IDL.Nat64], [Result_51], ['query']),
+    'get_engagement_dashboard' : IDL.Func([IDL.Nat64], [Result_52], ['query']),
+    'get_entity' : IDL.Func([IDL.Nat64], [Result_25], ['query']),
+    'get_financial_statement' : IDL.Func([IDL.Nat64], [Result_38], ['query']),
+    'get_gl_import' : IDL.Func([IDL.Nat64], [Result_53], ['query']),
     'get_invitations_for_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_34],
+        [Result_54],
         ['query'],
       ),
+    'get_jet_run' : IDL.Func([IDL.Nat64], [Result_55], ['query']),
+    'get_journal_entries' : IDL.Func([IDL.Nat64], [Result_56], ['query']),
+    'get_journal_lines' : IDL.Func([IDL.Nat64], [Result_57], ['query']),
     'get_line_items_for_taxonomy' : IDL.Func(
         [XBRLTaxonomy],
         [IDL.Vec(FSLineItem)],
         ['query'],
       ),
-    'get_my_document_requests' : IDL.Func([], [Result_31], ['query']),
-    'get_my_engagements' : IDL.Func([], [Result_35], ['query']),
-    'get_my_invitations' : IDL.Func([], [Result_34], ['query']),
-    'get_organization' : IDL.Func([IDL.Nat64], [Result_18], ['query']),
+    'get_mapping_profile' : IDL.Func([IDL.Nat64], [Result_27], ['query']),
+    'get_multi_period_trend' : IDL.Func(
+        [MultiPeriodTrendRequest],
+        [Result_58],
+        ['query'],
+      ),
+    'get_my_document_requests' : IDL.Func([], [Result_50], ['query']),
+    'get_my_engagements' : IDL.Func([], [Result_59], ['query']),
+    'get_my_invitations' : IDL.Func([], [Result_54], ['query']),
+    'get_organization' : IDL.Func([IDL.Nat64], [Result_29], ['query']),
+    'get_reconciliation' : IDL.Func([IDL.Nat64], [Result_30], ['query']),
     'get_resource_activity_logs' : IDL.Func(
         [IDL.Text, IDL.Text, IDL.Opt(IDL.Nat64)],
-        [Result_25],
+        [Result_39],
         ['query'],
       ),
-    'get_template' : IDL.Func([IDL.Nat64], [Result_19], ['query']),
-    'get_trial_balance' : IDL.Func([IDL.Nat64], [Result_21], ['query']),
+    'get_template' : IDL.Func([IDL.Nat64], [Result_31], ['query']),
+    'get_trial_balance' : IDL.Func([IDL.Nat64], [Result_33], ['query']),
     'get_trial_balance_accounts' : IDL.Func(
         [IDL.Nat64],
-        [Result_36],
+        [Result_60],
+        ['query'],
+      ),
+    'get_trial_balance_period_chain' : IDL.Func(
+        [IDL.Nat64],
+        [Result_61],
+        ['query'],
+      ),
+    'get_uncorrected_misstatements' : IDL.Func(
+        [IDL.Nat64],
+        [Result_62],
+        ['query'],
+      ),
+    'get_unreconciled_accounts' : IDL.Func(
+        [IDL.Nat64, IDL.Int64],
+        [Result_63],
         ['query'],
       ),
     'get_user_activity_logs' : IDL.Func(
         [IDL.Principal, IDL.Opt(IDL.Nat64)],
-        [Result_25],
+        [Result_39],
         ['query'],
       ),
-    'get_working_paper' : IDL.Func([IDL.Nat64], [Result_22], ['query']),
+    'get_working_paper' : IDL.Func([IDL.Nat64], [Result_34], ['query']),
     'grant_client_access' : IDL.Func([GrantClientAccessRequest], [Result], []),
     'grant_document_access' : IDL.Func(
         [IDL.Nat64, IDL.Principal],
-        [Result_1],
+        [Result_3],
         [],
       ),
-    'import_excel' : IDL.Func([ImportExcelRequest], [Result_29], []),
+    'import_excel' : IDL.Func([ImportExcelRequest], [Result_46], []),
+    'import_fx_rates' : IDL.Func([IDL.Vec(SetFxRateRequest)], [Result_64], []),
+    'import_gl_journal' : IDL.Func([ImportGlJournalRequest], [Result_53], []),
     'import_trial_balance_csv' : IDL.Func(
-        [IDL.Nat64, IDL.Text, IDL.Vec(CsvAccountRow)],
-        [Result_21],
+        [IDL.Nat64, IDL.Text, IDL.Vec(CsvAccountRow), IDL.Opt(IDL.Text)],
+        [Result_33],
+        [],
+      ),
+    'import_trial_balance_from_dataset' : IDL.Func(
+        [
+          IDL.Nat64,
+          IDL.Nat64,
+          IDL.Text,
+          IDL.Opt(ColumnMapping),
+          IDL.Opt(IDL.Text),
+          IDL.Opt(IDL.Vec(IDL.Nat8)),
+        ],
+        [Result_33],
         [],
       ),
     'link_document_to_working_paper' : IDL.Func(
         [IDL.Nat64, IDL.Nat64],
-        [Result_1],
+        [Result_3],
         [],
       ),
-    'list_ajes_by_engagement' : IDL.Func([IDL.Nat64], [Result_37], ['query']),
+    'list_account_mappings' : IDL.Func([CoaOwner], [Result_14], ['query']),
+    'list_ajes_by_engagement' : IDL.Func([IDL.Nat64], [Result_65], ['query']),
+    'list_annotations' : IDL.Func([IDL.Nat64], [Result_66], ['query']),
+    'list_audit_samples_by_engagement' : IDL.Func(
+        [IDL.Nat64],
+        [Result_67],
+        ['query'],
+      ),
+    'list_book_adjustments' : IDL.Func([IDL.Nat64], [Result_68], ['query']),
     'list_client_acceptances_by_client' : IDL.Func(
         [IDL.Nat64],
-        [Result_38],
+        [Result_69],
         ['query'],
       ),
-    'list_clients' : IDL.Func([], [Result_39], ['query']),
-    'list_clients_by_entity' : IDL.Func([IDL.Nat64], [Result_39], ['query']),
+    'list_clients' : IDL.Func([], [Result_70], ['query']),
+    'list_clients_by_entity' : IDL.Func([IDL.Nat64], [Result_70], ['query']),
     'list_clients_by_organization' : IDL.Func(
         [IDL.Nat64],
-        [Result_39],
+        [Result_70],
         ['query'],
       ),
+    'list_coa_accounts' : IDL.Func([CoaOwner], [Result_6], ['query']),
     'list_conflict_checks_by_client' : IDL.Func(
         [IDL.Nat64],
-        [Result_40],
+        [Result_71],
         ['query'],
       ),
-    'list_datasets' : IDL.Func([], [Result_41], ['query']),
+    'list_consolidations' : IDL.Func([IDL.Nat64], [Result_72], ['query']),
+    'list_cross_references' : IDL.Func([IDL.Nat64], [Result_73], ['query']),
+    'list_dataset_versions' : IDL.Func([IDL.Nat64], [Result_74], ['query']),
+    'list_datasets' : IDL.Func([], [Result_75], ['query']),
     'list_datasets_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_41],
+        [Result_75],
         ['query'],
       ),
-    'list_documents' : IDL.Func([], [Result_42], ['query']),
-    'list_documents_by_entity' : IDL.Func([IDL.Nat64], [Result_42], ['query']),
+    'list_digit_analyses_by_working_paper' : IDL.Func(
+        [IDL.Nat64],
+        [Result_76],
+        ['query'],
+      ),
+    'list_documents' : IDL.Func([], [Result_77], ['query']),
+    'list_documents_by_entity' : IDL.Func([IDL.Nat64], [Result_77], ['query']),
     'list_documents_by_organization' : IDL.Func(
         [IDL.Nat64],
-        [Result_42],
+        [Result_77],
         ['query'],
       ),
     'list_engagement_letters_by_client' : IDL.Func(
         [IDL.Nat64],
-        [Result_43],
+        [Result_78],
         ['query'],
       ),
-    'list_engagement_templates' : IDL.Func([], [Result_44], ['query']),
-    'list_engagements' : IDL.Func([], [Result_45], ['query']),
+    'list_engagement_templates' : IDL.Func([], [Result_79], ['query']),
+    'list_engagements' : IDL.Func([], [Result_80], ['query']),
     'list_engagements_by_client' : IDL.Func(
         [IDL.Nat64],
-        [Result_45],
+        [Result_80],
         ['query'],
       ),
     'list_engagements_by_entity' : IDL.Func(
         [IDL.Nat64],
-        [Result_45],
+        [Result_80],
         ['query'],
       ),
     'list_engagements_by_organization' : IDL.Func(
         [IDL.Nat64],
-        [Result_45],
+        [Result_80],
         ['query'],
       ),
-    'list_entities' : IDL.Func([], [Result_46], ['query']),
+    'list_entities' : IDL.Func([], [Result_81], ['query']),
     'list_entities_by_organization' : IDL.Func(
         [IDL.Nat64],
-        [Result_46],
+        [Result_81],
+        ['query'],
+      ),
+    'list_expectations_by_engagement' : IDL.Func(
+        [IDL.Nat64],
+        [Result_82],
         ['query'],
       ),
     'list_financial_statements_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_47],
+        [Result_83],
+        ['query'],
+      ),
+    'list_fx_rates' : IDL.Func([IDL.Opt(IDL.Text)], [Result_64], ['query']),
+    'list_gl_imports_by_engagement' : IDL.Func(
+        [IDL.Nat64],
+        [Result_84],
+        ['query'],
+      ),
+    'list_jet_runs_by_engagement' : IDL.Func(
+        [IDL.Nat64],
+        [Result_85],
+        ['query'],
+      ),
+    'list_mapping_profiles_by_client' : IDL.Func(
+        [IDL.Nat64],
+        [Result_86],
         ['query'],
       ),
     'list_milestones_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_48],
+        [Result_87],
         ['query'],
       ),
-    'list_organizations' : IDL.Func([], [Result_49], ['query']),
-    'list_templates' : IDL.Func([], [Result_50], ['query']),
+    'list_organizations' : IDL.Func([], [Result_88], ['query']),
+    'list_reconciliations' : IDL.Func([IDL.Nat64], [Result_89], ['query']),
+    'list_review_notes' : IDL.Func([IDL.Nat64], [Result_90], ['query']),
+    'list_templates' : IDL.Func([], [Result_91], ['query']),
+    'list_tick_marks' : IDL.Func([IDL.Nat64], [Result_92], ['query']),
     'list_time_entries_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_51],
+        [Result_93],
         ['query'],
       ),
     'list_trial_balances_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_52],
+        [Result_61],
         ['query'],
       ),
-    'list_users' : IDL.Func([], [Result_53], ['query']),
+    'list_users' : IDL.Func([], [Result_94], ['query']),
     'list_working_papers_by_engagement' : IDL.Func(
         [IDL.Nat64],
-        [Result_54],
+        [Result_95],
         ['query'],
       ),
-    'map_account_to_fs_line' : IDL.Func([IDL.Nat64, IDL.Text], [Result_2], []),
-    'post_aje' : IDL.Func([IDL.Nat64], [Result_4], []),
-    'reject_invitation' : IDL.Func([RejectInvitationRequest], [Result_16], []),
-    'revert_activity_entry' : IDL.Func([IDL.Nat64], [Result_1], []),
-    'review_aje' : IDL.Func([IDL.Nat64, IDL.Bool], [Result_4], []),
+    'map_account_to_fs_line' : IDL.Func([IDL.Nat64, IDL.Text], [Result_5], []),
+    'mask_dataset_columns' : IDL.Func(
+        [MaskDatasetColumnsRequest],
+        [Result_46],
+        [],
+      ),
+    'pass_aje' : IDL.Func([PassAjeRequest], [Result_8], []),
+    'post_aje' : IDL.Func([IDL.Nat64], [Result_8], []),
+    'refresh_working_paper' : IDL.Func([IDL.Nat64], [Result_34], []),
+    'reimport_dataset' : IDL.Func([ReimportDatasetRequest], [Result_96], []),
+    'reject_invitation' : IDL.Func([RejectInvitationRequest], [Result_26], []),
+    'resolve_review_note' : IDL.Func(
+        [ResolveReviewNoteRequest],
+        [Result_4],
+        [],
+      ),
+    'revert_activity_entry' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'review_aje' : IDL.Func([IDL.Nat64, IDL.Bool], [Result_8], []),
     'revoke_document_access' : IDL.Func(
         [IDL.Nat64, IDL.Principal],
-        [Result_1],
+        [Result_3],
+        [],
+      ),
+    'rollforward_trial_balance' : IDL.Func(
+        [RollforwardRequest],
+        [Result_33],
+        [],
+      ),
+    'run_data_quality_checks' : IDL.Func(
+        [RunDataQualityRequest],
+        [Result_45],
+        [],
+      ),
+    'run_digit_analysis' : IDL.Func([RunDigitAnalysisRequest], [Result_48], []),
+    'run_journal_entry_tests' : IDL.Func(
+        [IDL.Nat64, JetConfig],
+        [Result_55],
+        [],
+      ),
+    'send_engagement_letter' : IDL.Func([IDL.Nat64], [Result_23], []),
+    'set_coa_accounts' : IDL.Func(
+        [CoaOwner, IDL.Vec(CoaAccountInput)],
+        [Result_6],
+        [],
+      ),
+    'set_engagement_materiality' : IDL.Func(
+        [SetMaterialityRequest],
+        [Result_21],
         [],
       ),
-    'send_engagement_letter' : IDL.Func([IDL.Nat64], [Result_13], []),
+    'set_fx_rate' : IDL.Func([SetFxRateRequest], [Result_97], []),
+    'set_working_paper_cells' : IDL.Func([SetCellsRequest], [Result_34], []),
     'sign_engagement_letter' : IDL.Func(
         [SignEngagementLetterRequest],
-        [Result_13],
+        [Result_23],
+        [],
+      ),
+    'sign_off_reconciliation' : IDL.Func(
+        [IDL.Nat64, SignOffRole],
+        [Result_30],
         [],
       ),
-    'submit_aje' : IDL.Func([IDL.Nat64], [Result_4], []),
+    'sign_off_working_paper' : IDL.Func(
+        [IDL.Nat64, SignOffRole],
+        [Result_34],
+        [],
+      ),
+    'submit_aje' : IDL.Func([IDL.Nat64], [Result_8], []),
+    'suggest_mapping_profiles' : IDL.Func(
+        [IDL.Nat64, IDL.Opt(IDL.Nat64)],
+        [Result_98],
+        ['query'],
+      ),
+    'translate_trial_balance' : IDL.Func(
+        [TranslateTrialBalanceRequest],
+        [Result_99],
+        [],
+      ),
+    'unmask_dataset_column' : IDL.Func(
+        [IDL.Nat64, IDL.Text, IDL.Text],
+        [Result_100],
+        [],
+      ),
+    'update_aje' : IDL.Func([UpdateAjeRequest], [Result_8], []),
     'update_checklist_item' : IDL.Func(
         [UpdateChecklistItemRequest],
-        [Result_3],
+        [Result_7],
         [],
       ),
-    'update_client' : IDL.Func([UpdateClientRequest], [Result_9], []),
-    'update_engagement' : IDL.Func([UpdateEngagementRequest], [Result_11], []),
-    'update_entity' : IDL.Func([UpdateEntityRequest], [Result_15], []),
+    'update_client' : IDL.Func([UpdateClientRequest], [Result_19], []),
+    'update_engagement' : IDL.Func([UpdateEngagementRequest], [Result_21], []),
+    'update_entity' : IDL.Func([UpdateEntityRequest], [Result_25], []),
     'update_fs_line_mapping' : IDL.Func(
         [UpdateFSLineMappingRequest],
-        [Result_1],
+        [Result_3],
         [],
       ),
-    'update_milestone' : IDL.Func([UpdateMilestoneRequest], [Result_17], []),
+    'update_mapping_profile' : IDL.Func(
+        [UpdateMappingProfileRequest],
+        [Result_27],
+        [],
+      ),
+    'update_milestone' : IDL.Func([UpdateMilestoneRequest], [Result_28], []),
     'update_organization' : IDL.Func(
         [UpdateOrganizationRequest],
-        [Result_18],
+        [Result_29],
+        [],
+      ),
+    'update_reconciliation' : IDL.Func(
+        [UpdateReconciliationRequest],
+        [Result_30],
+        [],
+      ),
+    'update_user_email' : IDL.Func([IDL.Text], [Result_3], []),
+    'update_user_language' : IDL.Func([IDL.Text], [Result_3], []),
+    'update_user_name' : IDL.Func([IDL.Text], [Result_3], []),
+    'update_user_role' : IDL.Func([IDL.Principal, UserRole], [Result_3], []),
+    'update_working_paper' : IDL.Func(
+        [UpdateWorkingPaperRequest],
+        [Result_34],
         [],
       ),
-    'update_user_email' : IDL.Func([IDL.Text], [Result_1], []),
-    'update_user_language' : IDL.Func([IDL.Text], [Result_1], []),
-    'update_user_name' : IDL.Func([IDL.Text], [Result_1], []),
-    'update_user_role' : IDL.Func([IDL.Principal, UserRole], [Result_1], []),
-    'upload_document' : IDL.Func([UploadDocumentRequest], [Result_30], []),
-    'validate_trial_balance' : IDL.Func([IDL.Nat64], [Result_55], ['query']),
-    'verify_activity_log' : IDL.Func([IDL.Nat64], [Result_56], ['query']),
-    'verify_aje_blockchain' : IDL.Func([IDL.Nat64], [Result_57], ['query']),
-    'verify_blockchain_chain' : IDL.Func([], [Result_58], ['query']),
+    'upload_document' : IDL.Func([UploadDocumentRequest], [Result_49], []),
+    'validate_trial_balance' : IDL.Func([IDL.Nat64], [Result_101], ['query']),
+    'verify_activity_log' : IDL.Func([IDL.Nat64], [Result_102], ['query']),
+    'verify_aje_blockchain' : IDL.Func([IDL.Nat64], [Result_103], ['query']),
+    'verify_blockchain_chain' : IDL.Func([], [Result_104], ['query']),
   });
 };
 export const init = ({ IDL }) => { return []; };
@@ -52,6 +52,9 @@ const DataImport = () => {
         engagement_id: [],
         file_name: file.name,
         file_data: Array.from(bytes),
+        masking_policies: [],
+        auto_mask_pii: [true],
+        mapping_profile_id: [],
       }]);
 
       setDataset(result);
@@ -97,6 +97,16 @@ interface Dashboard {
   on_schedule: boolean;
   at_risk_milestones: Milestone[];
   recent_time_entries: TimeEntry[];
+  unexplained_variances: UnexplainedVariance[];
+}
+
+interface UnexplainedVariance {
+  id: bigint;
+  target_name: string;
+  expected: bigint;
+  actual: bigint;
+  variance: bigint;
+  variance_percent: [] | [number];
 }
 
 export default function EngagementPlanning() {
@@ -555,6 +565,40 @@ export default function EngagementPlanning() {
                 </Typography>
               )}
             </Paper>
+
+            {dashboard.unexplained_variances.length > 0 && (
+              <Paper sx={{ p: 3, mt: 3 }}>
+                <Typography variant="h6" gutterBottom>
+                  Unexplained Variances
+                </Typography>
+                <TableContainer>
+                  <Table>
+                    <TableHead>
+                      <TableRow>
+                        <TableCell>Item</TableCell>
+                        <TableCell align="right">Expected</TableCell>
+                        <TableCell align="right">Actual</TableCell>
+                        <TableCell align="right">Variance</TableCell>
+                        <TableCell align="right">%</TableCell>
+                      </TableRow>
+                    </TableHead>
+                    <TableBody>
+                      {dashboard.unexplained_variances.map((v) => (
+                        <TableRow key={v.id.toString()}>
+                          <TableCell>{v.target_name}</TableCell>
+                          <TableCell align="right">{(Number(v.expected) / 100).toLocaleString()}</TableCell>
+                          <TableCell align="right">{(Number(v.actual) / 100).toLocaleString()}</TableCell>
+                          <TableCell align="right">{(Number(v.variance) / 100).toLocaleString()}</TableCell>
+                          <TableCell align="right">
+                            {v.variance_percent.length > 0 ? `${v.variance_percent[0]!.toFixed(1)}%` : '-'}
+                          </TableCell>
+                        </TableRow>
+                      ))}
+                    </TableBody>
+                  </Table>
+                </TableContainer>
+              </Paper>
+            )}
           </>
         )}
 
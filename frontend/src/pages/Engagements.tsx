@@ -113,6 +113,7 @@ const Engagements = () => {
         start_date: BigInt(new Date(formData.start_date).getTime() * 1000000),
         end_date: BigInt(new Date(formData.end_date).getTime() * 1000000),
         status: formData.status,
+        prior_year_engagement_id: [],
       }]);
       setDialogOpen(false);
       loadData();
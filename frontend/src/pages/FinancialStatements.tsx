@@ -53,6 +53,7 @@ interface FSLine {
   line_item: FSLineItem;
   amount: bigint; // in cents
   mapped_accounts: bigint[];
+  prior_amounts: [] | [([] | [bigint])[]];
 }
 
 interface FSNote {
@@ -70,6 +71,7 @@ interface FinancialStatement {
   taxonomy: any;
   period_end_date: string;
   lines: FSLine[];
+  comparative_periods: [] | [any[]];
   notes: FSNote[];
   created_at: bigint;
   created_by: any;
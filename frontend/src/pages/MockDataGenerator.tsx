@@ -213,6 +213,7 @@ const MockDataGenerator = () => {
           start_date: BigInt(Date.now() * 1000000), // Current date
           end_date: BigInt((Date.now() + 90 * 24 * 60 * 60 * 1000) * 1000000), // +90 days
           status: 'In Progress',
+          prior_year_engagement_id: [],
         },
         {
           name: 'Delta Textiles - Q1 2024 Review',
@@ -221,6 +222,7 @@ const MockDataGenerator = () => {
           start_date: BigInt((Date.now() - 30 * 24 * 60 * 60 * 1000) * 1000000), // -30 days
           end_date: BigInt(Date.now() * 1000000), // Today
           status: 'Completed',
+          prior_year_engagement_id: [],
         },
       ];
 
@@ -273,6 +275,7 @@ const MockDataGenerator = () => {
           credit_balance: acc.credit,
           fs_line_item: [acc.fs_line], // Option<String>
           notes: [], // Option<String>
+          currency: [], // Option<String>
         };
         const account: any = await call('add_trial_balance_account', [trialBalance.id, accountReq]);
         createdAccounts.push(account);
@@ -33,6 +33,8 @@ interface TrialBalance {
   id: bigint;
   engagement_id: bigint;
   period_end_date: string;
+  period_type: [] | [any];
+  prior_trial_balance_id: [] | [bigint];
   description: string;
   currency: string;
   is_adjusted: boolean;
@@ -49,6 +51,9 @@ interface TBAccount {
   credit_balance: bigint;
   fs_line_item: [] | [string];
   notes: string;
+  opening_balance: [] | [bigint];
+  currency: [] | [string];
+  coa_account_id: [] | [bigint];
 }
 
 interface Validation {
@@ -248,6 +253,7 @@ export default function TrialBalance() {
           account_name: account_name,
           debit_balance: BigInt(Math.round(debitValue * 100)),
           credit_balance: BigInt(Math.round(creditValue * 100)),
+          currency: [],
         };
       });
 
@@ -255,6 +261,7 @@ export default function TrialBalance() {
         BigInt(formData.engagement_id),
         formData.period_end_date,
         rows,
+        formData.currency ? [formData.currency] : [],
       ]);
 
       setImportDialogOpen(false);
@@ -72,8 +72,14 @@ const WorkingPapers = () => {
     setSelectedDataset(datasetId);
     const dataset = datasets.find((d) => d.id.toString() === datasetId);
     if (dataset && dataset.sheets.length > 0) {
-      const columns = dataset.sheets[0].columns.map((c) => c.name);
-      setAvailableColumns(columns);
+      const sheet =
+        dataset.sheets.find((s) => s.name === dataset.suggested_mapping?.sheet_name) ??
+        dataset.sheets[0];
+      setAvailableColumns(sheet.columns.map((c) => c.name));
+      // Pre-fill from the client's matched mapping profile
+      if (dataset.suggested_mapping) {
+        setColumnMapping(dataset.suggested_mapping.column_mapping);
+      }
     }
   };
 
@@ -81,10 +87,13 @@ const WorkingPapers = () => {
     try {
       await call('create_working_paper', [{
         engagement_id: BigInt(selectedEngagement),
-        dataset_id: BigInt(selectedDataset),
+        dataset_id: [BigInt(selectedDataset)],
         name: workingPaperName,
+        reference_code: [],
         column_mapping: columnMapping,
         selected_accounts: [],
+        trial_balance_id: [],
+        trend_thresholds: [],
       }]);
       alert('Working paper created successfully!');
       setActiveStep(0);
@@ -285,7 +294,9 @@ const WorkingPapers = () => {
             <Table>
               <TableHead>
                 <TableRow>
+                  <TableCell>Ref</TableCell>
                   <TableCell>Name</TableCell>
+                  <TableCell>Status</TableCell>
                   <TableCell>Created</TableCell>
                   <TableCell>Ratios</TableCell>
                   <TableCell>Linked Docs</TableCell>
@@ -294,7 +305,11 @@ const WorkingPapers = () => {
               <TableBody>
                 {workingPapers.map((wp) => (
                   <TableRow key={wp.id.toString()}>
+                    <TableCell>{wp.reference_code ?? ''}</TableCell>
                     <TableCell>{wp.name}</TableCell>
+                    <TableCell>
+                      <Chip label={wp.status ? Object.keys(wp.status)[0] : 'Draft'} size="small" />
+                    </TableCell>
                     <TableCell>
                       {new Date(Number(wp.created_at) / 1000000).toLocaleDateString()}
                     </TableCell>
@@ -71,10 +71,69 @@ export interface Engagement {
   start_date: bigint;
   end_date: bigint;
   status: string;
+  prior_year_engagement_id?: bigint;
+  materiality?: Materiality;
   created_at: bigint;
   created_by: string;
 }
 
+export interface Materiality {
+  overall: bigint;
+  performance: bigint;
+  clearly_trivial: bigint;
+  basis: string;
+  set_at: bigint;
+  set_by: string;
+}
+
+export interface SetMaterialityRequest {
+  engagement_id: bigint;
+  overall: bigint;
+  performance: bigint;
+  clearly_trivial?: bigint;
+  basis: string;
+}
+
+export type MisstatementClassification =
+  | { Factual: null }
+  | { Judgmental: null }
+  | { Projected: null };
+
+export interface FsLineEffect {
+  fs_line_item: string;
+  amount: bigint;
+}
+
+export interface UncorrectedMisstatement {
+  aje_id: bigint;
+  engagement_id: bigint;
+  aje_number: string;
+  description: string;
+  classification: MisstatementClassification;
+  is_prior_year_turnaround: boolean;
+  fs_line_effects: FsLineEffect[];
+  profit_before_tax_effect: bigint;
+  equity_effect: bigint;
+}
+
+export interface MisstatementSummary {
+  engagement_id: bigint;
+  materiality?: Materiality;
+  items: UncorrectedMisstatement[];
+  fs_lines: FsLineEffect[];
+  current_year_profit_effect: bigint;
+  turnaround_profit_effect: bigint;
+  profit_before_tax_effect: bigint;
+  equity_effect: bigint;
+  factual_total: bigint;
+  judgmental_total: bigint;
+  projected_total: bigint;
+  exceeds_performance_materiality?: boolean;
+  exceeds_overall_materiality?: boolean;
+  conclusion: string;
+  generated_at: bigint;
+}
+
 export enum ColumnType {
   Numeric = 'Numeric',
   Text = 'Text',
@@ -83,11 +142,28 @@ export enum ColumnType {
   Currency = 'Currency',
 }
 
+export type PIIKind =
+  | { PersonName: null }
+  | { Email: null }
+  | { PhoneNumber: null }
+  | { EgyptianNationalId: null }
+  | { SaudiNationalId: null }
+  | { UaeEmiratesId: null }
+  | { Iban: null };
+
+export interface PIIFinding {
+  kind: PIIKind;
+  confidence: number;
+  matched_rows: bigint;
+}
+
 export interface PIIDetection {
   has_names: boolean;
   has_emails: boolean;
   has_phone_numbers: boolean;
   has_national_ids: boolean;
+  has_ibans?: boolean;
+  findings?: PIIFinding[];
 }
 
 export interface ColumnMetadata {
@@ -100,12 +176,37 @@ export interface ColumnMetadata {
   max_value: string;
   sample_values: string[];
   pii_detection: PIIDetection;
+  masking?: MaskingMethod;
+}
+
+export type MaskingMethod =
+  | { Redact: null }
+  | { Hash: null }
+  | { PartialMask: { visible_prefix: number; visible_suffix: number } };
+
+export interface ColumnMaskingPolicy {
+  sheet_name?: string;
+  column_name: string;
+  method: MaskingMethod;
+}
+
+export interface PIIVaultEntry {
+  id: bigint;
+  dataset_id: bigint;
+  dataset_version: number;
+  sheet_name: string;
+  column_name: string;
+  raw_values: string[];
+  raw_sample_values: string[];
+  created_at: bigint;
+  created_by: string;
 }
 
 export interface SheetData {
   name: string;
   columns: ColumnMetadata[];
   row_count: bigint;
+  header_row?: number;
   data: string[][];
 }
 
@@ -116,9 +217,111 @@ export interface ImportedDataset {
   file_name: string;
   file_size: bigint;
   sheets: SheetData[];
+  masking_policies?: ColumnMaskingPolicy[];
+  suggested_mapping?: MappingProfileMatch;
+  quality_report?: DataQualityReport;
+  version: number;
+  created_at: bigint;
+  created_by: string;
+}
+
+export type RuleSeverity = { Info: null } | { Warning: null } | { Critical: null };
+
+export type DataQualityCheck =
+  | { Unique: { column: string } }
+  | { NotBlank: { column: string } }
+  | { NotBothNonZero: { first_column: string; second_column: string } }
+  | { ExcludeTotalsRow: { label_columns: string[] } }
+  | { DateWithinPeriod: { column: string; period_start: string; period_end: string } };
+
+export interface DataQualityRule {
+  name: string;
+  check: DataQualityCheck;
+  severity: RuleSeverity;
+}
+
+export interface RuleViolation {
+  row_number: bigint;
+  column_name: string;
+  value: string;
+  message: string;
+}
+
+export interface RuleResult {
+  rule_name: string;
+  severity: RuleSeverity;
+  passed: boolean;
+  violation_count: bigint;
+  violations: RuleViolation[];
+}
+
+export interface DataQualityReport {
+  dataset_id: bigint;
+  dataset_version: number;
+  sheet_name: string;
+  rows_checked: bigint;
+  total_rows: bigint;
+  results: RuleResult[];
+  critical_failures: number;
+  warnings: number;
+  block_on_critical: boolean;
+  evaluated_at: bigint;
+  evaluated_by: string;
+}
+
+export interface DatasetVersion {
+  id: bigint;
+  dataset_id: bigint;
   version: number;
+  file_name: string;
+  file_size: bigint;
+  sheets: SheetData[];
+  masking_policies: ColumnMaskingPolicy[];
   created_at: bigint;
   created_by: string;
+  superseded_at: bigint;
+  superseded_by: string;
+}
+
+export type RowChangeType = { Added: null } | { Removed: null } | { Changed: null };
+
+export interface CellChange {
+  column_name: string;
+  old_value: string;
+  new_value: string;
+}
+
+export interface RowChange {
+  key: string;
+  change_type: RowChangeType;
+  old_row_index?: bigint;
+  new_row_index?: bigint;
+  cell_changes: CellChange[];
+}
+
+export interface SheetDiff {
+  sheet_name: string;
+  added_columns: string[];
+  removed_columns: string[];
+  added_count: bigint;
+  removed_count: bigint;
+  changed_count: bigint;
+  unchanged_count: bigint;
+  rows: RowChange[];
+}
+
+export interface DatasetDiff {
+  dataset_id: bigint;
+  from_version: number;
+  to_version: number;
+  key_column: string;
+  sheets: SheetDiff[];
+}
+
+export interface ReimportResult {
+  dataset: ImportedDataset;
+  diff: DatasetDiff;
+  stale_working_paper_ids: bigint[];
 }
 
 export interface ColumnMapping {
@@ -135,6 +338,63 @@ export interface ColumnMapping {
   department?: string;
   project?: string;
   notes?: string;
+  sign_convention?: SignConvention;
+}
+
+export type ErpSystem =
+  | { Sap: null }
+  | { Oracle: null }
+  | { Odoo: null }
+  | { Dynamics: null }
+  | { Other: string };
+
+export type SignConvention =
+  | { DebitCreditColumns: null }
+  | { SignedDebitPositive: null }
+  | { SignedCreditPositive: null };
+
+export type MappingField =
+  | { AccountNumber: null }
+  | { AccountName: null }
+  | { Currency: null }
+  | { OpeningDebit: null }
+  | { OpeningCredit: null }
+  | { PeriodDebit: null }
+  | { PeriodCredit: null }
+  | { YtdDebit: null }
+  | { YtdCredit: null }
+  | { Entity: null }
+  | { Department: null }
+  | { Project: null }
+  | { Notes: null };
+
+export interface HeaderAlias {
+  field: MappingField;
+  aliases: string[];
+}
+
+export interface ImportMappingProfile {
+  id: bigint;
+  client_id: bigint;
+  name: string;
+  erp_system: ErpSystem;
+  sheet_name?: string;
+  header_row: number;
+  sign_convention: SignConvention;
+  header_aliases: HeaderAlias[];
+  created_at: bigint;
+  created_by: string;
+  updated_at: bigint;
+}
+
+export interface MappingProfileMatch {
+  profile_id: bigint;
+  profile_name: string;
+  sheet_name: string;
+  score: number;
+  matched_fields: number;
+  unmatched_fields: MappingField[];
+  column_mapping: ColumnMapping;
 }
 
 export interface AccountData {
@@ -154,17 +414,58 @@ export interface AccountData {
 }
 
 export interface Leadsheet {
+  source?: LeadsheetSource;
   accounts: AccountData[];
-  opening_balance: number;
+  lines?: LeadsheetLine[];
+  opening_balance?: number;
+  period_activity?: number;
+  unadjusted_balance?: number;
   adjustments: number;
   closing_balance: number;
   created_at: bigint;
 }
 
-export interface FinancialRatio {
+export type LeadsheetSource = { Dataset: null } | { TrialBalance: null };
+
+export interface LeadsheetLine {
+  account_id: bigint;
+  account_number: string;
+  account_name: string;
+  fs_line_item?: string;
+  unadjusted_balance: bigint;
+  adjustments: bigint;
+  adjusted_balance: bigint;
+  aje_ids: bigint[];
+}
+
+export interface LeadsheetChange {
+  account_number: string;
+  account_name: string;
+  previous_balance?: number;
+  current_balance?: number;
+  change: number;
+}
+
+export interface LeadsheetRefresh {
+  refreshed_at: bigint;
+  refreshed_by: string;
+  previous_closing_balance: number;
+  closing_balance: number;
+  changes: LeadsheetChange[];
+}
+
+export interface RatioInput {
   name: string;
   value: number;
+  source: string;
+}
+
+export interface FinancialRatio {
+  name: string;
+  value?: number;
   formula: string;
+  inputs?: RatioInput[];
+  unavailable_reason?: string;
 }
 
 export interface TrendAnalysis {
@@ -186,18 +487,181 @@ export interface VarianceAnalysis {
 export interface WorkingPaper {
   id: bigint;
   engagement_id: bigint;
-  dataset_id: bigint;
+  dataset_id?: bigint;
   name: string;
+  reference_code?: string;
   column_mapping: ColumnMapping;
+  selected_accounts?: string[];
   leadsheet?: Leadsheet;
+  refresh_history?: LeadsheetRefresh[];
+  cells?: WorkingPaperCell[];
   ratios: FinancialRatio[];
   trend_analysis: TrendAnalysis[];
   variance_analysis: VarianceAnalysis[];
   linked_document_ids: bigint[];
+  trial_balance_id?: bigint;
+  multi_period_trend?: MultiPeriodTrend;
+  dataset_version?: number;
+  is_stale?: boolean;
+  stale_reason?: string;
+  status?: WorkingPaperStatus;
+  prepared_by?: SignOff;
+  reviewed_by?: SignOff;
+  approved_by?: SignOff;
+  created_at: bigint;
+  created_by: string;
+  last_modified_at?: bigint;
+  last_modified_by?: string;
+}
+
+export interface WorkingPaperCell {
+  address: string;
+  input: string;
+  value: CellValue;
+  error?: string;
+}
+
+export type CellValue =
+  | { Empty: null }
+  | { Number: number }
+  | { Text: string }
+  | { Boolean: boolean };
+
+export interface CellInput {
+  address: string;
+  input: string;
+}
+
+export interface SetCellsRequest {
+  working_paper_id: bigint;
+  cells: CellInput[];
+}
+
+export type WorkingPaperStatus =
+  | { Draft: null }
+  | { Prepared: null }
+  | { Reviewed: null }
+  | { Approved: null }
+  | { Locked: null };
+
+export type SignOffRole = { Preparer: null } | { Reviewer: null } | { Approver: null };
+
+export interface SignOff {
+  signed_by: string;
+  signed_at: bigint;
+}
+
+export type ReviewNoteTarget = { WorkingPaper: null } | { Cell: string } | { Account: string };
+
+export type ReviewNoteStatus = { Open: null } | { Resolved: null };
+
+export interface ReviewNote {
+  id: bigint;
+  working_paper_id: bigint;
+  engagement_id: bigint;
+  target: ReviewNoteTarget;
+  note: string;
+  status: ReviewNoteStatus;
+  response?: string;
+  raised_by: string;
+  raised_at: bigint;
+  resolved_by?: string;
+  resolved_at?: bigint;
+}
+
+export interface UpdateWorkingPaperRequest {
+  id: bigint;
+  dataset_id?: bigint;
+  name: string;
+  reference_code?: string;
+  column_mapping: ColumnMapping;
+  selected_accounts: string[];
+  trial_balance_id?: bigint;
+  trend_thresholds?: SignificanceThresholds;
+}
+
+export interface AddReviewNoteRequest {
+  working_paper_id: bigint;
+  target: ReviewNoteTarget;
+  note: string;
+}
+
+export interface ResolveReviewNoteRequest {
+  note_id: bigint;
+  response: string;
+}
+
+export type ReferenceTarget =
+  | { WorkingPaper: bigint }
+  | { Aje: bigint }
+  | { Document: bigint }
+  | { FsLine: string };
+
+export interface CrossReference {
+  id: bigint;
+  engagement_id: bigint;
+  working_paper_id: bigint;
+  account_number?: string;
+  target: ReferenceTarget;
+  description: string;
+  created_at: bigint;
+  created_by: string;
+}
+
+export interface TickMark {
+  id: bigint;
+  engagement_id: bigint;
+  symbol: string;
+  meaning: string;
+  created_at: bigint;
+  created_by: string;
+}
+
+export interface Annotation {
+  id: bigint;
+  engagement_id: bigint;
+  working_paper_id: bigint;
+  account_number: string;
+  tick_mark_id: bigint;
+  comment?: string;
   created_at: bigint;
   created_by: string;
 }
 
+export interface AddCrossReferenceRequest {
+  working_paper_id: bigint;
+  account_number?: string;
+  target: ReferenceTarget;
+  description: string;
+}
+
+export interface DefineTickMarkRequest {
+  engagement_id: bigint;
+  symbol: string;
+  meaning: string;
+}
+
+export interface AddAnnotationRequest {
+  working_paper_id: bigint;
+  account_number: string;
+  tick_mark_id: bigint;
+  comment?: string;
+}
+
+export interface BrokenReference {
+  working_paper_id: bigint;
+  source: string;
+  target: string;
+  reason: string;
+}
+
+export interface ReferenceIntegrityReport {
+  engagement_id: bigint;
+  references_checked: bigint;
+  broken: BrokenReference[];
+  checked_at: bigint;
+}
+
 export interface Document {
   id: bigint;
   name: string;
@@ -246,3 +710,330 @@ export interface VerificationResult {
   message: string;
 }
 
+export interface GlColumnMapping {
+  entry_number: string;
+  posting_date: string;
+  account_number: string;
+  account_name?: string;
+  amount?: string;
+  debit?: string;
+  credit?: string;
+  posted_by?: string;
+  entry_date?: string;
+  description?: string;
+}
+
+export interface GlImport {
+  id: bigint;
+  engagement_id: bigint;
+  dataset_id: bigint;
+  dataset_version: number;
+  sheet_name: string;
+  mapping: GlColumnMapping;
+  entry_count: bigint;
+  line_count: bigint;
+  total_debits: bigint;
+  total_credits: bigint;
+  unbalanced_entries: string[];
+  skipped_rows: bigint[];
+  complete_population: boolean;
+  imported_at: bigint;
+  imported_by: string;
+}
+
+export interface JournalEntry {
+  id: bigint;
+  gl_import_id: bigint;
+  engagement_id: bigint;
+  entry_number: string;
+  posting_date: string;
+  entry_date?: string;
+  posted_by: string;
+  description: string;
+  total_debit: bigint;
+  total_credit: bigint;
+  line_count: number;
+}
+
+export interface JournalLine {
+  id: bigint;
+  journal_entry_id: bigint;
+  gl_import_id: bigint;
+  line_number: number;
+  account_number: string;
+  account_name: string;
+  debit: bigint;
+  credit: bigint;
+  description: string;
+  row_index: bigint;
+}
+
+export type JetFlag =
+  | { WeekendPosting: null }
+  | { HolidayPosting: null }
+  | { RoundAmount: null }
+  | { JustBelowThreshold: null }
+  | { UnusualUserAccount: null }
+  | { PostClose: null }
+  | { Duplicate: null }
+  | { Unbalanced: null };
+
+export interface JetConfig {
+  period_end_date: string;
+  close_date?: string;
+  weekend_days?: number[];
+  holidays: string[];
+  round_amount_unit?: bigint;
+  approval_thresholds: bigint[];
+  just_below_percent?: number;
+  rare_pair_max_count?: number;
+}
+
+export interface JetFinding {
+  journal_entry_id: bigint;
+  entry_number: string;
+  posting_date: string;
+  posted_by: string;
+  amount: bigint;
+  flags: JetFlag[];
+  risk_score: number;
+  details: string[];
+}
+
+export interface JetRun {
+  id: bigint;
+  engagement_id: bigint;
+  gl_import_id: bigint;
+  config: JetConfig;
+  entries_tested: bigint;
+  flagged_entries: bigint;
+  flag_counts: { flag: JetFlag; count: bigint }[];
+  findings: JetFinding[];
+  run_at: bigint;
+  run_by: string;
+}
+
+export type DigitTest = { FirstDigit: null } | { FirstTwoDigits: null } | { LastDigit: null };
+
+export type ConformityLevel =
+  | { CloseConformity: null }
+  | { AcceptableConformity: null }
+  | { MarginallyAcceptable: null }
+  | { Nonconformity: null };
+
+export interface DigitFrequency {
+  digits: string;
+  expected_proportion: number;
+  observed_proportion: number;
+  expected_count: number;
+  observed_count: bigint;
+  z_statistic: number;
+}
+
+export interface DigitDeviation {
+  digits: string;
+  expected_count: number;
+  observed_count: bigint;
+  z_statistic: number;
+  row_indexes: bigint[];
+}
+
+export interface DigitAnalysis {
+  id: bigint;
+  working_paper_id: bigint;
+  engagement_id: bigint;
+  dataset_id: bigint;
+  dataset_version: number;
+  sheet_name: string;
+  column_name: string;
+  test: DigitTest;
+  records_tested: bigint;
+  records_excluded: bigint;
+  complete_population: boolean;
+  frequencies: DigitFrequency[];
+  chi_square: number;
+  degrees_of_freedom: number;
+  chi_square_critical: number;
+  mad: number;
+  conformity: ConformityLevel;
+  top_deviations: DigitDeviation[];
+  created_at: bigint;
+  created_by: string;
+}
+
+export type SamplingMethod =
+  | { MonetaryUnit: { confidence: number; tolerable_misstatement: number; expected_misstatement: number } }
+  | { SimpleRandom: { sample_size: number } }
+  | { Stratified: { band_upper_bounds: number[]; sample_sizes: number[] } };
+
+export interface StratumSummary {
+  index: number;
+  lower_bound?: number;
+  upper_bound?: number;
+  population_count: bigint;
+  population_value: number;
+  sample_size: bigint;
+}
+
+export interface SampleItem {
+  row_index: bigint;
+  reference: string;
+  book_amount: number;
+  stratum?: number;
+  selection_note: string;
+  audited_amount?: number;
+}
+
+export interface SampleEvaluation {
+  items_tested: bigint;
+  misstatements_found: bigint;
+  known_misstatement: number;
+  projected_misstatement: number;
+  basic_precision: number;
+  incremental_allowance: number;
+  upper_misstatement_limit: number;
+  tolerable_misstatement?: number;
+  exceeds_tolerable?: boolean;
+  confidence: number;
+  evaluated_at: bigint;
+  evaluated_by: string;
+}
+
+export interface AuditSample {
+  id: bigint;
+  engagement_id: bigint;
+  name: string;
+  dataset_id: bigint;
+  dataset_version: number;
+  sheet_name: string;
+  amount_column: string;
+  reference_column?: string;
+  method: SamplingMethod;
+  seed: bigint;
+  population_count: bigint;
+  population_value: number;
+  excluded_count: bigint;
+  sampling_interval?: number;
+  strata: StratumSummary[];
+  items: SampleItem[];
+  complete_population: boolean;
+  evaluation?: SampleEvaluation;
+  created_at: bigint;
+  created_by: string;
+}
+
+export interface CreateAuditSampleRequest {
+  engagement_id: bigint;
+  name: string;
+  dataset_id: bigint;
+  sheet_name?: string;
+  amount_column: string;
+  reference_column?: string;
+  method: SamplingMethod;
+  seed?: bigint;
+  file_data?: Uint8Array;
+}
+
+export interface SampleItemResult {
+  row_index: bigint;
+  audited_amount: number;
+}
+
+export interface EvaluateAuditSampleRequest {
+  sample_id: bigint;
+  results: SampleItemResult[];
+  confidence?: number;
+  tolerable_misstatement?: number;
+}
+
+export interface SignificanceThresholds {
+  amount?: bigint;
+  percent?: number;
+}
+
+export interface TrendPeriod {
+  engagement_id: bigint;
+  engagement_name: string;
+  trial_balance_id: bigint;
+  period_end_date: string;
+}
+
+export interface TrendChange {
+  change?: bigint;
+  change_percent?: number;
+}
+
+export interface TrendRow {
+  key: string;
+  name: string;
+  amounts: (bigint | undefined)[];
+  changes: TrendChange[];
+  is_significant: boolean;
+  significance_reason?: string;
+}
+
+export interface MultiPeriodTrend {
+  engagement_id: bigint;
+  trial_balance_id: bigint;
+  periods: TrendPeriod[];
+  accounts: TrendRow[];
+  fs_lines: TrendRow[];
+  thresholds: SignificanceThresholds;
+  gaps: string[];
+  generated_at: bigint;
+}
+
+export interface MultiPeriodTrendRequest {
+  trial_balance_id: bigint;
+  years?: number;
+  thresholds?: SignificanceThresholds;
+}
+
+export type ExpectationTarget = { Account: string } | { FsLine: string };
+
+export interface RegressionPoint {
+  driver: number;
+  amount: bigint;
+}
+
+export type ExpectationMethod =
+  | { PriorYearGrowth: { growth_percent: number } }
+  | { Budget: { dataset_id: bigint; sheet_name?: string; key_column: string; amount_column: string } }
+  | { Regression: { driver_name: string; observations: RegressionPoint[]; current_driver: number } };
+
+export interface AnalyticalExpectation {
+  id: bigint;
+  engagement_id: bigint;
+  trial_balance_id: bigint;
+  target: ExpectationTarget;
+  target_name: string;
+  method: ExpectationMethod;
+  basis: string;
+  r_squared?: number;
+  expected: bigint;
+  actual: bigint;
+  variance: bigint;
+  variance_percent?: number;
+  threshold_amount?: bigint;
+  threshold_percent?: number;
+  outside_threshold: boolean;
+  explanation?: string;
+  explained_by?: string;
+  explained_at?: bigint;
+  created_at: bigint;
+  created_by: string;
+}
+
+export interface CreateExpectationRequest {
+  trial_balance_id: bigint;
+  target: ExpectationTarget;
+  method: ExpectationMethod;
+  threshold_amount?: bigint;
+  threshold_percent?: number;
+  file_data?: Uint8Array;
+}
+
+export interface ExplainVarianceRequest {
+  expectation_id: bigint;
+  explanation: string;
+}